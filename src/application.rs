@@ -3,15 +3,25 @@ use glib::clone;
 use gtk::prelude::*;
 use gtk::{gio, glib};
 
-use crate::config::{APPLICATION_ID, VERSION};
+use crate::config::{APPLICATION_ID, G_LOG_DOMAIN, VERSION};
 use crate::i18n::translators_list;
-use crate::{services::GameOfLifeSettings, widgets::GameOfLifePreferencesWindow, GameOfLifeWindow};
+use crate::{
+    services::{enable_safe_mode, GameOfLifeSettings, SearchActivation, SearchProvider, Shortcut},
+    widgets::GameOfLifePreferencesWindow,
+    GameOfLifeWindow,
+};
+
+/// The command line option starting the application in safe mode
+const SAFE_MODE_OPTION: &str = "safe-mode";
 
 mod imp {
     use super::*;
+    use std::cell::RefCell;
 
     #[derive(Debug, Default)]
-    pub struct GameOfLifeApplication {}
+    pub struct GameOfLifeApplication {
+        pub(super) search_provider: RefCell<Option<SearchProvider>>,
+    }
 
     #[glib::object_subclass]
     impl ObjectSubclass for GameOfLifeApplication {
@@ -29,19 +39,34 @@ mod imp {
             self.parent_constructed();
             let obj = self.obj();
             obj.setup_gactions();
-            obj.set_accels_for_action("app.quit", &["<primary>q"]);
-            obj.set_accels_for_action("app.preferences", &["<ctrl>comma"]);
-            obj.set_accels_for_action("win.play", &["space"]);
-            obj.set_accels_for_action("win.snapshot", &["<ctrl>s"]);
-            obj.set_accels_for_action("win.open-snapshot", &["<ctrl>o"]);
-            obj.set_accels_for_action("win.toggle-design-mode", &["e"]);
-            obj.set_accels_for_action("win.new", &["<ctrl>n"]);
-            obj.set_accels_for_action("win.new-empty", &["<ctrl>e"]);
-            obj.set_accels_for_action("win.random-seed", &["<ctrl>r"]);
+            obj.add_main_option(
+                SAFE_MODE_OPTION,
+                glib::Char::from(0),
+                glib::OptionFlags::NONE,
+                glib::OptionArg::None,
+                "Start with the default settings, to recover from crashes",
+                None,
+            );
         }
     }
 
     impl ApplicationImpl for GameOfLifeApplication {
+        fn handle_local_options(&self, options: &glib::VariantDict) -> i32 {
+            if options.contains(SAFE_MODE_OPTION) {
+                glib::g_message!(G_LOG_DOMAIN, "Starting in safe mode");
+                enable_safe_mode();
+            }
+            self.parent_handle_local_options(options)
+        }
+
+        fn startup(&self) {
+            self.parent_startup();
+            // Settings are only read from here on, once safe mode is known
+            let obj = self.obj();
+            obj.setup_shortcuts();
+            obj.setup_search_provider();
+        }
+
         // We connect to the activate callback to create a window when the application
         // has been launched. Additionally, this callback notifies us when the user
         // tries to launch a "second instance" of the application. When they try
@@ -59,6 +84,21 @@ mod imp {
             // Ask the window manager/compositor to present the window
             window.present();
         }
+
+        // Project files opened from the file manager or the command line
+        // replace the universe of the current window
+        fn open(&self, files: &[gio::File], _hint: &str) {
+            self.activate();
+            if let Some(window) = self
+                .obj()
+                .active_window()
+                .and_then(|window| window.downcast::<GameOfLifeWindow>().ok())
+            {
+                if let Some(file) = files.first() {
+                    window.open_project(file);
+                }
+            }
+        }
     }
 
     impl GtkApplicationImpl for GameOfLifeApplication {}
@@ -105,6 +145,77 @@ impl GameOfLifeApplication {
         self.add_action(&disable_design_hint_action);
     }
 
+    /// Binds the keyboard shortcuts, rebinding them whenever they are
+    /// edited in the preferences
+    fn setup_shortcuts(&self) {
+        let settings = GameOfLifeSettings::default();
+        self.apply_shortcuts(&settings);
+        settings.connect_changed(
+            "shortcuts",
+            clone!(@weak self as app, @strong settings => move |_, _| {
+                app.apply_shortcuts(&settings);
+            }),
+        );
+    }
+
+    fn apply_shortcuts(&self, settings: &GameOfLifeSettings) {
+        let bindings = settings.shortcuts();
+        for shortcut in Shortcut::ALL {
+            let accelerator = bindings.accelerator(shortcut);
+            let accelerators: &[&str] = match accelerator.is_empty() {
+                true => &[],
+                false => &[accelerator],
+            };
+            self.set_accels_for_action(shortcut.action_name(), accelerators);
+        }
+    }
+
+    /// Exports the GNOME Shell search provider, finding bundled patterns from
+    /// the overview
+    fn setup_search_provider(&self) {
+        let (connection, object_path) = match (self.dbus_connection(), self.dbus_object_path()) {
+            (Some(connection), Some(object_path)) => (connection, object_path),
+            _ => return,
+        };
+
+        let object_path = format!("{}/SearchProvider", object_path);
+        let activate = clone!(@weak self as app => move |activation| {
+            app.activate_search_result(activation);
+        });
+        match SearchProvider::register(&connection, &object_path, activate) {
+            Ok(search_provider) => {
+                self.imp().search_provider.replace(Some(search_provider));
+            }
+            Err(error) => {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Could not export the search provider: {}",
+                    error
+                );
+            }
+        }
+    }
+
+    fn activate_search_result(&self, activation: SearchActivation) {
+        self.activate();
+        let window = match self
+            .active_window()
+            .and_then(|window| window.downcast::<GameOfLifeWindow>().ok())
+        {
+            Some(window) => window,
+            None => return,
+        };
+
+        match activation {
+            SearchActivation::Pattern(library_pattern) => {
+                window.open_library_pattern(&library_pattern);
+            }
+            SearchActivation::Search(library_pattern) => {
+                window.pattern_library_dialog(library_pattern.as_ref().map(|pattern| pattern.id()));
+            }
+        }
+    }
+
     fn show_about(&self) {
         let window = self.active_window().unwrap();
         let dialog = adw::AboutWindow::builder()
@@ -0,0 +1,369 @@
+//! Runs a pattern from the command line, without a display. The universe is
+//! evolved for a number of generations and the final state is printed to the
+//! standard output, either as an RLE pattern or as statistics in JSON.
+
+use crate::models::formats::{parse_pattern, PatternFormat};
+use crate::models::{
+    Engine, SparseUniverse, Universe, UniverseCell, UniversePattern, UniversePointMatrix,
+    UniverseRule, UniverseSnapshot,
+};
+use std::io::Read;
+use std::str::FromStr;
+use std::time::Instant;
+
+/// The flag switching the application to headless mode
+pub const HEADLESS_FLAG: &str = "--headless";
+
+const DEFAULT_GENERATIONS: u64 = 100;
+
+const USAGE: &str = "\
+Usage: game-of-life --headless [OPTIONS] INPUT
+       game-of-life --headless --list-engines
+
+Evolves the pattern in INPUT, or in the standard input if INPUT is -, and
+prints the final state to the standard output.
+
+Options:
+  --rule RULE            The rule to evolve the pattern with, such as B3/S23.
+                         Defaults to the rule of the pattern, then to B3/S23
+  --generations COUNT    The number of generations to compute (default: 100)
+  --size ROWSxCOLUMNS    Evolves the pattern in a bounded universe wrapping
+                         around its edges, instead of an unbounded one
+  --output rle|json      Prints the final pattern as RLE, or statistics about
+                         the run as JSON (default: rle)
+  --list-engines         Prints the simulation engines and what they support
+                         as JSON
+  --help                 Prints this message";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HeadlessOutput {
+    Rle,
+    Json,
+}
+
+/// What the command line asked for
+#[derive(Debug)]
+enum HeadlessCommand {
+    Run(HeadlessOptions),
+    ListEngines,
+    Usage,
+}
+
+#[derive(Debug)]
+struct HeadlessOptions {
+    input: String,
+    rule: Option<UniverseRule>,
+    generations: u64,
+    size: Option<(usize, usize)>,
+    output: HeadlessOutput,
+}
+
+/// What a headless run computed, as printed in the JSON output
+struct HeadlessRun {
+    rule: UniverseRule,
+    generations: u64,
+    population: usize,
+    peak_population: usize,
+    final_pattern: UniversePattern,
+    elapsed_ms: u128,
+}
+
+/// Whether the application was started in headless mode
+pub fn is_headless(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == HEADLESS_FLAG)
+}
+
+/// Runs the headless mode with the command line `args`, which include the
+/// program name, and returns the exit code of the process
+pub fn run(args: &[String]) -> i32 {
+    let options = match parse_options(&args[1..]) {
+        Ok(HeadlessCommand::Run(options)) => options,
+        Ok(HeadlessCommand::ListEngines) => {
+            println!("{}", engines_json());
+            return 0;
+        }
+        Ok(HeadlessCommand::Usage) => {
+            println!("{}", USAGE);
+            return 0;
+        }
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            return 2;
+        }
+    };
+
+    let text = match read_input(&options.input) {
+        Ok(text) => text,
+        Err(error) => {
+            eprintln!("Could not read {}: {}", options.input, error);
+            return 1;
+        }
+    };
+
+    let pattern = match parse_pattern(&text) {
+        Ok(pattern) => pattern,
+        Err(_) => {
+            eprintln!("{} is not a valid pattern", options.input);
+            return 1;
+        }
+    };
+
+    let rule = options
+        .rule
+        .clone()
+        .or_else(|| pattern.rule().cloned())
+        .unwrap_or_default();
+
+    let engine = match options.size {
+        Some(_) => Engine::Bounded,
+        None => Engine::Unbounded,
+    };
+    let capabilities = engine.capabilities();
+    if !capabilities.supports_rule(&rule) {
+        eprintln!(
+            "Elementary rules and rules with birth on zero neighbours, dying states, colors, ants or other neighbourhoods than the eight cells around need a bounded universe, use --size"
+        );
+        return 2;
+    }
+
+    let result = match options.size {
+        Some((rows, columns)) => {
+            if !capabilities.supports_size(rows, columns) {
+                eprintln!("The universe cannot be larger than {}", size_limit(engine));
+                return 2;
+            }
+            run_bounded(&pattern, rule, options.generations, rows, columns)
+        }
+        None => run_unbounded(&pattern, rule, options.generations),
+    };
+
+    match options.output {
+        HeadlessOutput::Rle => print!("{}", PatternFormat::Rle.write(&result.final_pattern)),
+        HeadlessOutput::Json => println!("{}", to_json(&result)),
+    }
+
+    0
+}
+
+/// Reads the options following the headless flag
+fn parse_options(args: &[String]) -> Result<HeadlessCommand, String> {
+    let mut input = None;
+    let mut rule = None;
+    let mut generations = DEFAULT_GENERATIONS;
+    let mut size = None;
+    let mut output = HeadlessOutput::Rle;
+
+    let mut args = args.iter().filter(|arg| *arg != HEADLESS_FLAG);
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .cloned()
+                .ok_or_else(|| format!("Missing value for {}", name))
+        };
+
+        match arg.as_str() {
+            "--help" | "-h" => return Ok(HeadlessCommand::Usage),
+            "--list-engines" => return Ok(HeadlessCommand::ListEngines),
+            "--rule" => {
+                let text = value("--rule")?;
+                rule = Some(
+                    UniverseRule::from_str(&text).map_err(|_| format!("Invalid rule: {}", text))?,
+                );
+            }
+            "--generations" => {
+                let text = value("--generations")?;
+                generations = text
+                    .parse()
+                    .map_err(|_| format!("Invalid generation count: {}", text))?;
+            }
+            "--size" => {
+                let text = value("--size")?;
+                size = Some(parse_size(&text).ok_or_else(|| format!("Invalid size: {}", text))?);
+            }
+            "--output" => {
+                output = match value("--output")?.as_str() {
+                    "rle" => HeadlessOutput::Rle,
+                    "json" => HeadlessOutput::Json,
+                    other => return Err(format!("Unknown output: {}", other)),
+                };
+            }
+            other if other.starts_with("--") => return Err(format!("Unknown option: {}", other)),
+            other => {
+                if input.replace(other.to_owned()).is_some() {
+                    return Err(String::from("Only one input can be given"));
+                }
+            }
+        }
+    }
+
+    Ok(HeadlessCommand::Run(HeadlessOptions {
+        input: input.ok_or_else(|| String::from("Missing input"))?,
+        rule,
+        generations,
+        size,
+        output,
+    }))
+}
+
+/// Parses a `ROWSxCOLUMNS` size, both dimensions being positive
+fn parse_size(text: &str) -> Option<(usize, usize)> {
+    let (rows, columns) = text.split_once('x')?;
+    let rows: usize = rows.trim().parse().ok()?;
+    let columns: usize = columns.trim().parse().ok()?;
+    (rows > 0 && columns > 0).then_some((rows, columns))
+}
+
+fn read_input(input: &str) -> std::io::Result<String> {
+    match input {
+        "-" => {
+            let mut text = String::new();
+            std::io::stdin().read_to_string(&mut text)?;
+            Ok(text)
+        }
+        path => std::fs::read_to_string(path),
+    }
+}
+
+/// Evolves `pattern` in a `rows`x`columns` universe wrapping around its edges,
+/// with the pattern stamped at its center
+fn run_bounded(
+    pattern: &UniversePattern,
+    rule: UniverseRule,
+    generations: u64,
+    rows: usize,
+    columns: usize,
+) -> HeadlessRun {
+    let snapshot = pattern.snapshot();
+    let mut universe = Universe::new_empty(rows, columns);
+    universe.set_rule(rule.clone());
+    universe.stamp(
+        snapshot,
+        rows.saturating_sub(snapshot.rows()) / 2,
+        columns.saturating_sub(snapshot.columns()) / 2,
+    );
+
+    let started = Instant::now();
+    let mut peak_population = universe.alive_cells_count();
+    for _ in 0..generations {
+        universe.tick();
+        peak_population = peak_population.max(universe.alive_cells_count());
+    }
+    let elapsed_ms = started.elapsed().as_millis();
+
+    let final_snapshot = universe.snapshot();
+    let final_snapshot = match final_snapshot.bounding_box() {
+        Some((top, left, rows, columns)) => final_snapshot.crop(top, left, rows, columns),
+        None => Universe::new_empty(0, 0).snapshot(),
+    };
+
+    HeadlessRun {
+        generations,
+        population: universe.alive_cells_count(),
+        peak_population,
+        final_pattern: final_pattern(final_snapshot, &rule),
+        rule,
+        elapsed_ms,
+    }
+}
+
+/// Evolves `pattern` on an unbounded plane
+fn run_unbounded(pattern: &UniversePattern, rule: UniverseRule, generations: u64) -> HeadlessRun {
+    let snapshot = pattern.snapshot();
+    let mut universe = SparseUniverse::new(snapshot.rows(), snapshot.columns());
+    universe.set_rule(rule.clone());
+    for (row, column) in snapshot.iter_alive() {
+        universe.set_world_cell(row as i64, column as i64, UniverseCell::Alive);
+    }
+
+    let started = Instant::now();
+    let mut peak_population = universe.alive_cells_count();
+    for _ in 0..generations {
+        universe.tick();
+        peak_population = peak_population.max(universe.alive_cells_count());
+    }
+    let elapsed_ms = started.elapsed().as_millis();
+
+    let final_snapshot = universe
+        .living_snapshot()
+        .unwrap_or_else(|| Universe::new_empty(0, 0).snapshot());
+
+    HeadlessRun {
+        generations,
+        population: universe.alive_cells_count(),
+        peak_population,
+        final_pattern: final_pattern(final_snapshot, &rule),
+        rule,
+        elapsed_ms,
+    }
+}
+
+fn final_pattern(snapshot: UniverseSnapshot, rule: &UniverseRule) -> UniversePattern {
+    let mut pattern = UniversePattern::new(snapshot);
+    pattern.set_rule(Some(rule.clone()));
+    pattern
+}
+
+/// Formats the statistics of `run` as a JSON object
+fn to_json(run: &HeadlessRun) -> String {
+    let snapshot = run.final_pattern.snapshot();
+    let generations_per_second = match run.elapsed_ms {
+        0 => String::from("null"),
+        elapsed => format!("{:.1}", run.generations as f64 * 1000.0 / elapsed as f64),
+    };
+
+    format!(
+        "{{\"rule\": \"{}\", \"generations\": {}, \"population\": {}, \"peak_population\": {}, \
+         \"bounding_box\": {{\"rows\": {}, \"columns\": {}}}, \"elapsed_ms\": {}, \
+         \"generations_per_second\": {}}}",
+        run.rule,
+        run.generations,
+        run.population,
+        run.peak_population,
+        snapshot.rows(),
+        snapshot.columns(),
+        run.elapsed_ms,
+        generations_per_second
+    )
+}
+
+fn size_limit(engine: Engine) -> String {
+    match engine.capabilities().max_size() {
+        Some((rows, columns)) => format!("{}x{}", rows, columns),
+        None => String::from("unlimited"),
+    }
+}
+
+/// Formats the engines and their capabilities as a JSON array
+fn engines_json() -> String {
+    let engines: Vec<String> = Engine::ALL
+        .iter()
+        .map(|engine| {
+            let capabilities = engine.capabilities();
+            let max_size = match capabilities.max_size() {
+                Some((rows, columns)) => {
+                    format!("{{\"rows\": {}, \"columns\": {}}}", rows, columns)
+                }
+                None => String::from("null"),
+            };
+            format!(
+                "{{\"name\": \"{}\", \"max_size\": {}, \"birth_on_zero\": {}, \
+                 \"unbounded\": {}, \"parallel\": {}, \"freeze_zones\": {}, \
+                 \"injection\": {}, \"dying_states\": {}, \"neighbourhoods\": {}, \
+                 \"colors\": {}, \"ants\": {}, \"elementary\": {}}}",
+                engine,
+                max_size,
+                capabilities.birth_on_zero(),
+                capabilities.unbounded(),
+                capabilities.parallel(),
+                capabilities.freeze_zones(),
+                capabilities.injection(),
+                capabilities.dying_states(),
+                capabilities.neighbourhoods(),
+                capabilities.colors(),
+                capabilities.ants(),
+                capabilities.elementary()
+            )
+        })
+        .collect();
+    format!("[{}]", engines.join(", "))
+}
@@ -4,6 +4,15 @@ pub fn i18n(format: &str) -> String {
     gettext(format)
 }
 
+/// Translates `format` and replaces each `{}` placeholder with the matching argument
+pub fn i18n_f(format: &str, args: &[&str]) -> String {
+    let mut translated = gettext(format);
+    for arg in args {
+        translated = translated.replacen("{}", arg, 1);
+    }
+    translated
+}
+
 pub fn translators_list() -> Vec<&'static str> {
     vec!["Andrea Coronese (English, Italian)", "Rene Coty (French)"]
 }
@@ -1,11 +1,13 @@
 mod application;
 mod config;
+mod headless;
 mod i18n;
-mod models;
 mod services;
 mod widgets;
 mod window;
 
+use game_of_life_core as models;
+
 use self::application::GameOfLifeApplication;
 use self::window::GameOfLifeWindow;
 
@@ -15,6 +17,12 @@ use gtk::gio;
 use gtk::prelude::*;
 
 fn main() {
+    // Runs without a display when asked to, before anything GTK is set up
+    let args: Vec<String> = std::env::args().collect();
+    if headless::is_headless(&args) {
+        std::process::exit(headless::run(&args));
+    }
+
     // Set up gettext translations
     bindtextdomain(GETTEXT_PACKAGE, LOCALEDIR).expect("Unable to bind the text domain");
     bind_textdomain_codeset(GETTEXT_PACKAGE, "UTF-8")
@@ -26,6 +34,8 @@ fn main() {
         .expect("Could not load resources");
     gio::resources_register(&resources);
 
+    services::install_crash_reporter();
+
     gtk::glib::set_application_name("Game of Life");
     gtk::glib::set_program_name(Some("game-of-life"));
 
@@ -34,7 +44,7 @@ fn main() {
     // desktop features such as file opening and single-instance applications.
     let app = GameOfLifeApplication::new(
         "com.github.sixpounder.GameOfLife",
-        &gio::ApplicationFlags::empty(),
+        &gio::ApplicationFlags::HANDLES_OPEN,
     );
 
     // Run the application. This function will block until the application
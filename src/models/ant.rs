@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The most colors cells of an ant rule can go through, one per turn
+pub const ANT_MAX_COLORS: usize = 16;
+
+/// Which way an ant turns on a cell of some color, before stepping forward
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AntTurn {
+    Left,
+    Right,
+
+    /// Keeps going the same way
+    None,
+
+    /// Turns back the way it came from
+    UTurn,
+}
+
+impl AntTurn {
+    pub const ALL: [AntTurn; 4] = [AntTurn::Left, AntTurn::Right, AntTurn::None, AntTurn::UTurn];
+
+    /// The letter of the turn in ant rulestrings, as in `RL`
+    pub fn letter(&self) -> char {
+        match self {
+            AntTurn::Left => 'L',
+            AntTurn::Right => 'R',
+            AntTurn::None => 'N',
+            AntTurn::UTurn => 'U',
+        }
+    }
+
+    pub fn from_letter(letter: char) -> Option<AntTurn> {
+        AntTurn::ALL
+            .into_iter()
+            .find(|turn| turn.letter() == letter.to_ascii_uppercase())
+    }
+}
+
+/// The way an ant faces
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AntHeading {
+    #[default]
+    North,
+    East,
+    South,
+    West,
+}
+
+impl AntHeading {
+    /// Headings in clockwise order
+    pub const ALL: [AntHeading; 4] = [
+        AntHeading::North,
+        AntHeading::East,
+        AntHeading::South,
+        AntHeading::West,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AntHeading::North => "north",
+            AntHeading::East => "east",
+            AntHeading::South => "south",
+            AntHeading::West => "west",
+        }
+    }
+
+    /// The heading after making `turn`
+    pub fn turned(&self, turn: AntTurn) -> AntHeading {
+        let quarters = match turn {
+            AntTurn::None => 0,
+            AntTurn::Right => 1,
+            AntTurn::UTurn => 2,
+            AntTurn::Left => 3,
+        };
+        AntHeading::ALL[(self.index() + quarters) % 4]
+    }
+
+    /// The `(row, column)` offset of a step forward
+    pub fn offset(&self) -> (isize, isize) {
+        match self {
+            AntHeading::North => (-1, 0),
+            AntHeading::East => (0, 1),
+            AntHeading::South => (1, 0),
+            AntHeading::West => (0, -1),
+        }
+    }
+
+    /// The position of the heading in `ALL`
+    pub fn index(&self) -> usize {
+        match self {
+            AntHeading::North => 0,
+            AntHeading::East => 1,
+            AntHeading::South => 2,
+            AntHeading::West => 3,
+        }
+    }
+}
+
+impl FromStr for AntHeading {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AntHeading::ALL
+            .into_iter()
+            .find(|heading| heading.as_str() == value)
+            .ok_or_else(|| format!("Unknown ant heading {}", value))
+    }
+}
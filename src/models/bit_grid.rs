@@ -0,0 +1,411 @@
+use super::{Neighbourhood, UniverseRule, HENSEL_NEIGHBOURS};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// The bits of the cells in even columns of a word
+const EVEN_COLUMNS: u64 = 0x5555_5555_5555_5555;
+
+/// A toroidal grid of cells packed as bits, each row stored in its own run of
+/// `u64` words. Generations are computed a word at a time: the eight neighbours
+/// of 64 cells are summed in parallel by bit-sliced counters, or those of the
+/// other neighbourhoods of adjacent cells. Neighbourhoods reaching further, and
+/// isotropic rules telling apart how neighbours are arranged, are counted cell
+/// by cell.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    rows: usize,
+    columns: usize,
+    words_per_row: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    /// Creates a grid of `rows`x`columns` dead cells
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let words_per_row = columns.div_ceil(WORD_BITS);
+        Self {
+            rows,
+            columns,
+            words_per_row,
+            words: vec![0; rows * words_per_row],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    fn word_index(&self, row: usize, column: usize) -> (usize, u64) {
+        (
+            row * self.words_per_row + column / WORD_BITS,
+            1 << (column % WORD_BITS),
+        )
+    }
+
+    /// Whether the cell at `row`x`column` is alive. Cells out of the grid are dead.
+    pub fn get(&self, row: usize, column: usize) -> bool {
+        if row >= self.rows || column >= self.columns {
+            return false;
+        }
+
+        let (index, mask) = self.word_index(row, column);
+        self.words[index] & mask != 0
+    }
+
+    /// Sets the cell at `row`x`column`
+    /// # Panics
+    /// Panics if the cell is out of the grid
+    pub fn set(&mut self, row: usize, column: usize, alive: bool) {
+        assert!(
+            row < self.rows && column < self.columns,
+            "Could not set cell at row {row} column {column}"
+        );
+
+        let (index, mask) = self.word_index(row, column);
+        match alive {
+            true => self.words[index] |= mask,
+            false => self.words[index] &= !mask,
+        }
+    }
+
+    /// Counts the living cells
+    pub fn count_alive(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    /// The smallest `(top, left, rows, columns)` area containing all the living
+    /// cells, or `None` if there are none
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (row, words) in self.words.chunks(self.words_per_row.max(1)).enumerate() {
+            let Some(first) = words.iter().position(|word| *word != 0) else {
+                continue;
+            };
+            let last = words.iter().rposition(|word| *word != 0).unwrap_or(first);
+            let left = first * WORD_BITS + words[first].trailing_zeros() as usize;
+            let right = last * WORD_BITS + WORD_BITS - 1 - words[last].leading_zeros() as usize;
+            bounds = Some(match bounds {
+                None => (row, left, row, right),
+                Some((top, min_left, _, max_right)) => {
+                    (top, min_left.min(left), row, max_right.max(right))
+                }
+            });
+        }
+
+        bounds.map(|(top, left, bottom, right)| (top, left, bottom - top + 1, right - left + 1))
+    }
+
+    /// Iterates over the `(row, column)` coordinates of the living cells
+    pub fn iter_alive(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .filter(|(_, word)| **word != 0)
+            .flat_map(move |(index, word)| {
+                let row = index / self.words_per_row;
+                let first_column = (index % self.words_per_row) * WORD_BITS;
+                let mut alive = *word;
+                std::iter::from_fn(move || {
+                    if alive == 0 {
+                        return None;
+                    }
+                    let bit = alive.trailing_zeros() as usize;
+                    alive &= alive - 1;
+                    Some((row, first_column + bit))
+                })
+            })
+    }
+
+    /// Iterates over the `(row, column)` coordinates of the cells that differ
+    /// between this grid and `other`, which must have the same size
+    pub fn iter_differences<'a>(
+        &'a self,
+        other: &'a BitGrid,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .enumerate()
+            .filter(|(_, (word, other_word))| word != other_word)
+            .flat_map(move |(index, (word, other_word))| {
+                let row = index / self.words_per_row;
+                let first_column = (index % self.words_per_row) * WORD_BITS;
+                let mut changed = word ^ other_word;
+                std::iter::from_fn(move || {
+                    if changed == 0 {
+                        return None;
+                    }
+                    let bit = changed.trailing_zeros() as usize;
+                    changed &= changed - 1;
+                    Some((row, first_column + bit))
+                })
+            })
+    }
+
+    /// Computes the next generation of the grid according to `rule`
+    pub fn next_generation(&self, rule: &UniverseRule) -> BitGrid {
+        let mut next = BitGrid::new(self.rows, self.columns);
+        if self.rows == 0 || self.columns == 0 {
+            return next;
+        }
+        if rule.is_elementary() {
+            return self.next_generation_elementary(rule);
+        }
+        if rule.range() > 1 {
+            return self.next_generation_in_range(rule);
+        }
+        if rule.is_isotropic() {
+            return self.next_generation_arranged(rule);
+        }
+
+        let size = rule.neighbourhood_size();
+        let birth_counts: Vec<u16> = (0..=size).filter(|count| rule.is_birth(*count)).collect();
+        let survival_counts: Vec<u16> = (0..=size)
+            .filter(|count| rule.is_survival(*count))
+            .collect();
+        let last_word_mask = match self.columns % WORD_BITS {
+            0 => u64::MAX,
+            bits => (1 << bits) - 1,
+        };
+
+        // The cells in the columns beside, on the previous and the next rows,
+        // are the diagonal neighbours. Von Neumann neighbourhoods leave them
+        // out, hexagonal cells in even columns do not touch the next row and
+        // those in odd columns the previous one.
+        let (previous_row_mask, next_row_mask) = match rule.neighbourhood() {
+            Neighbourhood::Moore => (u64::MAX, u64::MAX),
+            Neighbourhood::VonNeumann => (0, 0),
+            Neighbourhood::Hexagonal => (EVEN_COLUMNS, !EVEN_COLUMNS),
+        };
+
+        let (mut west, mut east) = (
+            vec![0; self.words_per_row * 3],
+            vec![0; self.words_per_row * 3],
+        );
+
+        for row in 0..self.rows {
+            let neighbour_rows = [
+                (row + self.rows - 1) % self.rows,
+                row,
+                (row + 1) % self.rows,
+            ];
+            for (position, neighbour_row) in neighbour_rows.iter().enumerate() {
+                let range = position * self.words_per_row..(position + 1) * self.words_per_row;
+                self.shift_row(*neighbour_row, &mut west[range.clone()], &mut east[range]);
+            }
+
+            for word in 0..self.words_per_row {
+                let above = self.words[neighbour_rows[0] * self.words_per_row + word];
+                let alive = self.words[row * self.words_per_row + word];
+                let below = self.words[neighbour_rows[2] * self.words_per_row + word];
+                let sides = |shifted: &[u64], position: usize| {
+                    shifted[position * self.words_per_row + word]
+                };
+
+                // Bit-sliced counters: bit i of the count of each cell is in counter[i]
+                let mut counter = [0u64; 4];
+                for neighbours in [
+                    above,
+                    below,
+                    sides(&west, 0) & previous_row_mask,
+                    sides(&east, 0) & previous_row_mask,
+                    sides(&west, 1),
+                    sides(&east, 1),
+                    sides(&west, 2) & next_row_mask,
+                    sides(&east, 2) & next_row_mask,
+                ] {
+                    let mut carry = neighbours;
+                    for bit in counter.iter_mut() {
+                        let next_carry = *bit & carry;
+                        *bit ^= carry;
+                        carry = next_carry;
+                    }
+                }
+
+                let count_is = |count: u16| {
+                    counter
+                        .iter()
+                        .enumerate()
+                        .fold(u64::MAX, |mask, (bit, plane)| match count >> bit & 1 {
+                            1 => mask & plane,
+                            _ => mask & !plane,
+                        })
+                };
+
+                let born = birth_counts
+                    .iter()
+                    .fold(0, |mask, count| mask | count_is(*count));
+                let survives = survival_counts
+                    .iter()
+                    .fold(0, |mask, count| mask | count_is(*count));
+
+                let mut next_word = (born & !alive) | (survives & alive);
+                if word == self.words_per_row - 1 {
+                    next_word &= last_word_mask;
+                }
+                next.words[row * self.words_per_row + word] = next_word;
+            }
+        }
+
+        next
+    }
+
+    /// Computes the next generation for neighbourhoods reaching past the cells
+    /// around, counting the neighbours of every cell first
+    fn next_generation_in_range(&self, rule: &UniverseRule) -> BitGrid {
+        let counts = match rule.neighbourhood() {
+            Neighbourhood::Moore => self.moore_counts(rule.range()),
+            neighbourhood => self.tallied_counts(&neighbourhood.offsets(rule.range())),
+        };
+
+        let mut next = BitGrid::new(self.rows, self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let count = counts[row * self.columns + column];
+                let alive = match self.get(row, column) {
+                    true => rule.is_survival(count),
+                    false => rule.is_birth(count),
+                };
+                if alive {
+                    next.set(row, column, true);
+                }
+            }
+        }
+        next
+    }
+
+    /// Computes the next generation for elementary rules: every column moves
+    /// back one, and the last one, holding the line of cells, is worked out
+    /// from the cells beside along it, wrapping around
+    fn next_generation_elementary(&self, rule: &UniverseRule) -> BitGrid {
+        let mut next = BitGrid::new(self.rows, self.columns);
+        let last = self.columns - 1;
+        for row in 0..self.rows {
+            for column in 0..last {
+                if self.get(row, column + 1) {
+                    next.set(row, column, true);
+                }
+            }
+            let before = self.get((row + self.rows - 1) % self.rows, last);
+            let after = self.get((row + 1) % self.rows, last);
+            if rule.elementary_next(before, self.get(row, last), after) {
+                next.set(row, last, true);
+            }
+        }
+        next
+    }
+
+    /// Computes the next generation for isotropic rules, looking up the living
+    /// cells around each cell
+    fn next_generation_arranged(&self, rule: &UniverseRule) -> BitGrid {
+        let (rows, columns) = (self.rows as isize, self.columns as isize);
+        let mut next = BitGrid::new(self.rows, self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let neighbours = HENSEL_NEIGHBOURS.iter().enumerate().fold(
+                    0u8,
+                    |neighbours, (bit, (delta_row, delta_column))| {
+                        let neighbour_row = (row as isize + delta_row).rem_euclid(rows) as usize;
+                        let neighbour_column =
+                            (column as isize + delta_column).rem_euclid(columns) as usize;
+                        neighbours | (self.get(neighbour_row, neighbour_column) as u8) << bit
+                    },
+                );
+                let alive = match self.get(row, column) {
+                    true => rule.is_survival_among(neighbours),
+                    false => rule.is_birth_among(neighbours),
+                };
+                if alive {
+                    next.set(row, column, true);
+                }
+            }
+        }
+        next
+    }
+
+    /// Counts the living neighbours of every cell within `range` along rows
+    /// and columns, row after row. Each count is read off a summed-area table
+    /// of the grid, padded with the cells it wraps around to, so that the cost
+    /// does not grow with the range.
+    fn moore_counts(&self, range: u8) -> Vec<u16> {
+        let range = range as usize;
+        let (padded_rows, padded_columns) = (self.rows + 2 * range, self.columns + 2 * range);
+
+        // sums[i][j] holds the number of living cells above and left of the
+        // padded cell at i x j, excluded
+        let width = padded_columns + 1;
+        let mut sums = vec![0u32; (padded_rows + 1) * width];
+        for padded_row in 0..padded_rows {
+            let row = (padded_row + self.rows * range - range) % self.rows;
+            let mut row_sum = 0;
+            for padded_column in 0..padded_columns {
+                let column = (padded_column + self.columns * range - range) % self.columns;
+                row_sum += self.get(row, column) as u32;
+                sums[(padded_row + 1) * width + padded_column + 1] =
+                    sums[padded_row * width + padded_column + 1] + row_sum;
+            }
+        }
+
+        let side = 2 * range + 1;
+        let mut counts = Vec::with_capacity(self.rows * self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let square = sums[(row + side) * width + column + side]
+                    + sums[row * width + column]
+                    - sums[row * width + column + side]
+                    - sums[(row + side) * width + column];
+                counts.push((square - self.get(row, column) as u32) as u16);
+            }
+        }
+        counts
+    }
+
+    /// Counts the living neighbours of every cell at `offsets`, row after row,
+    /// by adding each living cell to the count of the cells it neighbours
+    fn tallied_counts(&self, offsets: &[(isize, isize)]) -> Vec<u16> {
+        let (rows, columns) = (self.rows as isize, self.columns as isize);
+        let mut counts = vec![0u16; self.rows * self.columns];
+        for row in 0..self.rows {
+            for column in (0..self.columns).filter(|column| self.get(row, *column)) {
+                for (delta_row, delta_column) in offsets.iter() {
+                    let neighbour_row = (row as isize + delta_row).rem_euclid(rows) as usize;
+                    let neighbour_column =
+                        (column as isize + delta_column).rem_euclid(columns) as usize;
+                    counts[neighbour_row * self.columns + neighbour_column] += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Fills `west` and `east` with the cells of `row` shifted so that each
+    /// cell lines up with its west or east neighbour, wrapping around the edges
+    fn shift_row(&self, row: usize, west: &mut [u64], east: &mut [u64]) {
+        let words = &self.words[row * self.words_per_row..(row + 1) * self.words_per_row];
+        let last = self.words_per_row - 1;
+        let last_column_bit = (self.columns - 1) % WORD_BITS;
+
+        for index in 0..self.words_per_row {
+            let previous_top = match index {
+                0 => words[last] >> last_column_bit & 1,
+                _ => words[index - 1] >> (WORD_BITS - 1),
+            };
+            west[index] = words[index] << 1 | previous_top;
+
+            let next_bottom = match index == last {
+                true => 0,
+                false => words[index + 1] & 1,
+            };
+            east[index] = words[index] >> 1 | next_bottom << (WORD_BITS - 1);
+        }
+
+        east[last] |= (words[0] & 1) << last_column_bit;
+    }
+}
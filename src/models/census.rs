@@ -0,0 +1,285 @@
+use crate::{Universe, UniverseCell, UniversePointMatrix, UniverseSnapshot};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+/// Living cells at most this many cells apart belong to the same object, so
+/// that objects falling apart in some of their phases, such as spaceships or
+/// the pulsar, are still recognized whole
+const CENSUS_OBJECT_REACH: usize = 2;
+
+/// Objects larger than this on either side are never looked up, none of the
+/// known ones being that large
+const CENSUS_MAX_OBJECT_SIZE: usize = 16;
+
+/// The empty border around known objects while their phases are evolved
+const CENSUS_PHASE_MARGIN: usize = 4;
+
+/// What a recognized object does over time
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CensusObjectKind {
+    /// Never changes
+    StillLife,
+
+    /// Cycles through its phases in place
+    Oscillator,
+
+    /// Cycles through its phases while moving
+    Spaceship,
+}
+
+/// A common object of Conway's Life that censuses recognize, given by one of
+/// its phases, as rows of `.` and `O` separated by `/`, and its period
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CensusObject {
+    name: &'static str,
+    kind: CensusObjectKind,
+    cells: &'static str,
+    period: usize,
+}
+
+impl CensusObject {
+    const fn new(
+        name: &'static str,
+        kind: CensusObjectKind,
+        cells: &'static str,
+        period: usize,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            cells,
+            period,
+        }
+    }
+
+    /// Every object censuses recognize
+    pub fn known() -> &'static [CensusObject] {
+        &KNOWN_OBJECTS
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn kind(&self) -> CensusObjectKind {
+        self.kind
+    }
+
+    pub fn period(&self) -> usize {
+        self.period
+    }
+
+    /// Every phase of this object, as evolved under Conway's Life
+    fn phases(&self) -> Vec<UniverseSnapshot> {
+        let lines: Vec<&str> = self.cells.split('/').collect();
+        let columns = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+        let mut universe = Universe::new_empty(
+            lines.len() + 2 * CENSUS_PHASE_MARGIN,
+            columns + 2 * CENSUS_PHASE_MARGIN,
+        );
+        for (row, line) in lines.iter().enumerate() {
+            for (column, _) in line.chars().enumerate().filter(|(_, c)| *c == 'O') {
+                universe.set_cell(
+                    row + CENSUS_PHASE_MARGIN,
+                    column + CENSUS_PHASE_MARGIN,
+                    UniverseCell::Alive,
+                );
+            }
+        }
+
+        (0..self.period)
+            .map(|_| {
+                let phase = universe.snapshot();
+                universe.tick();
+                phase
+            })
+            .collect()
+    }
+}
+
+const KNOWN_OBJECTS: [CensusObject; 15] = [
+    CensusObject::new("Block", CensusObjectKind::StillLife, "OO/OO", 1),
+    CensusObject::new("Beehive", CensusObjectKind::StillLife, ".OO./O..O/.OO.", 1),
+    CensusObject::new(
+        "Loaf",
+        CensusObjectKind::StillLife,
+        ".OO./O..O/.O.O/..O.",
+        1,
+    ),
+    CensusObject::new("Boat", CensusObjectKind::StillLife, "OO./O.O/.O.", 1),
+    CensusObject::new("Ship", CensusObjectKind::StillLife, "OO./O.O/.OO", 1),
+    CensusObject::new("Tub", CensusObjectKind::StillLife, ".O./O.O/.O.", 1),
+    CensusObject::new(
+        "Pond",
+        CensusObjectKind::StillLife,
+        ".OO./O..O/O..O/.OO.",
+        1,
+    ),
+    CensusObject::new("Blinker", CensusObjectKind::Oscillator, "OOO", 2),
+    CensusObject::new("Toad", CensusObjectKind::Oscillator, ".OOO/OOO.", 2),
+    CensusObject::new(
+        "Beacon",
+        CensusObjectKind::Oscillator,
+        "OO../OO../..OO/..OO",
+        2,
+    ),
+    CensusObject::new(
+        "Pulsar",
+        CensusObjectKind::Oscillator,
+        "..OOO...OOO../............./O....O.O....O/O....O.O....O/O....O.O....O/..OOO...OOO../\
+         ............./..OOO...OOO../O....O.O....O/O....O.O....O/O....O.O....O/\
+         ............./..OOO...OOO..",
+        3,
+    ),
+    CensusObject::new("Glider", CensusObjectKind::Spaceship, ".O./..O/OOO", 4),
+    CensusObject::new(
+        "Lightweight spaceship",
+        CensusObjectKind::Spaceship,
+        ".O..O/O..../O...O/OOOO.",
+        4,
+    ),
+    CensusObject::new(
+        "Middleweight spaceship",
+        CensusObjectKind::Spaceship,
+        "...O../.O...O/O...../O....O/OOOOO.",
+        4,
+    ),
+    CensusObject::new(
+        "Heavyweight spaceship",
+        CensusObjectKind::Spaceship,
+        "...OO../.O....O/O....../O.....O/OOOOOO.",
+        4,
+    ),
+];
+
+/// The known objects by the canonical hash of each of their phases
+static KNOWN_PHASES: Lazy<HashMap<u64, CensusObject>> = Lazy::new(|| {
+    KNOWN_OBJECTS
+        .iter()
+        .flat_map(|object| {
+            object
+                .phases()
+                .into_iter()
+                .map(|phase| (phase.canonical_hash(), *object))
+        })
+        .collect()
+});
+
+/// The objects found in a universe. Nearby living cells are grouped into
+/// objects, which are recognized by their canonical form whatever their
+/// position, rotation or reflection. Groups matching no known object are only
+/// counted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObjectCensus {
+    counts: Vec<(CensusObject, usize)>,
+    unrecognized: usize,
+}
+
+impl ObjectCensus {
+    /// Takes the census of `snapshot`. Objects wrapping around its edges are
+    /// seen as two.
+    pub fn take(snapshot: &UniverseSnapshot) -> Self {
+        let (rows, columns) = (snapshot.rows(), snapshot.columns());
+        let mut alive = vec![false; rows * columns];
+        for (row, column) in snapshot.iter_alive() {
+            alive[row * columns + column] = true;
+        }
+
+        let mut counts: HashMap<CensusObject, usize> = HashMap::new();
+        let mut unrecognized = 0;
+        for (row, column) in snapshot.iter_alive() {
+            if !alive[row * columns + column] {
+                continue;
+            }
+
+            let cells = take_object(&mut alive, rows, columns, (row, column));
+            match recognize(&cells) {
+                Some(object) => *counts.entry(object).or_default() += 1,
+                None => unrecognized += 1,
+            }
+        }
+
+        let mut counts: Vec<(CensusObject, usize)> = counts.into_iter().collect();
+        counts.sort_by(|(a, a_count), (b, b_count)| {
+            (a.kind, b_count, a.name).cmp(&(b.kind, a_count, b.name))
+        });
+        Self {
+            counts,
+            unrecognized,
+        }
+    }
+
+    /// How many of each known object were found, still lifes first, then
+    /// oscillators and spaceships, the most common first among each kind
+    pub fn counts(&self) -> &[(CensusObject, usize)] {
+        &self.counts
+    }
+
+    /// How many of `object` were found
+    pub fn count(&self, object: &CensusObject) -> usize {
+        self.counts
+            .iter()
+            .find(|(candidate, _)| candidate == object)
+            .map(|(_, count)| *count)
+            .unwrap_or_default()
+    }
+
+    /// The number of objects that are none of the known ones
+    pub fn unrecognized(&self) -> usize {
+        self.unrecognized
+    }
+
+    /// The number of objects found, known or not
+    pub fn total(&self) -> usize {
+        self.counts.iter().map(|(_, count)| count).sum::<usize>() + self.unrecognized
+    }
+}
+
+/// Collects the living cells of the object around `start`, clearing them
+/// from `alive`
+fn take_object(
+    alive: &mut [bool],
+    rows: usize,
+    columns: usize,
+    start: (usize, usize),
+) -> Vec<(usize, usize)> {
+    let mut cells = vec![];
+    let mut pending = vec![start];
+    alive[start.0 * columns + start.1] = false;
+    while let Some((row, column)) = pending.pop() {
+        cells.push((row, column));
+        for near_row in
+            row.saturating_sub(CENSUS_OBJECT_REACH)..(row + CENSUS_OBJECT_REACH + 1).min(rows)
+        {
+            for near_column in column.saturating_sub(CENSUS_OBJECT_REACH)
+                ..(column + CENSUS_OBJECT_REACH + 1).min(columns)
+            {
+                let index = near_row * columns + near_column;
+                if alive[index] {
+                    alive[index] = false;
+                    pending.push((near_row, near_column));
+                }
+            }
+        }
+    }
+    cells
+}
+
+/// The known object made of `cells`, if any
+fn recognize(cells: &[(usize, usize)]) -> Option<CensusObject> {
+    let top = cells.iter().map(|(row, _)| *row).min()?;
+    let left = cells.iter().map(|(_, column)| *column).min()?;
+    let rows = cells.iter().map(|(row, _)| row - top).max()? + 1;
+    let columns = cells.iter().map(|(_, column)| column - left).max()? + 1;
+    if rows > CENSUS_MAX_OBJECT_SIZE || columns > CENSUS_MAX_OBJECT_SIZE {
+        return None;
+    }
+
+    let mut universe = Universe::new_empty(rows, columns);
+    for (row, column) in cells {
+        universe.set_cell(row - top, column - left, UniverseCell::Alive);
+    }
+    KNOWN_PHASES
+        .get(&universe.snapshot().canonical_hash())
+        .copied()
+}
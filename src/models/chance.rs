@@ -0,0 +1,86 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Turns a life-like rule into a stochastic one: cells the rule would bring to
+/// life are only born with `birth_probability`, and cells it would keep alive
+/// only survive with `survival_probability`. On top of that, noise flips any
+/// cell with a probability called the `temperature`, whatever the rule. Draws
+/// come from a generator seeded with `seed`, so the same universe evolves the
+/// same way on every run.
+#[derive(Clone, Debug)]
+pub struct UniverseChance {
+    birth_probability: f64,
+    survival_probability: f64,
+    temperature: f64,
+    seed: u64,
+    rng: StdRng,
+}
+
+impl PartialEq for UniverseChance {
+    fn eq(&self, other: &Self) -> bool {
+        self.birth_probability == other.birth_probability
+            && self.survival_probability == other.survival_probability
+            && self.temperature == other.temperature
+            && self.seed == other.seed
+    }
+}
+
+impl UniverseChance {
+    pub fn new(birth_probability: f64, survival_probability: f64, seed: u64) -> Self {
+        Self {
+            birth_probability: birth_probability.clamp(0.0, 1.0),
+            survival_probability: survival_probability.clamp(0.0, 1.0),
+            temperature: 0.0,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn with_temperature(mut self, temperature: f64) -> Self {
+        self.temperature = temperature.clamp(0.0, 1.0);
+        self
+    }
+
+    pub fn birth_probability(&self) -> f64 {
+        self.birth_probability
+    }
+
+    pub fn survival_probability(&self) -> f64 {
+        self.survival_probability
+    }
+
+    /// The probability any cell is flipped at every generation
+    pub fn temperature(&self) -> f64 {
+        self.temperature
+    }
+
+    /// Whether noise flips cells at all
+    pub fn is_noisy(&self) -> bool {
+        self.temperature > 0.0
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Starts drawing again from the beginning of the seeded sequence
+    pub fn reset(&mut self) {
+        self.rng = StdRng::seed_from_u64(self.seed);
+    }
+
+    /// Decides whether a cell the rule brings to life (`was_alive` false) or
+    /// keeps alive (`was_alive` true) actually is alive in the next generation
+    pub fn allows(&mut self, was_alive: bool) -> bool {
+        let probability = match was_alive {
+            true => self.survival_probability,
+            false => self.birth_probability,
+        };
+        probability >= 1.0 || self.rng.gen::<f64>() < probability
+    }
+
+    /// Decides whether noise flips a cell in the next generation. Nothing is
+    /// drawn without noise, so runs without it are not affected.
+    pub fn flips(&mut self) -> bool {
+        self.is_noisy() && self.rng.gen::<f64>() < self.temperature
+    }
+}
@@ -0,0 +1,93 @@
+/// The difference between two states of a universe, as computed by
+/// `UniverseSnapshot::diff`. Cells are identified by `(row, column)`.
+/// Meant for comparing arbitrary states, the grid tracking the cells changed
+/// by each generation straight from its bit grids instead.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellDelta {
+    births: Vec<(usize, usize)>,
+    deaths: Vec<(usize, usize)>,
+    bounding_box_before: Option<(usize, usize, usize, usize)>,
+    bounding_box_after: Option<(usize, usize, usize, usize)>,
+}
+
+impl CellDelta {
+    pub fn new(
+        births: Vec<(usize, usize)>,
+        deaths: Vec<(usize, usize)>,
+        bounding_box_before: Option<(usize, usize, usize, usize)>,
+        bounding_box_after: Option<(usize, usize, usize, usize)>,
+    ) -> Self {
+        Self {
+            births,
+            deaths,
+            bounding_box_before,
+            bounding_box_after,
+        }
+    }
+
+    /// The cells that are alive in the new state only
+    pub fn births(&self) -> &[(usize, usize)] {
+        &self.births
+    }
+
+    /// The cells that are alive in the old state only
+    pub fn deaths(&self) -> &[(usize, usize)] {
+        &self.deaths
+    }
+
+    /// Whether both states have the same living cells
+    pub fn is_empty(&self) -> bool {
+        self.births.is_empty() && self.deaths.is_empty()
+    }
+
+    /// The `(top, left, rows, columns)` area containing the living cells of
+    /// the old state, or `None` if there were none
+    pub fn bounding_box_before(&self) -> Option<(usize, usize, usize, usize)> {
+        self.bounding_box_before
+    }
+
+    /// The `(top, left, rows, columns)` area containing the living cells of
+    /// the new state, or `None` if there are none
+    pub fn bounding_box_after(&self) -> Option<(usize, usize, usize, usize)> {
+        self.bounding_box_after
+    }
+
+    /// How many `(rows, columns)` the top left corner of the bounding box moved
+    /// by, or `None` if either state has no living cells
+    pub fn bounding_box_offset(&self) -> Option<(isize, isize)> {
+        let (top_before, left_before, _, _) = self.bounding_box_before?;
+        let (top_after, left_after, _, _) = self.bounding_box_after?;
+        Some((
+            top_after as isize - top_before as isize,
+            left_after as isize - left_before as isize,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CellDelta;
+
+    #[test]
+    fn is_empty_without_births_nor_deaths() {
+        assert!(CellDelta::default().is_empty());
+        assert!(CellDelta::new(vec![], vec![], Some((1, 1, 2, 2)), Some((1, 1, 2, 2))).is_empty());
+        assert!(!CellDelta::new(vec![(0, 0)], vec![], None, Some((0, 0, 1, 1))).is_empty());
+        assert!(!CellDelta::new(vec![], vec![(0, 0)], Some((0, 0, 1, 1)), None).is_empty());
+    }
+
+    #[test]
+    fn bounding_box_offset_moves_top_left_corner() {
+        let delta = CellDelta::new(vec![], vec![], Some((4, 2, 3, 3)), Some((5, 1, 3, 3)));
+        assert_eq!(delta.bounding_box_offset(), Some((1, -1)));
+    }
+
+    #[test]
+    fn bounding_box_offset_needs_living_cells_in_both_states() {
+        let born = CellDelta::new(vec![(0, 0)], vec![], None, Some((0, 0, 1, 1)));
+        assert_eq!(born.bounding_box_offset(), None);
+
+        let died = CellDelta::new(vec![], vec![(0, 0)], Some((0, 0, 1, 1)), None);
+        assert_eq!(died.bounding_box_offset(), None);
+    }
+}
@@ -0,0 +1,171 @@
+use super::{Neighbourhood, UniverseRule};
+use std::fmt;
+use std::str::FromStr;
+
+/// The largest number of rows and columns of a bounded universe
+pub const UNIVERSE_MAX_SIZE: usize = 1000;
+
+/// The simulation engines a universe can be evolved with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Engine {
+    /// A fixed rows×columns grid wrapping around its edges, backed by `Universe`
+    Bounded,
+
+    /// An infinite plane storing only living cells, backed by `SparseUniverse`
+    Unbounded,
+}
+
+impl Engine {
+    pub const ALL: [Engine; 2] = [Engine::Bounded, Engine::Unbounded];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Engine::Bounded => "bounded",
+            Engine::Unbounded => "unbounded",
+        }
+    }
+
+    /// What this engine can simulate, so that options it does not support can
+    /// be turned off before they are used
+    pub fn capabilities(&self) -> EngineCapabilities {
+        match self {
+            Engine::Bounded => EngineCapabilities {
+                max_size: Some((UNIVERSE_MAX_SIZE, UNIVERSE_MAX_SIZE)),
+                birth_on_zero: true,
+                unbounded: false,
+                parallel: false,
+                freeze_zones: true,
+                injection: true,
+                dying_states: true,
+                neighbourhoods: true,
+                colors: true,
+                ants: true,
+                elementary: true,
+            },
+            Engine::Unbounded => EngineCapabilities {
+                max_size: None,
+                birth_on_zero: false,
+                unbounded: true,
+                parallel: false,
+                freeze_zones: false,
+                injection: false,
+                dying_states: false,
+                neighbourhoods: false,
+                colors: false,
+                ants: false,
+                elementary: false,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Engine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Engine {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Engine::ALL
+            .into_iter()
+            .find(|engine| engine.as_str() == s)
+            .ok_or(())
+    }
+}
+
+/// The features supported by an `Engine`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EngineCapabilities {
+    max_size: Option<(usize, usize)>,
+    birth_on_zero: bool,
+    unbounded: bool,
+    parallel: bool,
+    freeze_zones: bool,
+    injection: bool,
+    dying_states: bool,
+    neighbourhoods: bool,
+    colors: bool,
+    ants: bool,
+    elementary: bool,
+}
+
+impl EngineCapabilities {
+    /// The largest `(rows, columns)` universe, or `None` if there is no limit
+    pub fn max_size(&self) -> Option<(usize, usize)> {
+        self.max_size
+    }
+
+    /// Whether rules with birth on zero neighbours (`B0`) can be simulated
+    pub fn birth_on_zero(&self) -> bool {
+        self.birth_on_zero
+    }
+
+    /// Whether patterns can travel indefinitely instead of wrapping around
+    pub fn unbounded(&self) -> bool {
+        self.unbounded
+    }
+
+    /// Whether generations are computed on several threads
+    pub fn parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// Whether areas of the universe can be frozen
+    pub fn freeze_zones(&self) -> bool {
+        self.freeze_zones
+    }
+
+    /// Whether patterns can be injected from an edge
+    pub fn injection(&self) -> bool {
+        self.injection
+    }
+
+    /// Whether Generations rules, whose cells go through dying states, can be
+    /// simulated
+    pub fn dying_states(&self) -> bool {
+        self.dying_states
+    }
+
+    /// Whether rules counting other neighbours than the eight cells around,
+    /// such as hexagonal or von Neumann ones, can be simulated
+    pub fn neighbourhoods(&self) -> bool {
+        self.neighbourhoods
+    }
+
+    /// Whether rules telling living cells apart by color, such as Immigration
+    /// and QuadLife, can be simulated
+    pub fn colors(&self) -> bool {
+        self.colors
+    }
+
+    /// Whether ant rules, such as Langton's Ant, can be simulated
+    pub fn ants(&self) -> bool {
+        self.ants
+    }
+
+    /// Whether elementary rules, whose line of cells scrolls back every
+    /// generation, can be simulated
+    pub fn elementary(&self) -> bool {
+        self.elementary
+    }
+
+    pub fn supports_rule(&self, rule: &UniverseRule) -> bool {
+        (self.birth_on_zero || !rule.is_birth(0))
+            && (self.dying_states || !rule.has_dying_states())
+            && (self.neighbourhoods
+                || (rule.neighbourhood() == Neighbourhood::Moore && rule.range() == 1))
+            && (self.colors || !rule.is_colored())
+            && (self.ants || !rule.is_ant())
+            && (self.elementary || !rule.is_elementary())
+    }
+
+    /// Whether a `rows`x`columns` universe fits within the limits of the engine
+    pub fn supports_size(&self, rows: usize, columns: usize) -> bool {
+        self.max_size.map_or(true, |(max_rows, max_columns)| {
+            rows <= max_rows && columns <= max_columns
+        })
+    }
+}
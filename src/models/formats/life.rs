@@ -0,0 +1,227 @@
+use super::FormatError;
+use crate::{
+    Universe, UniverseCell, UniversePattern, UniversePointMatrix, UniverseRule, UNIVERSE_MAX_SIZE,
+};
+
+pub const LIFE_106_HEADER: &str = "#Life 1.06";
+pub const LIFE_105_HEADER: &str = "#Life 1.05";
+
+/// Builds a pattern spanning the bounding box of `cells`, given as
+/// `(row, column)` coordinates that may be negative. Patterns spanning more than
+/// `UNIVERSE_MAX_SIZE` cells on either side are rejected.
+fn pattern_from_cells(cells: &[(i64, i64)]) -> Result<UniversePattern, FormatError> {
+    let top = cells.iter().map(|(row, _)| *row).min().unwrap_or(0);
+    let left = cells.iter().map(|(_, column)| *column).min().unwrap_or(0);
+    let bottom = cells.iter().map(|(row, _)| *row).max().unwrap_or(0);
+    let right = cells.iter().map(|(_, column)| *column).max().unwrap_or(0);
+
+    let span = |first: i64, last: i64| {
+        last.checked_sub(first)
+            .and_then(|distance| distance.checked_add(1))
+            .and_then(|span| usize::try_from(span).ok())
+            .filter(|span| *span <= UNIVERSE_MAX_SIZE)
+            .ok_or(FormatError::Invalid)
+    };
+    let rows = span(top, bottom)?;
+    let columns = span(left, right)?;
+    let mut universe = Universe::new_empty(rows, columns);
+    for (row, column) in cells {
+        universe.set_cell(
+            (row - top) as usize,
+            (column - left) as usize,
+            UniverseCell::Alive,
+        );
+    }
+
+    Ok(UniversePattern::new(universe.snapshot()))
+}
+
+/// Reads a pattern in the Life 1.06 format: a `#Life 1.06` header followed by
+/// the `x y` coordinates of each living cell, one per line
+pub fn parse_life_106(text: &str) -> Result<UniversePattern, FormatError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some(LIFE_106_HEADER) {
+        return Err(FormatError::Invalid);
+    }
+
+    let mut cells: Vec<(i64, i64)> = vec![];
+    for line in lines.filter(|line| !line.starts_with('#')) {
+        let mut fields = line.split_whitespace().map(str::parse::<i64>);
+        match (fields.next(), fields.next(), fields.next()) {
+            (Some(Ok(x)), Some(Ok(y)), None) => cells.push((y, x)),
+            _ => return Err(FormatError::Invalid),
+        }
+    }
+
+    pattern_from_cells(&cells)
+}
+
+/// Writes a pattern in the Life 1.06 format. Coordinates are relative to the
+/// top left corner of the pattern.
+pub fn write_life_106(pattern: &UniversePattern) -> String {
+    let mut text = format!("{}\n", LIFE_106_HEADER);
+    for (row, column) in pattern.snapshot().iter_alive() {
+        text.push_str(&format!("{} {}\n", column, row));
+    }
+    text
+}
+
+/// Reads a pattern in the Life 1.05 format used by xlife: after the `#Life 1.05`
+/// header come `#D` description lines, an optional `#N` (Conway's rule) or
+/// `#R` line with the rule in S/B notation, then blocks of `.` and `*` rows, each
+/// introduced by a `#P x y` line placing its top left corner.
+pub fn parse_life_105(text: &str) -> Result<UniversePattern, FormatError> {
+    let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+    if lines.next() != Some(LIFE_105_HEADER) {
+        return Err(FormatError::Invalid);
+    }
+
+    let mut comments: Vec<String> = vec![];
+    let mut rule: Option<UniverseRule> = None;
+    let mut cells: Vec<(i64, i64)> = vec![];
+    let mut block: Option<(i64, i64)> = None;
+
+    for line in lines {
+        if let Some(line) = line.strip_prefix('#') {
+            let (kind, content) = line.split_at(line.len().min(1));
+            let content = content.trim();
+            match kind {
+                "D" | "C" => comments.push(content.to_string()),
+                "N" => rule = Some(UniverseRule::default()),
+                "R" => {
+                    rule = Some(
+                        content
+                            .parse::<UniverseRule>()
+                            .map_err(|_| FormatError::Invalid)?,
+                    )
+                }
+                "P" => {
+                    let mut fields = content.split_whitespace().map(str::parse::<i64>);
+                    block = match (fields.next(), fields.next()) {
+                        (Some(Ok(x)), Some(Ok(y))) => Some((y, x)),
+                        _ => return Err(FormatError::Invalid),
+                    };
+                }
+                _ => (),
+            }
+            continue;
+        }
+
+        // Rows before any #P line are placed at the origin
+        let (row, left) = block.get_or_insert((0, 0));
+        for (offset, c) in line.chars().enumerate() {
+            match c {
+                '.' => (),
+                '*' => {
+                    let column = left
+                        .checked_add(offset as i64)
+                        .ok_or(FormatError::Invalid)?;
+                    cells.push((*row, column));
+                }
+                _ => return Err(FormatError::Invalid),
+            }
+        }
+        *row = row.checked_add(1).ok_or(FormatError::Invalid)?;
+    }
+
+    let mut pattern = pattern_from_cells(&cells)?;
+    pattern.set_rule(rule);
+    for comment in comments {
+        pattern.add_comment(comment);
+    }
+
+    Ok(pattern)
+}
+
+/// Writes a pattern in the Life 1.05 format, as a single block centered on the
+/// origin. The name of the pattern is written as its first description line.
+pub fn write_life_105(pattern: &UniversePattern) -> String {
+    let snapshot = pattern.snapshot();
+    let mut text = format!("{}\n", LIFE_105_HEADER);
+
+    for description in pattern
+        .name()
+        .into_iter()
+        .chain(pattern.comments().iter().map(|comment| comment.as_str()))
+    {
+        text.push_str(&format!("#D {}\n", description));
+    }
+
+    match pattern.rule() {
+        Some(rule) if *rule != UniverseRule::default() => {
+            text.push_str(&format!("#R {}\n", rule.to_survival_birth_string()))
+        }
+        _ => text.push_str("#N\n"),
+    }
+
+    let columns = snapshot.columns();
+    text.push_str(&format!(
+        "#P {} {}\n",
+        -(columns as i64 / 2),
+        -(snapshot.rows() as i64 / 2)
+    ));
+
+    let mut alive_cells = vec![false; snapshot.rows() * columns];
+    for (row, column) in snapshot.iter_alive() {
+        alive_cells[row * columns + column] = true;
+    }
+
+    // Trailing dead cells are omitted, empty rows are written as a single dot
+    for cells in alive_cells.chunks(columns.max(1)) {
+        let length = cells
+            .iter()
+            .rposition(|alive| *alive)
+            .map(|last_alive| last_alive + 1)
+            .unwrap_or(0);
+        match length {
+            0 => text.push('.'),
+            _ => text.extend(cells[..length].iter().map(|alive| match alive {
+                true => '*',
+                false => '.',
+            })),
+        }
+        text.push('\n');
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_life_105, parse_life_106};
+    use crate::UniversePointMatrix;
+
+    #[test]
+    fn parses_negative_coordinates() {
+        let pattern = parse_life_106("#Life 1.06\n-1 -1\n1 0\n").expect("A valid pattern");
+        let snapshot = pattern.snapshot();
+        assert_eq!((snapshot.rows(), snapshot.columns()), (2, 3));
+        assert_eq!(
+            snapshot.iter_alive().collect::<Vec<_>>(),
+            vec![(0, 0), (1, 2)]
+        );
+    }
+
+    #[test]
+    fn rejects_extreme_coordinates() {
+        let text = format!("#Life 1.06\n{} 0\n{} 0\n", i64::MIN, i64::MAX);
+        assert!(parse_life_106(&text).is_err());
+
+        let text = format!("#Life 1.06\n0 {}\n0 {}\n", i64::MIN, i64::MAX);
+        assert!(parse_life_106(&text).is_err());
+    }
+
+    #[test]
+    fn rejects_spans_above_the_maximum() {
+        assert!(parse_life_106("#Life 1.06\n0 0\n100000 0\n").is_err());
+        assert!(parse_life_105("#Life 1.05\n#P 0 0\n*\n#P 0 100000\n*\n").is_err());
+    }
+
+    #[test]
+    fn rejects_blocks_running_past_the_largest_coordinate() {
+        let text = format!("#Life 1.05\n#P {} 0\n.*\n", i64::MAX);
+        assert!(parse_life_105(&text).is_err());
+
+        let text = format!("#Life 1.05\n#P 0 {}\n*\n*\n", i64::MAX);
+        assert!(parse_life_105(&text).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use super::FormatError;
+use crate::UniverseRule;
+
+/// The mime type metadata about copied patterns is offered as on the clipboard,
+/// alongside their RLE text
+pub const PATTERN_METADATA_MIME_TYPE: &str = "application/x-game-of-life-metadata";
+
+/// Where a copied pattern comes from: the rule and generation of the universe it
+/// was taken from, and the version of the application that copied it
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PatternMetadata {
+    rule: UniverseRule,
+    generation: u64,
+    version: String,
+}
+
+impl PatternMetadata {
+    pub fn new(rule: UniverseRule, generation: u64, version: &str) -> Self {
+        Self {
+            rule,
+            generation,
+            version: version.to_string(),
+        }
+    }
+
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Writes the metadata as `key = value` lines
+    pub fn write(&self) -> String {
+        format!(
+            "rule = {}\ngeneration = {}\nversion = {}\n",
+            self.rule, self.generation, self.version
+        )
+    }
+
+    /// Reads metadata written by `write`. Unknown keys are skipped, so that
+    /// newer versions can add their own.
+    pub fn parse(text: &str) -> Result<Self, FormatError> {
+        let mut rule: Option<UniverseRule> = None;
+        let mut generation: Option<u64> = None;
+        let mut version = String::new();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let (key, value) = line.split_once('=').ok_or(FormatError::Invalid)?;
+            let value = value.trim();
+            match key.trim() {
+                "rule" => rule = value.parse::<UniverseRule>().ok(),
+                "generation" => generation = value.parse::<u64>().ok(),
+                "version" => version = value.to_string(),
+                _ => (),
+            }
+        }
+
+        Ok(Self {
+            rule: rule.ok_or(FormatError::Invalid)?,
+            generation: generation.unwrap_or(0),
+            version,
+        })
+    }
+}
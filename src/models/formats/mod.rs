@@ -0,0 +1,91 @@
+mod life;
+mod metadata;
+mod plaintext;
+mod rle;
+
+pub use life::*;
+pub use metadata::*;
+pub use plaintext::*;
+pub use rle::*;
+
+use super::UniversePattern;
+
+#[derive(Debug)]
+pub enum FormatError {
+    /// The text is not a valid pattern for the format
+    Invalid,
+}
+
+/// The text formats patterns can be read from and written to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternFormat {
+    Rle,
+    Plaintext,
+    Life106,
+    Life105,
+}
+
+impl PatternFormat {
+    pub const ALL: [PatternFormat; 4] = [
+        PatternFormat::Rle,
+        PatternFormat::Plaintext,
+        PatternFormat::Life106,
+        PatternFormat::Life105,
+    ];
+
+    /// The file extension used for the format. Life 1.06 and Life 1.05 files
+    /// share the same one and are told apart by their header.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            PatternFormat::Rle => "rle",
+            PatternFormat::Plaintext => "cells",
+            PatternFormat::Life106 | PatternFormat::Life105 => "lif",
+        }
+    }
+
+    /// The format files with `extension` are written in, if any
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "rle" => Some(PatternFormat::Rle),
+            "cells" => Some(PatternFormat::Plaintext),
+            "lif" | "life" => Some(PatternFormat::Life106),
+            _ => None,
+        }
+    }
+
+    /// Recognizes the formats starting with a header line
+    pub fn detect(text: &str) -> Option<Self> {
+        match text.trim_start().lines().next().map(str::trim) {
+            Some(LIFE_106_HEADER) => Some(PatternFormat::Life106),
+            Some(LIFE_105_HEADER) => Some(PatternFormat::Life105),
+            _ => None,
+        }
+    }
+
+    pub fn parse(&self, text: &str) -> Result<UniversePattern, FormatError> {
+        match self {
+            PatternFormat::Rle => parse_rle(text),
+            PatternFormat::Plaintext => parse_plaintext(text),
+            PatternFormat::Life106 => parse_life_106(text),
+            PatternFormat::Life105 => parse_life_105(text),
+        }
+    }
+
+    pub fn write(&self, pattern: &UniversePattern) -> String {
+        match self {
+            PatternFormat::Rle => write_rle(pattern),
+            PatternFormat::Plaintext => write_plaintext(pattern),
+            PatternFormat::Life106 => write_life_106(pattern),
+            PatternFormat::Life105 => write_life_105(pattern),
+        }
+    }
+}
+
+/// Reads a pattern in any of the supported formats, recognizing Life 1.06 and
+/// Life 1.05 files by their header and trying RLE before plaintext otherwise
+pub fn parse_pattern(text: &str) -> Result<UniversePattern, FormatError> {
+    match PatternFormat::detect(text) {
+        Some(format) => format.parse(text),
+        None => parse_rle(text).or_else(|_| parse_plaintext(text)),
+    }
+}
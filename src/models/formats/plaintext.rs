@@ -0,0 +1,86 @@
+use super::FormatError;
+use crate::{Universe, UniverseCell, UniversePattern, UniversePointMatrix};
+
+/// Reads a pattern in the plaintext format of LifeWiki (`.cells` files): lines
+/// starting with `!` are comments, `!Name:` giving the name of the pattern, and
+/// the others are rows of `.` for dead cells and `O` for living ones. Rows may
+/// be shorter than the widest one, the missing cells being dead.
+pub fn parse_plaintext(text: &str) -> Result<UniversePattern, FormatError> {
+    let mut name: Option<String> = None;
+    let mut comments: Vec<String> = vec![];
+    let mut lines: Vec<&str> = vec![];
+
+    for line in text.lines() {
+        let line = line.trim_end();
+        if let Some(comment) = line.strip_prefix('!') {
+            match comment.strip_prefix("Name:") {
+                Some(value) => name = Some(value.trim().to_string()),
+                None => comments.push(comment.trim().to_string()),
+            }
+        } else {
+            lines.push(line);
+        }
+    }
+
+    // Blank lines before and after the pattern are not rows of it
+    while lines.last().map(|line| line.is_empty()).unwrap_or(false) {
+        lines.pop();
+    }
+    let first_row = lines
+        .iter()
+        .position(|line| !line.is_empty())
+        .ok_or(FormatError::Invalid)?;
+    let lines = &lines[first_row..];
+
+    let rows = lines.len();
+    let columns = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut universe = Universe::new_empty(rows, columns.max(1));
+
+    for (row, line) in lines.iter().enumerate() {
+        for (column, c) in line.chars().enumerate() {
+            match c {
+                '.' => (),
+                'O' | '*' => universe.set_cell(row, column, UniverseCell::Alive),
+                _ => return Err(FormatError::Invalid),
+            }
+        }
+    }
+
+    let mut pattern = UniversePattern::new(universe.snapshot());
+    pattern.set_name(name);
+    for comment in comments {
+        pattern.add_comment(comment);
+    }
+
+    Ok(pattern)
+}
+
+/// Writes a pattern in the plaintext format. Rows are written in full, so that
+/// the pattern keeps its size when read back.
+pub fn write_plaintext(pattern: &UniversePattern) -> String {
+    let snapshot = pattern.snapshot();
+    let mut text = String::new();
+
+    if let Some(name) = pattern.name() {
+        text.push_str(&format!("!Name: {}\n", name));
+    }
+    for comment in pattern.comments() {
+        text.push_str(&format!("!{}\n", comment));
+    }
+
+    let columns = snapshot.columns();
+    let mut cells = vec!['.'; snapshot.rows() * columns];
+    for (row, column) in snapshot.iter_alive() {
+        cells[row * columns + column] = 'O';
+    }
+
+    for row in cells.chunks(columns.max(1)) {
+        text.extend(row.iter());
+        text.push('\n');
+    }
+    text
+}
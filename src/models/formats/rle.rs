@@ -0,0 +1,207 @@
+use super::FormatError;
+use crate::{
+    Universe, UniverseCell, UniversePattern, UniversePointMatrix, UniverseRule, UNIVERSE_MAX_SIZE,
+};
+
+/// Lines of the encoded pattern are kept within this length, as the format mandates
+const RLE_LINE_LENGTH: usize = 70;
+
+/// Reads a pattern in the run length encoded format used by Golly and LifeWiki.
+/// Multi-state cells are read as living cells.
+pub fn parse_rle(text: &str) -> Result<UniversePattern, FormatError> {
+    let mut name: Option<String> = None;
+    let mut comments: Vec<String> = vec![];
+    let mut size: Option<(usize, usize)> = None;
+    let mut rule: Option<UniverseRule> = None;
+    let mut body = String::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix('#') {
+            let (kind, content) = comment.split_at(comment.len().min(1));
+            let content = content.trim().to_string();
+            match kind {
+                "N" => name = Some(content),
+                "C" | "c" | "O" => comments.push(content),
+                _ => (),
+            }
+        } else if size.is_none() && line.starts_with('x') {
+            let mut columns = None;
+            let mut rows = None;
+
+            // The rule comes last, and Larger than Life rules have commas of their own
+            let (fields, rule_field) = match line.find("rule") {
+                Some(index) => line.split_at(index),
+                None => (line, ""),
+            };
+            if let Some((_, value)) = rule_field.split_once('=') {
+                rule = value.trim().parse::<UniverseRule>().ok();
+            }
+
+            for field in fields.split(',').filter(|field| !field.trim().is_empty()) {
+                let (key, value) = field.split_once('=').ok_or(FormatError::Invalid)?;
+                let value = value.trim();
+                match key.trim() {
+                    "x" => columns = value.parse::<usize>().ok(),
+                    "y" => rows = value.parse::<usize>().ok(),
+                    _ => (),
+                }
+            }
+            size = Some((
+                rows.ok_or(FormatError::Invalid)?,
+                columns.ok_or(FormatError::Invalid)?,
+            ));
+        } else {
+            body.push_str(line);
+            if line.contains('!') {
+                break;
+            }
+        }
+    }
+
+    let (rows, columns) = size.ok_or(FormatError::Invalid)?;
+    if rows > UNIVERSE_MAX_SIZE || columns > UNIVERSE_MAX_SIZE {
+        return Err(FormatError::Invalid);
+    }
+    let mut universe = Universe::new_empty(rows.max(1), columns.max(1));
+    let (mut row, mut column, mut run) = (0usize, 0usize, 0usize);
+
+    for c in body.chars() {
+        match c {
+            '0'..='9' => run = run * 10 + c.to_digit(10).unwrap() as usize,
+            '!' => break,
+            '$' => {
+                row += run.max(1);
+                column = 0;
+                run = 0;
+            }
+            'b' | '.' => {
+                column += run.max(1);
+                run = 0;
+            }
+            c if c.is_ascii_alphabetic() => {
+                for _ in 0..run.max(1) {
+                    if row >= rows || column >= columns {
+                        return Err(FormatError::Invalid);
+                    }
+                    universe.set_cell(row, column, UniverseCell::Alive);
+                    column += 1;
+                }
+                run = 0;
+            }
+            c if c.is_whitespace() => (),
+            _ => return Err(FormatError::Invalid),
+        }
+    }
+
+    let mut pattern = UniversePattern::new(universe.snapshot());
+    pattern.set_name(name);
+    pattern.set_rule(rule);
+    for comment in comments {
+        pattern.add_comment(comment);
+    }
+
+    Ok(pattern)
+}
+
+/// Appends a run of `count` times `tag` to `lines`, wrapping lines before they
+/// grow longer than `RLE_LINE_LENGTH`
+fn push_run(lines: &mut Vec<String>, count: usize, tag: char) {
+    if count == 0 {
+        return;
+    }
+
+    let item = match count {
+        1 => tag.to_string(),
+        _ => format!("{}{}", count, tag),
+    };
+
+    match lines.last_mut() {
+        Some(line) if line.len() + item.len() <= RLE_LINE_LENGTH => line.push_str(&item),
+        _ => lines.push(item),
+    }
+}
+
+/// Writes a pattern in the run length encoded format. Trailing dead cells of each
+/// line are omitted, and runs of empty lines are collapsed into a single `$` run.
+pub fn write_rle(pattern: &UniversePattern) -> String {
+    let snapshot = pattern.snapshot();
+    let mut text = String::new();
+
+    if let Some(name) = pattern.name() {
+        text.push_str(&format!("#N {}\n", name));
+    }
+    for comment in pattern.comments() {
+        text.push_str(&format!("#C {}\n", comment));
+    }
+
+    let rule = pattern.rule().cloned().unwrap_or_default();
+    text.push_str(&format!(
+        "x = {}, y = {}, rule = {}\n",
+        snapshot.columns(),
+        snapshot.rows(),
+        rule
+    ));
+
+    let columns = snapshot.columns();
+    let mut alive_cells = vec![false; snapshot.rows() * columns];
+    for (row, column) in snapshot.iter_alive() {
+        alive_cells[row * columns + column] = true;
+    }
+
+    let mut lines: Vec<String> = vec![];
+    let mut pending_rows = 0;
+    for cells in alive_cells.chunks(columns.max(1)) {
+        let length = match cells.iter().rposition(|alive| *alive) {
+            Some(last_alive) => last_alive + 1,
+            None => {
+                pending_rows += 1;
+                continue;
+            }
+        };
+
+        push_run(&mut lines, pending_rows, '$');
+        pending_rows = 1;
+
+        let mut column = 0;
+        while column < length {
+            let alive = cells[column];
+            let run = cells[column..length]
+                .iter()
+                .take_while(|cell| **cell == alive)
+                .count();
+            push_run(&mut lines, run, if alive { 'o' } else { 'b' });
+            column += run;
+        }
+    }
+    push_run(&mut lines, 1, '!');
+
+    text.push_str(&lines.join("\n"));
+    text.push('\n');
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_rle;
+    use crate::UniversePointMatrix;
+
+    #[test]
+    fn parses_size_from_header() {
+        let pattern = parse_rle("x = 3, y = 1\n3o!").expect("A valid pattern");
+        let snapshot = pattern.snapshot();
+        assert_eq!((snapshot.rows(), snapshot.columns()), (1, 3));
+        assert_eq!(snapshot.iter_alive().count(), 3);
+    }
+
+    #[test]
+    fn rejects_sizes_above_the_maximum() {
+        assert!(parse_rle("x = 100000, y = 100000\no!").is_err());
+        assert!(parse_rle("x = 1, y = 100000\no!").is_err());
+        assert!(parse_rle("x = 100000, y = 1\no!").is_err());
+    }
+}
@@ -0,0 +1,91 @@
+use super::BitGrid;
+use std::collections::VecDeque;
+
+/// The most generations a heatmap can span
+pub const HEATMAP_MAX_GENERATIONS: usize = 1000;
+
+/// Tells how often each cell has been alive over the last generations of a
+/// universe, telling settled ash apart from active fronts. Each cell has a
+/// counter of the generations it was alive in, the oldest generation being
+/// taken off the counters once a newer one comes in past the window.
+#[derive(Clone, Debug)]
+pub struct ActivityHeatmap {
+    rows: usize,
+    columns: usize,
+    window: usize,
+    counts: Vec<u16>,
+    generations: VecDeque<BitGrid>,
+    last_generation: Option<u64>,
+}
+
+impl ActivityHeatmap {
+    /// Creates a heatmap spanning the last `window` generations, up to
+    /// `HEATMAP_MAX_GENERATIONS`
+    pub fn new(window: usize) -> Self {
+        Self {
+            rows: 0,
+            columns: 0,
+            window: window.clamp(1, HEATMAP_MAX_GENERATIONS),
+            counts: vec![],
+            generations: VecDeque::new(),
+            last_generation: None,
+        }
+    }
+
+    /// The number of generations the heatmap spans
+    pub fn window(&self) -> usize {
+        self.window
+    }
+
+    /// Spans the last `value` generations instead, forgetting older ones
+    pub fn set_window(&mut self, value: usize) {
+        self.window = value.clamp(1, HEATMAP_MAX_GENERATIONS);
+        while self.generations.len() > self.window {
+            self.drop_oldest();
+        }
+    }
+
+    /// Records the living `cells` of `generation`. Generations that do not
+    /// come after the last one recorded, or cells of another size, start over.
+    pub fn record(&mut self, generation: u64, cells: &BitGrid) {
+        let follows = self.last_generation.is_some_and(|last| generation > last);
+        if !follows || (cells.rows(), cells.columns()) != (self.rows, self.columns) {
+            self.rows = cells.rows();
+            self.columns = cells.columns();
+            self.clear();
+        }
+
+        if self.generations.len() == self.window {
+            self.drop_oldest();
+        }
+        for (row, column) in cells.iter_alive() {
+            self.counts[row * self.columns + column] += 1;
+        }
+        self.generations.push_back(cells.clone());
+        self.last_generation = Some(generation);
+    }
+
+    /// Forgets every generation recorded
+    pub fn clear(&mut self) {
+        self.counts = vec![0; self.rows * self.columns];
+        self.generations.clear();
+        self.last_generation = None;
+    }
+
+    fn drop_oldest(&mut self) {
+        if let Some(oldest) = self.generations.pop_front() {
+            for (row, column) in oldest.iter_alive() {
+                self.counts[row * self.columns + column] -= 1;
+            }
+        }
+    }
+
+    /// The share of the generations recorded the cell at `row`x`column` was
+    /// alive in, from 0 to 1. Cells out of the heatmap never were.
+    pub fn heat(&self, row: usize, column: usize) -> f64 {
+        if row >= self.rows || column >= self.columns || self.generations.is_empty() {
+            return 0.0;
+        }
+        self.counts[row * self.columns + column] as f64 / self.generations.len() as f64
+    }
+}
@@ -0,0 +1,244 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// The `(row, column)` offsets of the eight neighbours of a cell, going round
+/// from the one on the previous row. Arrangements of living neighbours have a
+/// bit per neighbour, in this order.
+pub const HENSEL_NEIGHBOURS: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// The letters telling apart the arrangements of 0 to 8 living neighbours,
+/// in the order rulestrings write them
+const LETTERS: [&str; 9] = [
+    "",
+    "ce",
+    "cekain",
+    "cekainyqjr",
+    "cekainyqjrtwz",
+    "cekainyqjr",
+    "cekain",
+    "ce",
+    "",
+];
+
+/// An arrangement of each letter of 0 to 4 living neighbours. Those of 5 to 8
+/// are their complements: the letter of the cells left dead.
+const REPRESENTATIVES: [&[u8]; 5] = [
+    &[0x00],
+    &[0x02, 0x01],
+    &[0x0a, 0x05, 0x09, 0x03, 0x11, 0x22],
+    &[0x2a, 0x15, 0x25, 0x07, 0x83, 0x0b, 0x29, 0x23, 0x43, 0x13],
+    &[
+        0xaa, 0x55, 0x4b, 0x0f, 0x1b, 0x8b, 0x2b, 0x27, 0x53, 0x17, 0x93, 0x63, 0x33,
+    ],
+];
+
+/// The letters of the arrangements of `count` living neighbours. There are
+/// none for 0 and 8, which can only be arranged one way.
+pub fn hensel_letters(count: u8) -> &'static str {
+    LETTERS.get(count as usize).copied().unwrap_or_default()
+}
+
+/// The number of arrangements of `count` living neighbours that are told
+/// apart, those that only differ by a rotation or a reflection being the same
+pub fn hensel_arrangements(count: u8) -> usize {
+    match count {
+        0..=8 => hensel_letters(count).len().max(1),
+        _ => 0,
+    }
+}
+
+/// The arrangement of `count` living neighbours the letter at `index` stands
+/// for, a bit per neighbour of `HENSEL_NEIGHBOURS`
+pub fn hensel_arrangement(count: u8, index: usize) -> u8 {
+    match count {
+        0..=4 => REPRESENTATIVES[count as usize][index],
+        _ => !REPRESENTATIVES[8 - count as usize][index],
+    }
+}
+
+/// `neighbours` turned by `symmetry % 4` quarter turns, and mirrored first
+/// from 4 on
+fn transform(neighbours: u8, symmetry: u8) -> u8 {
+    let neighbours = match symmetry >= 4 {
+        true => (0..8)
+            .filter(|bit| neighbours >> bit & 1 == 1)
+            .fold(0, |mirrored, bit| mirrored | 1 << ((8 - bit) % 8)),
+        false => neighbours,
+    };
+    neighbours.rotate_left(2 * (symmetry as u32 % 4))
+}
+
+/// The number of living neighbours and the index of the letter of each of
+/// the 256 arrangements
+fn classes() -> &'static [(u8, u8); 256] {
+    static CLASSES: OnceLock<[(u8, u8); 256]> = OnceLock::new();
+    CLASSES.get_or_init(|| {
+        let mut classes = [(0, 0); 256];
+        for count in 0..=8 {
+            for index in 0..hensel_arrangements(count) {
+                let arrangement = hensel_arrangement(count, index);
+                for symmetry in 0..8 {
+                    classes[transform(arrangement, symmetry) as usize] = (count, index as u8);
+                }
+            }
+        }
+        classes
+    })
+}
+
+/// The arrangements of living neighbours an isotropic rule is met for, told
+/// by their number and letter in Hensel notation as in `2-a3ij`: a count
+/// alone stands for all of its arrangements, followed by letters for only
+/// those or by `-` and letters for all but those. Arrangements that only
+/// differ by a rotation or a reflection are the same.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HenselConditions {
+    /// For each count, a bit per letter index
+    letters: [u16; 9],
+}
+
+impl HenselConditions {
+    /// All the arrangements of the enabled counts
+    pub fn from_counts(counts: &[bool]) -> Self {
+        let mut conditions = Self::default();
+        for (count, _) in counts.iter().take(9).enumerate().filter(|(_, on)| **on) {
+            conditions.letters[count] = all_letters(count as u8);
+        }
+        conditions
+    }
+
+    /// Whether each count is met for all of its arrangements or none, as in
+    /// totalistic rules
+    pub fn is_totalistic(&self) -> bool {
+        (0..=8).all(|count| {
+            let letters = self.letters[count as usize];
+            letters == 0 || letters == all_letters(count)
+        })
+    }
+
+    /// Whether the condition is met for some arrangement of `count`
+    pub fn has_count(&self, count: u8) -> bool {
+        self.letters
+            .get(count as usize)
+            .is_some_and(|letters| *letters != 0)
+    }
+
+    /// The counts the condition is met for some arrangement of
+    pub fn counts(&self) -> Vec<bool> {
+        (0..=8).map(|count| self.has_count(count)).collect()
+    }
+
+    /// Whether the condition is met for the arrangement of `count` whose
+    /// letter is at `index`
+    pub fn contains(&self, count: u8, index: usize) -> bool {
+        self.letters
+            .get(count as usize)
+            .is_some_and(|letters| letters >> index & 1 == 1)
+    }
+
+    pub fn set(&mut self, count: u8, index: usize, enabled: bool) {
+        if index >= hensel_arrangements(count) {
+            return;
+        }
+        match enabled {
+            true => self.letters[count as usize] |= 1 << index,
+            false => self.letters[count as usize] &= !(1 << index),
+        }
+    }
+
+    /// Whether the condition is met for `neighbours`, a bit per living
+    /// neighbour of `HENSEL_NEIGHBOURS`
+    pub fn holds_for(&self, neighbours: u8) -> bool {
+        let (count, index) = classes()[neighbours as usize];
+        self.contains(count, index as usize)
+    }
+}
+
+fn all_letters(count: u8) -> u16 {
+    (1 << hensel_arrangements(count)) - 1
+}
+
+impl FromStr for HenselConditions {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid Hensel conditions {}", value);
+        let mut conditions = Self::default();
+        // The count being read, whether its letters are left out and its letters
+        let mut current: Option<(u8, bool, u16)> = None;
+        let mut flush = |current: Option<(u8, bool, u16)>| match current {
+            Some((_, true, 0)) => Err(invalid()),
+            Some((count, negated, letters)) => {
+                let all = all_letters(count);
+                conditions.letters[count as usize] |= match (negated, letters) {
+                    (false, 0) => all,
+                    (false, letters) => letters,
+                    (true, letters) => all & !letters,
+                };
+                Ok(())
+            }
+            None => Ok(()),
+        };
+
+        for c in value.chars() {
+            match (c, current.as_mut()) {
+                ('0'..='8', _) => {
+                    flush(current)?;
+                    current = Some((c as u8 - b'0', false, 0));
+                }
+                ('-', Some((_, negated @ false, 0))) => *negated = true,
+                (letter, Some((count, _, letters))) => {
+                    let index = hensel_letters(*count)
+                        .find(letter.to_ascii_lowercase())
+                        .ok_or_else(invalid)?;
+                    *letters |= 1 << index;
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        flush(current)?;
+        Ok(conditions)
+    }
+}
+
+impl fmt::Display for HenselConditions {
+    /// Writes each count with the letters of its arrangements, or those left
+    /// out after `-` when they are fewer
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for count in 0..=8u8 {
+            let letters = self.letters[count as usize];
+            if letters == 0 {
+                continue;
+            }
+
+            write!(f, "{}", count)?;
+            let all = all_letters(count);
+            if letters == all {
+                continue;
+            }
+            let (prefix, shown) = match letters.count_ones() * 2 > all.count_ones() {
+                true => ("-", all & !letters),
+                false => ("", letters),
+            };
+            let shown: String = hensel_letters(count)
+                .chars()
+                .enumerate()
+                .filter(|(index, _)| shown >> index & 1 == 1)
+                .map(|(_, letter)| letter)
+                .collect();
+            write!(f, "{}{}", prefix, shown)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use super::BitGrid;
+use std::collections::VecDeque;
+
+/// The recent generations of a universe, to rewind it. Only living cells are
+/// kept, bit-packed, so that a few hundred generations of a large universe
+/// still fit in a few megabytes. The states of dying cells are kept as well
+/// for rules that have them.
+#[derive(Clone, Debug, Default)]
+pub struct UniverseHistory {
+    capacity: usize,
+    states: VecDeque<(u64, BitGrid, Option<Vec<u8>>)>,
+}
+
+impl UniverseHistory {
+    /// Changes how many generations are kept, forgetting the oldest ones if needed
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.states.len() > self.capacity {
+            self.states.pop_front();
+        }
+    }
+
+    /// Records the cells of `generation`, with the states of its `dying` cells
+    /// if any. Generations recorded after it are forgotten, as they belonged to
+    /// a timeline that was rewound.
+    pub fn record(&mut self, generation: u64, cells: &BitGrid, dying: Option<&[u8]>) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        while self
+            .states
+            .back()
+            .is_some_and(|(recorded, _, _)| *recorded >= generation)
+        {
+            self.states.pop_back();
+        }
+
+        if self.states.len() == self.capacity {
+            self.states.pop_front();
+        }
+        self.states
+            .push_back((generation, cells.clone(), dying.map(<[u8]>::to_vec)));
+    }
+
+    /// The living cells of `generation` and the states of its dying cells, if
+    /// it is still recorded
+    pub fn get(&self, generation: u64) -> Option<(&BitGrid, Option<&[u8]>)> {
+        let (first, _, _) = self.states.front()?;
+        let index = generation.checked_sub(*first)? as usize;
+        self.states
+            .get(index)
+            .filter(|(recorded, _, _)| *recorded == generation)
+            .map(|(_, cells, dying)| (cells, dying.as_deref()))
+    }
+
+    /// The `(first, last)` generations recorded, if any
+    pub fn range(&self) -> Option<(u64, u64)> {
+        Some((self.states.front()?.0, self.states.back()?.0))
+    }
+
+    pub fn clear(&mut self) {
+        self.states.clear();
+    }
+}
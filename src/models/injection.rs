@@ -0,0 +1,113 @@
+use super::{UniversePointMatrix, UniverseSnapshot};
+use std::str::FromStr;
+
+/// The edge of the universe, as shown on screen, patterns are injected from.
+/// Rows of the universe are laid out horizontally, so the top edge is the
+/// first column and the left edge is the first row.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InjectionEdge {
+    #[default]
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl InjectionEdge {
+    pub const ALL: [InjectionEdge; 4] = [
+        InjectionEdge::Top,
+        InjectionEdge::Right,
+        InjectionEdge::Bottom,
+        InjectionEdge::Left,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InjectionEdge::Top => "top",
+            InjectionEdge::Right => "right",
+            InjectionEdge::Bottom => "bottom",
+            InjectionEdge::Left => "left",
+        }
+    }
+}
+
+impl FromStr for InjectionEdge {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        InjectionEdge::ALL
+            .into_iter()
+            .find(|edge| edge.as_str() == value)
+            .ok_or_else(|| format!("Unknown injection edge {}", value))
+    }
+}
+
+/// A stage of the evolution that stamps a pattern on an edge of the universe
+/// every `period` generations, such as a stream of gliders to throw at
+/// reflectors and eaters. The pattern is given as injected from the top edge,
+/// and rotated so that the same side faces the chosen edge.
+#[derive(Clone, Debug)]
+pub struct UniverseInjection {
+    pattern: UniverseSnapshot,
+    edge: InjectionEdge,
+    period: u32,
+}
+
+impl PartialEq for UniverseInjection {
+    fn eq(&self, other: &Self) -> bool {
+        self.edge == other.edge
+            && self.period == other.period
+            && self.pattern.rows() == other.pattern.rows()
+            && self.pattern.columns() == other.pattern.columns()
+            && self.pattern.iter_alive().eq(other.pattern.iter_alive())
+    }
+}
+
+impl UniverseInjection {
+    pub fn new(pattern: UniverseSnapshot, edge: InjectionEdge, period: u32) -> Self {
+        Self {
+            pattern,
+            edge,
+            period: period.max(1),
+        }
+    }
+
+    pub fn edge(&self) -> InjectionEdge {
+        self.edge
+    }
+
+    pub fn period(&self) -> u32 {
+        self.period
+    }
+
+    /// The `(row, column)` coordinates of the cells to bring to life in a
+    /// `rows`x`columns` universe reaching `generation`. The pattern is centered
+    /// along the edge and wraps around if larger than the universe.
+    pub fn cells(&self, generation: u64, rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        if rows == 0 || columns == 0 || generation % self.period as u64 != 0 {
+            return vec![];
+        }
+
+        let (pattern_rows, pattern_columns) = (self.pattern.rows(), self.pattern.columns());
+        let centered = |length: usize, size: usize| (length.saturating_sub(size)) / 2;
+        self.pattern
+            .iter_alive()
+            .map(|(row, column)| match self.edge {
+                InjectionEdge::Top => (centered(rows, pattern_rows) + row, column),
+                InjectionEdge::Bottom => (
+                    centered(rows, pattern_rows) + pattern_rows - 1 - row,
+                    columns.saturating_sub(pattern_columns) + pattern_columns - 1 - column,
+                ),
+                InjectionEdge::Left => (
+                    column,
+                    centered(columns, pattern_rows) + pattern_rows - 1 - row,
+                ),
+                InjectionEdge::Right => (
+                    rows.saturating_sub(pattern_columns) + pattern_columns - 1 - column,
+                    centered(columns, pattern_rows) + row,
+                ),
+            })
+            .map(|(row, column)| (row % rows, column % columns))
+            .collect()
+    }
+}
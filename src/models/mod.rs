@@ -1,5 +1,47 @@
+//! The simulation core: universes, rules, history and pattern formats. It does
+//! not depend on GTK, the application is built on top of it.
+
+mod ant;
+mod bit_grid;
+mod census;
+mod chance;
+mod delta;
+mod engine;
+pub mod formats;
+mod heatmap;
+mod hensel;
+mod history;
+mod injection;
+mod neighbourhood;
+mod pattern;
 mod prelude;
+mod rain;
+mod rule;
+mod soup;
+mod sparse_universe;
+mod stabilization;
+mod tool;
 mod universe;
+mod wireworld;
 
+pub use ant::*;
+pub use bit_grid::*;
+pub use census::*;
+pub use chance::*;
+pub use delta::*;
+pub use engine::*;
+pub use heatmap::*;
+pub use hensel::*;
+pub use history::*;
+pub use injection::*;
+pub use neighbourhood::*;
+pub use pattern::*;
 pub use prelude::*;
+pub use rain::*;
+pub use rule::*;
+pub use soup::*;
+pub use sparse_universe::*;
+pub use stabilization::*;
+pub use tool::*;
 pub use universe::*;
+pub use wireworld::*;
@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// The farthest, in cells, neighbours can be from a cell
+pub const NEIGHBOURHOOD_MAX_RANGE: u8 = 10;
+
+/// The cells around a cell whose living neighbours a rule counts. Moore and
+/// von Neumann neighbourhoods reach as far as their range, 1 for the cells
+/// touching a cell.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Neighbourhood {
+    /// The cells within range along both rows and columns, the eight
+    /// surrounding a cell at range 1
+    #[default]
+    Moore,
+
+    /// The cells within range in as many steps along rows and columns, the
+    /// four touching the sides of a cell at range 1
+    VonNeumann,
+
+    /// The six cells touching a hexagonal cell. Hexagons are laid out in
+    /// offset columns, every odd column being shifted by half a cell along
+    /// the rows: cells in even columns touch the previous row of the columns
+    /// beside them, cells in odd columns the next one. The tiling only wraps
+    /// around seamlessly with an even number of columns.
+    Hexagonal,
+}
+
+impl Neighbourhood {
+    pub const ALL: [Neighbourhood; 3] = [
+        Neighbourhood::Moore,
+        Neighbourhood::VonNeumann,
+        Neighbourhood::Hexagonal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Neighbourhood::Moore => "moore",
+            Neighbourhood::VonNeumann => "von-neumann",
+            Neighbourhood::Hexagonal => "hexagonal",
+        }
+    }
+
+    /// The farthest range this neighbourhood can reach
+    pub fn max_range(&self) -> u8 {
+        match self {
+            Neighbourhood::Hexagonal => 1,
+            _ => NEIGHBOURHOOD_MAX_RANGE,
+        }
+    }
+
+    /// The number of neighbours of a cell at `range`, and so the largest
+    /// count a rule can ask for
+    pub fn size(&self, range: u8) -> u16 {
+        let range = range as u16;
+        match self {
+            Neighbourhood::Moore => (2 * range + 1).pow(2) - 1,
+            Neighbourhood::VonNeumann => 2 * range * (range + 1),
+            Neighbourhood::Hexagonal => 6,
+        }
+    }
+
+    /// The `(row, column)` offsets of the neighbours of a cell at `range`.
+    /// Hexagonal cells in odd columns have the neighbours of cells in even
+    /// columns mirrored, these are the latter.
+    pub fn offsets(&self, range: u8) -> Vec<(isize, isize)> {
+        let range = range as isize;
+        let mut offsets = vec![];
+        for delta_row in -range..=range {
+            for delta_column in -range..=range {
+                let within = match self {
+                    Neighbourhood::Moore => true,
+                    Neighbourhood::VonNeumann => delta_row.abs() + delta_column.abs() <= range,
+                    Neighbourhood::Hexagonal => delta_column == 0 || delta_row <= 0,
+                };
+                if within && (delta_row, delta_column) != (0, 0) {
+                    offsets.push((delta_row, delta_column));
+                }
+            }
+        }
+        offsets
+    }
+
+    /// The letter rulestrings using this neighbourhood end with, followed by
+    /// the range unless it is 1
+    fn letter(&self) -> char {
+        match self {
+            Neighbourhood::Moore => 'M',
+            Neighbourhood::VonNeumann => 'V',
+            Neighbourhood::Hexagonal => 'H',
+        }
+    }
+
+    /// What rulestrings using this neighbourhood at `range` end with, as in
+    /// `B2/S13V` or `B3/S23M2`. The Moore neighbourhood at range 1 is the
+    /// default and has none.
+    pub fn suffix(&self, range: u8) -> String {
+        match (self, range) {
+            (Neighbourhood::Moore, 1) => String::new(),
+            (_, 1) => self.letter().to_string(),
+            _ => format!("{}{}", self.letter(), range),
+        }
+    }
+
+    /// The neighbourhood rulestrings ending with `letter` use, if any
+    pub fn from_letter(letter: char) -> Option<Neighbourhood> {
+        Neighbourhood::ALL
+            .into_iter()
+            .find(|neighbourhood| neighbourhood.letter() == letter.to_ascii_uppercase())
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Neighbourhood::ALL
+            .into_iter()
+            .find(|neighbourhood| neighbourhood.as_str() == value)
+            .ok_or_else(|| format!("Unknown neighbourhood {}", value))
+    }
+}
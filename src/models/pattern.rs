@@ -0,0 +1,51 @@
+use super::{UniverseRule, UniverseSnapshot};
+
+/// A pattern, as read from a pattern file or from the bundled library. The cells
+/// of the pattern are kept in a snapshot that spans its bounding box.
+#[derive(Clone, Debug)]
+pub struct UniversePattern {
+    name: Option<String>,
+    comments: Vec<String>,
+    rule: Option<UniverseRule>,
+    snapshot: UniverseSnapshot,
+}
+
+impl UniversePattern {
+    pub fn new(snapshot: UniverseSnapshot) -> Self {
+        Self {
+            name: None,
+            comments: vec![],
+            rule: None,
+            snapshot,
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn set_name(&mut self, value: Option<String>) {
+        self.name = value;
+    }
+
+    pub fn comments(&self) -> &[String] {
+        &self.comments
+    }
+
+    pub fn add_comment(&mut self, value: String) {
+        self.comments.push(value);
+    }
+
+    /// The rule the pattern was designed for, if the file specified one
+    pub fn rule(&self) -> Option<&UniverseRule> {
+        self.rule.as_ref()
+    }
+
+    pub fn set_rule(&mut self, value: Option<UniverseRule>) {
+        self.rule = value;
+    }
+
+    pub fn snapshot(&self) -> &UniverseSnapshot {
+        &self.snapshot
+    }
+}
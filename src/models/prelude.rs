@@ -1,24 +1,7 @@
-use gtk::glib;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-#[derive(Clone, Debug, glib::Enum, Copy, PartialEq)]
-#[enum_type(name = "UniverseGridMode")]
-pub enum UniverseGridMode {
-    /// The grid can receive interactive inputs, such as mouse clicks
-    Unlocked = 0,
-
-    /// The grid will not receive interactive inputs
-    Locked = 1,
-}
-
-impl Default for UniverseGridMode {
-    fn default() -> Self {
-        Self::Locked
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum UniverseCell {
     Dead = 0,
     Alive = 1,
@@ -92,6 +75,63 @@ impl UniversePoint {
     }
 }
 
+/// A rectangular area of a universe, with its top left corner at `row`x`column`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniverseArea {
+    row: usize,
+    column: usize,
+    rows: usize,
+    columns: usize,
+}
+
+impl UniverseArea {
+    pub fn new(row: usize, column: usize, rows: usize, columns: usize) -> Self {
+        Self {
+            row,
+            column,
+            rows,
+            columns,
+        }
+    }
+
+    /// The area spanning from one corner cell to the opposite one, in any order
+    pub fn from_corners(first: (usize, usize), second: (usize, usize)) -> Self {
+        let (top, bottom) = (first.0.min(second.0), first.0.max(second.0));
+        let (left, right) = (first.1.min(second.1), first.1.max(second.1));
+        Self::new(top, left, bottom - top + 1, right - left + 1)
+    }
+
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Whether this area and `other` have cells in common
+    pub fn intersects(&self, other: &UniverseArea) -> bool {
+        self.row < other.row + other.rows
+            && other.row < self.row + self.rows
+            && self.column < other.column + other.columns
+            && other.column < self.column + self.columns
+    }
+
+    /// Iterates over the coordinates of the cells in this area, as `(row, column)`
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> {
+        let (row, column, columns) = (self.row, self.column, self.columns);
+        (0..self.rows * self.columns).map(move |idx| (row + idx / columns, column + idx % columns))
+    }
+}
+
 pub trait UniversePointMatrix {
     type SetCellError;
 
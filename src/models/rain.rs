@@ -0,0 +1,71 @@
+use rand::Rng;
+
+/// Where random cells are injected by `UniverseRain`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RainPlacement {
+    /// Anywhere in the universe
+    Uniform,
+
+    /// Only on the outermost rows and columns
+    Edges,
+}
+
+/// A perturbation that brings random cells to life at every generation,
+/// keeping soups from dying out. The rate is the average number of cells
+/// injected per generation, and may be fractional.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniverseRain {
+    rate: f64,
+    placement: RainPlacement,
+}
+
+impl UniverseRain {
+    pub fn new(rate: f64, placement: RainPlacement) -> Self {
+        Self {
+            rate: rate.max(0.0),
+            placement,
+        }
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+
+    pub fn placement(&self) -> RainPlacement {
+        self.placement
+    }
+
+    /// Picks the `(row, column)` coordinates of the cells to bring to life
+    /// in a `rows`x`columns` area for one generation
+    pub fn drops(&self, rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        if rows == 0 || columns == 0 {
+            return vec![];
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut count = self.rate.trunc() as usize;
+        if rng.gen::<f64>() < self.rate.fract() {
+            count += 1;
+        }
+
+        (0..count)
+            .map(|_| match self.placement {
+                RainPlacement::Uniform => (rng.gen_range(0..rows), rng.gen_range(0..columns)),
+                RainPlacement::Edges => {
+                    // Pick a position along the perimeter, walking it clockwise
+                    let perimeter = 2 * (rows + columns);
+                    let position = rng.gen_range(0..perimeter);
+                    if position < columns {
+                        (0, position)
+                    } else if position < columns + rows {
+                        (position - columns, columns - 1)
+                    } else if position < 2 * columns + rows {
+                        (rows - 1, position - columns - rows)
+                    } else {
+                        (position - 2 * columns - rows, 0)
+                    }
+                }
+            })
+            .collect()
+    }
+}
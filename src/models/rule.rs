@@ -0,0 +1,900 @@
+use super::{
+    AntTurn, HenselConditions, Neighbourhood, UniverseCell, WireWorldCell, ANT_MAX_COLORS,
+};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// The number of cell states of life-like rules: dead and alive
+const LIFE_LIKE_STATES: u8 = 2;
+
+/// The name WireWorld goes by in rulestrings
+const WIREWORLD_NAME: &str = "WireWorld";
+
+/// The names the colored variants of the Game of Life go by in rulestrings,
+/// along with their number of colors
+const COLORED_NAMES: [(&str, u8); 2] = [("Immigration", 2), ("QuadLife", 4)];
+
+/// What ant rulestrings start with, before the turn of each color
+const ANT_PREFIX: &str = "Ant:";
+
+/// What elementary rulestrings start with, before the Wolfram number
+const ELEMENTARY_PREFIX: &str = "W";
+
+fn life_like_states() -> u8 {
+    LIFE_LIKE_STATES
+}
+
+fn adjacent_range() -> u8 {
+    1
+}
+
+fn single_color() -> u8 {
+    1
+}
+
+/// A life-like rule, expressed as the numbers of living neighbours that make
+/// a dead cell come alive (birth) and keep a living cell alive (survival).
+///
+/// Generations rules have more than two `states`: instead of dying at once,
+/// living cells that do not survive go through `states - 2` dying states, one
+/// per generation. Dying cells do not count as living neighbours and cannot
+/// be born again until they are dead.
+///
+/// Rules may count the living cells of another neighbourhood than the eight
+/// surrounding cells, or reach further out, ending with its letter and range
+/// as in `B2/S13V`, `B2/S34H` or `B3/S23M2`. Counts can go as high as the
+/// size of the neighbourhood: Larger than Life rules, reaching up to
+/// `NEIGHBOURHOOD_MAX_RANGE` cells away, give them as ranges instead, as in
+/// `R5,C0,M1,S34..58,B34..45,NM`.
+///
+/// Isotropic non-totalistic rules tell apart how the living cells around are
+/// arranged too, in Hensel notation as in `B2-a/S12`: see `HenselConditions`.
+///
+/// Immigration and QuadLife are the Game of Life with living cells of two and
+/// four colors: cells keep their color while they live and are born in the
+/// color most of their parents have. QuadLife cells born among three parents
+/// of different colors take the fourth one.
+///
+/// WireWorld is told apart: its living cells are electron heads, which always
+/// decay to tails and then to conductors, where they stay. Only conductors
+/// become heads, next to one or two of them.
+///
+/// Ant rules, such as Langton's Ant `Ant:RL`, do not count neighbours either:
+/// ants walk over cells of as many colors as there are turns, making the turn
+/// of the color of their cell, moving it on to the next color and stepping
+/// forward. Cells of any color but the first one are the living cells.
+///
+/// Elementary rules, such as `W30`, are Wolfram's automata of a single line
+/// of cells, each living in the next generation as told by the bit of the
+/// rule number its own state and those of the two cells beside make up. The
+/// last column holds the line and the columns before it the generations
+/// before, scrolling back one column every generation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UniverseRule {
+    birth: Vec<bool>,
+    survival: Vec<bool>,
+    #[serde(default = "life_like_states")]
+    states: u8,
+    #[serde(default)]
+    wireworld: bool,
+    #[serde(default)]
+    neighbourhood: Neighbourhood,
+    #[serde(default = "adjacent_range")]
+    range: u8,
+    /// The birth and survival arrangements of isotropic non-totalistic rules,
+    /// `None` when counts alone matter
+    #[serde(default)]
+    isotropic: Option<(HenselConditions, HenselConditions)>,
+    #[serde(default = "single_color")]
+    colors: u8,
+    /// The turn ants make on each color of cells, none for other rules
+    #[serde(default)]
+    turns: Vec<AntTurn>,
+    /// The Wolfram number of elementary rules, `None` for other rules
+    #[serde(default)]
+    elementary: Option<u8>,
+}
+
+impl Default for UniverseRule {
+    /// Conway's Game of Life, B3/S23
+    fn default() -> Self {
+        let mut rule = Self {
+            birth: vec![false; 9],
+            survival: vec![false; 9],
+            states: LIFE_LIKE_STATES,
+            wireworld: false,
+            neighbourhood: Neighbourhood::Moore,
+            range: 1,
+            isotropic: None,
+            colors: 1,
+            turns: vec![],
+            elementary: None,
+        };
+        rule.birth[3] = true;
+        rule.survival[2] = true;
+        rule.survival[3] = true;
+        rule
+    }
+}
+
+impl UniverseRule {
+    /// The WireWorld automaton, to build logic circuits with
+    pub fn wireworld() -> Self {
+        let mut rule = Self {
+            birth: vec![false; 9],
+            survival: vec![false; 9],
+            states: WireWorldCell::Conductor.state() + 1,
+            wireworld: true,
+            neighbourhood: Neighbourhood::Moore,
+            range: 1,
+            isotropic: None,
+            colors: 1,
+            turns: vec![],
+            elementary: None,
+        };
+        rule.birth[1] = true;
+        rule.birth[2] = true;
+        rule
+    }
+
+    pub fn is_wireworld(&self) -> bool {
+        self.wireworld
+    }
+
+    /// The ant walking over cells of one color per turn of `turns`, which are
+    /// cut down to `ANT_MAX_COLORS`
+    pub fn ant(turns: &[AntTurn]) -> Self {
+        Self {
+            birth: vec![false; 9],
+            survival: vec![false; 9],
+            turns: turns.iter().copied().take(ANT_MAX_COLORS).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Langton's Ant, turning right on dead cells and left on living ones
+    pub fn langtons_ant() -> Self {
+        Self::ant(&[AntTurn::Right, AntTurn::Left])
+    }
+
+    /// Whether ants walk over the cells instead of cells counting neighbours
+    pub fn is_ant(&self) -> bool {
+        !self.turns.is_empty()
+    }
+
+    /// The number of colors cells of an ant rule go through, 0 for other rules
+    pub fn ant_colors(&self) -> u8 {
+        self.turns.len() as u8
+    }
+
+    /// The turn an ant makes on a cell of `color`
+    pub fn ant_turn(&self, color: u8) -> AntTurn {
+        self.turns
+            .get(color as usize)
+            .copied()
+            .unwrap_or(AntTurn::None)
+    }
+
+    /// The elementary rule of Wolfram number `number`
+    pub fn elementary(number: u8) -> Self {
+        Self {
+            birth: vec![false; 9],
+            survival: vec![false; 9],
+            elementary: Some(number),
+            ..Self::default()
+        }
+    }
+
+    /// Whether cells live on a single line scrolling back every generation
+    /// instead of counting neighbours
+    pub fn is_elementary(&self) -> bool {
+        self.elementary.is_some()
+    }
+
+    /// The Wolfram number of elementary rules
+    pub fn elementary_number(&self) -> Option<u8> {
+        self.elementary
+    }
+
+    /// Whether a cell of the line of an elementary rule lives in the next
+    /// generation, given whether the cell before it, itself and the cell after
+    /// it live
+    pub fn elementary_next(&self, before: bool, middle: bool, after: bool) -> bool {
+        let pattern = (before as u8) << 2 | (middle as u8) << 1 | after as u8;
+        self.elementary
+            .is_some_and(|number| number >> pattern & 1 == 1)
+    }
+
+    /// Immigration, the Game of Life with living cells of two colors
+    pub fn immigration() -> Self {
+        Self {
+            colors: 2,
+            ..Self::default()
+        }
+    }
+
+    /// QuadLife, the Game of Life with living cells of four colors
+    pub fn quadlife() -> Self {
+        Self {
+            colors: 4,
+            ..Self::default()
+        }
+    }
+
+    /// The number of colors living cells can have, 1 for rules that do not
+    /// tell them apart
+    pub fn colors(&self) -> u8 {
+        self.colors
+    }
+
+    /// Whether living cells have colors, as in Immigration and QuadLife
+    pub fn is_colored(&self) -> bool {
+        self.colors > 1
+    }
+
+    /// The color a cell born among living neighbours of `parent_colors` takes:
+    /// the one most of them have, or the one none of them has when three
+    /// parents all have different colors out of four. Ties go to the lowest
+    /// color.
+    pub fn born_color(&self, parent_colors: &[u8]) -> u8 {
+        let mut tally = vec![0usize; self.colors as usize];
+        for color in parent_colors {
+            if let Some(count) = tally.get_mut(*color as usize) {
+                *count += 1;
+            }
+        }
+
+        let most = tally.iter().copied().max().unwrap_or(0);
+        if self.colors == 4 && most == 1 && parent_colors.len() == 3 {
+            if let Some(missing) = tally.iter().position(|count| *count == 0) {
+                return missing as u8;
+            }
+        }
+        tally
+            .iter()
+            .position(|count| *count == most)
+            .unwrap_or_default() as u8
+    }
+
+    /// The cells whose living neighbours are counted
+    pub fn neighbourhood(&self) -> Neighbourhood {
+        self.neighbourhood
+    }
+
+    /// How far, in cells, the neighbourhood reaches
+    pub fn range(&self) -> u8 {
+        self.range
+    }
+
+    /// The number of neighbours of each cell
+    pub fn neighbourhood_size(&self) -> u16 {
+        self.neighbourhood.size(self.range)
+    }
+
+    /// This rule counting the neighbours of `neighbourhood` at `range`
+    /// instead, as far as it can reach. Counts past its size are dropped, as
+    /// are the colors of living cells.
+    pub fn with_neighbourhood(mut self, neighbourhood: Neighbourhood, range: u8) -> Self {
+        self.neighbourhood = neighbourhood;
+        self.range = range.clamp(1, neighbourhood.max_range());
+        if (self.neighbourhood, self.range) != (Neighbourhood::Moore, 1) {
+            self.isotropic = None;
+            self.colors = 1;
+        }
+        let counts = self.neighbourhood_size() as usize + 1;
+        self.birth.resize(counts, false);
+        self.survival.resize(counts, false);
+        self
+    }
+
+    /// Whether births and survivals depend on how the living neighbours are
+    /// arranged and not only on their number
+    pub fn is_isotropic(&self) -> bool {
+        self.isotropic.is_some()
+    }
+
+    /// The arrangements of living neighbours that make a dead cell come alive,
+    /// all of those of the birth counts for totalistic rules
+    pub fn birth_conditions(&self) -> HenselConditions {
+        match &self.isotropic {
+            Some((birth, _)) => *birth,
+            None => HenselConditions::from_counts(&self.birth),
+        }
+    }
+
+    /// The arrangements of living neighbours that keep a living cell alive
+    pub fn survival_conditions(&self) -> HenselConditions {
+        match &self.isotropic {
+            Some((_, survival)) => *survival,
+            None => HenselConditions::from_counts(&self.survival),
+        }
+    }
+
+    /// This rule met for the `birth` and `survival` arrangements of living
+    /// neighbours instead, counting the eight cells around. Conditions taking
+    /// in all the arrangements of their counts make a totalistic rule. Living
+    /// cells are no longer told apart by color, nor walked over by ants, nor
+    /// do they live on a single line.
+    pub fn with_conditions(self, birth: HenselConditions, survival: HenselConditions) -> Self {
+        let mut rule = self.with_neighbourhood(Neighbourhood::Moore, 1);
+        rule.birth = birth.counts();
+        rule.survival = survival.counts();
+        rule.colors = 1;
+        rule.turns.clear();
+        rule.elementary = None;
+        rule.isotropic = match birth.is_totalistic() && survival.is_totalistic() {
+            true => None,
+            false => Some((birth, survival)),
+        };
+        rule
+    }
+
+    /// Whether a dead cell comes alive with some arrangement of
+    /// `alive_neighbours` living neighbours
+    pub fn is_birth(&self, alive_neighbours: u16) -> bool {
+        self.birth
+            .get(alive_neighbours as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Whether a living cell lives on with some arrangement of
+    /// `alive_neighbours` living neighbours
+    pub fn is_survival(&self, alive_neighbours: u16) -> bool {
+        self.survival
+            .get(alive_neighbours as usize)
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Whether a dead cell whose living neighbours are `neighbours`, a bit per
+    /// cell of `HENSEL_NEIGHBOURS`, comes alive
+    pub fn is_birth_among(&self, neighbours: u8) -> bool {
+        match &self.isotropic {
+            Some((birth, _)) => birth.holds_for(neighbours),
+            None => self.is_birth(neighbours.count_ones() as u16),
+        }
+    }
+
+    /// Whether a living cell whose living neighbours are `neighbours` lives on
+    pub fn is_survival_among(&self, neighbours: u8) -> bool {
+        match &self.isotropic {
+            Some((_, survival)) => survival.holds_for(neighbours),
+            None => self.is_survival(neighbours.count_ones() as u16),
+        }
+    }
+
+    /// The number of states cells go through: dead, alive and the dying states
+    /// in between, 2 for life-like rules
+    pub fn states(&self) -> u8 {
+        self.states
+    }
+
+    /// Whether living cells that do not survive go through dying states
+    pub fn has_dying_states(&self) -> bool {
+        self.states > LIFE_LIKE_STATES
+    }
+
+    /// The state a cell in `state` decays to in the next generation when it
+    /// does not live on: living cells start dying, dying cells get closer to
+    /// death and dead cells stay dead. WireWorld conductors stay conductors.
+    pub fn decay(&self, state: u8) -> u8 {
+        match state.checked_add(1) {
+            Some(next) if state > 0 && next < self.states => next,
+            Some(_) if state > 0 && self.wireworld => WireWorldCell::Conductor.state(),
+            _ => 0,
+        }
+    }
+
+    /// Whether a cell in the non-living `state` can be born: dead cells can,
+    /// dying ones cannot. In WireWorld, only conductors can.
+    pub fn can_be_born(&self, state: u8) -> bool {
+        match self.wireworld {
+            true => state == WireWorldCell::Conductor.state(),
+            false => state == 0,
+        }
+    }
+
+    /// Computes the next state of a cell given its current state and
+    /// the number of its living neighbours
+    pub fn next_state(&self, cell: UniverseCell, alive_neighbours: u16) -> UniverseCell {
+        let alive = match cell {
+            UniverseCell::Alive => self.is_survival(alive_neighbours),
+            UniverseCell::Dead => self.is_birth(alive_neighbours),
+        };
+
+        match alive {
+            true => UniverseCell::Alive,
+            false => UniverseCell::Dead,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum RuleParseError {
+    Invalid,
+}
+
+fn parse_counts(value: &str, size: u16) -> Result<Vec<bool>, RuleParseError> {
+    let mut counts = vec![false; size as usize + 1];
+    for c in value.chars() {
+        match c.to_digit(10) {
+            Some(digit) if digit <= size as u32 => counts[digit as usize] = true,
+            _ => return Err(RuleParseError::Invalid),
+        }
+    }
+    Ok(counts)
+}
+
+impl FromStr for UniverseRule {
+    type Err = RuleParseError;
+
+    /// Parses a rulestring in B/S notation (`B3/S23`), also accepting
+    /// the older S/B notation (`23/3`). Generations rules tell their number of
+    /// states last, as in `B2/S345/C4` or `345/2/4`. Rules using another
+    /// neighbourhood than the eight surrounding cells end with its letter and
+    /// range, as in `B2/S13V` or `B3/S23M2`. Larger than Life rulestrings,
+    /// such as `R5,C0,M1,S34..58,B34..45,NM`, and isotropic non-totalistic
+    /// ones, such as `B2-a/S12`, are accepted too. WireWorld, Immigration and
+    /// QuadLife go by their names, ant rules are told by their turns as in
+    /// `Ant:RL` and elementary rules by their Wolfram number as in `W30`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        if let Some(turns) = value
+            .get(..ANT_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(ANT_PREFIX))
+            .map(|_| &value[ANT_PREFIX.len()..])
+        {
+            return parse_ant_turns(turns);
+        }
+        if value.eq_ignore_ascii_case(WIREWORLD_NAME) {
+            return Ok(Self::wireworld());
+        }
+        if let Some(number) = value
+            .get(..ELEMENTARY_PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(ELEMENTARY_PREFIX))
+            .map(|_| &value[ELEMENTARY_PREFIX.len()..])
+        {
+            return number
+                .parse::<u8>()
+                .map(Self::elementary)
+                .map_err(|_| RuleParseError::Invalid);
+        }
+        if let Some((_, colors)) = COLORED_NAMES
+            .iter()
+            .find(|(name, _)| value.eq_ignore_ascii_case(name))
+        {
+            return Ok(Self {
+                colors: *colors,
+                ..Self::default()
+            });
+        }
+        if value.starts_with(['R', 'r']) {
+            return parse_larger_than_life(value);
+        }
+
+        let (value, neighbourhood, range) = split_neighbourhood(value)?;
+        let size = neighbourhood.size(range);
+
+        let (value, states) = match value.splitn(3, '/').nth(2) {
+            Some(states) => {
+                let upper_states = states.to_ascii_uppercase();
+                let states = match upper_states.strip_prefix('C') {
+                    Some(states) if value.starts_with(['B', 'b']) => states,
+                    None if !value.starts_with(['B', 'b']) => &upper_states,
+                    _ => return Err(RuleParseError::Invalid),
+                };
+                let (life_like, _) = value.rsplit_once('/').ok_or(RuleParseError::Invalid)?;
+                (life_like, parse_states(states)?)
+            }
+            None => (value, LIFE_LIKE_STATES),
+        };
+        let (first, second) = value.split_once('/').ok_or(RuleParseError::Invalid)?;
+
+        let upper_first = first.to_ascii_uppercase();
+        let upper_second = second.to_ascii_uppercase();
+        let (birth, survival) = match (
+            upper_first.strip_prefix('B'),
+            upper_second.strip_prefix('S'),
+        ) {
+            (Some(birth), Some(survival)) => (birth.to_string(), survival.to_string()),
+            (None, None) if !first.is_empty() || !second.is_empty() => {
+                // S/B notation
+                (second.to_string(), first.to_string())
+            }
+            _ => return Err(RuleParseError::Invalid),
+        };
+
+        // Letters tell arrangements of the eight cells around apart
+        let non_totalistic = |counts: &str| counts.contains(|c: char| !c.is_ascii_digit());
+        if non_totalistic(&birth) || non_totalistic(&survival) {
+            if (neighbourhood, range) != (Neighbourhood::Moore, 1) {
+                return Err(RuleParseError::Invalid);
+            }
+            let birth = birth.parse().map_err(|_| RuleParseError::Invalid)?;
+            let survival = survival.parse().map_err(|_| RuleParseError::Invalid)?;
+            let rule = Self {
+                states,
+                ..Self::default()
+            };
+            return Ok(rule.with_conditions(birth, survival));
+        }
+
+        Ok(Self {
+            birth: parse_counts(&birth, size)?,
+            survival: parse_counts(&survival, size)?,
+            states,
+            wireworld: false,
+            neighbourhood,
+            range,
+            isotropic: None,
+            colors: 1,
+            turns: vec![],
+            elementary: None,
+        })
+    }
+}
+
+/// Parses the turns of an ant rule, a letter per color as in `RLR`
+fn parse_ant_turns(value: &str) -> Result<UniverseRule, RuleParseError> {
+    let turns = value
+        .chars()
+        .map(AntTurn::from_letter)
+        .collect::<Option<Vec<AntTurn>>>()
+        .ok_or(RuleParseError::Invalid)?;
+    match (2..=ANT_MAX_COLORS).contains(&turns.len()) {
+        true => Ok(UniverseRule::ant(&turns)),
+        false => Err(RuleParseError::Invalid),
+    }
+}
+
+/// Splits the neighbourhood letter and range off the end of a rulestring,
+/// the Moore neighbourhood at range 1 if there are none
+fn split_neighbourhood(value: &str) -> Result<(&str, Neighbourhood, u8), RuleParseError> {
+    let without_range = value.trim_end_matches(|c: char| c.is_ascii_digit());
+    let Some(neighbourhood) = without_range
+        .chars()
+        .last()
+        .and_then(Neighbourhood::from_letter)
+    else {
+        return Ok((value, Neighbourhood::Moore, 1));
+    };
+
+    let range = match &value[without_range.len()..] {
+        "" => 1,
+        range => range.parse::<u8>().map_err(|_| RuleParseError::Invalid)?,
+    };
+    match (1..=neighbourhood.max_range()).contains(&range) {
+        true => Ok((
+            &without_range[..without_range.len() - 1],
+            neighbourhood,
+            range,
+        )),
+        false => Err(RuleParseError::Invalid),
+    }
+}
+
+/// Parses a Larger than Life rulestring: the range, the number of states
+/// (0 and 1 for two), whether the middle cell counts, the survival and birth
+/// ranges and the Moore (`NM`) or von Neumann (`NN`) neighbourhood, as in
+/// `R5,C0,M1,S34..58,B34..45,NM`. A middle cell that counts only adds to its
+/// own count when alive, so survival counts are lowered by one instead.
+fn parse_larger_than_life(value: &str) -> Result<UniverseRule, RuleParseError> {
+    let fields: Vec<String> = value
+        .split(',')
+        .map(|field| field.trim().to_ascii_uppercase())
+        .collect();
+    let [range, states, middle, survival, birth, neighbourhood] = fields.as_slice() else {
+        return Err(RuleParseError::Invalid);
+    };
+
+    let neighbourhood = match neighbourhood.as_str() {
+        "NM" => Neighbourhood::Moore,
+        "NN" => Neighbourhood::VonNeumann,
+        _ => return Err(RuleParseError::Invalid),
+    };
+    let range = match range.strip_prefix('R').map(str::parse::<u8>) {
+        Some(Ok(range)) if (1..=neighbourhood.max_range()).contains(&range) => range,
+        _ => return Err(RuleParseError::Invalid),
+    };
+    let states = match states.strip_prefix('C').map(str::parse::<u8>) {
+        Some(Ok(states)) => states.max(LIFE_LIKE_STATES),
+        _ => return Err(RuleParseError::Invalid),
+    };
+    let middle = match middle.as_str() {
+        "M0" => false,
+        "M1" => true,
+        _ => return Err(RuleParseError::Invalid),
+    };
+
+    let size = neighbourhood.size(range);
+    let survival = survival.strip_prefix('S').ok_or(RuleParseError::Invalid)?;
+    let mut survival = parse_count_range(survival, size + middle as u16)?;
+    if middle {
+        survival.remove(0);
+    }
+    let birth = birth.strip_prefix('B').ok_or(RuleParseError::Invalid)?;
+
+    Ok(UniverseRule {
+        birth: parse_count_range(birth, size)?,
+        survival,
+        states,
+        wireworld: false,
+        neighbourhood,
+        range,
+        isotropic: None,
+        colors: 1,
+        turns: vec![],
+        elementary: None,
+    })
+}
+
+/// Parses a range of counts up to `size`, as in `34..58`. No counts at all
+/// are left empty.
+fn parse_count_range(value: &str, size: u16) -> Result<Vec<bool>, RuleParseError> {
+    let mut counts = vec![false; size as usize + 1];
+    if value.is_empty() {
+        return Ok(counts);
+    }
+
+    let (min, max) = value.split_once("..").ok_or(RuleParseError::Invalid)?;
+    match (min.parse::<u16>(), max.parse::<u16>()) {
+        (Ok(min), Ok(max)) if min <= max && max <= size => {
+            counts[min as usize..=max as usize].fill(true);
+            Ok(counts)
+        }
+        _ => Err(RuleParseError::Invalid),
+    }
+}
+
+fn parse_states(value: &str) -> Result<u8, RuleParseError> {
+    match value.parse::<u8>() {
+        Ok(states) if states >= LIFE_LIKE_STATES => Ok(states),
+        _ => Err(RuleParseError::Invalid),
+    }
+}
+
+fn format_counts(values: &[bool]) -> String {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(count, _)| count.to_string())
+        .collect()
+}
+
+/// The lowest and highest of the counts, as in `34..58`, or nothing if there
+/// are none
+fn format_count_range(values: &[bool]) -> String {
+    let mut counts = values
+        .iter()
+        .enumerate()
+        .filter(|(_, enabled)| **enabled)
+        .map(|(count, _)| count);
+    match (counts.next(), counts.next_back()) {
+        (Some(min), Some(max)) => format!("{}..{}", min, max),
+        (Some(count), None) => format!("{}..{}", count, count),
+        _ => String::new(),
+    }
+}
+
+impl UniverseRule {
+    /// The birth and survival counts, each followed by the letters of its
+    /// arrangements for isotropic rules
+    fn counts_strings(&self) -> (String, String) {
+        match &self.isotropic {
+            Some((birth, survival)) => (birth.to_string(), survival.to_string()),
+            None => (format_counts(&self.birth), format_counts(&self.survival)),
+        }
+    }
+
+    /// The rule in ant notation, as in `Ant:RL`
+    fn to_ant_string(&self) -> String {
+        let turns: String = self.turns.iter().map(AntTurn::letter).collect();
+        format!("{}{}", ANT_PREFIX, turns)
+    }
+
+    /// The rule in Wolfram notation, as in `W30`, if elementary
+    fn to_elementary_string(&self) -> Option<String> {
+        self.elementary
+            .map(|number| format!("{}{}", ELEMENTARY_PREFIX, number))
+    }
+
+    /// The name of the colored variant of the Game of Life this rule is, if any
+    fn colored_name(&self) -> Option<&'static str> {
+        COLORED_NAMES
+            .iter()
+            .find(|(_, colors)| *colors == self.colors)
+            .map(|(name, _)| *name)
+    }
+
+    /// Whether some counts are past 9 and cannot be told by single digits,
+    /// leaving the Larger than Life notation to write the rule with
+    fn needs_count_ranges(&self) -> bool {
+        self.birth
+            .iter()
+            .skip(10)
+            .chain(self.survival.iter().skip(10))
+            .any(|enabled| *enabled)
+    }
+
+    /// The rule in Larger than Life notation, as in `R5,C0,M0,S33..57,B34..45,NM`.
+    /// The middle cell never counts.
+    fn to_larger_than_life_string(&self) -> String {
+        let states = match self.has_dying_states() {
+            true => self.states,
+            false => 0,
+        };
+        let neighbourhood = match self.neighbourhood {
+            Neighbourhood::VonNeumann => "NN",
+            _ => "NM",
+        };
+        format!(
+            "R{},C{},M0,S{},B{},{}",
+            self.range,
+            states,
+            format_count_range(&self.survival),
+            format_count_range(&self.birth),
+            neighbourhood
+        )
+    }
+
+    /// The rule in the older S/B notation (`23/3`), as used by Life 1.05 files.
+    /// WireWorld, Immigration and QuadLife go by their names, and ant rules,
+    /// elementary rules and Larger than Life rules with counts past 9 are
+    /// written in their own notation.
+    pub fn to_survival_birth_string(&self) -> String {
+        if self.wireworld {
+            return WIREWORLD_NAME.to_string();
+        }
+        if self.is_ant() {
+            return self.to_ant_string();
+        }
+        if let Some(rule) = self.to_elementary_string() {
+            return rule;
+        }
+        if let Some(name) = self.colored_name() {
+            return name.to_string();
+        }
+        if self.needs_count_ranges() {
+            return self.to_larger_than_life_string();
+        }
+
+        let (birth, survival) = self.counts_strings();
+        let rule = format!("{}/{}", survival, birth);
+        let rule = match self.has_dying_states() {
+            true => format!("{}/{}", rule, self.states),
+            false => rule,
+        };
+        format!("{}{}", rule, self.neighbourhood.suffix(self.range))
+    }
+}
+
+impl fmt::Display for UniverseRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.wireworld {
+            return write!(f, "{}", WIREWORLD_NAME);
+        }
+        if self.is_ant() {
+            return write!(f, "{}", self.to_ant_string());
+        }
+        if let Some(rule) = self.to_elementary_string() {
+            return write!(f, "{}", rule);
+        }
+        if let Some(name) = self.colored_name() {
+            return write!(f, "{}", name);
+        }
+        if self.needs_count_ranges() {
+            return write!(f, "{}", self.to_larger_than_life_string());
+        }
+
+        let (birth, survival) = self.counts_strings();
+        write!(f, "B{}/S{}", birth, survival)?;
+        if self.has_dying_states() {
+            write!(f, "/C{}", self.states)?;
+        }
+        write!(f, "{}", self.neighbourhood.suffix(self.range))
+    }
+}
+
+/// Well-known rules offered to pick from, along with how their dying cells
+/// are best colored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RulePreset {
+    Conway,
+    Seeds,
+    DayAndNight,
+    LifeWithoutDeath,
+    BriansBrain,
+    StarWars,
+    HexagonalLife,
+    BoscosRule,
+    Immigration,
+    QuadLife,
+    WireWorld,
+    LangtonsAnt,
+    Rule30,
+}
+
+impl RulePreset {
+    pub const ALL: [RulePreset; 13] = [
+        RulePreset::Conway,
+        RulePreset::Seeds,
+        RulePreset::DayAndNight,
+        RulePreset::LifeWithoutDeath,
+        RulePreset::BriansBrain,
+        RulePreset::StarWars,
+        RulePreset::HexagonalLife,
+        RulePreset::BoscosRule,
+        RulePreset::Immigration,
+        RulePreset::QuadLife,
+        RulePreset::WireWorld,
+        RulePreset::LangtonsAnt,
+        RulePreset::Rule30,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RulePreset::Conway => "conway",
+            RulePreset::Seeds => "seeds",
+            RulePreset::DayAndNight => "day-and-night",
+            RulePreset::LifeWithoutDeath => "life-without-death",
+            RulePreset::BriansBrain => "brians-brain",
+            RulePreset::StarWars => "star-wars",
+            RulePreset::HexagonalLife => "hexagonal-life",
+            RulePreset::BoscosRule => "boscos-rule",
+            RulePreset::Immigration => "immigration",
+            RulePreset::QuadLife => "quadlife",
+            RulePreset::WireWorld => "wireworld",
+            RulePreset::LangtonsAnt => "langtons-ant",
+            RulePreset::Rule30 => "rule-30",
+        }
+    }
+
+    pub fn rule(&self) -> UniverseRule {
+        match self {
+            RulePreset::Conway => UniverseRule::default(),
+            RulePreset::Seeds => "B2/S".parse().unwrap(),
+            RulePreset::DayAndNight => "B3678/S34678".parse().unwrap(),
+            RulePreset::LifeWithoutDeath => "B3/S012345678".parse().unwrap(),
+            RulePreset::BriansBrain => "B2/S/C3".parse().unwrap(),
+            RulePreset::StarWars => "B2/S345/C4".parse().unwrap(),
+            RulePreset::HexagonalLife => "B2/S34H".parse().unwrap(),
+            RulePreset::BoscosRule => "R5,C0,M1,S34..58,B34..45,NM".parse().unwrap(),
+            RulePreset::Immigration => UniverseRule::immigration(),
+            RulePreset::QuadLife => UniverseRule::quadlife(),
+            RulePreset::WireWorld => UniverseRule::wireworld(),
+            RulePreset::LangtonsAnt => UniverseRule::langtons_ant(),
+            RulePreset::Rule30 => UniverseRule::elementary(30),
+        }
+    }
+
+    /// The color dying cells start from before fading out, for rules that
+    /// have them. WireWorld brings its own colors.
+    pub fn dying_color(&self) -> Option<&'static str> {
+        match self {
+            RulePreset::BriansBrain => Some("#3584E4"),
+            RulePreset::StarWars => Some("#E66100"),
+            _ => None,
+        }
+    }
+
+    /// The preset `rule` is, if any
+    pub fn from_rule(rule: &UniverseRule) -> Option<RulePreset> {
+        RulePreset::ALL
+            .into_iter()
+            .find(|preset| preset.rule() == *rule)
+    }
+}
+
+impl FromStr for RulePreset {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        RulePreset::ALL
+            .into_iter()
+            .find(|preset| preset.as_str() == value)
+            .ok_or_else(|| format!("Unknown rule preset {}", value))
+    }
+}
@@ -0,0 +1,127 @@
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::str::FromStr;
+
+/// The share of living cells in random universes, unless told otherwise
+pub const UNIVERSE_DEFAULT_SOUP_DENSITY: f64 = 0.4;
+
+/// How the cells of a soup mirror each other across the area it fills
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SoupSymmetry {
+    /// Every cell is drawn on its own
+    #[default]
+    None,
+
+    /// The first and last rows mirror each other, and so on inwards
+    MirrorRows,
+
+    /// The first and last columns mirror each other, and so on inwards
+    MirrorColumns,
+
+    /// Both rows and columns are mirrored, the four quarters matching
+    FourFold,
+
+    /// Cells mirror each other across the diagonal from the top left corner.
+    /// Only the square part of the area along that diagonal is mirrored.
+    Diagonal,
+}
+
+impl SoupSymmetry {
+    pub const ALL: [SoupSymmetry; 5] = [
+        SoupSymmetry::None,
+        SoupSymmetry::MirrorRows,
+        SoupSymmetry::MirrorColumns,
+        SoupSymmetry::FourFold,
+        SoupSymmetry::Diagonal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SoupSymmetry::None => "none",
+            SoupSymmetry::MirrorRows => "mirror-rows",
+            SoupSymmetry::MirrorColumns => "mirror-columns",
+            SoupSymmetry::FourFold => "four-fold",
+            SoupSymmetry::Diagonal => "diagonal",
+        }
+    }
+
+    /// The cell `row`x`column` of a `rows`x`columns` area takes its value from,
+    /// relative to the top left corner of the area
+    pub fn source(&self, row: usize, column: usize, rows: usize, columns: usize) -> (usize, usize) {
+        let mirror_row = row.min(rows - 1 - row);
+        let mirror_column = column.min(columns - 1 - column);
+        match self {
+            SoupSymmetry::None => (row, column),
+            SoupSymmetry::MirrorRows => (mirror_row, column),
+            SoupSymmetry::MirrorColumns => (row, mirror_column),
+            SoupSymmetry::FourFold => (mirror_row, mirror_column),
+            SoupSymmetry::Diagonal if row < columns && column < rows => {
+                (row.min(column), row.max(column))
+            }
+            SoupSymmetry::Diagonal => (row, column),
+        }
+    }
+}
+
+impl FromStr for SoupSymmetry {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SoupSymmetry::ALL
+            .into_iter()
+            .find(|symmetry| symmetry.as_str() == value)
+            .ok_or_else(|| format!("Unknown soup symmetry {}", value))
+    }
+}
+
+/// How random cells are drawn: the share of them that is alive, how they mirror
+/// each other, and the seed of the generator drawing them. Soups drawn from the
+/// same seed are the same, so that interesting ones can be reproduced; without
+/// a seed each one differs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniverseSoup {
+    density: f64,
+    symmetry: SoupSymmetry,
+    seed: Option<u64>,
+}
+
+impl Default for UniverseSoup {
+    fn default() -> Self {
+        Self::new(UNIVERSE_DEFAULT_SOUP_DENSITY, None)
+    }
+}
+
+impl UniverseSoup {
+    pub fn new(density: f64, seed: Option<u64>) -> Self {
+        Self {
+            density: density.clamp(0.0, 1.0),
+            symmetry: SoupSymmetry::None,
+            seed,
+        }
+    }
+
+    pub fn with_symmetry(mut self, symmetry: SoupSymmetry) -> Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    pub fn symmetry(&self) -> SoupSymmetry {
+        self.symmetry
+    }
+
+    pub fn density(&self) -> f64 {
+        self.density
+    }
+
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// A generator drawing this soup, from the start of its sequence
+    pub fn rng(&self) -> StdRng {
+        match self.seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        }
+    }
+}
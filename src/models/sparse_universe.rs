@@ -0,0 +1,440 @@
+use super::{
+    BitGrid, Universe, UniverseCell, UniverseChance, UniversePoint, UniversePointMatrix,
+    UniverseRain, UniverseRule, UniverseSnapshot, HENSEL_NEIGHBOURS,
+};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+/// How many generations an unbounded universe remembers dead cells for, the
+/// longest death trail it can show
+pub const SPARSE_UNIVERSE_TRAIL_GENERATIONS: u64 = 32;
+
+/// The region of an unbounded universe that is currently projected
+/// onto the grid, expressed in world coordinates
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SparseViewport {
+    pub top: i64,
+    pub left: i64,
+    pub rows: usize,
+    pub columns: usize,
+}
+
+impl SparseViewport {
+    pub fn new(top: i64, left: i64, rows: usize, columns: usize) -> Self {
+        Self {
+            top,
+            left,
+            rows,
+            columns,
+        }
+    }
+
+    /// Maps a world coordinate to a viewport-relative `(row, column)`, if visible
+    pub fn to_local(self, row: i64, column: i64) -> Option<(usize, usize)> {
+        let local_row = row - self.top;
+        let local_column = column - self.left;
+        if (0..self.rows as i64).contains(&local_row)
+            && (0..self.columns as i64).contains(&local_column)
+        {
+            Some((local_row as usize, local_column as usize))
+        } else {
+            None
+        }
+    }
+
+    /// Maps a viewport-relative `(row, column)` to world coordinates
+    pub fn to_world(self, row: usize, column: usize) -> (i64, i64) {
+        (self.top + row as i64, self.left + column as i64)
+    }
+
+    /// The same region moved by `rows` and `columns` over the plane
+    pub fn panned(self, rows: i64, columns: i64) -> Self {
+        Self {
+            top: self.top.saturating_add(rows),
+            left: self.left.saturating_add(columns),
+            ..self
+        }
+    }
+
+    /// The region of the same size with the world coordinate `row`x`column`
+    /// at its center
+    pub fn centered_on(self, row: i64, column: i64) -> Self {
+        Self {
+            top: row.saturating_sub(self.rows as i64 / 2),
+            left: column.saturating_sub(self.columns as i64 / 2),
+            ..self
+        }
+    }
+}
+
+/// Represents a universe without boundaries. Only living cells are stored,
+/// so patterns can travel indefinitely instead of wrapping around the edges
+/// of a fixed rows×columns grid.
+/// Rules with birth on zero neighbours (`B0`) are not supported, as they would
+/// fill the infinite plane.
+#[derive(Clone, Debug)]
+pub struct SparseUniverse {
+    alive: HashSet<(i64, i64)>,
+    /// The number of generations each living cell has survived for, if any
+    ages: HashMap<(i64, i64), u32>,
+    /// The last generation each recently dead cell was alive in
+    last_alive: HashMap<(i64, i64), u64>,
+    viewport: SparseViewport,
+    rule: UniverseRule,
+    rain: Option<UniverseRain>,
+    chance: Option<UniverseChance>,
+    generations: u64,
+    /// The cells born and died in the last generation
+    last_changes: (usize, usize),
+}
+
+impl SparseUniverse {
+    pub fn new(rows: usize, columns: usize) -> Self {
+        Self {
+            alive: HashSet::new(),
+            ages: HashMap::new(),
+            last_alive: HashMap::new(),
+            viewport: SparseViewport::new(0, 0, rows, columns),
+            rule: UniverseRule::default(),
+            rain: None,
+            chance: None,
+            generations: 0,
+            last_changes: (0, 0),
+        }
+    }
+
+    /// The number of generations computed since this universe was created
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    /// How many cells were born and how many died in the last generation
+    pub fn last_changes(&self) -> (usize, usize) {
+        self.last_changes
+    }
+
+    pub fn set_rule(&mut self, value: UniverseRule) {
+        self.rule = value;
+    }
+
+    /// Sets the random cells injected at every generation. Drops fall on the
+    /// visible viewport, as the plane around it is unbounded.
+    pub fn set_rain(&mut self, value: Option<UniverseRain>) {
+        self.rain = value;
+    }
+
+    /// Sets the probabilities births and survivals happen with. Noise flips
+    /// cells of the visible viewport only, as the plane around it is unbounded.
+    pub fn set_chance(&mut self, value: Option<UniverseChance>) {
+        self.chance = value;
+    }
+
+    /// Sets the cell at world coordinates `row`x`column`
+    pub fn set_world_cell(&mut self, row: i64, column: i64, cell: UniverseCell) {
+        self.ages.remove(&(row, column));
+        self.last_alive.remove(&(row, column));
+        match cell {
+            UniverseCell::Alive => self.alive.insert((row, column)),
+            UniverseCell::Dead => self.alive.remove(&(row, column)),
+        };
+    }
+
+    /// The region of the plane projected onto the grid
+    pub fn viewport(&self) -> SparseViewport {
+        self.viewport
+    }
+
+    /// Moves the region of the plane projected onto the grid. Its size is the
+    /// one of the projected universe, and is kept as it is.
+    pub fn set_viewport(&mut self, top: i64, left: i64) {
+        self.viewport.top = top;
+        self.viewport.left = left;
+    }
+
+    /// Moves the viewport by `rows` and `columns` over the plane
+    pub fn pan_viewport(&mut self, rows: i64, columns: i64) {
+        self.viewport = self.viewport.panned(rows, columns);
+    }
+
+    /// Counts the living cells, including those out of the viewport
+    pub fn alive_cells_count(&self) -> usize {
+        self.alive.len()
+    }
+
+    pub fn is_alive_at(&self, row: i64, column: i64) -> bool {
+        self.alive.contains(&(row, column))
+    }
+
+    /// The living cells around `(row, column)`, a bit per cell of
+    /// `HENSEL_NEIGHBOURS`
+    fn neighbours_of(&self, (row, column): (i64, i64)) -> u8 {
+        HENSEL_NEIGHBOURS.iter().enumerate().fold(
+            0,
+            |neighbours, (bit, (delta_row, delta_column))| {
+                let alive =
+                    self.is_alive_at(row + *delta_row as i64, column + *delta_column as i64);
+                neighbours | (alive as u8) << bit
+            },
+        )
+    }
+
+    /// Computes the next generation. Only living cells and their neighbours
+    /// are visited, so the cost is proportional to the population rather
+    /// than to the area covered by the pattern.
+    pub fn tick(&mut self) {
+        let mut neighbours: HashMap<(i64, i64), u8> = HashMap::with_capacity(self.alive.len() * 8);
+        for &(row, column) in self.alive.iter() {
+            for delta_row in -1..=1 {
+                for delta_col in -1..=1 {
+                    if delta_row == 0 && delta_col == 0 {
+                        continue;
+                    }
+                    *neighbours
+                        .entry((row + delta_row, column + delta_col))
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+
+        // Cells that are alive but have no living neighbours never show up in
+        // the neighbours map, yet they may survive under rules such as S0
+        if self.rule.is_survival(0) {
+            for coords in self.alive.iter() {
+                neighbours.entry(*coords).or_insert(0);
+            }
+        }
+
+        let mut next: Vec<((i64, i64), bool)> = neighbours
+            .into_iter()
+            .filter_map(|(coords, count)| {
+                let was_alive = self.alive.contains(&coords);
+                let alive = match (was_alive, self.rule.is_isotropic()) {
+                    (true, false) => self.rule.is_survival(count.into()),
+                    (false, false) => self.rule.is_birth(count.into()),
+                    (true, true) => self.rule.is_survival_among(self.neighbours_of(coords)),
+                    (false, true) => self.rule.is_birth_among(self.neighbours_of(coords)),
+                };
+                alive.then_some((coords, was_alive))
+            })
+            .collect();
+
+        if let Some(chance) = self.chance.as_mut() {
+            // Draws are made in a stable order so that runs can be reproduced
+            next.sort_unstable();
+            next.retain(|(_, was_alive)| chance.allows(*was_alive));
+        }
+
+        self.ages = next
+            .iter()
+            .filter(|(_, was_alive)| *was_alive)
+            .map(|(coords, _)| {
+                let age = self.ages.get(coords).copied().unwrap_or(0);
+                (*coords, age.saturating_add(1))
+            })
+            .collect();
+
+        let generation = self.generations;
+        for coords in self.alive.iter() {
+            self.last_alive.insert(*coords, generation);
+        }
+        self.last_alive
+            .retain(|_, last_alive| generation - *last_alive < SPARSE_UNIVERSE_TRAIL_GENERATIONS);
+
+        let survivors = self.ages.len();
+        let mut births = next.len() - survivors;
+        let mut deaths = self.alive.len() - survivors;
+        self.alive = next.into_iter().map(|(coords, _)| coords).collect();
+
+        if let Some(chance) = self.chance.as_mut().filter(|chance| chance.is_noisy()) {
+            for row in 0..self.viewport.rows {
+                for column in 0..self.viewport.columns {
+                    if !chance.flips() {
+                        continue;
+                    }
+                    // Flipped cells undo their birth or death, or change on their own
+                    let coords = self.viewport.to_world(row, column);
+                    let was_alive = self.last_alive.get(&coords) == Some(&generation);
+                    match (self.alive.remove(&coords), was_alive) {
+                        (true, true) => {
+                            self.ages.remove(&coords);
+                            deaths += 1;
+                        }
+                        (true, false) => births -= 1,
+                        (false, true) => {
+                            self.alive.insert(coords);
+                            deaths -= 1;
+                        }
+                        (false, false) => {
+                            self.alive.insert(coords);
+                            births += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        if let Some(rain) = self.rain.as_ref() {
+            for (row, column) in rain.drops(self.viewport.rows, self.viewport.columns) {
+                if self.alive.insert(self.viewport.to_world(row, column)) {
+                    births += 1;
+                }
+            }
+        }
+
+        self.generations += 1;
+        self.last_changes = (births, deaths);
+    }
+
+    /// A hash of the living cells of this universe, telling apart different states.
+    /// Each cell is hashed on its own and combined regardless of the set ordering.
+    pub fn state_hash(&self) -> u64 {
+        self.alive
+            .iter()
+            .fold(self.alive.len() as u64, |acc, coords| {
+                let mut hasher = DefaultHasher::new();
+                coords.hash(&mut hasher);
+                acc ^ hasher.finish()
+            })
+    }
+
+    /// The smallest `(top, left, rows, columns)` area of the plane containing
+    /// all the living cells, or `None` if there are none
+    pub fn bounding_box(&self) -> Option<(i64, i64, usize, usize)> {
+        let top = self.alive.iter().map(|&(row, _)| row).min()?;
+        let bottom = self.alive.iter().map(|&(row, _)| row).max()?;
+        let left = self.alive.iter().map(|&(_, column)| column).min()?;
+        let right = self.alive.iter().map(|&(_, column)| column).max()?;
+        Some((
+            top,
+            left,
+            (bottom - top + 1) as usize,
+            (right - left + 1) as usize,
+        ))
+    }
+
+    /// Copies the living cells into a snapshot spanning their bounding box,
+    /// wherever they are on the plane, or `None` if there are none
+    pub fn living_snapshot(&self) -> Option<UniverseSnapshot> {
+        let (top, left, rows, columns) = self.bounding_box()?;
+
+        let mut universe = Universe::new_empty(rows, columns);
+        for &(row, column) in self.alive.iter() {
+            universe.set_cell(
+                (row - top) as usize,
+                (column - left) as usize,
+                UniverseCell::Alive,
+            );
+        }
+        Some(universe.snapshot())
+    }
+
+    /// Projects the visible viewport onto a bounded `Universe`, which is what
+    /// the grid widget renders. Cells outside the viewport are not visited.
+    pub fn project(&self) -> Universe {
+        let mut universe = Universe::new_empty(self.viewport.rows, self.viewport.columns);
+        universe.restore(
+            self.generations,
+            BitGrid::new(self.viewport.rows, self.viewport.columns),
+        );
+        for (&(row, column), &last_alive) in self.last_alive.iter() {
+            if let Some((local_row, local_column)) = self.viewport.to_local(row, column) {
+                universe.set_last_alive_generation(local_row, local_column, last_alive);
+            }
+        }
+        for &(row, column) in self.alive.iter() {
+            if let Some((local_row, local_column)) = self.viewport.to_local(row, column) {
+                universe.set_cell(local_row, local_column, UniverseCell::Alive);
+                if let Some(age) = self.ages.get(&(row, column)) {
+                    universe.set_cell_age(local_row, local_column, *age);
+                }
+            }
+        }
+        universe
+    }
+}
+
+impl From<&Universe> for SparseUniverse {
+    fn from(universe: &Universe) -> Self {
+        let mut sparse = Self::new(universe.rows(), universe.columns());
+        sparse.set_rule(universe.rule().clone());
+        sparse.set_rain(universe.rain().copied());
+        sparse.set_chance(universe.chance().cloned());
+        sparse.generations = universe.generations();
+        for point in universe.iter_cells() {
+            if point.cell().is_alive() {
+                let coords = (point.row() as i64, point.column() as i64);
+                sparse.set_world_cell(coords.0, coords.1, UniverseCell::Alive);
+                let age = universe.cell_age(point.row(), point.column());
+                if age > 0 {
+                    sparse.ages.insert(coords, age);
+                }
+            }
+        }
+        sparse
+    }
+}
+
+impl UniversePointMatrix for SparseUniverse {
+    type SetCellError = ();
+
+    fn rows(&self) -> usize {
+        self.viewport.rows
+    }
+
+    fn columns(&self) -> usize {
+        self.viewport.columns
+    }
+
+    fn get(&self, row: usize, column: usize) -> Option<UniversePoint> {
+        if row >= self.viewport.rows || column >= self.viewport.columns {
+            return None;
+        }
+
+        let (world_row, world_column) = self.viewport.to_world(row, column);
+        let cell = match self.is_alive_at(world_row, world_column) {
+            true => UniverseCell::Alive,
+            false => UniverseCell::Dead,
+        };
+        Some(UniversePoint::new(row, column, cell, 0.0))
+    }
+
+    fn set(
+        &mut self,
+        row: usize,
+        column: usize,
+        value: UniverseCell,
+    ) -> Result<UniversePoint, Self::SetCellError> {
+        let (world_row, world_column) = self.viewport.to_world(row, column);
+        self.set_world_cell(world_row, world_column, value);
+        self.get(row, column).ok_or(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SparseUniverse;
+    use crate::{UniverseCell, UniversePointMatrix};
+
+    #[test]
+    fn panned_viewport_projects_cells_out_of_the_starting_one() {
+        let mut universe = SparseUniverse::new(4, 4);
+        universe.set_world_cell(10, -3, UniverseCell::Alive);
+        assert_eq!(universe.project().alive_cells_count(), 0);
+
+        universe.pan_viewport(9, -4);
+        let projection = universe.project();
+        assert_eq!((projection.rows(), projection.columns()), (4, 4));
+        assert!(projection.get(1, 1).unwrap().cell().is_alive());
+        assert!(universe.get(1, 1).unwrap().cell().is_alive());
+    }
+
+    #[test]
+    fn cells_set_through_a_moved_viewport_land_on_the_plane() {
+        let mut universe = SparseUniverse::new(3, 3);
+        universe.set_viewport(-100, 50);
+        universe.set(2, 0, UniverseCell::Alive).ok();
+        assert!(universe.is_alive_at(-98, 50));
+        assert_eq!(universe.viewport().centered_on(0, 0).top, -1);
+    }
+}
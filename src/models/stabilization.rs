@@ -0,0 +1,70 @@
+use std::collections::VecDeque;
+
+/// The longest oscillator period detected by default. Covers common oscillators
+/// such as blinkers (2), pulsars (3) and pentadecathlons (15).
+const DEFAULT_MAX_PERIOD: usize = 30;
+
+/// Tells whether a universe settled down, that is whether it died out, became
+/// a still life or keeps cycling through a short period oscillation.
+/// States are compared through the hashes provided by `Universe::state_hash`.
+#[derive(Clone, Debug)]
+pub struct StabilizationDetector {
+    history: VecDeque<u64>,
+    max_period: usize,
+    patience: usize,
+    stable_for: usize,
+    period: Option<usize>,
+}
+
+impl StabilizationDetector {
+    /// Creates a detector reporting stabilization once the universe has been
+    /// settled for `patience` generations
+    pub fn new(patience: usize) -> Self {
+        Self {
+            history: VecDeque::with_capacity(DEFAULT_MAX_PERIOD),
+            max_period: DEFAULT_MAX_PERIOD,
+            patience,
+            stable_for: 0,
+            period: None,
+        }
+    }
+
+    /// Records the state of a new generation. Returns `true` if the universe
+    /// has been settled for at least the configured number of generations.
+    pub fn observe(&mut self, state_hash: u64) -> bool {
+        match self.history.iter().rposition(|hash| *hash == state_hash) {
+            Some(index) => {
+                self.stable_for += 1;
+                self.period = Some(self.history.len() - index);
+            }
+            None => {
+                self.stable_for = 0;
+                self.period = None;
+            }
+        }
+
+        if self.history.len() == self.max_period {
+            self.history.pop_front();
+        }
+        self.history.push_back(state_hash);
+
+        self.is_stable()
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.stable_for >= self.patience
+    }
+
+    /// The number of generations after which the last observed state repeats,
+    /// 1 being a still life, or `None` if it did not repeat any recent state
+    pub fn period(&self) -> Option<usize> {
+        self.period
+    }
+
+    /// Forgets the observed generations, as when a new universe is set
+    pub fn reset(&mut self) {
+        self.history.clear();
+        self.stable_for = 0;
+        self.period = None;
+    }
+}
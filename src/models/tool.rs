@@ -0,0 +1,129 @@
+use super::UniverseCell;
+use std::str::FromStr;
+
+/// The widest brush, in cells
+pub const BRUSH_MAX_SIZE: usize = 9;
+
+/// What dragging over a universe does while designing it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DesignTool {
+    /// Brings the cells under the brush to life
+    #[default]
+    Pencil,
+
+    /// Kills the cells under the brush
+    Eraser,
+
+    /// Draws a straight line of living cells from where the drag started to
+    /// where it ended
+    Line,
+
+    /// Draws the outline of a rectangle of living cells with opposite corners
+    /// where the drag started and ended
+    Rectangle,
+
+    /// Same as `Rectangle`, filling it with living cells
+    FilledRectangle,
+}
+
+impl DesignTool {
+    pub const ALL: [DesignTool; 5] = [
+        DesignTool::Pencil,
+        DesignTool::Eraser,
+        DesignTool::Line,
+        DesignTool::Rectangle,
+        DesignTool::FilledRectangle,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DesignTool::Pencil => "pencil",
+            DesignTool::Eraser => "eraser",
+            DesignTool::Line => "line",
+            DesignTool::Rectangle => "rectangle",
+            DesignTool::FilledRectangle => "filled-rectangle",
+        }
+    }
+
+    /// The state the tool sets cells to
+    pub fn cell(&self) -> UniverseCell {
+        match self {
+            DesignTool::Eraser => UniverseCell::Dead,
+            _ => UniverseCell::Alive,
+        }
+    }
+
+    /// Whether the tool draws a shape once the drag ends, rather than painting
+    /// the cells under the brush along the way
+    pub fn draws_shapes(&self) -> bool {
+        matches!(
+            self,
+            DesignTool::Line | DesignTool::Rectangle | DesignTool::FilledRectangle
+        )
+    }
+
+    /// The cells of the shape drawn by a drag from the cell `from` to the cell
+    /// `to`, as `(row, column)`. Brush tools draw no shape.
+    pub fn shape_cells(&self, from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+        let (top, bottom) = (from.0.min(to.0), from.0.max(to.0));
+        let (left, right) = (from.1.min(to.1), from.1.max(to.1));
+        match self {
+            DesignTool::Pencil | DesignTool::Eraser => vec![],
+            DesignTool::Line => line_cells(from, to),
+            DesignTool::Rectangle => {
+                let mut cells = vec![];
+                for row in top..=bottom {
+                    for column in left..=right {
+                        if row == top || row == bottom || column == left || column == right {
+                            cells.push((row, column));
+                        }
+                    }
+                }
+                cells
+            }
+            DesignTool::FilledRectangle => (top..=bottom)
+                .flat_map(|row| (left..=right).map(move |column| (row, column)))
+                .collect(),
+        }
+    }
+}
+
+impl FromStr for DesignTool {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        DesignTool::ALL
+            .into_iter()
+            .find(|tool| tool.as_str() == value)
+            .ok_or_else(|| format!("Unknown design tool {}", value))
+    }
+}
+
+/// The cells of the straight line between the cells `from` and `to`, both
+/// included, as traced by Bresenham's algorithm
+fn line_cells(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let (mut row, mut column) = (from.0 as i64, from.1 as i64);
+    let (to_row, to_column) = (to.0 as i64, to.1 as i64);
+    let delta_row = (to_row - row).abs();
+    let delta_column = -(to_column - column).abs();
+    let step_row = if row < to_row { 1 } else { -1 };
+    let step_column = if column < to_column { 1 } else { -1 };
+
+    let mut error = delta_row + delta_column;
+    let mut cells = vec![];
+    loop {
+        cells.push((row as usize, column as usize));
+        if row == to_row && column == to_column {
+            return cells;
+        }
+        let doubled_error = 2 * error;
+        if doubled_error >= delta_column {
+            error += delta_column;
+            row += step_row;
+        }
+        if doubled_error <= delta_row {
+            error += delta_row;
+            column += step_column;
+        }
+    }
+}
@@ -1,24 +1,36 @@
-use super::{UniverseCell, UniversePoint, UniversePointMatrix};
-use crate::config::G_LOG_DOMAIN;
+use super::{
+    AntHeading, BitGrid, CellDelta, UniverseArea, UniverseCell, UniverseChance, UniverseInjection,
+    UniversePoint, UniversePointMatrix, UniverseRain, UniverseRule, UniverseSoup,
+};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 
-const UNIVERSE_RANDOM_ALIVE_PROBABILITY: f64 = 0.6;
 const UNIVERSE_CELL_INITIAL_CORPSE_HEAT: f64 = 0.65;
 const UNIVERSE_DEFAULT_FREEZE_RATE: f64 = 0.30;
 
+/// The bits of the state of a cell under an ant rule telling its color. The
+/// bits above tell the heading of the ant on it, if any, counting from 1.
+const ANT_COLOR_MASK: u8 = 0x0f;
+const ANT_HEADING_SHIFT: u8 = 4;
+
+/// The heading of the ant on a cell in `state` under an ant rule, if any
+fn ant_heading(state: u8) -> Option<AntHeading> {
+    match state >> ANT_HEADING_SHIFT {
+        0 => None,
+        heading => AntHeading::ALL.get(heading as usize - 1).copied(),
+    }
+}
+
 fn compute_initial_delta(universe: &mut Universe) {
     let mut initial_delta: Vec<UniversePoint> = vec![];
     for row in 0..universe.rows {
         for column in 0..universe.columns {
-            let index = universe.get_index(row, column);
-            let delta_point = UniversePoint::new(
-                row,
-                column,
-                universe.cells[index],
-                universe.death_map[index],
-            );
+            let (cell, corpse_heat) = universe.get_cell(row, column);
+            let delta_point = UniversePoint::new(row, column, *cell, *corpse_heat);
             initial_delta.push(delta_point);
         }
     }
@@ -28,15 +40,35 @@ fn compute_initial_delta(universe: &mut Universe) {
 
 /// Represents a universe as a collection of "cells"
 /// which can be in two states: `Alive` or `Dead`
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(into = "UniverseRecord", try_from = "UniverseRecord")]
 pub struct Universe {
     columns: usize,
     rows: usize,
-    cells: Vec<UniverseCell>,
+    cells: BitGrid,
     death_map: Vec<f64>,
+    /// The number of generations each living cell has survived for
+    age_map: Vec<u32>,
+    /// One past the last generation each cell was alive in, 0 if it never was
+    last_alive_map: Vec<u64>,
+    /// The cells that died in the last generation, whose corpses are still warm
+    warm_corpses: Vec<(usize, usize)>,
+    /// The state of each dying cell under a Generations rule, the color of
+    /// each living cell under a colored rule, or the color of each cell and
+    /// the heading of the ant on it under an ant rule, 0 for other cells
+    decay_map: Vec<u8>,
     corpse_freeze_rate: f64,
     generations: u64,
     last_delta: Option<Vec<UniversePoint>>,
+    /// The cells born and died in the last generation
+    last_changes: (usize, usize),
+    rule: UniverseRule,
+    rain: Option<UniverseRain>,
+    chance: Option<UniverseChance>,
+    injection: Option<UniverseInjection>,
+    freeze_zones: Vec<UniverseArea>,
+    /// The steps every ant takes in a generation under an ant rule
+    ant_steps: u32,
 }
 
 impl Default for Universe {
@@ -53,8 +85,13 @@ impl Universe {
     }
 
     pub fn new_random(rows: usize, columns: usize) -> Universe {
+        Self::new_soup(rows, columns, &UniverseSoup::default())
+    }
+
+    /// Creates a universe filled with random cells drawn from `soup`
+    pub fn new_soup(rows: usize, columns: usize, soup: &UniverseSoup) -> Universe {
         let mut universe = Self::create(rows, columns);
-        universe.random_seed();
+        universe.fill_soup(&UniverseArea::new(0, 0, rows, columns), soup);
         compute_initial_delta(&mut universe);
         universe
     }
@@ -64,38 +101,25 @@ impl Universe {
     }
 
     fn create(rows: usize, columns: usize) -> Universe {
-        let s: usize = rows * columns;
-        let mut cells: Vec<UniverseCell> = Vec::with_capacity(s);
-        let mut death_map: Vec<f64> = Vec::with_capacity(s);
-
-        for i in 0..s {
-            cells.insert(i, UniverseCell::Dead);
-            death_map.insert(i, 0.0);
-        }
-
         Universe {
             rows,
             columns,
-            cells,
+            cells: BitGrid::new(rows, columns),
             corpse_freeze_rate: UNIVERSE_DEFAULT_FREEZE_RATE,
-            death_map,
+            death_map: vec![0.0; rows * columns],
+            age_map: vec![0; rows * columns],
+            last_alive_map: vec![0; rows * columns],
+            warm_corpses: vec![],
+            decay_map: vec![0; rows * columns],
             generations: 0,
             last_delta: None,
-        }
-    }
-
-    /// Seeds this universe with random values
-    fn random_seed(&mut self) {
-        let mut rng = rand::thread_rng();
-        for i in 0..self.rows {
-            for j in 0..self.columns {
-                let y: f64 = rng.gen();
-                if y >= UNIVERSE_RANDOM_ALIVE_PROBABILITY {
-                    self.set_cell(i, j, UniverseCell::Alive);
-                } else {
-                    self.set_cell(i, j, UniverseCell::Dead);
-                }
-            }
+            last_changes: (0, 0),
+            rule: UniverseRule::default(),
+            rain: None,
+            chance: None,
+            injection: None,
+            freeze_zones: vec![],
+            ant_steps: 1,
         }
     }
 
@@ -110,135 +134,641 @@ impl Universe {
 
     /// Sets cell at `row`x`column` coordinates
     pub fn set_cell(&mut self, row: usize, column: usize, cell: UniverseCell) {
-        let i = self.get_index(row, column);
-        self.cells[i] = cell;
+        let index = self.get_index(row, column);
+        // Edited cells leave no trail behind
+        if self.cells.get(row, column) != cell.is_alive() {
+            if let Some(age) = self.age_map.get_mut(index) {
+                *age = 0;
+            }
+            if let Some(last_alive) = self.last_alive_map.get_mut(index) {
+                *last_alive = 0;
+            }
+        }
+        // Dying cells are either brought back to life or put to rest, and ants
+        // are wiped along with the color of their cell
+        let ant = self.rule.is_ant();
+        if let Some(state) = self.decay_map.get_mut(index) {
+            *state = (ant && cell.is_alive()) as u8;
+        }
+        self.cells.set(row, column, cell.is_alive());
+    }
+
+    /// Sets the cell at `row`x`column` to `state`, as told by `cell_state`.
+    /// States the rule does not have leave the cell dead.
+    pub fn set_cell_state(&mut self, row: usize, column: usize, state: u8) {
+        match state {
+            1 => self.set_cell(row, column, UniverseCell::Alive),
+            _ => {
+                self.set_cell(row, column, UniverseCell::Dead);
+                if state < self.rule.states() {
+                    let index = self.get_index(row, column);
+                    self.decay_map[index] = state;
+                }
+            }
+        }
+    }
+
+    /// The state of the cell at `row`x`column`: 0 if dead, 1 if alive and the
+    /// dying state it is in under a Generations rule otherwise
+    pub fn cell_state(&self, row: usize, column: usize) -> u8 {
+        match self.cells.get(row, column) {
+            true => 1,
+            false => self
+                .decay_map
+                .get(self.get_index(row, column))
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// The color of the living cell at `row`x`column` under a colored rule such
+    /// as Immigration, or of any cell under an ant rule, 0 for other cells
+    pub fn cell_color(&self, row: usize, column: usize) -> u8 {
+        let state = self.decay_map[self.get_index(row, column)];
+        match (self.rule.is_colored(), self.rule.is_ant()) {
+            (true, _) if self.cells.get(row, column) => state,
+            (_, true) => state & ANT_COLOR_MASK,
+            _ => 0,
+        }
+    }
+
+    /// Paints the living cell at `row`x`column` in `color` under a colored
+    /// rule. Dead cells and colors the rule does not have are left alone.
+    pub fn set_cell_color(&mut self, row: usize, column: usize, color: u8) {
+        if self.rule.is_colored() && self.cells.get(row, column) && color < self.rule.colors() {
+            let index = self.get_index(row, column);
+            self.decay_map[index] = color;
+        }
+    }
+
+    /// The colors of the living neighbours of the cell at `row`x`column`
+    fn neighbour_colors(&self, row: usize, column: usize) -> Vec<u8> {
+        let mut colors = Vec::with_capacity(8);
+        for delta_row in [self.rows - 1, 0, 1] {
+            for delta_column in [self.columns - 1, 0, 1] {
+                let neighbour_row = (row + delta_row) % self.rows;
+                let neighbour_column = (column + delta_column) % self.columns;
+                if (delta_row, delta_column) != (0, 0)
+                    && self.cells.get(neighbour_row, neighbour_column)
+                {
+                    colors.push(self.decay_map[self.get_index(neighbour_row, neighbour_column)]);
+                }
+            }
+        }
+        colors
+    }
+
+    /// The heading of the ant on the cell at `row`x`column`, if any
+    pub fn ant_at(&self, row: usize, column: usize) -> Option<AntHeading> {
+        match self.rule.is_ant() {
+            true => ant_heading(self.decay_map[self.get_index(row, column)]),
+            false => None,
+        }
+    }
+
+    /// Puts an ant facing `heading` on the cell at `row`x`column`, in place of
+    /// the one already there if any. Only ant rules have ants.
+    pub fn place_ant(&mut self, row: usize, column: usize, heading: AntHeading) {
+        if self.rule.is_ant() {
+            let index = self.get_index(row, column);
+            self.decay_map[index] = self.decay_map[index] & ANT_COLOR_MASK
+                | (heading.index() as u8 + 1) << ANT_HEADING_SHIFT;
+        }
+    }
+
+    /// The ants of this universe as `(row, column, heading)`, row after row
+    pub fn ants(&self) -> Vec<(usize, usize, AntHeading)> {
+        if !self.rule.is_ant() {
+            return vec![];
+        }
+        self.decay_map
+            .iter()
+            .enumerate()
+            .filter_map(|(index, state)| {
+                ant_heading(*state)
+                    .map(|heading| (index / self.columns, index % self.columns, heading))
+            })
+            .collect()
+    }
+
+    /// The steps every ant takes in a generation under an ant rule
+    pub fn ant_steps(&self) -> u32 {
+        self.ant_steps
+    }
+
+    pub fn set_ant_steps(&mut self, value: u32) {
+        self.ant_steps = value.max(1);
+    }
+
+    /// The number of generations the cell at `row`x`column` has survived for,
+    /// 0 for dead cells and cells born in the last generation
+    pub fn cell_age(&self, row: usize, column: usize) -> u32 {
+        self.age_map
+            .get(self.get_index(row, column))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub(crate) fn set_cell_age(&mut self, row: usize, column: usize, age: u32) {
+        let index = self.get_index(row, column);
+        if let Some(cell_age) = self.age_map.get_mut(index) {
+            *cell_age = age;
+        }
+    }
+
+    /// The last generation the cell at `row`x`column` was alive in, the current
+    /// one for living cells, or `None` if it was not alive since the universe
+    /// was set up
+    pub fn last_alive_generation(&self, row: usize, column: usize) -> Option<u64> {
+        if self.cells.get(row, column) {
+            return Some(self.generations);
+        }
+
+        match self.last_alive_map.get(self.get_index(row, column)) {
+            Some(0) | None => None,
+            Some(last_alive) => Some(last_alive - 1),
+        }
+    }
+
+    pub(crate) fn set_last_alive_generation(&mut self, row: usize, column: usize, generation: u64) {
+        let index = self.get_index(row, column);
+        if let Some(last_alive) = self.last_alive_map.get_mut(index) {
+            *last_alive = generation + 1;
+        }
     }
 
     /// Gets the cell at `row`x`column`.
     /// # Panics
     /// Panics if no cell is found
     pub fn get_cell(&self, row: usize, column: usize) -> (&UniverseCell, &f64) {
-        let idx = self.get_index(row, column);
-        match self.cells.get(idx) {
-            Some(cell) => (cell, self.death_map.get(idx).unwrap_or(&0.0)),
-            None => panic!("Could not get cell at row {row} column {column}"),
+        if row >= self.rows || column >= self.columns {
+            panic!("Could not get cell at row {row} column {column}");
         }
+
+        let cell = match self.cells.get(row, column) {
+            true => &UniverseCell::Alive,
+            false => &UniverseCell::Dead,
+        };
+        let idx = self.get_index(row, column);
+        (cell, self.death_map.get(idx).unwrap_or(&0.0))
     }
 
-    /// Computes the next state of a cell given its current state
-    /// and the state of its neighbours
-    fn cell_next_state(&self, row: usize, column: usize) -> UniverseCell {
-        let (cell, _) = self.get_cell(row, column);
-        let alive_cells_around = self.cell_living_neighbours_count(row, column);
+    /// Iterates over this universe and computes its next generation.
+    /// Alters the struct in-place and returns the cells that changed, either
+    /// because they were born or died or because their corpse froze further.
+    pub fn tick(&mut self) -> &[UniversePoint] {
+        if self.rule.is_ant() {
+            return self.tick_ants();
+        }
 
-        match cell {
-            UniverseCell::Alive => {
-                if !(2..=3).contains(&alive_cells_around) {
-                    UniverseCell::Dead
-                } else {
-                    // This include the condition alive_cells_around == 2 || alive_cells_around == 3
-                    UniverseCell::Alive
+        let mut next = self.cells.next_generation(&self.rule);
+
+        // Dying cells cannot be born again before they are dead
+        let decays = self.rule.has_dying_states();
+        if decays {
+            for (index, state) in self.decay_map.iter().enumerate() {
+                let (row, column) = (index / self.columns, index % self.columns);
+                if !self.cells.get(row, column) && !self.rule.can_be_born(*state) {
+                    next.set(row, column, false);
+                }
+            }
+        }
+
+        if let Some(chance) = self.chance.as_mut() {
+            for row in 0..self.rows {
+                for column in 0..self.columns {
+                    if next.get(row, column) && !chance.allows(self.cells.get(row, column)) {
+                        next.set(row, column, false);
+                    }
+                }
+            }
+
+            // Noise flips cells anywhere but in freeze zones, set back below
+            if chance.is_noisy() {
+                for row in 0..self.rows {
+                    for column in 0..self.columns {
+                        if chance.flips() {
+                            next.set(row, column, !next.get(row, column));
+                        }
+                    }
                 }
             }
+        }
+
+        if let Some(rain) = self.rain.as_ref() {
+            for (row, column) in rain.drops(self.rows, self.columns) {
+                next.set(row, column, true);
+            }
+        }
+
+        if let Some(injection) = self.injection.as_ref() {
+            for (row, column) in injection.cells(self.generations + 1, self.rows, self.columns) {
+                next.set(row, column, true);
+            }
+        }
+
+        // Cells in freeze zones keep their state whatever happens around them
+        let mut frozen_decay = vec![];
+        for zone in self.freeze_zones.iter() {
+            for (row, column) in zone.iter() {
+                if row < self.rows && column < self.columns {
+                    next.set(row, column, self.cells.get(row, column));
+                    let index = self.get_index(row, column);
+                    frozen_decay.push((index, self.decay_map[index]));
+                }
+            }
+        }
 
-            UniverseCell::Dead => {
-                if alive_cells_around == 3 {
+        let mut delta: Vec<UniversePoint> = vec![];
+
+        // Corpses of the previous generation freeze
+        for (row, column) in std::mem::take(&mut self.warm_corpses) {
+            let index = self.get_index(row, column);
+            self.death_map[index] = 0.0;
+            let cell = match next.get(row, column) {
+                true => UniverseCell::Alive,
+                false => UniverseCell::Dead,
+            };
+            delta.push(UniversePoint::new(row, column, cell, 0.0));
+        }
+
+        let (mut births, mut deaths) = (0, 0);
+        for (row, column) in next.iter_differences(&self.cells) {
+            let index = self.get_index(row, column);
+            let cell = match next.get(row, column) {
+                true => {
+                    // Cell becomes alive
+                    births += 1;
+                    self.death_map[index] = 0.0;
                     UniverseCell::Alive
-                } else {
+                }
+                false => {
+                    // Cell dies
+                    deaths += 1;
+                    self.death_map[index] = UNIVERSE_CELL_INITIAL_CORPSE_HEAT;
+                    self.warm_corpses.push((row, column));
                     UniverseCell::Dead
                 }
+            };
+            delta.push(UniversePoint::new(row, column, cell, self.death_map[index]));
+        }
+
+        // Survivors grow older, anything else starts over
+        for (index, age) in self.age_map.iter_mut().enumerate() {
+            let (row, column) = (index / self.columns, index % self.columns);
+            let was_alive = self.cells.get(row, column);
+            if was_alive {
+                self.last_alive_map[index] = self.generations + 1;
+            }
+            *age = match was_alive && next.get(row, column) {
+                true => age.saturating_add(1),
+                false => 0,
+            };
+        }
+
+        // Born cells take the color most of their parents have, survivors keep
+        // theirs
+        if self.rule.is_colored() {
+            let mut colors = vec![0; self.decay_map.len()];
+            for (index, color) in colors.iter_mut().enumerate() {
+                let (row, column) = (index / self.columns, index % self.columns);
+                *color = match (self.cells.get(row, column), next.get(row, column)) {
+                    (true, true) => self.decay_map[index],
+                    (false, true) => self.rule.born_color(&self.neighbour_colors(row, column)),
+                    (_, false) => 0,
+                };
+            }
+            for (index, color) in frozen_decay.iter() {
+                colors[*index] = *color;
+            }
+            self.decay_map = colors;
+        }
+
+        // Dying cells decay one state further, cells that just died start dying
+        if decays {
+            for index in 0..self.decay_map.len() {
+                let (row, column) = (index / self.columns, index % self.columns);
+                let (was_alive, is_alive) = (self.cells.get(row, column), next.get(row, column));
+                let state = self.decay_map[index];
+                self.decay_map[index] = match (was_alive, is_alive) {
+                    (_, true) => 0,
+                    (true, false) => self.rule.decay(1),
+                    (false, false) => self.rule.decay(state),
+                };
+                // Births and deaths are already part of the delta
+                if !was_alive && !is_alive && state != self.decay_map[index] {
+                    delta.push(UniversePoint::new(
+                        row,
+                        column,
+                        UniverseCell::Dead,
+                        self.death_map[index],
+                    ));
+                }
+            }
+            for (index, state) in frozen_decay {
+                self.decay_map[index] = state;
             }
         }
+
+        self.cells = next;
+        self.generations += 1;
+        self.last_changes = (births, deaths);
+        self.last_delta.insert(delta)
     }
 
-    /// Counts living adiacents cells for a given cell at `row`x`column` coordinates
-    fn cell_living_neighbours_count(&self, row: usize, column: usize) -> u8 {
-        let mut count = 0;
-        let rows = [self.rows - 1, 0, 1];
-        let cols = [self.columns - 1, 0, 1];
-        for delta_row in rows.iter() {
-            for delta_col in cols.iter() {
-                if *delta_row == 0 && *delta_col == 0 {
-                    continue;
+    /// Walks every ant `ant_steps` steps under an ant rule: each makes the turn
+    /// of the color of its cell, moves the cell on to the next color and steps
+    /// forward, wrapping around the edges. Ants walk one after the other in
+    /// reading order, and ants stepping onto the same cell merge. Rain, chance,
+    /// injection and freeze zones only apply to rules counting neighbours.
+    fn tick_ants(&mut self) -> &[UniversePoint] {
+        let colors = self.rule.ant_colors();
+        let (mut births, mut deaths) = (0, 0);
+        let mut changed = vec![];
+        for _ in 0..self.ant_steps {
+            let ants = self.ants();
+            if ants.is_empty() {
+                break;
+            }
+
+            // Ants are lifted first, so that none is stepped on before it walks
+            for (row, column, _) in ants.iter() {
+                let index = self.get_index(*row, *column);
+                self.decay_map[index] &= ANT_COLOR_MASK;
+            }
+            let mut moved = Vec::with_capacity(ants.len());
+            for (row, column, heading) in ants {
+                let index = self.get_index(row, column);
+                let color = self.decay_map[index];
+                let heading = heading.turned(self.rule.ant_turn(color));
+                let next_color = (color + 1) % colors;
+                self.decay_map[index] = next_color;
+                match (color != 0, next_color != 0) {
+                    (false, true) => births += 1,
+                    (true, false) => deaths += 1,
+                    _ => (),
                 }
+                self.cells.set(row, column, next_color != 0);
+                changed.push(index);
+
+                let (row_offset, column_offset) = heading.offset();
+                moved.push((
+                    (row as isize + row_offset).rem_euclid(self.rows as isize) as usize,
+                    (column as isize + column_offset).rem_euclid(self.columns as isize) as usize,
+                    heading,
+                ));
+            }
+            for (row, column, heading) in moved {
+                self.place_ant(row, column, heading);
+            }
+        }
 
-                let neighbor_row = (row + delta_row) % self.rows;
-                let neighbor_col = (column + delta_col) % self.columns;
-                let idx = self.get_index(neighbor_row, neighbor_col);
-                match self.cells[idx] {
-                    UniverseCell::Alive => count += 1,
-                    UniverseCell::Dead => (),
+        changed.sort_unstable();
+        changed.dedup();
+        let delta = changed
+            .into_iter()
+            .map(|index| {
+                let (row, column) = (index / self.columns, index % self.columns);
+                let cell = match self.cells.get(row, column) {
+                    true => UniverseCell::Alive,
+                    false => UniverseCell::Dead,
                 };
+                UniversePoint::new(row, column, cell, 0.0)
+            })
+            .collect();
+        self.generations += 1;
+        self.last_changes = (births, deaths);
+        self.last_delta.insert(delta)
+    }
+
+    /// Stamps the living cells of `pattern` onto this universe, with the top left
+    /// corner of the pattern at `row`x`column`. The pattern wraps around the edges.
+    pub fn stamp(&mut self, pattern: &UniverseSnapshot, row: usize, column: usize) {
+        self.stamp_blended(pattern, row, column, StampBlend::Or);
+    }
+
+    /// Same as `stamp`, combining the cells of `pattern` with the cells
+    /// underneath as told by `blend`
+    pub fn stamp_blended(
+        &mut self,
+        pattern: &UniverseSnapshot,
+        row: usize,
+        column: usize,
+        blend: StampBlend,
+    ) {
+        for pattern_row in 0..pattern.rows {
+            for pattern_column in 0..pattern.columns {
+                let (target_row, target_column) = (
+                    (row + pattern_row) % self.rows,
+                    (column + pattern_column) % self.columns,
+                );
+                let existing = *self.get_cell(target_row, target_column).0;
+                let stamped = pattern.cells[pattern.get_index(pattern_row, pattern_column)];
+                let cell = blend.blend(existing, stamped);
+                if cell != existing || stamped.is_alive() {
+                    self.set_cell(target_row, target_column, cell);
+                }
             }
         }
-        count
     }
 
-    /// Iterates over this universe and computes its next generation.
-    /// Alters the struct in-place.
-    pub fn tick(&mut self) {
-        let mut new_state = Self::new(self.columns, self.rows);
-
-        let mut delta: Vec<UniversePoint> = Vec::with_capacity(self.cells.capacity());
-        for point in self.iter_cells() {
-            let index = self.get_index(point.row(), point.column());
-            let cell_current_state = point.cell();
-            let cell_next_state = self.cell_next_state(point.row(), point.column());
-            let death_map_item_ref = new_state.death_map.get_mut(index).unwrap();
-            new_state.cells[index] = cell_next_state;
-
-            if cell_next_state != *cell_current_state {
-                match cell_next_state {
-                    UniverseCell::Alive => {
-                        // Cell becomes alive
-                        *death_map_item_ref = 0.0;
-                    }
-                    UniverseCell::Dead => {
-                        // Cell dies
-                        *death_map_item_ref = UNIVERSE_CELL_INITIAL_CORPSE_HEAT;
-                    }
+    /// Grows or crops this universe to `rows`x`columns`, keeping the cells
+    /// around `anchor` in place. Cells falling outside of the new size are lost,
+    /// new cells are dead.
+    pub fn resize(&mut self, rows: usize, columns: usize, anchor: ResizeAnchor) {
+        let (row_offset, column_offset) = match anchor {
+            ResizeAnchor::Center => (
+                (rows as isize - self.rows as isize) / 2,
+                (columns as isize - self.columns as isize) / 2,
+            ),
+            ResizeAnchor::TopLeft => (0, 0),
+        };
+        self.reframe(rows, columns, row_offset, column_offset);
+    }
+
+    /// Moves every cell by `row_offset` rows and `column_offset` columns,
+    /// keeping the size of this universe. Cells moved past the edges are lost.
+    pub fn shift(&mut self, row_offset: isize, column_offset: isize) {
+        self.reframe(self.rows, self.columns, row_offset, column_offset);
+    }
+
+    /// Crops this universe to the `rows`x`columns` area from `top`x`left`,
+    /// which may reach past the edges: new cells there are dead
+    pub fn crop_to(&mut self, top: isize, left: isize, rows: usize, columns: usize) {
+        self.reframe(rows, columns, -top, -left);
+    }
+
+    /// Makes this universe `rows`x`columns`, moving every cell by `row_offset`
+    /// rows and `column_offset` columns
+    fn reframe(&mut self, rows: usize, columns: usize, row_offset: isize, column_offset: isize) {
+        let moved = |row: usize, column: usize| {
+            let (row, column) = (row as isize + row_offset, column as isize + column_offset);
+            match row >= 0 && column >= 0 && (row as usize) < rows && (column as usize) < columns {
+                true => Some((row as usize, column as usize)),
+                false => None,
+            }
+        };
+
+        let mut cells = BitGrid::new(rows, columns);
+        let mut death_map = vec![0.0; rows * columns];
+        let mut age_map = vec![0; rows * columns];
+        let mut last_alive_map = vec![0; rows * columns];
+        let mut decay_map = vec![0; rows * columns];
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                if let Some((new_row, new_column)) = moved(row, column) {
+                    cells.set(new_row, new_column, self.cells.get(row, column));
+                    death_map[new_row * columns + new_column] =
+                        self.death_map[self.get_index(row, column)];
+                    age_map[new_row * columns + new_column] =
+                        self.age_map[self.get_index(row, column)];
+                    last_alive_map[new_row * columns + new_column] =
+                        self.last_alive_map[self.get_index(row, column)];
+                    decay_map[new_row * columns + new_column] =
+                        self.decay_map[self.get_index(row, column)];
                 }
-                delta.push(UniversePoint::new(
-                    point.row(),
-                    point.column(),
-                    cell_next_state,
-                    *death_map_item_ref,
-                ));
-            } else {
-                // Dead cell corpse keeps freezing
-                if *cell_current_state == UniverseCell::Dead && *death_map_item_ref > 0.0 {
-                    *death_map_item_ref = point.corpse_heat() - UNIVERSE_DEFAULT_FREEZE_RATE;
+            }
+        }
+
+        self.warm_corpses = self
+            .warm_corpses
+            .iter()
+            .filter_map(|(row, column)| moved(*row, *column))
+            .collect();
+        self.rows = rows;
+        self.columns = columns;
+        self.cells = cells;
+        self.death_map = death_map;
+        self.age_map = age_map;
+        self.last_alive_map = last_alive_map;
+        self.decay_map = decay_map;
+        compute_initial_delta(self);
+    }
+
+    /// The living cells of this universe
+    pub fn cells(&self) -> &BitGrid {
+        &self.cells
+    }
+
+    /// Brings this universe back to a past `generation` whose living cells were
+    /// `cells`, as recorded in a `UniverseHistory`. Corpses, ages, trails,
+    /// dying cells and colors are forgotten.
+    /// # Panics
+    /// Panics if `cells` do not have the size of this universe
+    pub fn restore(&mut self, generation: u64, cells: BitGrid) {
+        assert!(
+            cells.rows() == self.rows && cells.columns() == self.columns,
+            "Could not restore cells of a different size"
+        );
+
+        self.cells = cells;
+        self.death_map = vec![0.0; self.rows * self.columns];
+        self.age_map = vec![0; self.rows * self.columns];
+        self.last_alive_map = vec![0; self.rows * self.columns];
+        self.decay_map = vec![0; self.rows * self.columns];
+        self.warm_corpses.clear();
+        self.generations = generation;
+        compute_initial_delta(self);
+    }
+
+    /// The states of the cells that are neither dead nor alive, the colors of
+    /// the living cells under colored rules, or the colors of the cells and
+    /// the ants on them under ant rules, row after row, or `None` if there are
+    /// none
+    pub fn dying_states(&self) -> Option<&[u8]> {
+        self.decay_map
+            .iter()
+            .any(|state| *state > 0)
+            .then_some(self.decay_map.as_slice())
+    }
+
+    /// Brings back the cells that are neither dead nor alive, or the colors of
+    /// cells and ants, as told by `dying_states`, after the living cells were
+    /// restored
+    /// # Panics
+    /// Panics if `states` do not have the size of this universe
+    pub fn restore_dying_states(&mut self, states: &[u8]) {
+        assert!(
+            states.len() == self.decay_map.len(),
+            "Could not restore states of a different size"
+        );
+
+        for (index, state) in states.iter().enumerate() {
+            let (row, column) = (index / self.columns, index % self.columns);
+            let restored = match (self.rule.is_colored(), self.rule.is_ant()) {
+                (true, _) => self.cells.get(row, column) && *state < self.rule.colors(),
+                (_, true) => {
+                    let color = *state & ANT_COLOR_MASK;
+                    self.cells.get(row, column) == (color != 0) && color < self.rule.ant_colors()
                 }
+                _ => !self.cells.get(row, column) && *state < self.rule.states(),
+            };
+            if restored {
+                self.decay_map[index] = *state;
             }
         }
-        self.cells = new_state.cells.clone();
-        self.death_map = new_state.death_map.clone();
-        self.generations += 1;
-        self.last_delta = Some(delta);
+        compute_initial_delta(self);
+    }
+
+    /// A hash of the cells of this universe, telling apart different states
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.cells.hash(&mut hasher);
+        if self.decay_map.iter().any(|state| *state > 0) {
+            self.decay_map.hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Kills every cell in `area`
+    pub fn clear_area(&mut self, area: &UniverseArea) {
+        for (row, column) in area.iter() {
+            self.set_cell(row % self.rows, column % self.columns, UniverseCell::Dead);
+        }
+    }
+
+    /// Seeds the cells in `area` with random values, with the same
+    /// density as a random universe
+    pub fn randomize_area(&mut self, area: &UniverseArea) {
+        self.fill_soup(area, &UniverseSoup::default());
+    }
+
+    /// Seeds the cells in `area` with random values drawn from `soup`. Every
+    /// cell is drawn first, so that the same seed gives the same cells whatever
+    /// the symmetry, then mirrored cells copy their source.
+    pub fn fill_soup(&mut self, area: &UniverseArea, soup: &UniverseSoup) {
+        let mut rng = soup.rng();
+        let drawn: Vec<bool> = area.iter().map(|_| rng.gen_bool(soup.density())).collect();
+
+        let (rows, columns) = (area.rows(), area.columns());
+        for (row, column) in area.iter() {
+            let (source_row, source_column) =
+                soup.symmetry()
+                    .source(row - area.row(), column - area.column(), rows, columns);
+            let cell = match drawn[source_row * columns + source_column] {
+                true => UniverseCell::Alive,
+                false => UniverseCell::Dead,
+            };
+            self.set_cell(row % self.rows, column % self.columns, cell);
+        }
     }
 
     /// Counts and returns the number of alive cells
     /// in this universe
     pub fn alive_cells_count(&self) -> usize {
-        self.cells
-            .iter()
-            .filter(|cell| match cell {
-                UniverseCell::Alive => true,
-                UniverseCell::Dead => false,
-            })
-            .count()
+        self.cells.count_alive()
+    }
+
+    /// The smallest `(top, left, rows, columns)` area containing all the living
+    /// cells, or `None` if there are none
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        self.cells.bounding_box()
     }
 
     /// Counts and returns the number of dead cells
     /// in this universe
     pub fn dead_cells_count(&self) -> usize {
-        self.cells
-            .iter()
-            .filter(|cell| match cell {
-                UniverseCell::Alive => false,
-                UniverseCell::Dead => true,
-            })
-            .count()
+        self.rows * self.columns - self.cells.count_alive()
     }
 
     /// Gets the last delta for this universe.
@@ -251,7 +781,7 @@ impl Universe {
         }
     }
 
-    pub fn iter_cells(&self) -> UniverseIterator {
+    pub fn iter_cells(&self) -> UniverseIterator<'_> {
         UniverseIterator::new(self)
     }
 
@@ -266,6 +796,103 @@ impl Universe {
     pub fn set_corpse_freeze_rate(&mut self, value: f64) {
         self.corpse_freeze_rate = value;
     }
+
+    /// The number of generations computed since this universe was created
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    /// How many cells were born and how many died in the last generation
+    pub fn last_changes(&self) -> (usize, usize) {
+        self.last_changes
+    }
+
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    /// Sets the rule this universe evolves by. Cells in dying states the rule
+    /// does not have are put to rest, as are all of them when switching to or
+    /// from WireWorld. Colors are kept between rules with as many of them.
+    /// Switching to an ant rule puts an ant facing north in the middle, living
+    /// cells taking the second color, and ants stay between ant rules.
+    /// Switching to an elementary rule brings a single cell to life in the
+    /// middle of the line if none lives there.
+    pub fn set_rule(&mut self, value: UniverseRule) {
+        let starts_line = value.is_elementary() && !self.rule.is_elementary();
+        let switches_family = value.is_wireworld() != self.rule.is_wireworld()
+            || value.colors() != self.rule.colors()
+            || value.is_ant() != self.rule.is_ant();
+        let states = match (value.is_colored(), value.is_ant()) {
+            (true, _) => value.colors(),
+            (_, true) => value.ant_colors(),
+            _ => value.states(),
+        };
+        for state in self.decay_map.iter_mut() {
+            match value.is_ant() && !switches_family {
+                // Colors the rule does not have turn back into the second one
+                true if *state & ANT_COLOR_MASK >= states => {
+                    *state = *state & !ANT_COLOR_MASK | 1;
+                }
+                true => (),
+                false if *state >= states || switches_family => *state = 0,
+                false => (),
+            }
+        }
+        self.rule = value;
+
+        if switches_family && self.rule.is_ant() {
+            for (index, state) in self.decay_map.iter_mut().enumerate() {
+                *state = self.cells.get(index / self.columns, index % self.columns) as u8;
+            }
+            self.place_ant(self.rows / 2, self.columns / 2, AntHeading::North);
+        }
+
+        if starts_line && self.columns > 0 {
+            let line = self.columns - 1;
+            if (0..self.rows).all(|row| !self.cells.get(row, line)) {
+                self.set_cell(self.rows / 2, line, UniverseCell::Alive);
+            }
+        }
+    }
+
+    /// The random cells injected at every generation, if any
+    pub fn rain(&self) -> Option<&UniverseRain> {
+        self.rain.as_ref()
+    }
+
+    pub fn set_rain(&mut self, value: Option<UniverseRain>) {
+        self.rain = value;
+    }
+
+    /// The probabilities births and survivals happen with and cells are flipped
+    /// with, if the rule is stochastic
+    pub fn chance(&self) -> Option<&UniverseChance> {
+        self.chance.as_ref()
+    }
+
+    pub fn set_chance(&mut self, value: Option<UniverseChance>) {
+        self.chance = value;
+    }
+
+    /// The pattern periodically stamped on an edge, if any
+    pub fn injection(&self) -> Option<&UniverseInjection> {
+        self.injection.as_ref()
+    }
+
+    pub fn set_injection(&mut self, value: Option<UniverseInjection>) {
+        self.injection = value;
+    }
+
+    /// The areas whose cells never change while evolving, acting as a constant
+    /// boundary for the cells around them. Parts out of the universe are ignored.
+    pub fn freeze_zones(&self) -> &[UniverseArea] {
+        &self.freeze_zones
+    }
+
+    pub fn set_freeze_zones(&mut self, value: Vec<UniverseArea>) {
+        self.freeze_zones = value;
+    }
 }
 
 impl UniversePointMatrix for Universe {
@@ -280,10 +907,12 @@ impl UniversePointMatrix for Universe {
     }
 
     fn get(&self, row: usize, column: usize) -> Option<UniversePoint> {
-        let idx = self.get_index(row, column);
-        self.cells.get(idx).map(|cell| {
-            UniversePoint::new(row, column, *cell, *self.death_map.get(idx).unwrap_or(&0.0))
-        })
+        if row >= self.rows || column >= self.columns {
+            return None;
+        }
+
+        let (cell, corpse_heat) = self.get_cell(row, column);
+        Some(UniversePoint::new(row, column, *cell, *corpse_heat))
     }
 
     fn set(
@@ -352,12 +981,12 @@ impl<'a> Iterator for UniverseIterator<'a> {
 
 impl fmt::Display for Universe {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for line in self.cells.as_slice().chunks(self.columns()) {
-            for &cell in line {
-                let symbol = if cell == UniverseCell::Dead {
-                    '◻'
-                } else {
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                let symbol = if self.cells.get(row, column) {
                     '◼'
+                } else {
+                    '◻'
                 };
                 write!(f, "{symbol}")?;
             }
@@ -369,12 +998,83 @@ impl fmt::Display for Universe {
 
 impl Drop for Universe {
     fn drop(&mut self) {
-        self.cells.clear();
+        self.warm_corpses.clear();
         self.generations = 0;
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+/// The point of a universe that stays in place when it is resized
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResizeAnchor {
+    #[default]
+    Center,
+    TopLeft,
+}
+
+/// How the cells of a stamped pattern combine with the cells underneath
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StampBlend {
+    /// The living cells of the pattern are added to the universe
+    #[default]
+    Or,
+
+    /// The pattern replaces the cells underneath, dead cells included
+    Overwrite,
+
+    /// The living cells of the pattern toggle the cells underneath
+    Xor,
+}
+
+impl StampBlend {
+    pub const ALL: [StampBlend; 3] = [StampBlend::Or, StampBlend::Overwrite, StampBlend::Xor];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StampBlend::Or => "or",
+            StampBlend::Overwrite => "overwrite",
+            StampBlend::Xor => "xor",
+        }
+    }
+
+    /// The cell resulting from stamping `stamped` over `existing`
+    pub fn blend(&self, existing: UniverseCell, stamped: UniverseCell) -> UniverseCell {
+        match (self, stamped) {
+            (StampBlend::Overwrite, _) => stamped,
+            (StampBlend::Or, UniverseCell::Alive) => UniverseCell::Alive,
+            (StampBlend::Xor, UniverseCell::Alive) => !existing,
+            (_, UniverseCell::Dead) => existing,
+        }
+    }
+}
+
+impl FromStr for StampBlend {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        StampBlend::ALL
+            .into_iter()
+            .find(|blend| blend.as_str() == value)
+            .ok_or_else(|| format!("Unknown stamp blend {}", value))
+    }
+}
+
+/// A transformation of the cells of a snapshot, in terms of its rows and columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotTransform {
+    /// Rotates by 90°, the first row becoming the last column
+    RotateClockwise,
+
+    /// Rotates by 90°, the first row becoming the first column
+    RotateCounterClockwise,
+
+    /// Reverses the order of rows
+    FlipRows,
+
+    /// Reverses the order of columns
+    FlipColumns,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct UniverseSnapshot {
     rows: usize,
     columns: usize,
@@ -387,7 +1087,7 @@ pub struct UniverseSnapshot {
 impl From<&Universe> for UniverseSnapshot {
     fn from(value: &Universe) -> Self {
         UniverseSnapshot {
-            cells: value.cells.clone(),
+            cells: value.iter_cells().map(|point| *point.cell()).collect(),
             death_map: value.death_map.clone(),
             rows: value.rows(),
             columns: value.columns(),
@@ -403,6 +1103,136 @@ impl UniverseSnapshot {
     pub fn serialize(&self) -> Result<Vec<u8>, bincode::Error> {
         bincode::serialize(self)
     }
+
+    /// The smallest `(top, left, rows, columns)` area containing all the living
+    /// cells, or `None` if there are none
+    pub fn bounding_box(&self) -> Option<(usize, usize, usize, usize)> {
+        let mut bounds: Option<(usize, usize, usize, usize)> = None;
+        for (row, column) in self.iter_alive() {
+            bounds = Some(match bounds {
+                None => (row, column, row, column),
+                Some((top, left, bottom, right)) => (
+                    top.min(row),
+                    left.min(column),
+                    bottom.max(row),
+                    right.max(column),
+                ),
+            });
+        }
+
+        bounds.map(|(top, left, bottom, right)| (top, left, bottom - top + 1, right - left + 1))
+    }
+
+    /// Copies the `rows`x`columns` area with its top left corner at `top`x`left`
+    /// into a new snapshot. Parts of the area outside of this snapshot are dead.
+    pub fn crop(&self, top: usize, left: usize, rows: usize, columns: usize) -> UniverseSnapshot {
+        let mut universe = Universe::new_empty(rows, columns);
+        for (row, column) in self.iter_alive() {
+            if (top..top + rows).contains(&row) && (left..left + columns).contains(&column) {
+                universe.set_cell(row - top, column - left, UniverseCell::Alive);
+            }
+        }
+        universe.snapshot()
+    }
+
+    /// Returns a copy of this snapshot with `transform` applied to it
+    pub fn transformed(&self, transform: SnapshotTransform) -> UniverseSnapshot {
+        let (rows, columns) = (self.rows, self.columns);
+        let (new_rows, new_columns) = match transform {
+            SnapshotTransform::RotateClockwise | SnapshotTransform::RotateCounterClockwise => {
+                (columns, rows)
+            }
+            SnapshotTransform::FlipRows | SnapshotTransform::FlipColumns => (rows, columns),
+        };
+
+        let mut universe = Universe::new_empty(new_rows, new_columns);
+        for (row, column) in self.iter_alive() {
+            let (new_row, new_column) = match transform {
+                SnapshotTransform::RotateClockwise => (column, rows - 1 - row),
+                SnapshotTransform::RotateCounterClockwise => (columns - 1 - column, row),
+                SnapshotTransform::FlipRows => (rows - 1 - row, column),
+                SnapshotTransform::FlipColumns => (row, columns - 1 - column),
+            };
+            universe.set_cell(new_row, new_column, UniverseCell::Alive);
+        }
+        universe.snapshot()
+    }
+
+    /// The living cells of this snapshot, cropped to their bounding box and
+    /// in the orientation whose cells sort first among the eight rotations
+    /// and reflections. Snapshots of the same pattern, however placed, rotated
+    /// or mirrored, share the same canonical form.
+    pub fn canonical(&self) -> UniverseSnapshot {
+        let cropped = match self.bounding_box() {
+            Some((top, left, rows, columns)) => self.crop(top, left, rows, columns),
+            None => return self.crop(0, 0, 0, 0),
+        };
+
+        let mut orientations = vec![];
+        let mut rotated = cropped;
+        for _ in 0..4 {
+            let flipped = rotated.transformed(SnapshotTransform::FlipRows);
+            let next = rotated.transformed(SnapshotTransform::RotateClockwise);
+            orientations.push(rotated);
+            orientations.push(flipped);
+            rotated = next;
+        }
+
+        orientations
+            .into_iter()
+            .min_by_key(|snapshot| {
+                (
+                    snapshot.rows,
+                    snapshot.columns,
+                    snapshot.iter_alive().collect::<Vec<_>>(),
+                )
+            })
+            .unwrap()
+    }
+
+    /// A hash of the canonical form of this snapshot, equal for snapshots of
+    /// the same pattern regardless of position, rotation and reflection
+    pub fn canonical_hash(&self) -> u64 {
+        let canonical = self.canonical();
+        let mut hasher = DefaultHasher::new();
+        (canonical.rows, canonical.columns).hash(&mut hasher);
+        canonical
+            .iter_alive()
+            .for_each(|cell| cell.hash(&mut hasher));
+        hasher.finish()
+    }
+
+    /// Computes what changed from this snapshot to `other`. Snapshots of different
+    /// sizes are compared over their union, cells out of a snapshot being dead.
+    pub fn diff(&self, other: &UniverseSnapshot) -> CellDelta {
+        let is_alive = |snapshot: &UniverseSnapshot, row: usize, column: usize| {
+            row < snapshot.rows
+                && column < snapshot.columns
+                && snapshot.cells[snapshot.get_index(row, column)].is_alive()
+        };
+
+        let (mut births, mut deaths) = (vec![], vec![]);
+        for row in 0..self.rows.max(other.rows) {
+            for column in 0..self.columns.max(other.columns) {
+                match (is_alive(self, row, column), is_alive(other, row, column)) {
+                    (false, true) => births.push((row, column)),
+                    (true, false) => deaths.push((row, column)),
+                    _ => (),
+                }
+            }
+        }
+
+        CellDelta::new(births, deaths, self.bounding_box(), other.bounding_box())
+    }
+
+    /// Iterates over the coordinates of living cells, as `(row, column)`
+    pub fn iter_alive(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cell)| cell.is_alive())
+            .map(|(idx, _)| (idx / self.columns, idx % self.columns))
+    }
 }
 
 impl UniversePointMatrix for UniverseSnapshot {
@@ -436,6 +1266,18 @@ impl UniversePointMatrix for UniverseSnapshot {
 #[derive(Debug)]
 pub enum SnapshotError {
     Invalid,
+
+    /// The data could not be deserialized, for the given reason
+    Undecodable(String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Invalid => write!(f, "invalid universe data"),
+            SnapshotError::Undecodable(reason) => write!(f, "invalid universe data: {}", reason),
+        }
+    }
 }
 
 impl TryFrom<&Vec<u8>> for UniverseSnapshot {
@@ -443,27 +1285,254 @@ impl TryFrom<&Vec<u8>> for UniverseSnapshot {
     fn try_from(value: &Vec<u8>) -> Result<Self, Self::Error> {
         match bincode::deserialize::<Self>(value.as_ref()) {
             Ok(snapshot) => Ok(snapshot),
-            Err(error) => {
-                glib::g_critical!(G_LOG_DOMAIN, "{}", error);
-                Err(SnapshotError::Invalid)
-            }
+            Err(error) => Err(SnapshotError::Undecodable(error.to_string())),
         }
     }
 }
 
 impl From<UniverseSnapshot> for Universe {
     fn from(snapshot: UniverseSnapshot) -> Self {
-        let mut death_map = Vec::with_capacity(snapshot.rows * snapshot.columns);
-        death_map.fill(0.0);
+        let mut cells = BitGrid::new(snapshot.rows, snapshot.columns);
+        for (row, column) in snapshot.iter_alive() {
+            cells.set(row, column, true);
+        }
 
         Self {
             rows: snapshot.rows,
             columns: snapshot.columns,
             corpse_freeze_rate: UNIVERSE_DEFAULT_FREEZE_RATE,
-            death_map,
-            cells: snapshot.cells,
+            death_map: vec![0.0; snapshot.rows * snapshot.columns],
+            age_map: vec![0; snapshot.rows * snapshot.columns],
+            last_alive_map: vec![0; snapshot.rows * snapshot.columns],
+            warm_corpses: vec![],
+            decay_map: vec![0; snapshot.rows * snapshot.columns],
+            cells,
             generations: 0,
             last_delta: None,
+            last_changes: (0, 0),
+            rule: UniverseRule::default(),
+            rain: None,
+            chance: None,
+            injection: None,
+            freeze_zones: vec![],
+            ant_steps: 1,
+        }
+    }
+}
+
+/// How a universe is written in project files: its size, generation and rule,
+/// and its cells as rows of `.` (dead) and `O` (alive) as in plaintext patterns.
+/// Dying cells are listed apart as `(row, column, state)`, and the colors of
+/// living cells under colored and ant rules as `(row, column, color)`. Ants
+/// are listed as `(row, column, heading)`.
+#[derive(Serialize, Deserialize)]
+struct UniverseRecord {
+    rows: usize,
+    columns: usize,
+    generation: u64,
+    rule: String,
+    cells: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    dying: Vec<(usize, usize, u8)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    colors: Vec<(usize, usize, u8)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    ants: Vec<(usize, usize, String)>,
+}
+
+impl From<Universe> for UniverseRecord {
+    fn from(universe: Universe) -> Self {
+        let cells = (0..universe.rows)
+            .map(|row| {
+                (0..universe.columns)
+                    .map(|column| match universe.cells.get(row, column) {
+                        true => 'O',
+                        false => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        let states: Vec<(usize, usize, u8)> = universe
+            .decay_map
+            .iter()
+            .enumerate()
+            .map(|(index, state)| match universe.rule.is_ant() {
+                true => (index, state & ANT_COLOR_MASK),
+                false => (index, *state),
+            })
+            .filter(|(_, state)| *state > 0)
+            .map(|(index, state)| (index / universe.columns, index % universe.columns, state))
+            .collect();
+        let (dying, colors) = match universe.rule.is_colored() || universe.rule.is_ant() {
+            true => (vec![], states),
+            false => (states, vec![]),
+        };
+        let ants = universe
+            .ants()
+            .into_iter()
+            .map(|(row, column, heading)| (row, column, heading.as_str().to_string()))
+            .collect();
+
+        Self {
+            rows: universe.rows,
+            columns: universe.columns,
+            generation: universe.generations,
+            rule: universe.rule.to_string(),
+            cells,
+            dying,
+            colors,
+            ants,
         }
     }
 }
+
+impl TryFrom<UniverseRecord> for Universe {
+    type Error = SnapshotError;
+
+    fn try_from(record: UniverseRecord) -> Result<Self, Self::Error> {
+        if record.rows == 0 || record.columns == 0 || record.cells.len() != record.rows {
+            return Err(SnapshotError::Invalid);
+        }
+
+        let mut cells = BitGrid::new(record.rows, record.columns);
+        for (row, line) in record.cells.iter().enumerate() {
+            if line.chars().count() != record.columns {
+                return Err(SnapshotError::Invalid);
+            }
+            for (column, c) in line.chars().enumerate() {
+                match c {
+                    'O' => cells.set(row, column, true),
+                    '.' => (),
+                    _ => return Err(SnapshotError::Invalid),
+                }
+            }
+        }
+
+        let mut universe = Self::new_empty(record.rows, record.columns);
+        universe.rule = record
+            .rule
+            .parse::<UniverseRule>()
+            .map_err(|_| SnapshotError::Invalid)?;
+        universe.restore(record.generation, cells);
+        for (row, column, state) in record.dying {
+            if row >= record.rows
+                || column >= record.columns
+                || universe.cells.get(row, column)
+                || !(2..universe.rule.states()).contains(&state)
+            {
+                return Err(SnapshotError::Invalid);
+            }
+            let index = universe.get_index(row, column);
+            universe.decay_map[index] = state;
+        }
+        let colors = match universe.rule.is_ant() {
+            true => universe.rule.ant_colors(),
+            false => universe.rule.colors(),
+        };
+        for (row, column, color) in record.colors {
+            if row >= record.rows
+                || column >= record.columns
+                || !universe.cells.get(row, column)
+                || color == 0 && universe.rule.is_ant()
+                || color >= colors
+            {
+                return Err(SnapshotError::Invalid);
+            }
+            let index = universe.get_index(row, column);
+            universe.decay_map[index] = color;
+        }
+        // Living cells of ant rules without a color are in the second one
+        if universe.rule.is_ant() {
+            for (index, state) in universe.decay_map.iter_mut().enumerate() {
+                if *state == 0
+                    && universe
+                        .cells
+                        .get(index / record.columns, index % record.columns)
+                {
+                    *state = 1;
+                }
+            }
+        }
+        for (row, column, heading) in record.ants {
+            match heading.parse::<AntHeading>() {
+                Ok(heading) if row < record.rows && column < record.columns => {
+                    universe.place_ant(row, column, heading)
+                }
+                _ => return Err(SnapshotError::Invalid),
+            }
+        }
+        Ok(universe)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Universe, UniverseCell, UniverseSnapshot};
+
+    fn snapshot(rows: usize, columns: usize, alive: &[(usize, usize)]) -> UniverseSnapshot {
+        let mut universe = Universe::new_empty(rows, columns);
+        for &(row, column) in alive {
+            universe.set_cell(row, column, UniverseCell::Alive);
+        }
+        universe.snapshot()
+    }
+
+    #[test]
+    fn diff_of_identical_snapshots_is_empty() {
+        let blinker = snapshot(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+        let delta = blinker.diff(&blinker);
+        assert!(delta.is_empty());
+        assert_eq!(delta.bounding_box_offset(), Some((0, 0)));
+    }
+
+    #[test]
+    fn diff_reports_births_and_deaths() {
+        let horizontal = snapshot(5, 5, &[(2, 1), (2, 2), (2, 3)]);
+        let vertical = snapshot(5, 5, &[(1, 2), (2, 2), (3, 2)]);
+        let delta = horizontal.diff(&vertical);
+        assert_eq!(delta.births(), &[(1, 2), (3, 2)]);
+        assert_eq!(delta.deaths(), &[(2, 1), (2, 3)]);
+        assert_eq!(delta.bounding_box_before(), Some((2, 1, 1, 3)));
+        assert_eq!(delta.bounding_box_after(), Some((1, 2, 3, 1)));
+    }
+
+    #[test]
+    fn diff_tracks_bounding_box_offset() {
+        let block = snapshot(8, 8, &[(1, 1), (1, 2), (2, 1), (2, 2)]);
+        let moved = snapshot(8, 8, &[(4, 0), (4, 1), (5, 0), (5, 1)]);
+        let delta = block.diff(&moved);
+        assert_eq!(delta.births().len(), 4);
+        assert_eq!(delta.deaths().len(), 4);
+        assert_eq!(delta.bounding_box_offset(), Some((3, -1)));
+    }
+
+    #[test]
+    fn diff_against_empty_snapshot() {
+        let empty = snapshot(3, 3, &[]);
+        let single = snapshot(3, 3, &[(1, 1)]);
+
+        let birth = empty.diff(&single);
+        assert_eq!(birth.births(), &[(1, 1)]);
+        assert!(birth.deaths().is_empty());
+        assert_eq!(birth.bounding_box_offset(), None);
+
+        let death = single.diff(&empty);
+        assert!(death.births().is_empty());
+        assert_eq!(death.deaths(), &[(1, 1)]);
+        assert_eq!(death.bounding_box_offset(), None);
+    }
+
+    #[test]
+    fn diff_of_different_sizes_compares_their_union() {
+        let small = snapshot(2, 3, &[(0, 0), (1, 2)]);
+        let large = snapshot(4, 2, &[(0, 0), (3, 1)]);
+
+        let delta = small.diff(&large);
+        assert_eq!(delta.births(), &[(3, 1)]);
+        assert_eq!(delta.deaths(), &[(1, 2)]);
+
+        let reversed = large.diff(&small);
+        assert_eq!(reversed.births(), delta.deaths());
+        assert_eq!(reversed.deaths(), delta.births());
+    }
+}
@@ -0,0 +1,52 @@
+use std::str::FromStr;
+
+/// The cells drawn in a WireWorld universe, besides empty ones
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WireWorldCell {
+    /// A wire electrons travel along
+    #[default]
+    Conductor,
+
+    /// The front of an electron, becoming a tail in the next generation
+    Head,
+
+    /// The back of an electron, becoming a conductor again in the next generation
+    Tail,
+}
+
+impl WireWorldCell {
+    pub const ALL: [WireWorldCell; 3] = [
+        WireWorldCell::Conductor,
+        WireWorldCell::Head,
+        WireWorldCell::Tail,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WireWorldCell::Conductor => "conductor",
+            WireWorldCell::Head => "head",
+            WireWorldCell::Tail => "tail",
+        }
+    }
+
+    /// The state of the cell in a universe, as told by `Universe::cell_state`.
+    /// Heads are the living cells.
+    pub fn state(&self) -> u8 {
+        match self {
+            WireWorldCell::Head => 1,
+            WireWorldCell::Tail => 2,
+            WireWorldCell::Conductor => 3,
+        }
+    }
+}
+
+impl FromStr for WireWorldCell {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        WireWorldCell::ALL
+            .into_iter()
+            .find(|cell| cell.as_str() == value)
+            .ok_or_else(|| format!("Unknown WireWorld cell {}", value))
+    }
+}
@@ -0,0 +1,67 @@
+use crate::models::{SoupSymmetry, UniverseSoup};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// The cell and background colors the ambient mode cycles through
+const AMBIENT_PALETTES: [(&str, &str); 6] = [
+    ("#64BAFF", "#1C1C1C"),
+    ("#C061CB", "#3D3846"),
+    ("#F6D32D", "#241F31"),
+    ("#57E389", "#1A2B22"),
+    ("#FF7800", "#2E1A12"),
+    ("#DEDDDA", "#1A5FB4"),
+];
+
+/// Picks the soups and colors of the ambient mode, which reseeds the universe
+/// whenever it settles down so that it can run unattended indefinitely
+pub struct AmbientPlayer {
+    rng: StdRng,
+    density: f64,
+    palette_period: u32,
+    reseeds: u32,
+    palette: Option<usize>,
+}
+
+impl AmbientPlayer {
+    /// Creates a player drawing soups of `density`, switching colors every
+    /// `palette_period` reseeds, or never if it is 0
+    pub fn new(density: f64, palette_period: u32) -> Self {
+        Self {
+            rng: StdRng::from_entropy(),
+            density,
+            palette_period,
+            reseeds: 0,
+            palette: None,
+        }
+    }
+
+    /// Draws the next soup, mirrored along a random symmetry
+    pub fn next_soup(&mut self) -> UniverseSoup {
+        self.reseeds += 1;
+        if self.palette_period > 0 && self.reseeds % self.palette_period == 0 {
+            self.next_palette();
+        }
+
+        let symmetry = SoupSymmetry::ALL[1..]
+            .choose(&mut self.rng)
+            .copied()
+            .unwrap_or_default();
+        UniverseSoup::new(self.density, None).with_symmetry(symmetry)
+    }
+
+    /// The cell and background colors to paint the universe with, or `None`
+    /// for the ones of the current style until the first palette change
+    pub fn palette(&self) -> Option<(&'static str, &'static str)> {
+        self.palette.map(|index| AMBIENT_PALETTES[index])
+    }
+
+    /// Switches to a random palette other than the current one
+    fn next_palette(&mut self) {
+        let offset = self.rng.gen_range(1..AMBIENT_PALETTES.len());
+        self.palette = Some(match self.palette {
+            Some(index) => (index + offset) % AMBIENT_PALETTES.len(),
+            None => offset,
+        });
+    }
+}
@@ -0,0 +1,176 @@
+use std::fmt;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+const END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+const LOCAL_HEADER_SIZE: usize = 30;
+const CENTRAL_HEADER_SIZE: usize = 46;
+const ZIP_VERSION: u16 = 20;
+const UTF8_NAMES_FLAG: u16 = 0x0800;
+const STORED_METHOD: u16 = 0;
+/// January 1st 1980, the earliest date a zip archive can represent
+const DOS_EPOCH_DATE: u16 = 0x0021;
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// The data is not a zip archive, or is truncated
+    Malformed,
+
+    /// An entry is compressed, only stored entries are supported
+    Unsupported(String),
+
+    /// An entry does not match its checksum
+    Corrupted(String),
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::Malformed => write!(f, "malformed archive"),
+            ArchiveError::Unsupported(path) => write!(f, "{} is compressed", path),
+            ArchiveError::Corrupted(path) => write!(f, "{} is corrupted", path),
+        }
+    }
+}
+
+/// Computes the CRC-32 checksum used by zip archives and PNG images
+pub fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ *byte as u32, |crc, _| match crc & 1 {
+            1 => (crc >> 1) ^ 0xedb8_8320,
+            _ => crc >> 1,
+        })
+    })
+}
+
+/// Packs `entries`, as `(path, content)` pairs, in an uncompressed zip archive
+pub fn write_archive(entries: &[(String, Vec<u8>)]) -> Vec<u8> {
+    let mut archive: Vec<u8> = vec![];
+    let mut central_directory: Vec<u8> = vec![];
+
+    for (path, content) in entries {
+        let offset = archive.len() as u32;
+        let checksum = crc32(content);
+
+        push_u32(&mut archive, LOCAL_HEADER_SIGNATURE);
+        push_entry_header(&mut archive, path, content, checksum);
+        push_u16(&mut archive, 0);
+        archive.extend_from_slice(path.as_bytes());
+        archive.extend_from_slice(content);
+
+        push_u32(&mut central_directory, CENTRAL_HEADER_SIGNATURE);
+        push_u16(&mut central_directory, ZIP_VERSION);
+        push_entry_header(&mut central_directory, path, content, checksum);
+        // Extra field, comment, disk number, internal and external attributes
+        for _ in 0..4 {
+            push_u16(&mut central_directory, 0);
+        }
+        push_u32(&mut central_directory, 0);
+        push_u32(&mut central_directory, offset);
+        central_directory.extend_from_slice(path.as_bytes());
+    }
+
+    let central_directory_offset = archive.len() as u32;
+    archive.extend_from_slice(&central_directory);
+
+    push_u32(&mut archive, END_OF_CENTRAL_DIRECTORY_SIGNATURE);
+    push_u16(&mut archive, 0);
+    push_u16(&mut archive, 0);
+    push_u16(&mut archive, entries.len() as u16);
+    push_u16(&mut archive, entries.len() as u16);
+    push_u32(&mut archive, central_directory.len() as u32);
+    push_u32(&mut archive, central_directory_offset);
+    push_u16(&mut archive, 0);
+
+    archive
+}
+
+/// Unpacks the `(path, content)` entries of a zip archive. Only uncompressed
+/// archives, such as the ones produced by `write_archive`, can be read.
+pub fn read_archive(data: &[u8]) -> Result<Vec<(String, Vec<u8>)>, ArchiveError> {
+    let end = (0..=data.len().saturating_sub(END_OF_CENTRAL_DIRECTORY_SIZE))
+        .rev()
+        .find(|position| read_u32(data, *position) == Some(END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+        .ok_or(ArchiveError::Malformed)?;
+
+    let count = read_u16(data, end + 10).ok_or(ArchiveError::Malformed)? as usize;
+    let mut position = read_u32(data, end + 16).ok_or(ArchiveError::Malformed)? as usize;
+    let mut entries = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        if read_u32(data, position) != Some(CENTRAL_HEADER_SIGNATURE) {
+            return Err(ArchiveError::Malformed);
+        }
+
+        let field = |offset: usize| read_u16(data, position + offset).map(|value| value as usize);
+        let method = field(10).ok_or(ArchiveError::Malformed)?;
+        let checksum = read_u32(data, position + 16).ok_or(ArchiveError::Malformed)?;
+        let size = read_u32(data, position + 20).ok_or(ArchiveError::Malformed)? as usize;
+        let name_length = field(28).ok_or(ArchiveError::Malformed)?;
+        let extra_length = field(30).ok_or(ArchiveError::Malformed)?;
+        let comment_length = field(32).ok_or(ArchiveError::Malformed)?;
+        let offset = read_u32(data, position + 42).ok_or(ArchiveError::Malformed)? as usize;
+
+        let name_start = position + CENTRAL_HEADER_SIZE;
+        let path = data
+            .get(name_start..name_start + name_length)
+            .map(|name| String::from_utf8_lossy(name).to_string())
+            .ok_or(ArchiveError::Malformed)?;
+
+        if method != STORED_METHOD as usize {
+            return Err(ArchiveError::Unsupported(path));
+        }
+
+        if read_u32(data, offset) != Some(LOCAL_HEADER_SIGNATURE) {
+            return Err(ArchiveError::Malformed);
+        }
+        let local_name_length = read_u16(data, offset + 26).ok_or(ArchiveError::Malformed)?;
+        let local_extra_length = read_u16(data, offset + 28).ok_or(ArchiveError::Malformed)?;
+        let content_start =
+            offset + LOCAL_HEADER_SIZE + local_name_length as usize + local_extra_length as usize;
+        let content = data
+            .get(content_start..content_start + size)
+            .ok_or(ArchiveError::Malformed)?;
+
+        if crc32(content) != checksum {
+            return Err(ArchiveError::Corrupted(path));
+        }
+
+        entries.push((path, content.to_vec()));
+        position = name_start + name_length + extra_length + comment_length;
+    }
+
+    Ok(entries)
+}
+
+/// Writes the header fields shared by local and central entry headers
+fn push_entry_header(buffer: &mut Vec<u8>, path: &str, content: &[u8], checksum: u32) {
+    push_u16(buffer, ZIP_VERSION);
+    push_u16(buffer, UTF8_NAMES_FLAG);
+    push_u16(buffer, STORED_METHOD);
+    push_u16(buffer, 0);
+    push_u16(buffer, DOS_EPOCH_DATE);
+    push_u32(buffer, checksum);
+    push_u32(buffer, content.len() as u32);
+    push_u32(buffer, content.len() as u32);
+    push_u16(buffer, path.len() as u16);
+}
+
+fn push_u16(buffer: &mut Vec<u8>, value: u16) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn push_u32(buffer: &mut Vec<u8>, value: u32) {
+    buffer.extend_from_slice(&value.to_le_bytes());
+}
+
+fn read_u16(data: &[u8], position: usize) -> Option<u16> {
+    data.get(position..position + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(data: &[u8], position: usize) -> Option<u32> {
+    data.get(position..position + 4)
+        .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
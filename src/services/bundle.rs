@@ -0,0 +1,307 @@
+use super::{
+    encode_surface_png, read_archive, render_snapshot_surface, write_archive, ArchiveError,
+    GameOfLifeSettings, PatternLibrary, PlaylistEntry,
+};
+use crate::config::G_LOG_DOMAIN;
+use crate::models::{
+    formats::write_rle, RainPlacement, UniversePointMatrix, UniverseRain, UniverseRule,
+    UniverseSnapshot,
+};
+use std::fmt;
+
+const UNIVERSE_FILE: &str = "universe.snapshot";
+const NOTES_FILE: &str = "notes.txt";
+const SCENE_FILE: &str = "scene.ini";
+const PLAYLIST_FILE: &str = "playlist.ini";
+const PREVIEW_FILE: &str = "preview.png";
+const PATTERNS_DIR: &str = "patterns/";
+
+const SCENE_GROUP: &str = "Scene";
+const PLAYLIST_GROUP_PREFIX: &str = "Entry ";
+
+/// The largest side of the preview image, in pixels
+const PREVIEW_MAX_SIZE: usize = 512;
+
+#[derive(Debug)]
+pub enum BundleError {
+    /// The bundle is not a readable archive
+    Archive(ArchiveError),
+
+    /// The bundle does not contain a readable universe
+    MissingUniverse,
+}
+
+impl fmt::Display for BundleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BundleError::Archive(error) => write!(f, "{}", error),
+            BundleError::MissingUniverse => write!(f, "no readable universe"),
+        }
+    }
+}
+
+/// The simulation and appearance preferences a universe is meant to be seen with
+#[derive(Clone, Debug)]
+pub struct ScenePreset {
+    rule: UniverseRule,
+    evolution_speed: u32,
+    fg_color: String,
+    bg_color: String,
+    fg_color_dark: String,
+    bg_color_dark: String,
+    rain: Option<UniverseRain>,
+}
+
+impl ScenePreset {
+    /// Captures the current preferences
+    pub fn from_settings(settings: &GameOfLifeSettings) -> Self {
+        Self {
+            rule: settings.rule(),
+            evolution_speed: settings.evolution_speed(),
+            fg_color: settings.fg_color(),
+            bg_color: settings.bg_color(),
+            fg_color_dark: settings.fg_color_dark(),
+            bg_color_dark: settings.bg_color_dark(),
+            rain: settings.rain(),
+        }
+    }
+
+    /// Stores the preset as the current preferences
+    pub fn apply(&self, settings: &GameOfLifeSettings) {
+        settings.set_rule(&self.rule);
+        settings.set_evolution_speed(self.evolution_speed);
+        settings.set_fg_color(self.fg_color.clone());
+        settings.set_bg_color(self.bg_color.clone());
+        settings.set_fg_color_dark(self.fg_color_dark.clone());
+        settings.set_bg_color_dark(self.bg_color_dark.clone());
+        settings.set_rain(self.rain.as_ref());
+    }
+
+    fn to_key_file(&self) -> glib::KeyFile {
+        let key_file = glib::KeyFile::new();
+        key_file.set_string(SCENE_GROUP, "rule", &self.rule.to_string());
+        key_file.set_uint64(SCENE_GROUP, "evolution-speed", self.evolution_speed as u64);
+        key_file.set_string(SCENE_GROUP, "fg-color", &self.fg_color);
+        key_file.set_string(SCENE_GROUP, "bg-color", &self.bg_color);
+        key_file.set_string(SCENE_GROUP, "fg-color-dark", &self.fg_color_dark);
+        key_file.set_string(SCENE_GROUP, "bg-color-dark", &self.bg_color_dark);
+        key_file.set_double(
+            SCENE_GROUP,
+            "rain-rate",
+            self.rain.map(|rain| rain.rate()).unwrap_or(0.0),
+        );
+        key_file.set_boolean(
+            SCENE_GROUP,
+            "rain-at-edges",
+            self.rain
+                .map(|rain| rain.placement() == RainPlacement::Edges)
+                .unwrap_or(false),
+        );
+        key_file
+    }
+
+    /// Reads a preset, using the values of `fallback` for missing keys
+    fn from_key_file(key_file: &glib::KeyFile, fallback: ScenePreset) -> Self {
+        let string = |key: &str, default: String| {
+            key_file
+                .string(SCENE_GROUP, key)
+                .map(|value| value.to_string())
+                .unwrap_or(default)
+        };
+
+        let rule = key_file
+            .string(SCENE_GROUP, "rule")
+            .ok()
+            .and_then(|value| value.parse::<UniverseRule>().ok())
+            .unwrap_or(fallback.rule);
+        let evolution_speed = key_file
+            .uint64(SCENE_GROUP, "evolution-speed")
+            .map(|value| value.clamp(1, 100) as u32)
+            .unwrap_or(fallback.evolution_speed);
+        let rain = match key_file.double(SCENE_GROUP, "rain-rate") {
+            Ok(rate) if rate > 0.0 => {
+                let placement = match key_file.boolean(SCENE_GROUP, "rain-at-edges") {
+                    Ok(true) => RainPlacement::Edges,
+                    _ => RainPlacement::Uniform,
+                };
+                Some(UniverseRain::new(rate, placement))
+            }
+            Ok(_) => None,
+            Err(_) => fallback.rain,
+        };
+
+        Self {
+            rule,
+            evolution_speed,
+            fg_color: string("fg-color", fallback.fg_color),
+            bg_color: string("bg-color", fallback.bg_color),
+            fg_color_dark: string("fg-color-dark", fallback.fg_color_dark),
+            bg_color_dark: string("bg-color-dark", fallback.bg_color_dark),
+            rain,
+        }
+    }
+}
+
+/// A complete setup packed in a single zip file to be shared: the universe,
+/// its notes, the scene preset it is meant to be seen with, the demo playlist
+/// along with the patterns it references, and a preview image.
+#[derive(Clone, Debug)]
+pub struct WorkspaceBundle {
+    snapshot: UniverseSnapshot,
+    notes: String,
+    scene: ScenePreset,
+    playlist: Vec<PlaylistEntry>,
+}
+
+impl WorkspaceBundle {
+    pub fn new(
+        snapshot: UniverseSnapshot,
+        notes: String,
+        scene: ScenePreset,
+        playlist: Vec<PlaylistEntry>,
+    ) -> Self {
+        Self {
+            snapshot,
+            notes,
+            scene,
+            playlist,
+        }
+    }
+
+    pub fn snapshot(&self) -> &UniverseSnapshot {
+        &self.snapshot
+    }
+
+    pub fn notes(&self) -> &str {
+        &self.notes
+    }
+
+    pub fn scene(&self) -> &ScenePreset {
+        &self.scene
+    }
+
+    pub fn playlist(&self) -> &[PlaylistEntry] {
+        &self.playlist
+    }
+
+    /// Packs the bundle as zip data
+    pub fn to_bytes(&self) -> Result<Vec<u8>, bincode::Error> {
+        let mut entries = vec![(UNIVERSE_FILE.to_string(), self.snapshot.serialize()?)];
+        entries.push((NOTES_FILE.to_string(), self.notes.as_bytes().to_vec()));
+        entries.push((
+            SCENE_FILE.to_string(),
+            self.scene.to_key_file().to_data().as_bytes().to_vec(),
+        ));
+
+        let playlist = glib::KeyFile::new();
+        for (index, entry) in self.playlist.iter().enumerate() {
+            let group = format!("{}{}", PLAYLIST_GROUP_PREFIX, index);
+            playlist.set_string(&group, "pattern", entry.pattern_id());
+            playlist.set_uint64(&group, "generations", entry.generations() as u64);
+            playlist.set_uint64(&group, "speed", entry.speed() as u64);
+            playlist.set_uint64(&group, "zoom", entry.zoom() as u64);
+        }
+        entries.push((
+            PLAYLIST_FILE.to_string(),
+            playlist.to_data().as_bytes().to_vec(),
+        ));
+
+        for library_pattern in PatternLibrary::bundled().iter().filter(|library_pattern| {
+            self.playlist
+                .iter()
+                .any(|entry| entry.pattern_id() == library_pattern.id())
+        }) {
+            entries.push((
+                format!("{}{}.rle", PATTERNS_DIR, library_pattern.id()),
+                write_rle(library_pattern.pattern()).into_bytes(),
+            ));
+        }
+
+        if let Some(preview) = self.render_preview() {
+            entries.push((PREVIEW_FILE.to_string(), preview));
+        }
+
+        Ok(write_archive(&entries))
+    }
+
+    /// Unpacks a bundle from zip data. A missing scene or playlist is replaced
+    /// by the current one from `settings`.
+    pub fn from_bytes(data: &[u8], settings: &GameOfLifeSettings) -> Result<Self, BundleError> {
+        let entries = read_archive(data).map_err(BundleError::Archive)?;
+        let entry = |path: &str| {
+            entries
+                .iter()
+                .find(|(entry_path, _)| entry_path == path)
+                .map(|(_, content)| content)
+        };
+
+        let snapshot = entry(UNIVERSE_FILE)
+            .and_then(|content| UniverseSnapshot::try_from(content).ok())
+            .ok_or(BundleError::MissingUniverse)?;
+
+        let notes = entry(NOTES_FILE)
+            .map(|content| String::from_utf8_lossy(content).to_string())
+            .unwrap_or_default();
+
+        let scene = ScenePreset::from_settings(settings);
+        let scene = match entry(SCENE_FILE).and_then(|content| read_key_file(content)) {
+            Some(key_file) => ScenePreset::from_key_file(&key_file, scene),
+            None => scene,
+        };
+
+        let playlist = match entry(PLAYLIST_FILE).and_then(|content| read_key_file(content)) {
+            Some(key_file) => key_file
+                .groups()
+                .0
+                .iter()
+                .filter(|group| group.starts_with(PLAYLIST_GROUP_PREFIX))
+                .filter_map(|group| {
+                    Some(PlaylistEntry::new(
+                        key_file.string(group, "pattern").ok()?.as_str(),
+                        key_file.uint64(group, "generations").ok()? as u32,
+                        key_file.uint64(group, "speed").ok()? as u32,
+                        key_file.uint64(group, "zoom").ok()? as u32,
+                    ))
+                })
+                .collect(),
+            None => settings.playlist(),
+        };
+
+        Ok(Self {
+            snapshot,
+            notes,
+            scene,
+            playlist,
+        })
+    }
+
+    /// Renders the universe with the light scene colors as a PNG image, or
+    /// `None` if it could not be rendered, the bundle then going without
+    fn render_preview(&self) -> Option<Vec<u8>> {
+        let (rows, columns) = (self.snapshot.rows(), self.snapshot.columns());
+        let preview = render_snapshot_surface(
+            &self.snapshot,
+            PREVIEW_MAX_SIZE / rows.max(columns).max(1),
+            &self.scene.fg_color,
+            &self.scene.bg_color,
+        )
+        .map_err(|error| error.to_string())
+        .and_then(|surface| encode_surface_png(&surface).map_err(|error| error.to_string()));
+
+        match preview {
+            Ok(preview) => Some(preview),
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Could not render bundle preview: {}", error);
+                None
+            }
+        }
+    }
+}
+
+fn read_key_file(content: &[u8]) -> Option<glib::KeyFile> {
+    let key_file = glib::KeyFile::new();
+    key_file
+        .load_from_data(&String::from_utf8_lossy(content), glib::KeyFileFlags::NONE)
+        .ok()
+        .map(|_| key_file)
+}
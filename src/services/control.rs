@@ -0,0 +1,161 @@
+use crate::config::G_LOG_DOMAIN;
+use gtk::gio::{self, DBusConnection, DBusMethodInvocation, RegistrationId};
+use gtk::glib::{self, thread_guard::ThreadGuard, ToVariant, Variant};
+use std::sync::Mutex;
+
+/// The name of the D-Bus interface driving the running simulation
+pub const CONTROL_INTERFACE: &str = "com.github.sixpounder.GameOfLife.Control";
+
+/// The D-Bus error returned when a method call could not be carried out
+const CONTROL_ERROR: &str = "com.github.sixpounder.GameOfLife.Control.Error.Failed";
+
+const CONTROL_INTROSPECTION: &str = r#"
+<node>
+  <interface name="com.github.sixpounder.GameOfLife.Control">
+    <method name="Run"/>
+    <method name="Halt"/>
+    <method name="Step"/>
+    <method name="LoadPattern">
+      <arg type="s" name="pattern" direction="in"/>
+    </method>
+    <method name="GetPopulation">
+      <arg type="t" name="population" direction="out"/>
+    </method>
+    <signal name="GenerationChanged">
+      <arg type="t" name="generation"/>
+      <arg type="t" name="population"/>
+    </signal>
+  </interface>
+</node>
+"#;
+
+/// A method call received on the control interface
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlRequest {
+    Run,
+    Halt,
+    Step,
+
+    /// Replaces the universe with a pattern, in any of the supported formats
+    LoadPattern(String),
+
+    GetPopulation,
+}
+
+/// What a control request answers with, once handled
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlReply {
+    Done,
+    Population(usize),
+
+    /// The request failed, with a message for the caller
+    Failed(String),
+}
+
+/// The control interface, exported on the D-Bus connection of the application
+/// for as long as this value lives
+#[derive(Debug)]
+pub struct ControlService {
+    connection: DBusConnection,
+    object_path: String,
+    registration: Option<RegistrationId>,
+}
+
+impl ControlService {
+    /// Exports the control interface at `object_path`. Method calls are handed
+    /// to `handler`, which runs on the main thread.
+    pub fn register<F>(
+        connection: &DBusConnection,
+        object_path: &str,
+        handler: F,
+    ) -> Result<Self, glib::Error>
+    where
+        F: Fn(ControlRequest) -> ControlReply + 'static,
+    {
+        let node = gio::DBusNodeInfo::for_xml(CONTROL_INTROSPECTION)?;
+        let interface = node
+            .lookup_interface(CONTROL_INTERFACE)
+            .expect("The control interface is described");
+
+        // Calls are dispatched on the main context the interface was
+        // registered from, the guard only lets the handler cross the bound
+        let handler = Mutex::new(ThreadGuard::new(handler));
+        let registration = connection.register_object(
+            object_path,
+            &interface,
+            move |_, _, _, _, method, parameters, invocation| {
+                let request = match parse_request(method, &parameters) {
+                    Some(request) => request,
+                    None => {
+                        invocation.return_dbus_error(CONTROL_ERROR, "Unknown method");
+                        return;
+                    }
+                };
+
+                let reply = match handler.lock() {
+                    Ok(handler) => (handler.get_ref())(request),
+                    Err(_) => ControlReply::Failed(String::from("Unavailable")),
+                };
+                return_reply(&invocation, reply);
+            },
+            |_, _, _, _, _| ().to_variant(),
+            |_, _, _, _, _, _| false,
+        )?;
+
+        Ok(Self {
+            connection: connection.clone(),
+            object_path: object_path.to_owned(),
+            registration: Some(registration),
+        })
+    }
+
+    /// Tells listeners a new generation of `population` living cells was computed
+    pub fn emit_generation_changed(&self, generation: u64, population: usize) {
+        if let Err(error) = self.connection.emit_signal(
+            None,
+            &self.object_path,
+            CONTROL_INTERFACE,
+            "GenerationChanged",
+            Some(&(generation, population as u64).to_variant()),
+        ) {
+            glib::g_warning!(G_LOG_DOMAIN, "Could not emit GenerationChanged: {}", error);
+        }
+    }
+}
+
+impl Drop for ControlService {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.take() {
+            if let Err(error) = self.connection.unregister_object(registration) {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Could not unexport the control interface: {}",
+                    error
+                );
+            }
+        }
+    }
+}
+
+fn parse_request(method: &str, parameters: &Variant) -> Option<ControlRequest> {
+    match method {
+        "Run" => Some(ControlRequest::Run),
+        "Halt" => Some(ControlRequest::Halt),
+        "Step" => Some(ControlRequest::Step),
+        "LoadPattern" => parameters
+            .get::<(String,)>()
+            .map(|(pattern,)| ControlRequest::LoadPattern(pattern)),
+        "GetPopulation" => Some(ControlRequest::GetPopulation),
+        _ => None,
+    }
+}
+
+fn return_reply(invocation: &DBusMethodInvocation, reply: ControlReply) {
+    match reply {
+        ControlReply::Done => invocation.return_value(None),
+        ControlReply::Population(population) => {
+            invocation.return_value(Some(&(population as u64,).to_variant()))
+        }
+        ControlReply::Failed(message) => invocation.return_dbus_error(CONTROL_ERROR, &message),
+    }
+}
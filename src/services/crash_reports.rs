@@ -0,0 +1,135 @@
+use super::user_data_dir;
+use crate::config::VERSION;
+use once_cell::sync::Lazy;
+use std::collections::VecDeque;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+const CRASH_REPORTS_DIR_NAME: &str = "crash-reports";
+const REPORT_PREFIX: &str = "crash-";
+const REPORT_EXTENSION: &str = "txt";
+
+/// How many recent events are kept to be written in crash reports
+const RECENT_EVENTS_CAPACITY: usize = 50;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RECENT_EVENTS: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_EVENTS_CAPACITY)));
+static ENGINE_STATE: Lazy<Mutex<String>> = Lazy::new(Mutex::default);
+static STARTED: Lazy<std::time::Instant> = Lazy::new(std::time::Instant::now);
+
+/// Gets the directory crash reports are written to. The directory is not
+/// guaranteed to exist.
+pub fn crash_reports_dir() -> PathBuf {
+    user_data_dir().join(CRASH_REPORTS_DIR_NAME)
+}
+
+/// Installs a panic hook writing a crash report, while reports are enabled,
+/// before handing the panic over to the previous hook. Reports stay on this
+/// computer: nothing is ever sent anywhere.
+pub fn install_crash_reporter() {
+    Lazy::force(&STARTED);
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if ENABLED.load(Ordering::Relaxed) {
+            match write_crash_report(info) {
+                Ok(path) => eprintln!("Crash report written to {}", path.display()),
+                Err(error) => eprintln!("Could not write crash report: {}", error),
+            }
+        }
+        previous_hook(info);
+    }));
+}
+
+pub fn set_crash_reports_enabled(value: bool) {
+    ENABLED.store(value, Ordering::Relaxed);
+}
+
+/// Remembers something that happened, to be listed in crash reports
+pub fn record_event(event: &str) {
+    if let Ok(mut events) = RECENT_EVENTS.lock() {
+        if events.len() == RECENT_EVENTS_CAPACITY {
+            events.pop_front();
+        }
+        events.push_back(format!("{:.3} {}", elapsed_seconds(), event));
+    }
+}
+
+/// Sets the summary of the simulation state written in crash reports
+pub fn set_engine_state(summary: String) {
+    if let Ok(mut state) = ENGINE_STATE.lock() {
+        *state = summary;
+    }
+}
+
+/// The most recent crash report, if any
+pub fn latest_crash_report() -> Option<PathBuf> {
+    std::fs::read_dir(crash_reports_dir())
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|extension| extension == REPORT_EXTENSION)
+                && path
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(REPORT_PREFIX))
+        })
+        .max()
+}
+
+fn elapsed_seconds() -> f64 {
+    STARTED.elapsed().as_secs_f64()
+}
+
+// `PanicHookInfo` only exists from Rust 1.81 on, newer toolchains still
+// accepting `PanicInfo` as an alias of it
+#[allow(deprecated)]
+fn write_crash_report(info: &std::panic::PanicInfo) -> std::io::Result<PathBuf> {
+    let directory = crash_reports_dir();
+    std::fs::create_dir_all(&directory)?;
+
+    let timestamp = glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y%m%d-%H%M%S"))
+        .map(|timestamp| timestamp.to_string())
+        .unwrap_or_else(|_| format!("{:.0}", elapsed_seconds()));
+    let path = directory.join(format!(
+        "{}{}.{}",
+        REPORT_PREFIX, timestamp, REPORT_EXTENSION
+    ));
+
+    let mut file = std::fs::File::create(&path)?;
+    writeln!(file, "Game of Life {} crash report", VERSION)?;
+    writeln!(file, "Uptime: {:.3}s", elapsed_seconds())?;
+    writeln!(
+        file,
+        "Thread: {}",
+        std::thread::current().name().unwrap_or("unnamed")
+    )?;
+    writeln!(file, "Panic: {}", info)?;
+
+    // The panicking thread may hold these locks, don't wait for them
+    writeln!(file, "\nEngine state:")?;
+    match ENGINE_STATE.try_lock() {
+        Ok(state) => writeln!(file, "{}", state)?,
+        Err(_) => writeln!(file, "unavailable")?,
+    }
+
+    writeln!(file, "\nRecent events:")?;
+    match RECENT_EVENTS.try_lock() {
+        Ok(events) => {
+            for event in events.iter() {
+                writeln!(file, "{}", event)?;
+            }
+        }
+        Err(_) => writeln!(file, "unavailable")?,
+    }
+
+    writeln!(
+        file,
+        "\nBacktrace:\n{}",
+        std::backtrace::Backtrace::force_capture()
+    )?;
+    Ok(path)
+}
@@ -0,0 +1,237 @@
+use super::render_snapshot_surface;
+use crate::models::{
+    StabilizationDetector, Universe, UniversePointMatrix, UniverseRule, UniverseSnapshot,
+};
+use gtk::{cairo, glib};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The largest side of experiment thumbnails, in pixels
+const THUMBNAIL_MAX_SIZE: usize = 96;
+
+/// What an experiment produces besides its statistics: an image of the
+/// universe once the experiment ends, and the pattern it ends up with
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ExperimentOutputs {
+    thumbnail: bool,
+    final_pattern: bool,
+}
+
+impl ExperimentOutputs {
+    pub fn new(thumbnail: bool, final_pattern: bool) -> Self {
+        Self {
+            thumbnail,
+            final_pattern,
+        }
+    }
+}
+
+/// A job of the experiments queue: evolving a pattern under a rule for a
+/// number of generations, in a universe of the given size
+#[derive(Clone, Debug)]
+pub struct Experiment {
+    name: String,
+    pattern: UniverseSnapshot,
+    rule: UniverseRule,
+    generations: u32,
+    universe_size: (usize, usize),
+    outputs: ExperimentOutputs,
+}
+
+impl Experiment {
+    pub fn new(
+        name: &str,
+        pattern: UniverseSnapshot,
+        rule: UniverseRule,
+        generations: u32,
+        universe_size: (usize, usize),
+        outputs: ExperimentOutputs,
+    ) -> Self {
+        // Leave room for the pattern and a border around it
+        let universe_size = (
+            universe_size.0.max(pattern.rows() + 2),
+            universe_size.1.max(pattern.columns() + 2),
+        );
+        Self {
+            name: name.to_string(),
+            pattern,
+            rule,
+            generations,
+            universe_size,
+            outputs,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    pub fn generations(&self) -> u32 {
+        self.generations
+    }
+
+    /// Runs the experiment, with the pattern centered in the universe.
+    /// `progress` is told about every generation, and may return `false` to
+    /// give up, in which case `None` is returned.
+    pub fn run(&self, mut progress: impl FnMut(u32) -> bool) -> Option<ExperimentResult> {
+        let (rows, columns) = self.universe_size;
+        let mut universe = Universe::new_empty(rows, columns);
+        universe.set_rule(self.rule.clone());
+        universe.stamp(
+            &self.pattern,
+            (rows - self.pattern.rows()) / 2,
+            (columns - self.pattern.columns()) / 2,
+        );
+
+        let mut detector = StabilizationDetector::new(1);
+        let mut populations = Vec::with_capacity(self.generations as usize + 1);
+        populations.push(universe.alive_cells_count());
+        let mut settled = None;
+        for generation in 1..=self.generations {
+            universe.tick();
+            populations.push(universe.alive_cells_count());
+            if settled.is_none() && detector.observe(universe.state_hash()) {
+                settled = Some((universe.generations(), detector.period().unwrap_or(1)));
+            }
+
+            if !progress(generation) {
+                return None;
+            }
+        }
+
+        let snapshot = universe.snapshot();
+        let thumbnail = self.outputs.thumbnail.then(|| ExperimentThumbnail {
+            snapshot: snapshot.clone(),
+        });
+        let final_pattern = self
+            .outputs
+            .final_pattern
+            .then(|| match snapshot.bounding_box() {
+                Some((top, left, rows, columns)) => snapshot.crop(top, left, rows, columns),
+                None => snapshot.crop(0, 0, 0, 0),
+            });
+
+        Some(ExperimentResult {
+            populations,
+            settled,
+            thumbnail,
+            final_pattern,
+        })
+    }
+}
+
+/// The universe at the end of an experiment, kept to be painted as a
+/// thumbnail on the main thread
+#[derive(Clone, Debug)]
+pub struct ExperimentThumbnail {
+    snapshot: UniverseSnapshot,
+}
+
+impl ExperimentThumbnail {
+    /// Renders the thumbnail with `cell_color` and `background_color`, as
+    /// color specifications
+    pub fn render(
+        &self,
+        cell_color: &str,
+        background_color: &str,
+    ) -> Result<cairo::ImageSurface, cairo::Error> {
+        let (rows, columns) = (self.snapshot.rows(), self.snapshot.columns());
+        render_snapshot_surface(
+            &self.snapshot,
+            THUMBNAIL_MAX_SIZE / rows.max(columns).max(1),
+            cell_color,
+            background_color,
+        )
+    }
+}
+
+/// The outcome of an experiment
+#[derive(Clone, Debug)]
+pub struct ExperimentResult {
+    populations: Vec<usize>,
+    settled: Option<(u64, usize)>,
+    thumbnail: Option<ExperimentThumbnail>,
+    final_pattern: Option<UniverseSnapshot>,
+}
+
+impl ExperimentResult {
+    /// The number of cells alive after the last generation
+    pub fn population(&self) -> usize {
+        self.populations.last().copied().unwrap_or_default()
+    }
+
+    /// The largest number of cells alive at once
+    pub fn peak_population(&self) -> usize {
+        self.populations.iter().copied().max().unwrap_or_default()
+    }
+
+    /// The number of cells alive at every generation, starting with the
+    /// pattern itself
+    pub fn populations(&self) -> &[usize] {
+        &self.populations
+    }
+
+    /// The generation the universe settled down at and the period it then
+    /// repeats with, 1 being a still life, or `None` if it never settled
+    pub fn settled(&self) -> Option<(u64, usize)> {
+        self.settled
+    }
+
+    pub fn thumbnail(&self) -> Option<&ExperimentThumbnail> {
+        self.thumbnail.as_ref()
+    }
+
+    /// The living cells of the last generation, cropped to their bounding box
+    pub fn final_pattern(&self) -> Option<&UniverseSnapshot> {
+        self.final_pattern.as_ref()
+    }
+}
+
+/// Reports from the thread running the experiments queue, identifying
+/// experiments by their position in the queue
+pub enum ExperimentEvent {
+    /// The number of generations an experiment went through so far
+    Progress(usize, u32),
+    Finished(usize, ExperimentResult),
+    /// The queue was run through, or cancelled
+    Done,
+}
+
+/// Runs `experiments` one after the other on a worker thread, until they
+/// are all done or `cancelled` is set
+pub fn run_experiments(
+    experiments: Vec<Experiment>,
+    cancelled: Arc<AtomicBool>,
+) -> glib::Receiver<ExperimentEvent> {
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    std::thread::spawn(move || {
+        for (index, experiment) in experiments.iter().enumerate() {
+            // Reporting every generation would flood the main loop
+            let step = (experiment.generations() / 100).max(1);
+            let result = experiment.run(|generation| {
+                if generation % step == 0 {
+                    sender
+                        .send(ExperimentEvent::Progress(index, generation))
+                        .ok();
+                }
+                !cancelled.load(Ordering::Relaxed)
+            });
+
+            match result {
+                Some(result) => {
+                    sender.send(ExperimentEvent::Finished(index, result)).ok();
+                }
+                None => break,
+            }
+        }
+        sender.send(ExperimentEvent::Done).ok();
+    });
+
+    receiver
+}
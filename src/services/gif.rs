@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+const GIF_HEADER: &[u8; 6] = b"GIF89a";
+const MAX_CODE_SIZE: u32 = 12;
+const MAX_CODES: u16 = 1 << MAX_CODE_SIZE;
+const MAX_SUB_BLOCK_SIZE: usize = 255;
+
+/// Encodes animations of two color frames as GIF, playing in a loop
+pub struct GifEncoder {
+    gif: Vec<u8>,
+    width: u16,
+    height: u16,
+    delay: u16,
+}
+
+impl GifEncoder {
+    /// Starts an animation of `width`x`height` frames shown for `delay_ms`
+    /// milliseconds each, painted with the two colors of `palette`
+    pub fn new(width: u16, height: u16, palette: [[u8; 3]; 2], delay_ms: u32) -> Self {
+        let mut gif = GIF_HEADER.to_vec();
+        gif.extend_from_slice(&width.to_le_bytes());
+        gif.extend_from_slice(&height.to_le_bytes());
+        // Global color table of two entries, with 8 bits per channel
+        gif.extend_from_slice(&[0xf0, 0, 0]);
+        gif.extend(palette.iter().flatten());
+
+        // Netscape extension, looping forever
+        gif.extend_from_slice(&[0x21, 0xff, 0x0b]);
+        gif.extend_from_slice(b"NETSCAPE2.0");
+        gif.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+
+        Self {
+            gif,
+            width,
+            height,
+            // Delays are in hundredths of a second, and most viewers slow
+            // down anything shorter than two
+            delay: (delay_ms / 10).clamp(2, u16::MAX as u32) as u16,
+        }
+    }
+
+    /// Appends a frame, given as one palette index per pixel in rows
+    pub fn add_frame(&mut self, pixels: &[u8]) {
+        // Graphic control extension, setting the delay
+        self.gif.extend_from_slice(&[0x21, 0xf9, 0x04, 0x00]);
+        self.gif.extend_from_slice(&self.delay.to_le_bytes());
+        self.gif.extend_from_slice(&[0x00, 0x00]);
+
+        // Image descriptor, covering the whole screen
+        self.gif.push(0x2c);
+        self.gif.extend_from_slice(&[0, 0, 0, 0]);
+        self.gif.extend_from_slice(&self.width.to_le_bytes());
+        self.gif.extend_from_slice(&self.height.to_le_bytes());
+        self.gif.push(0x00);
+
+        let min_code_size = 2;
+        self.gif.push(min_code_size as u8);
+        let data = lzw_compress(pixels, min_code_size);
+        for block in data.chunks(MAX_SUB_BLOCK_SIZE) {
+            self.gif.push(block.len() as u8);
+            self.gif.extend_from_slice(block);
+        }
+        self.gif.push(0x00);
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        self.gif.push(0x3b);
+        self.gif
+    }
+}
+
+/// Packs variable length codes least significant bit first
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    buffer: u32,
+    bits: u32,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, size: u32) {
+        self.buffer |= (code as u32) << self.bits;
+        self.bits += size;
+        while self.bits >= 8 {
+            self.bytes.push(self.buffer as u8);
+            self.buffer >>= 8;
+            self.bits -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bits > 0 {
+            self.bytes.push(self.buffer as u8);
+        }
+        self.bytes
+    }
+}
+
+/// Compresses palette indices with the variable code size LZW flavour of GIF
+fn lzw_compress(indices: &[u8], min_code_size: u32) -> Vec<u8> {
+    let clear_code: u16 = 1 << min_code_size;
+    let end_code = clear_code + 1;
+    let mut writer = BitWriter::default();
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut code_size = min_code_size + 1;
+    let mut next_code = end_code + 1;
+
+    writer.write(clear_code, code_size);
+    let mut indices = indices.iter();
+    let mut prefix = match indices.next() {
+        Some(index) => *index as u16,
+        None => {
+            writer.write(end_code, code_size);
+            return writer.finish();
+        }
+    };
+
+    for &index in indices {
+        if let Some(&code) = table.get(&(prefix, index)) {
+            prefix = code;
+            continue;
+        }
+
+        writer.write(prefix, code_size);
+        if next_code < MAX_CODES {
+            if next_code == 1 << code_size {
+                code_size += 1;
+            }
+            table.insert((prefix, index), next_code);
+            next_code += 1;
+        } else {
+            writer.write(clear_code, code_size);
+            table.clear();
+            code_size = min_code_size + 1;
+            next_code = end_code + 1;
+        }
+        prefix = index as u16;
+    }
+
+    writer.write(prefix, code_size);
+    writer.write(end_code, code_size);
+    writer.finish()
+}
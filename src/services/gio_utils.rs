@@ -28,3 +28,9 @@ impl Template {
         }
     }
 }
+
+/// Gets the directory where the application stores user data, such as
+/// pattern notes. The directory is not guaranteed to exist.
+pub fn user_data_dir() -> std::path::PathBuf {
+    glib::user_data_dir().join("game-of-life")
+}
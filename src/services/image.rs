@@ -0,0 +1,66 @@
+use crate::models::{UniversePointMatrix, UniverseSnapshot};
+use gtk::{cairo, gdk, glib};
+
+/// The largest side of exported images, in pixels
+pub const IMAGE_MAX_SIZE: usize = 8192;
+
+/// Renders `snapshot` on an image surface with square cells of `cell_size`
+/// pixels. Cells are laid out as in the universe grid, rows along the x axis.
+/// Colors are specifications as stored in preferences, unparsable ones being
+/// black for cells and white for the background.
+pub fn render_snapshot_surface(
+    snapshot: &UniverseSnapshot,
+    cell_size: usize,
+    cell_color: &str,
+    background_color: &str,
+) -> Result<cairo::ImageSurface, cairo::Error> {
+    let cell_color = gdk::RGBA::parse(cell_color).unwrap_or(gdk::RGBA::BLACK);
+    let background_color = gdk::RGBA::parse(background_color).unwrap_or(gdk::RGBA::WHITE);
+    let cell_size = cell_size.max(1);
+    let surface = cairo::ImageSurface::create(
+        cairo::Format::ARgb32,
+        (snapshot.rows() * cell_size) as i32,
+        (snapshot.columns() * cell_size) as i32,
+    )?;
+
+    let context = cairo::Context::new(&surface)?;
+    context.set_source_rgba(
+        background_color.red() as f64,
+        background_color.green() as f64,
+        background_color.blue() as f64,
+        background_color.alpha() as f64,
+    );
+    context.paint()?;
+
+    context.set_source_rgba(
+        cell_color.red() as f64,
+        cell_color.green() as f64,
+        cell_color.blue() as f64,
+        cell_color.alpha() as f64,
+    );
+    for (row, column) in snapshot.iter_alive() {
+        context.rectangle(
+            (row * cell_size) as f64,
+            (column * cell_size) as f64,
+            cell_size as f64,
+            cell_size as f64,
+        );
+    }
+    context.fill()?;
+    drop(context);
+
+    surface.flush();
+    Ok(surface)
+}
+
+/// Encodes an image surface as a compressed PNG, through gdk-pixbuf
+pub fn encode_surface_png(surface: &cairo::ImageSurface) -> Result<Vec<u8>, glib::Error> {
+    let pixbuf = gdk::pixbuf_get_from_surface(surface, 0, 0, surface.width(), surface.height())
+        .ok_or_else(|| {
+            glib::Error::new(
+                glib::FileError::Nomem,
+                "Could not copy the surface to a pixbuf",
+            )
+        })?;
+    pixbuf.save_to_bufferv("png", &[])
+}
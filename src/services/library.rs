@@ -0,0 +1,171 @@
+use super::user_data_dir;
+use crate::config::G_LOG_DOMAIN;
+use crate::models::{
+    formats::{parse_plaintext, parse_rle, write_rle},
+    UniversePattern, UniverseSnapshot,
+};
+use gtk::gio;
+use std::path::PathBuf;
+
+const PATTERNS_PREFIX: &str = "/com/github/sixpounder/GameOfLife/patterns/";
+const USER_PATTERNS_DIR_NAME: &str = "patterns";
+
+/// A pattern of the library, identified by its file name without extension
+#[derive(Clone, Debug)]
+pub struct LibraryPattern {
+    id: String,
+    pattern: UniversePattern,
+    user: bool,
+}
+
+impl LibraryPattern {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Whether the pattern was saved by the user, as opposed to bundled with
+    /// the application
+    pub fn is_user(&self) -> bool {
+        self.user
+    }
+
+    pub fn pattern(&self) -> &UniversePattern {
+        &self.pattern
+    }
+
+    /// The display name of the pattern, falling back to its id
+    pub fn name(&self) -> String {
+        match self.pattern.name() {
+            Some(name) => name.to_string(),
+            None => self.id.replace('_', " "),
+        }
+    }
+}
+
+pub struct PatternLibrary {}
+
+impl PatternLibrary {
+    /// Reads the patterns bundled with the application, sorted by name.
+    /// Unreadable patterns are logged and skipped.
+    pub fn bundled() -> Vec<LibraryPattern> {
+        let children = match gio::resources_enumerate_children(
+            PATTERNS_PREFIX,
+            gio::ResourceLookupFlags::NONE,
+        ) {
+            Ok(children) => children,
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "Could not list bundled patterns: {}", error);
+                return vec![];
+            }
+        };
+
+        let mut patterns: Vec<LibraryPattern> = children
+            .iter()
+            .filter_map(|child| {
+                let resource = format!("{}{}", PATTERNS_PREFIX, child);
+                let bytes =
+                    gio::resources_lookup_data(&resource, gio::ResourceLookupFlags::NONE).ok()?;
+                Self::parse_pattern(child, &String::from_utf8_lossy(&bytes), false)
+            })
+            .collect();
+
+        patterns.sort_by_key(|pattern| pattern.name().to_lowercase());
+        patterns
+    }
+
+    /// Reads the patterns saved by the user, sorted by name. Unreadable
+    /// patterns are logged and skipped.
+    pub fn user() -> Vec<LibraryPattern> {
+        let entries = match std::fs::read_dir(user_patterns_dir()) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut patterns: Vec<LibraryPattern> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let text = std::fs::read_to_string(entry.path()).ok()?;
+                Self::parse_pattern(&file_name, &text, true)
+            })
+            .collect();
+
+        patterns.sort_by_key(|pattern| pattern.name().to_lowercase());
+        patterns
+    }
+
+    /// Reads both the bundled patterns and the ones saved by the user, sorted
+    /// by name
+    pub fn all() -> Vec<LibraryPattern> {
+        let mut patterns = Self::bundled();
+        patterns.extend(Self::user());
+        patterns.sort_by_key(|pattern| pattern.name().to_lowercase());
+        patterns
+    }
+
+    /// Finds a library pattern that is `snapshot` up to position, rotation
+    /// and reflection
+    pub fn find_duplicate(snapshot: &UniverseSnapshot) -> Option<LibraryPattern> {
+        let hash = snapshot.canonical_hash();
+        Self::all()
+            .into_iter()
+            .find(|library_pattern| library_pattern.pattern().snapshot().canonical_hash() == hash)
+    }
+
+    /// Saves `pattern` to the library of the user, under an id derived from
+    /// its name
+    pub fn save_user_pattern(pattern: &UniversePattern) -> std::io::Result<LibraryPattern> {
+        let dir = user_patterns_dir();
+        std::fs::create_dir_all(&dir)?;
+
+        let base_id: String = pattern
+            .name()
+            .unwrap_or("pattern")
+            .chars()
+            .map(|char| match char.is_alphanumeric() {
+                true => char.to_ascii_lowercase(),
+                false => '_',
+            })
+            .collect();
+        let mut id = base_id.clone();
+        let mut suffix = 1;
+        while Self::all()
+            .iter()
+            .any(|library_pattern| library_pattern.id() == id)
+        {
+            suffix += 1;
+            id = format!("{}_{}", base_id, suffix);
+        }
+
+        std::fs::write(dir.join(format!("{}.rle", id)), write_rle(pattern))?;
+        Ok(LibraryPattern {
+            id,
+            pattern: pattern.clone(),
+            user: true,
+        })
+    }
+
+    fn parse_pattern(file_name: &str, text: &str, user: bool) -> Option<LibraryPattern> {
+        let (id, parse): (_, fn(&str) -> _) = match file_name.strip_suffix(".rle") {
+            Some(id) => (id, parse_rle),
+            None => (file_name.strip_suffix(".cells")?, parse_plaintext),
+        };
+        match parse(text) {
+            Ok(pattern) => Some(LibraryPattern {
+                id: id.to_string(),
+                pattern,
+                user,
+            }),
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Bad pattern {}: {:?}", file_name, error);
+                None
+            }
+        }
+    }
+}
+
+/// Gets the directory patterns saved by the user are stored in. The
+/// directory is not guaranteed to exist.
+fn user_patterns_dir() -> PathBuf {
+    user_data_dir().join(USER_PATTERNS_DIR_NAME)
+}
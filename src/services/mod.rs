@@ -1,5 +1,51 @@
+mod ambient;
+mod archive;
+mod bundle;
+mod control;
+mod crash_reports;
+mod experiments;
+mod gif;
 mod gio_utils;
+mod image;
+mod library;
+mod notes;
+mod playlist;
+mod png;
+mod power;
+mod priority;
+mod project;
+mod recording;
+mod report;
+mod rules;
+mod search_provider;
 mod settings;
+mod shortcuts;
+mod simulation;
+mod soup_search;
+mod statistics;
 
+pub use ambient::*;
+pub use archive::*;
+pub use bundle::*;
+pub use control::*;
+pub use crash_reports::*;
+pub use experiments::*;
+pub use gif::*;
 pub use gio_utils::*;
+pub use image::*;
+pub use library::*;
+pub use notes::*;
+pub use playlist::*;
+pub use png::*;
+pub use power::*;
+pub use priority::*;
+pub use project::*;
+pub use recording::*;
+pub use report::*;
+pub use rules::*;
+pub use search_provider::*;
 pub use settings::*;
+pub use shortcuts::*;
+pub use simulation::*;
+pub use soup_search::*;
+pub use statistics::*;
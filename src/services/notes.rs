@@ -0,0 +1,87 @@
+use super::user_data_dir;
+use crate::config::G_LOG_DOMAIN;
+use std::cell::Cell;
+use std::path::PathBuf;
+
+const NOTES_FILE_NAME: &str = "notes.ini";
+const NOTES_KEY: &str = "notes";
+const UPDATED_KEY: &str = "updated";
+
+/// Free-form notes attached to patterns (templates or saved snapshots),
+/// persisted in the user data directory. Each pattern is a group of the
+/// underlying key file, identified by a stable pattern key.
+#[derive(Debug, Clone)]
+pub struct PatternNotes {
+    inner: glib::KeyFile,
+    path: PathBuf,
+    modified: Cell<bool>,
+}
+
+impl Default for PatternNotes {
+    fn default() -> Self {
+        let path = user_data_dir().join(NOTES_FILE_NAME);
+        let inner = glib::KeyFile::new();
+        if path.exists() {
+            if let Err(error) = inner.load_from_file(&path, glib::KeyFileFlags::KEEP_COMMENTS) {
+                glib::g_warning!(G_LOG_DOMAIN, "Could not load pattern notes: {}", error);
+            }
+        }
+
+        Self {
+            inner,
+            path,
+            modified: Cell::new(false),
+        }
+    }
+}
+
+impl PatternNotes {
+    /// Gets the notes for the pattern identified by `pattern_key`, or an
+    /// empty string if there are none
+    pub fn notes(&self, pattern_key: &str) -> String {
+        self.inner
+            .string(pattern_key, NOTES_KEY)
+            .map(|notes| notes.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Stores `value` as the notes for the pattern identified by `pattern_key`.
+    /// Empty notes remove the pattern entry altogether. Changes are only kept
+    /// in memory until `save` is called.
+    pub fn set_notes(&self, pattern_key: &str, value: &str) {
+        if value.trim().is_empty() {
+            if self.inner.has_group(pattern_key) {
+                self.inner.remove_group(pattern_key).ok();
+            }
+        } else {
+            self.inner.set_string(pattern_key, NOTES_KEY, value);
+            if let Ok(timestamp) = glib::DateTime::now_local().and_then(|now| now.format_iso8601())
+            {
+                self.inner.set_string(pattern_key, UPDATED_KEY, &timestamp);
+            }
+        }
+
+        self.modified.set(true);
+    }
+
+    /// Writes the notes to disk, if they changed since they were last written
+    pub fn save(&self) {
+        if !self.modified.get() {
+            return;
+        }
+
+        if let Some(parent) = self.path.parent() {
+            if let Err(error) = std::fs::create_dir_all(parent) {
+                glib::g_critical!(G_LOG_DOMAIN, "Could not create data directory: {}", error);
+                return;
+            }
+        }
+
+        match self.inner.save_to_file(&self.path) {
+            Ok(_) => self.modified.set(false),
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "Could not store pattern notes: {}", error)
+            }
+        }
+    }
+}
@@ -0,0 +1,103 @@
+/// A step of the demo playlist: a library pattern, run for a number of
+/// generations at a given speed and zoom level
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlaylistEntry {
+    pattern_id: String,
+    generations: u32,
+    speed: u32,
+    zoom: u32,
+}
+
+impl PlaylistEntry {
+    pub fn new(pattern_id: &str, generations: u32, speed: u32, zoom: u32) -> Self {
+        Self {
+            pattern_id: pattern_id.to_string(),
+            generations: generations.max(1),
+            speed: speed.clamp(1, 100),
+            zoom: zoom.max(1),
+        }
+    }
+
+    /// The id of the library pattern to play
+    pub fn pattern_id(&self) -> &str {
+        &self.pattern_id
+    }
+
+    /// How many generations the pattern runs for before moving to the next entry
+    pub fn generations(&self) -> u32 {
+        self.generations
+    }
+
+    /// The evolution speed, in generations per second
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    /// The zoom level, dividing the default universe size: higher values
+    /// show fewer, larger cells
+    pub fn zoom(&self) -> u32 {
+        self.zoom
+    }
+
+    /// The size of the universe the pattern should be played in, given the
+    /// default universe size and the size of the pattern itself
+    pub fn universe_size(
+        &self,
+        default_size: (usize, usize),
+        pattern_size: (usize, usize),
+    ) -> (usize, usize) {
+        let zoom = self.zoom as usize;
+        (
+            usize::max(default_size.0 / zoom, pattern_size.0 + 2),
+            usize::max(default_size.1 / zoom, pattern_size.1 + 2),
+        )
+    }
+}
+
+impl From<(String, u32, u32, u32)> for PlaylistEntry {
+    fn from(value: (String, u32, u32, u32)) -> Self {
+        Self::new(&value.0, value.1, value.2, value.3)
+    }
+}
+
+impl From<&PlaylistEntry> for (String, u32, u32, u32) {
+    fn from(entry: &PlaylistEntry) -> Self {
+        (
+            entry.pattern_id.clone(),
+            entry.generations,
+            entry.speed,
+            entry.zoom,
+        )
+    }
+}
+
+/// Keeps track of the playlist entry being played, looping back to the
+/// first one after the last
+#[derive(Clone, Debug)]
+pub struct PlaylistPlayer {
+    entries: Vec<PlaylistEntry>,
+    position: usize,
+}
+
+impl PlaylistPlayer {
+    /// Creates a player for `entries`, or `None` if there is nothing to play
+    pub fn new(entries: Vec<PlaylistEntry>) -> Option<Self> {
+        match entries.is_empty() {
+            true => None,
+            false => Some(Self {
+                entries,
+                position: 0,
+            }),
+        }
+    }
+
+    pub fn current(&self) -> &PlaylistEntry {
+        &self.entries[self.position]
+    }
+
+    /// Moves to the next entry and returns it
+    pub fn advance(&mut self) -> &PlaylistEntry {
+        self.position = (self.position + 1) % self.entries.len();
+        self.current()
+    }
+}
@@ -0,0 +1,115 @@
+use super::crc32;
+use gtk::{gdk, gio, prelude::*};
+
+const PNG_SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+const INDEXED_COLOR_TYPE: u8 = 3;
+
+/// Converts a color specification, as stored in preferences, to 8 bit RGBA.
+/// Unparsable colors are black.
+pub fn color_to_rgba_bytes(value: &str) -> [u8; 4] {
+    let color = gdk::RGBA::parse(value).unwrap_or(gdk::RGBA::BLACK);
+    [color.red(), color.green(), color.blue(), color.alpha()]
+        .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+/// Encodes animations of two color frames as APNG, playing in a loop. Frames
+/// are stored with one bit per pixel, then compressed.
+pub struct ApngEncoder {
+    png: Vec<u8>,
+    width: u32,
+    height: u32,
+    delay_ms: u16,
+    sequence: u32,
+}
+
+impl ApngEncoder {
+    /// Starts an animation of `frames` frames of `width`x`height` pixels,
+    /// shown for `delay_ms` milliseconds each and painted with the two colors
+    /// of `palette`
+    pub fn new(width: u32, height: u32, frames: u32, palette: [[u8; 3]; 2], delay_ms: u32) -> Self {
+        let mut header = vec![];
+        header.extend_from_slice(&width.to_be_bytes());
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[1, INDEXED_COLOR_TYPE, 0, 0, 0]);
+
+        let mut animation_control = frames.to_be_bytes().to_vec();
+        // Plays forever
+        animation_control.extend_from_slice(&0u32.to_be_bytes());
+
+        let mut png = PNG_SIGNATURE.to_vec();
+        push_chunk(&mut png, b"IHDR", &header);
+        push_chunk(&mut png, b"acTL", &animation_control);
+        push_chunk(&mut png, b"PLTE", &palette.concat());
+
+        Self {
+            png,
+            width,
+            height,
+            delay_ms: delay_ms.min(u16::MAX as u32) as u16,
+            sequence: 0,
+        }
+    }
+
+    /// Appends a frame, given as one palette index per pixel in rows
+    pub fn add_frame(&mut self, pixels: &[u8]) {
+        let mut frame_control = self.sequence.to_be_bytes().to_vec();
+        frame_control.extend_from_slice(&self.width.to_be_bytes());
+        frame_control.extend_from_slice(&self.height.to_be_bytes());
+        frame_control.extend_from_slice(&[0; 8]);
+        frame_control.extend_from_slice(&self.delay_ms.to_be_bytes());
+        frame_control.extend_from_slice(&1000u16.to_be_bytes());
+        // No disposal, the next frame replaces this one entirely
+        frame_control.extend_from_slice(&[0, 0]);
+        push_chunk(&mut self.png, b"fcTL", &frame_control);
+
+        let width = self.width as usize;
+        let mut scanlines = vec![];
+        for row in pixels.chunks(width).take(self.height as usize) {
+            scanlines.push(0);
+            for byte in row.chunks(8) {
+                let packed = byte.iter().enumerate().fold(0u8, |packed, (bit, index)| {
+                    packed | ((*index & 1) << (7 - bit))
+                });
+                scanlines.push(packed);
+            }
+        }
+        let data = zlib_compress(&scanlines);
+
+        // The first frame doubles as the still image of non animated viewers
+        if self.sequence == 0 {
+            push_chunk(&mut self.png, b"IDAT", &data);
+            self.sequence += 1;
+        } else {
+            let mut frame_data = (self.sequence + 1).to_be_bytes().to_vec();
+            frame_data.extend_from_slice(&data);
+            push_chunk(&mut self.png, b"fdAT", &frame_data);
+            self.sequence += 2;
+        }
+    }
+
+    pub fn finish(mut self) -> Vec<u8> {
+        push_chunk(&mut self.png, b"IEND", &[]);
+        self.png
+    }
+}
+
+fn push_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let checksum = crc32(&png[start..]);
+    png.extend_from_slice(&checksum.to_be_bytes());
+}
+
+/// Compresses `data` as a zlib stream, with the compressor of GIO
+fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    let output = gio::MemoryOutputStream::new_resizable();
+    let compressor = gio::ZlibCompressor::new(gio::ZlibCompressorFormat::Zlib, -1);
+    let stream = gio::ConverterOutputStream::new(&output, &compressor);
+    stream
+        .write_all(data, gio::Cancellable::NONE)
+        .and_then(|_| stream.close(gio::Cancellable::NONE))
+        .expect("Could not compress image data in memory");
+    output.steal_as_bytes().to_vec()
+}
@@ -0,0 +1,144 @@
+use crate::config::G_LOG_DOMAIN;
+use gtk::gio::{self, prelude::*, BusType, DBusProxy, DBusProxyFlags};
+use gtk::glib;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+const UPOWER_NAME: &str = "org.freedesktop.UPower";
+const UPOWER_PATH: &str = "/org/freedesktop/UPower";
+
+const POWER_PROFILES_NAME: &str = "net.hadess.PowerProfiles";
+const POWER_PROFILES_PATH: &str = "/net/hadess/PowerProfiles";
+
+/// The power profile in which applications are asked to spare the battery
+const POWER_SAVER_PROFILE: &str = "power-saver";
+
+#[derive(Default)]
+struct PowerMonitorInner {
+    on_battery: Cell<bool>,
+    power_saver: Cell<bool>,
+
+    /// Kept alive to keep receiving property changes
+    proxies: RefCell<Vec<DBusProxy>>,
+    listeners: RefCell<Vec<Rc<dyn Fn(bool)>>>,
+}
+
+/// Tells whether the system runs on battery or in power saver mode, as told
+/// by UPower and the power profiles daemon. Either being unavailable counts as
+/// not saving power.
+#[derive(Clone, Default)]
+pub struct PowerMonitor {
+    inner: Rc<PowerMonitorInner>,
+}
+
+impl std::fmt::Debug for PowerMonitor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PowerMonitor")
+            .field("on_battery", &self.inner.on_battery.get())
+            .field("power_saver", &self.inner.power_saver.get())
+            .finish()
+    }
+}
+
+impl PowerMonitor {
+    /// Starts watching the power state of the system
+    pub fn new() -> Self {
+        let monitor = Self::default();
+        monitor.watch(
+            UPOWER_NAME,
+            UPOWER_PATH,
+            |inner| &inner.on_battery,
+            |proxy| {
+                proxy
+                    .cached_property("OnBattery")
+                    .and_then(|value| value.get::<bool>())
+            },
+        );
+        monitor.watch(
+            POWER_PROFILES_NAME,
+            POWER_PROFILES_PATH,
+            |inner| &inner.power_saver,
+            |proxy| {
+                proxy
+                    .cached_property("ActiveProfile")
+                    .and_then(|value| value.get::<String>())
+                    .map(|profile| profile == POWER_SAVER_PROFILE)
+            },
+        );
+        monitor
+    }
+
+    /// Whether the system asks applications to save power
+    pub fn is_saving_power(&self) -> bool {
+        self.inner.on_battery.get() || self.inner.power_saver.get()
+    }
+
+    /// Calls `f` with whether the system asks to save power, whenever it changes
+    pub fn connect_changed<F: Fn(bool) + 'static>(&self, f: F) {
+        self.inner.listeners.borrow_mut().push(Rc::new(f));
+    }
+
+    /// Follows whether the service `name` asks to save power, as told by
+    /// `read` from its properties, into the `state` it is responsible for
+    fn watch(
+        &self,
+        name: &'static str,
+        path: &'static str,
+        state: fn(&PowerMonitorInner) -> &Cell<bool>,
+        read: impl Fn(&DBusProxy) -> Option<bool> + 'static,
+    ) {
+        let inner = Rc::downgrade(&self.inner);
+        DBusProxy::for_bus(
+            BusType::System,
+            DBusProxyFlags::DO_NOT_AUTO_START,
+            None,
+            name,
+            path,
+            name,
+            gio::Cancellable::NONE,
+            move |result| {
+                let proxy = match result {
+                    Ok(proxy) => proxy,
+                    Err(error) => {
+                        glib::g_debug!(G_LOG_DOMAIN, "Could not watch {}: {}", name, error);
+                        return;
+                    }
+                };
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+
+                let read = Rc::new(read);
+                let monitor = Self { inner };
+                monitor.update(state, read(&proxy));
+
+                let weak_inner = Rc::downgrade(&monitor.inner);
+                proxy.connect_local("g-properties-changed", false, move |values| {
+                    let proxy = values[0].get::<DBusProxy>().ok()?;
+                    if let Some(inner) = weak_inner.upgrade() {
+                        Self { inner }.update(state, read(&proxy));
+                    }
+                    None
+                });
+                monitor.inner.proxies.borrow_mut().push(proxy);
+            },
+        );
+    }
+
+    fn update(&self, state: fn(&PowerMonitorInner) -> &Cell<bool>, value: Option<bool>) {
+        let Some(value) = value else {
+            return;
+        };
+
+        let was_saving_power = self.is_saving_power();
+        state(&self.inner).set(value);
+
+        let saving_power = self.is_saving_power();
+        if saving_power != was_saving_power {
+            let listeners = self.inner.listeners.borrow().clone();
+            for listener in listeners {
+                listener(saving_power);
+            }
+        }
+    }
+}
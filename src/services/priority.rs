@@ -0,0 +1,78 @@
+#[cfg(target_os = "linux")]
+use crate::config::G_LOG_DOMAIN;
+use std::str::FromStr;
+
+/// The nice value simulation threads run with at low priority
+#[cfg(target_os = "linux")]
+const LOW_PRIORITY_NICENESS: libc::c_int = 10;
+
+/// How eagerly the scheduler runs the thread computing generations
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SimulationPriority {
+    /// Same as the rest of the application
+    #[default]
+    Normal,
+
+    /// Niced, so that other programs are served first when the system is busy
+    Low,
+
+    /// Only runs when the processor would otherwise be idle
+    Idle,
+}
+
+impl SimulationPriority {
+    pub const ALL: [SimulationPriority; 3] = [
+        SimulationPriority::Normal,
+        SimulationPriority::Low,
+        SimulationPriority::Idle,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimulationPriority::Normal => "normal",
+            SimulationPriority::Low => "low",
+            SimulationPriority::Idle => "idle",
+        }
+    }
+
+    /// Applies the priority to the calling thread. This is only supported on
+    /// Linux, where nice values and scheduling policies are per thread; elsewhere
+    /// it does nothing.
+    pub fn apply_to_current_thread(&self) {
+        #[cfg(target_os = "linux")]
+        // SAFETY: both calls only read their arguments and target the calling thread
+        let result = unsafe {
+            match self {
+                SimulationPriority::Normal => 0,
+                SimulationPriority::Low => {
+                    let thread_id = libc::syscall(libc::SYS_gettid) as libc::id_t;
+                    libc::setpriority(libc::PRIO_PROCESS, thread_id, LOW_PRIORITY_NICENESS)
+                }
+                SimulationPriority::Idle => {
+                    let param = libc::sched_param { sched_priority: 0 };
+                    libc::sched_setscheduler(0, libc::SCHED_IDLE, &param)
+                }
+            }
+        };
+
+        #[cfg(target_os = "linux")]
+        if result != 0 {
+            glib::g_warning!(
+                G_LOG_DOMAIN,
+                "Could not lower simulation thread priority: {}",
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+impl FromStr for SimulationPriority {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SimulationPriority::ALL
+            .into_iter()
+            .find(|priority| priority.as_str() == value)
+            .ok_or_else(|| format!("Unknown simulation priority {}", value))
+    }
+}
@@ -0,0 +1,113 @@
+use super::{user_data_dir, GameOfLifeSettings};
+use crate::models::Universe;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::PathBuf;
+
+/// The version of the project format written by this version of the application
+const PROJECT_FORMAT_VERSION: u32 = 1;
+
+const SESSION_FILE_NAME: &str = "last-session.gol";
+
+/// Gets the project the universe left on screen is kept in between sessions.
+/// The file is not guaranteed to exist.
+pub fn session_project_path() -> PathBuf {
+    user_data_dir().join(SESSION_FILE_NAME)
+}
+
+#[derive(Debug)]
+pub enum ProjectError {
+    /// The file is not a readable project
+    Invalid(toml::de::Error),
+
+    /// The project was written by a newer version of the application
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for ProjectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProjectError::Invalid(error) => write!(f, "{}", error),
+            ProjectError::UnsupportedVersion(version) => {
+                write!(f, "unsupported project version {}", version)
+            }
+        }
+    }
+}
+
+/// The colors a project is meant to be seen with, for both light and dark styles
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ProjectColors {
+    fg_color: String,
+    bg_color: String,
+    fg_color_dark: String,
+    bg_color_dark: String,
+}
+
+impl ProjectColors {
+    /// Captures the current colors
+    pub fn from_settings(settings: &GameOfLifeSettings) -> Self {
+        Self {
+            fg_color: settings.fg_color(),
+            bg_color: settings.bg_color(),
+            fg_color_dark: settings.fg_color_dark(),
+            bg_color_dark: settings.bg_color_dark(),
+        }
+    }
+
+    /// Stores the colors as the current preferences
+    pub fn apply(&self, settings: &GameOfLifeSettings) {
+        settings.set_fg_color(self.fg_color.clone());
+        settings.set_bg_color(self.bg_color.clone());
+        settings.set_fg_color_dark(self.fg_color_dark.clone());
+        settings.set_bg_color_dark(self.bg_color_dark.clone());
+    }
+}
+
+/// A universe saved to be worked on later, as a `.gol` file: its cells,
+/// generation and rule, whether it is unbounded, and the colors it is shown with.
+/// Projects are written as TOML documents.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GameOfLifeProject {
+    version: u32,
+    unbounded: bool,
+    colors: ProjectColors,
+    universe: Universe,
+}
+
+impl GameOfLifeProject {
+    pub fn new(universe: Universe, unbounded: bool, colors: ProjectColors) -> Self {
+        Self {
+            version: PROJECT_FORMAT_VERSION,
+            unbounded,
+            colors,
+            universe,
+        }
+    }
+
+    pub fn universe(&self) -> &Universe {
+        &self.universe
+    }
+
+    pub fn unbounded(&self) -> bool {
+        self.unbounded
+    }
+
+    pub fn colors(&self) -> &ProjectColors {
+        &self.colors
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, toml::ser::Error> {
+        toml::to_string(self).map(String::into_bytes)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ProjectError> {
+        let project: Self = toml::from_slice(data).map_err(ProjectError::Invalid)?;
+        match project.version {
+            version if version > PROJECT_FORMAT_VERSION => {
+                Err(ProjectError::UnsupportedVersion(version))
+            }
+            _ => Ok(project),
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use super::{color_to_rgba_bytes, ApngEncoder, GifEncoder};
+use crate::models::{UniversePointMatrix, UniverseSnapshot};
+use std::str::FromStr;
+
+/// The largest number of frames a recording holds
+pub const RECORDING_MAX_FRAMES: usize = 2000;
+
+/// The largest side of recorded animations, in pixels
+const RECORDING_MAX_SIZE: usize = 512;
+
+/// The file format runs are recorded to
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RecordingFormat {
+    #[default]
+    Gif,
+    Apng,
+}
+
+impl RecordingFormat {
+    pub const ALL: [RecordingFormat; 2] = [RecordingFormat::Gif, RecordingFormat::Apng];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordingFormat::Gif => "gif",
+            RecordingFormat::Apng => "apng",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            RecordingFormat::Gif => "gif",
+            RecordingFormat::Apng => "png",
+        }
+    }
+
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            RecordingFormat::Gif => "image/gif",
+            RecordingFormat::Apng => "image/apng",
+        }
+    }
+}
+
+impl FromStr for RecordingFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        RecordingFormat::ALL
+            .into_iter()
+            .find(|format| format.as_str() == value)
+            .ok_or_else(|| format!("Unknown recording format {}", value))
+    }
+}
+
+/// Frames of a run, captured every `interval` generations, to be encoded as
+/// an animation once the recording stops
+#[derive(Clone, Debug)]
+pub struct Recording {
+    format: RecordingFormat,
+    interval: u64,
+    frames: Vec<UniverseSnapshot>,
+    last_generation: Option<u64>,
+}
+
+impl Recording {
+    pub fn new(format: RecordingFormat, interval: u32) -> Self {
+        Self {
+            format,
+            interval: u64::from(interval.max(1)),
+            frames: vec![],
+            last_generation: None,
+        }
+    }
+
+    pub fn format(&self) -> RecordingFormat {
+        self.format
+    }
+
+    pub fn interval(&self) -> u32 {
+        self.interval as u32
+    }
+
+    pub fn frames_count(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.frames.len() >= RECORDING_MAX_FRAMES
+    }
+
+    /// Captures the universe at `generation` if it is `interval` generations
+    /// away from the last captured one. Snapshots are only taken when needed,
+    /// and ones sized differently from the first frame are left out.
+    pub fn capture(&mut self, generation: u64, snapshot: impl FnOnce() -> UniverseSnapshot) {
+        let due = self
+            .last_generation
+            .map_or(true, |last| generation.abs_diff(last) >= self.interval);
+        if !due || self.is_full() {
+            return;
+        }
+
+        let snapshot = snapshot();
+        let fits = self.frames.first().map_or(true, |first| {
+            first.rows() == snapshot.rows() && first.columns() == snapshot.columns()
+        });
+        if fits {
+            self.frames.push(snapshot);
+            self.last_generation = Some(generation);
+        }
+    }
+
+    /// Encodes the captured frames, each shown for `frame_delay_ms`
+    /// milliseconds. `progress` is told about every encoded frame, and may
+    /// return `false` to give up, in which case `None` is returned.
+    pub fn encode(
+        &self,
+        cell_color: &str,
+        background_color: &str,
+        frame_delay_ms: u32,
+        mut progress: impl FnMut(usize) -> bool,
+    ) -> Option<Vec<u8>> {
+        let first = self.frames.first()?;
+        let (rows, columns) = (first.rows().max(1), first.columns().max(1));
+        let cell_size = (RECORDING_MAX_SIZE / rows.max(columns)).max(1);
+        let (width, height) = (rows * cell_size, columns * cell_size);
+        let palette = [background_color, cell_color].map(|color| {
+            let [red, green, blue, _] = color_to_rgba_bytes(color);
+            [red, green, blue]
+        });
+
+        let mut encoder = match self.format {
+            RecordingFormat::Gif => AnimationEncoder::Gif(GifEncoder::new(
+                width as u16,
+                height as u16,
+                palette,
+                frame_delay_ms,
+            )),
+            RecordingFormat::Apng => AnimationEncoder::Apng(ApngEncoder::new(
+                width as u32,
+                height as u32,
+                self.frames.len() as u32,
+                palette,
+                frame_delay_ms,
+            )),
+        };
+
+        let mut pixels = vec![0; width * height];
+        for (index, frame) in self.frames.iter().enumerate() {
+            pixels.fill(0);
+            for (row, column) in frame.iter_alive() {
+                for y in column * cell_size..(column + 1) * cell_size {
+                    let start = y * width + row * cell_size;
+                    pixels[start..start + cell_size].fill(1);
+                }
+            }
+            encoder.add_frame(&pixels);
+
+            if !progress(index + 1) {
+                return None;
+            }
+        }
+
+        Some(encoder.finish())
+    }
+}
+
+enum AnimationEncoder {
+    Gif(GifEncoder),
+    Apng(ApngEncoder),
+}
+
+impl AnimationEncoder {
+    fn add_frame(&mut self, pixels: &[u8]) {
+        match self {
+            AnimationEncoder::Gif(encoder) => encoder.add_frame(pixels),
+            AnimationEncoder::Apng(encoder) => encoder.add_frame(pixels),
+        }
+    }
+
+    fn finish(self) -> Vec<u8> {
+        match self {
+            AnimationEncoder::Gif(encoder) => encoder.finish(),
+            AnimationEncoder::Apng(encoder) => encoder.finish(),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+/// The largest number of points of report charts, longer series are sampled
+const CHART_MAX_POINTS: usize = 240;
+const CHART_WIDTH: usize = 360;
+const CHART_HEIGHT: usize = 90;
+
+const REPORT_STYLE: &str = "body{font-family:sans-serif;max-width:60em;margin:2em auto;padding:0 1em;color:#222}\
+section{border-top:1px solid #ccc;padding:1em 0;display:grid;grid-template-columns:auto 1fr;gap:0 1.5em}\
+section>h2{grid-column:1/-1;font-size:1.2em}\
+img{image-rendering:pixelated;max-width:12em;border:1px solid #ccc}\
+table{border-collapse:collapse}td,th{text-align:left;padding:.1em 1em .1em 0}\
+svg polyline{fill:none;stroke:#3584e4;stroke-width:1.5}svg line{stroke:#999}\
+pre{grid-column:1/-1;background:#f6f5f4;padding:.5em;overflow:auto;max-height:20em}\
+figcaption{font-size:.85em;color:#666}";
+
+/// A self contained HTML document gathering findings, such as the results
+/// of experiments, to publish or archive them in one file
+#[derive(Clone, Debug, Default)]
+pub struct HtmlReport {
+    title: String,
+    sections: Vec<ReportSection>,
+}
+
+impl HtmlReport {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            sections: vec![],
+        }
+    }
+
+    pub fn add_section(&mut self, section: ReportSection) {
+        self.sections.push(section);
+    }
+
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str(&format!("<title>{}</title>\n", escape(&self.title)));
+        html.push_str(&format!("<style>{}</style>\n", REPORT_STYLE));
+        html.push_str("</head>\n<body>\n");
+        html.push_str(&format!("<h1>{}</h1>\n", escape(&self.title)));
+        for section in &self.sections {
+            section.write_html(&mut html);
+        }
+        html.push_str("</body>\n</html>\n");
+        html
+    }
+}
+
+/// A finding of a report: statistics, along with an optional image, chart
+/// and pattern
+#[derive(Clone, Debug, Default)]
+pub struct ReportSection {
+    heading: String,
+    stats: Vec<(String, String)>,
+    thumbnail: Option<Vec<u8>>,
+    chart: Option<(String, Vec<usize>)>,
+    pattern: Option<String>,
+}
+
+impl ReportSection {
+    pub fn new(heading: &str) -> Self {
+        Self {
+            heading: heading.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn add_stat(&mut self, label: &str, value: &str) {
+        self.stats.push((label.to_string(), value.to_string()));
+    }
+
+    /// Sets the image of the section, as PNG data
+    pub fn set_thumbnail(&mut self, png: Vec<u8>) {
+        self.thumbnail = Some(png);
+    }
+
+    /// Sets a line chart of `values`, described by `caption`
+    pub fn set_chart(&mut self, caption: &str, values: Vec<usize>) {
+        self.chart = Some((caption.to_string(), values));
+    }
+
+    /// Sets the pattern of the section, such as an RLE pattern
+    pub fn set_pattern(&mut self, text: &str) {
+        self.pattern = Some(text.to_string());
+    }
+
+    fn write_html(&self, html: &mut String) {
+        html.push_str("<section>\n");
+        html.push_str(&format!("<h2>{}</h2>\n", escape(&self.heading)));
+        if let Some(png) = self.thumbnail.as_ref() {
+            html.push_str(&format!(
+                "<img src=\"data:image/png;base64,{}\" alt=\"{}\">\n",
+                base64(png),
+                escape(&self.heading)
+            ));
+        }
+
+        html.push_str("<div>\n<table>\n");
+        for (label, value) in &self.stats {
+            html.push_str(&format!(
+                "<tr><th>{}</th><td>{}</td></tr>\n",
+                escape(label),
+                escape(value)
+            ));
+        }
+        html.push_str("</table>\n");
+        if let Some((caption, values)) = self.chart.as_ref() {
+            html.push_str("<figure>\n");
+            html.push_str(&chart_svg(values));
+            html.push_str(&format!("<figcaption>{}</figcaption>\n", escape(caption)));
+            html.push_str("</figure>\n");
+        }
+        html.push_str("</div>\n");
+
+        if let Some(pattern) = self.pattern.as_ref() {
+            html.push_str(&format!("<pre>{}</pre>\n", escape(pattern)));
+        }
+        html.push_str("</section>\n");
+    }
+}
+
+/// Draws `values` as an inline SVG line chart, scaled to the largest value
+fn chart_svg(values: &[usize]) -> String {
+    let step = values.len().div_ceil(CHART_MAX_POINTS).max(1);
+    let samples: Vec<usize> = values.iter().step_by(step).copied().collect();
+    let max = samples.iter().copied().max().unwrap_or_default().max(1) as f64;
+    let last = samples.len().saturating_sub(1).max(1) as f64;
+
+    let points: Vec<String> = samples
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            format!(
+                "{:.1},{:.1}",
+                index as f64 / last * CHART_WIDTH as f64,
+                CHART_HEIGHT as f64 * (1. - *value as f64 / max)
+            )
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\
+<line x1=\"0\" y1=\"{h}\" x2=\"{w}\" y2=\"{h}\"/>\
+<polyline points=\"{}\"/></svg>\n",
+        points.join(" "),
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT,
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut text = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let value = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for index in 0..4 {
+            match index <= chunk.len() {
+                true => text.push(ALPHABET[(value >> (18 - index * 6)) as usize & 0x3f] as char),
+                false => text.push('='),
+            }
+        }
+    }
+    text
+}
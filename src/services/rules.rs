@@ -0,0 +1,135 @@
+use super::user_data_dir;
+use crate::config::G_LOG_DOMAIN;
+use crate::models::UniverseRule;
+use std::path::PathBuf;
+
+const USER_RULES_DIR_NAME: &str = "rules";
+const RULE_GROUP: &str = "Rule";
+
+/// A rule saved by the user under a name, identified by its file name without
+/// extension
+#[derive(Clone, Debug)]
+pub struct UserRule {
+    id: String,
+    name: String,
+    rule: UniverseRule,
+}
+
+impl UserRule {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn rule(&self) -> &UniverseRule {
+        &self.rule
+    }
+
+    /// Reads the rules saved by the user, sorted by name. Unreadable rules
+    /// are logged and skipped.
+    pub fn all() -> Vec<UserRule> {
+        let entries = match std::fs::read_dir(user_rules_dir()) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut rules: Vec<UserRule> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let id = file_name.strip_suffix(".ini")?;
+                let key_file = glib::KeyFile::new();
+                if let Err(error) = key_file.load_from_file(entry.path(), glib::KeyFileFlags::NONE)
+                {
+                    glib::g_warning!(G_LOG_DOMAIN, "Bad rule {}: {}", file_name, error);
+                    return None;
+                }
+                Self::from_key_file(id, &key_file)
+            })
+            .collect();
+
+        rules.sort_by_key(|user_rule| user_rule.name.to_lowercase());
+        rules
+    }
+
+    /// Saves `rule` under `name`, in place of the rule already saved under
+    /// the same name if any
+    pub fn save(name: &str, rule: &UniverseRule) -> std::io::Result<UserRule> {
+        let dir = user_rules_dir();
+        std::fs::create_dir_all(&dir)?;
+
+        let rules = Self::all();
+        let id = match rules
+            .iter()
+            .find(|user_rule| user_rule.name.to_lowercase() == name.to_lowercase())
+        {
+            Some(user_rule) => user_rule.id.clone(),
+            None => {
+                let base_id: String = name
+                    .chars()
+                    .map(|char| match char.is_alphanumeric() {
+                        true => char.to_ascii_lowercase(),
+                        false => '_',
+                    })
+                    .collect();
+                let mut id = base_id.clone();
+                let mut suffix = 1;
+                while rules.iter().any(|user_rule| user_rule.id == id) {
+                    suffix += 1;
+                    id = format!("{}_{}", base_id, suffix);
+                }
+                id
+            }
+        };
+
+        let user_rule = UserRule {
+            id,
+            name: name.to_string(),
+            rule: rule.clone(),
+        };
+        std::fs::write(
+            dir.join(format!("{}.ini", user_rule.id)),
+            user_rule.to_key_file().to_data().as_bytes(),
+        )?;
+        Ok(user_rule)
+    }
+
+    fn to_key_file(&self) -> glib::KeyFile {
+        let key_file = glib::KeyFile::new();
+        key_file.set_string(RULE_GROUP, "name", &self.name);
+        key_file.set_string(RULE_GROUP, "rule", &self.rule.to_string());
+        key_file
+    }
+
+    fn from_key_file(id: &str, key_file: &glib::KeyFile) -> Option<UserRule> {
+        let rule = match key_file
+            .string(RULE_GROUP, "rule")
+            .map(|value| value.parse::<UniverseRule>())
+        {
+            Ok(Ok(rule)) => rule,
+            _ => {
+                glib::g_warning!(G_LOG_DOMAIN, "Bad rule {}: no readable rule", id);
+                return None;
+            }
+        };
+        let name = key_file
+            .string(RULE_GROUP, "name")
+            .map(|name| name.to_string())
+            .unwrap_or_else(|_| id.replace('_', " "));
+
+        Some(UserRule {
+            id: id.to_string(),
+            name,
+            rule,
+        })
+    }
+}
+
+/// Gets the directory rules saved by the user are stored in. The directory is
+/// not guaranteed to exist.
+fn user_rules_dir() -> PathBuf {
+    user_data_dir().join(USER_RULES_DIR_NAME)
+}
@@ -0,0 +1,234 @@
+use super::{LibraryPattern, PatternLibrary};
+use crate::config::{APPLICATION_ID, G_LOG_DOMAIN};
+use crate::models::UniversePointMatrix;
+use gtk::gio::{self, DBusConnection, DBusMethodInvocation, RegistrationId};
+use gtk::glib::{self, thread_guard::ThreadGuard, ToVariant, Variant};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// The interface GNOME Shell queries when searching from the overview
+pub const SEARCH_PROVIDER_INTERFACE: &str = "org.gnome.Shell.SearchProvider2";
+
+const SEARCH_PROVIDER_ERROR: &str = "org.gnome.Shell.SearchProvider2.Error.Failed";
+
+/// The most results shown in the overview, which only has room for a few
+const MAX_RESULTS: usize = 5;
+
+const SEARCH_PROVIDER_INTROSPECTION: &str = r#"
+<node>
+  <interface name="org.gnome.Shell.SearchProvider2">
+    <method name="GetInitialResultSet">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetSubsearchResultSet">
+      <arg type="as" name="previous_results" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="as" name="results" direction="out"/>
+    </method>
+    <method name="GetResultMetas">
+      <arg type="as" name="identifiers" direction="in"/>
+      <arg type="aa{sv}" name="metas" direction="out"/>
+    </method>
+    <method name="ActivateResult">
+      <arg type="s" name="identifier" direction="in"/>
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+    <method name="LaunchSearch">
+      <arg type="as" name="terms" direction="in"/>
+      <arg type="u" name="timestamp" direction="in"/>
+    </method>
+  </interface>
+</node>
+"#;
+
+/// What the user picked from the search results of the Shell
+#[derive(Debug, Clone)]
+pub enum SearchActivation {
+    /// A result was clicked, the pattern should be loaded
+    Pattern(LibraryPattern),
+
+    /// The search was continued in the application, the pattern library should
+    /// be shown with the best match selected, if any
+    Search(Option<LibraryPattern>),
+}
+
+/// Finds the patterns bundled with the application matching the terms typed in
+/// the overview of GNOME Shell. Exported on the D-Bus connection of the
+/// application for as long as this value lives.
+#[derive(Debug)]
+pub struct SearchProvider {
+    connection: DBusConnection,
+    registration: Option<RegistrationId>,
+}
+
+impl SearchProvider {
+    /// Exports the search provider at `object_path`. Activated results are
+    /// handed to `activate`, which runs on the main thread.
+    pub fn register<F>(
+        connection: &DBusConnection,
+        object_path: &str,
+        activate: F,
+    ) -> Result<Self, glib::Error>
+    where
+        F: Fn(SearchActivation) + 'static,
+    {
+        let node = gio::DBusNodeInfo::for_xml(SEARCH_PROVIDER_INTROSPECTION)?;
+        let interface = node
+            .lookup_interface(SEARCH_PROVIDER_INTERFACE)
+            .expect("The search provider interface is described");
+
+        // Bundled patterns never change while the application runs
+        let patterns = PatternLibrary::bundled();
+        let activate = Mutex::new(ThreadGuard::new(activate));
+        let registration = connection.register_object(
+            object_path,
+            &interface,
+            move |_, _, _, _, method, parameters, invocation| {
+                let activation = match method {
+                    "GetInitialResultSet" => {
+                        return match parameters.get::<(Vec<String>,)>() {
+                            Some((terms,)) => {
+                                return_results(&invocation, search(&patterns, &terms, None))
+                            }
+                            None => invalid_arguments(&invocation),
+                        };
+                    }
+                    "GetSubsearchResultSet" => {
+                        return match parameters.get::<(Vec<String>, Vec<String>)>() {
+                            Some((previous, terms)) => return_results(
+                                &invocation,
+                                search(&patterns, &terms, Some(&previous)),
+                            ),
+                            None => invalid_arguments(&invocation),
+                        };
+                    }
+                    "GetResultMetas" => {
+                        return match parameters.get::<(Vec<String>,)>() {
+                            Some((ids,)) => return_metas(&invocation, &patterns, &ids),
+                            None => invalid_arguments(&invocation),
+                        };
+                    }
+                    "ActivateResult" => match parameters.get::<(String, Vec<String>, u32)>() {
+                        Some((id, _, _)) => match find(&patterns, &id) {
+                            Some(pattern) => SearchActivation::Pattern(pattern.clone()),
+                            None => {
+                                invocation
+                                    .return_dbus_error(SEARCH_PROVIDER_ERROR, "Unknown pattern");
+                                return;
+                            }
+                        },
+                        None => return invalid_arguments(&invocation),
+                    },
+                    "LaunchSearch" => match parameters.get::<(Vec<String>, u32)>() {
+                        Some((terms, _)) => SearchActivation::Search(
+                            search(&patterns, &terms, None)
+                                .first()
+                                .and_then(|id| find(&patterns, id))
+                                .cloned(),
+                        ),
+                        None => return invalid_arguments(&invocation),
+                    },
+                    _ => {
+                        invocation.return_dbus_error(SEARCH_PROVIDER_ERROR, "Unknown method");
+                        return;
+                    }
+                };
+
+                // Replies first, the Shell does not wait for the window to show up
+                invocation.return_value(None);
+                if let Ok(activate) = activate.lock() {
+                    (activate.get_ref())(activation);
+                }
+            },
+            |_, _, _, _, _| ().to_variant(),
+            |_, _, _, _, _, _| false,
+        )?;
+
+        Ok(Self {
+            connection: connection.clone(),
+            registration: Some(registration),
+        })
+    }
+}
+
+impl Drop for SearchProvider {
+    fn drop(&mut self) {
+        if let Some(registration) = self.registration.take() {
+            if let Err(error) = self.connection.unregister_object(registration) {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Could not unexport the search provider: {}",
+                    error
+                );
+            }
+        }
+    }
+}
+
+fn find<'a>(patterns: &'a [LibraryPattern], id: &str) -> Option<&'a LibraryPattern> {
+    patterns.iter().find(|pattern| pattern.id() == id)
+}
+
+/// The ids of the patterns whose name or id contains every term, ignoring case.
+/// A subsearch only looks among the `previous` results.
+fn search(
+    patterns: &[LibraryPattern],
+    terms: &[String],
+    previous: Option<&[String]>,
+) -> Vec<String> {
+    let terms: Vec<String> = terms.iter().map(|term| term.to_lowercase()).collect();
+    if terms.iter().all(|term| term.trim().is_empty()) {
+        return vec![];
+    }
+
+    patterns
+        .iter()
+        .filter(|pattern| {
+            previous.map_or(true, |previous| {
+                previous.iter().any(|id| id == pattern.id())
+            })
+        })
+        .filter(|pattern| {
+            let name = pattern.name().to_lowercase();
+            let id = pattern.id().to_lowercase().replace('_', " ");
+            terms
+                .iter()
+                .all(|term| name.contains(term.as_str()) || id.contains(term.as_str()))
+        })
+        .take(MAX_RESULTS)
+        .map(|pattern| pattern.id().to_owned())
+        .collect()
+}
+
+fn return_results(invocation: &DBusMethodInvocation, results: Vec<String>) {
+    invocation.return_value(Some(&(results,).to_variant()));
+}
+
+fn return_metas(invocation: &DBusMethodInvocation, patterns: &[LibraryPattern], ids: &[String]) {
+    let metas: Vec<HashMap<&str, Variant>> = ids
+        .iter()
+        .filter_map(|id| find(patterns, id))
+        .map(|pattern| {
+            let snapshot = pattern.pattern().snapshot();
+            HashMap::from([
+                ("id", pattern.id().to_variant()),
+                ("name", pattern.name().to_variant()),
+                (
+                    "description",
+                    format!("{}×{}", snapshot.columns(), snapshot.rows()).to_variant(),
+                ),
+                ("gicon", APPLICATION_ID.to_variant()),
+            ])
+        })
+        .collect();
+    invocation.return_value(Some(&(metas,).to_variant()));
+}
+
+fn invalid_arguments(invocation: &DBusMethodInvocation) {
+    invocation.return_dbus_error(
+        "org.freedesktop.DBus.Error.InvalidArgs",
+        "Invalid arguments",
+    );
+}
@@ -1,7 +1,41 @@
+use super::{
+    FastForward, PatternLibrary, PlaylistEntry, RecordingFormat, Shortcut, ShortcutBindings,
+    SimulationPriority,
+};
 use crate::config::{APPLICATION_ID, G_LOG_DOMAIN};
+use crate::models::{
+    DesignTool, InjectionEdge, RainPlacement, SoupSymmetry, StampBlend, UniverseChance,
+    UniverseInjection, UniverseRain, UniverseRule, UniverseSoup, WireWorldCell,
+};
 use gtk::gdk;
 use gtk::gio::prelude::{SettingsExt, SettingsExtManual};
-use gtk::glib::IsA;
+use gtk::glib::{IsA, ToVariant};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    /// The in-memory backend every settings object shares in safe mode
+    static SAFE_MODE_BACKEND: RefCell<Option<gtk::gio::SettingsBackend>> = const { RefCell::new(None) };
+}
+
+/// Starts the application over from the default settings, without touching the
+/// stored ones: changes are kept in memory until the application quits. Has to
+/// be enabled before any settings object is created.
+pub fn enable_safe_mode() {
+    SAFE_MODE.store(true, Ordering::SeqCst);
+    SAFE_MODE_BACKEND.with(|backend| {
+        backend.replace(Some(gtk::gio::memory_settings_backend_new()));
+    });
+}
+
+/// Whether the application was started in safe mode, skipping anything that
+/// could bring a previous crash back
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::SeqCst)
+}
 
 #[derive(Debug, Clone)]
 pub struct GameOfLifeSettings {
@@ -10,9 +44,11 @@ pub struct GameOfLifeSettings {
 
 impl Default for GameOfLifeSettings {
     fn default() -> Self {
-        Self {
-            inner: gtk::gio::Settings::new(APPLICATION_ID),
-        }
+        let inner = SAFE_MODE_BACKEND.with(|backend| match backend.borrow().as_ref() {
+            Some(backend) => gtk::gio::Settings::with_backend(APPLICATION_ID, backend),
+            None => gtk::gio::Settings::new(APPLICATION_ID),
+        });
+        Self { inner }
     }
 }
 
@@ -48,10 +84,28 @@ impl GameOfLifeSettings {
             .expect("Could not store window width");
     }
 
+    pub fn window_maximized(&self) -> bool {
+        self.inner.boolean("window-maximized")
+    }
+
+    pub fn set_window_maximized(&self, value: bool) {
+        self.inner
+            .set_boolean("window-maximized", value)
+            .expect("Could not store window maximized state");
+    }
+
+    pub fn restore_session(&self) -> bool {
+        self.inner.boolean("restore-session")
+    }
+
     pub fn fg_color(&self) -> String {
         self.inner.string("fg-color").to_string()
     }
 
+    pub fn cell_accent_color(&self) -> bool {
+        self.inner.boolean("cell-accent-color")
+    }
+
     #[allow(dead_code)]
     pub fn fg_color_rgba(&self) -> gdk::RGBA {
         gdk::RGBA::parse(self.fg_color().as_str()).expect("Cannot parse RGBA")
@@ -97,6 +151,13 @@ impl GameOfLifeSettings {
             .expect("Could not store bg-color-dark preference");
     }
 
+    /// Restores the default light and dark colors
+    pub fn reset_colors(&self) {
+        for key in ["fg-color", "bg-color", "fg-color-dark", "bg-color-dark"] {
+            self.inner.reset(key);
+        }
+    }
+
     pub fn universe_width(&self) -> i32 {
         self.inner.int("universe-width")
     }
@@ -125,6 +186,54 @@ impl GameOfLifeSettings {
         self.inner.boolean("fade-out-cells")
     }
 
+    /// The size of cells in logical pixels, scrolling over universes larger
+    /// than the window, 0 to fit the universe in the window
+    pub fn cell_size(&self) -> u32 {
+        self.inner.uint("cell-size")
+    }
+
+    /// The number of generations dead cells fade out over, 0 for none
+    pub fn death_trail_length(&self) -> u32 {
+        self.inner.uint("death-trail-length")
+    }
+
+    pub fn cell_age_coloring(&self) -> bool {
+        self.inner.boolean("cell-age-coloring")
+    }
+
+    pub fn cell_age_color(&self) -> String {
+        self.inner.string("cell-age-color").to_string()
+    }
+
+    pub fn set_cell_age_color(&self, value: String) {
+        self.inner
+            .set_string("cell-age-color", &value)
+            .expect("Could not store the age color")
+    }
+
+    /// The color dying cells of Generations rules start from, empty for the
+    /// cell color
+    pub fn dying_color(&self) -> String {
+        self.inner.string("dying-color").to_string()
+    }
+
+    pub fn set_dying_color(&self, value: &str) {
+        self.inner
+            .set_string("dying-color", value)
+            .expect("Could not store the dying color")
+    }
+
+    /// The number of generations a cell has to survive to reach the age color
+    pub fn cell_age_span(&self) -> u32 {
+        self.inner.uint("cell-age-span")
+    }
+
+    /// Whether rows and columns are labeled along the edges of the grid when
+    /// cells are large enough
+    pub fn show_grid_coordinates(&self) -> bool {
+        self.inner.boolean("show-grid-coordinates")
+    }
+
     #[allow(dead_code)]
     pub fn set_draw_cells_outline(&self, value: bool) {
         self.inner
@@ -153,6 +262,385 @@ impl GameOfLifeSettings {
             .expect("Coult not store allow render during resize preference")
     }
 
+    pub fn rule(&self) -> UniverseRule {
+        let value = self.inner.string("rule");
+        value.parse::<UniverseRule>().unwrap_or_else(|_| {
+            glib::g_warning!(G_LOG_DOMAIN, "Invalid rule {}, using default", value);
+            UniverseRule::default()
+        })
+    }
+
+    pub fn set_rule(&self, value: &UniverseRule) {
+        self.inner
+            .set_string("rule", value.to_string().as_str())
+            .expect("Could not store rule preference")
+    }
+
+    pub fn pause_on_unfocus(&self) -> bool {
+        self.inner.boolean("pause-on-unfocus")
+    }
+
+    #[allow(dead_code)]
+    pub fn set_pause_on_unfocus(&self, value: bool) {
+        self.inner
+            .set_boolean("pause-on-unfocus", value)
+            .expect("Could not store pause on unfocus preference")
+    }
+
+    pub fn pause_when_hidden(&self) -> bool {
+        self.inner.boolean("pause-when-hidden")
+    }
+
+    pub fn throttle_when_saving_power(&self) -> bool {
+        self.inner.boolean("throttle-when-saving-power")
+    }
+
+    pub fn write_crash_reports(&self) -> bool {
+        self.inner.boolean("write-crash-reports")
+    }
+
+    pub fn screenshot_when_stable(&self) -> bool {
+        self.inner.boolean("screenshot-when-stable")
+    }
+
+    /// The folder screenshots of stable universes are saved to, by default
+    /// a folder in the pictures directory of the user
+    pub fn screenshot_folder(&self) -> std::path::PathBuf {
+        let value = self.inner.string("screenshot-folder");
+        match value.is_empty() {
+            true => glib::user_special_dir(glib::UserDirectory::Pictures)
+                .unwrap_or_else(glib::home_dir)
+                .join("Game of Life"),
+            false => std::path::PathBuf::from(value.as_str()),
+        }
+    }
+
+    pub fn set_screenshot_folder(&self, value: &std::path::Path) {
+        self.inner
+            .set_string("screenshot-folder", &value.to_string_lossy())
+            .expect("Could not store screenshot folder preference")
+    }
+
+    pub fn recording_format(&self) -> RecordingFormat {
+        let value = self.inner.string("recording-format");
+        value.parse::<RecordingFormat>().unwrap_or_else(|error| {
+            glib::g_warning!(G_LOG_DOMAIN, "{}", error);
+            RecordingFormat::default()
+        })
+    }
+
+    pub fn set_recording_format(&self, value: RecordingFormat) {
+        self.inner
+            .set_string("recording-format", value.as_str())
+            .expect("Could not store recording format preference")
+    }
+
+    pub fn recording_interval(&self) -> u32 {
+        self.inner.uint("recording-interval")
+    }
+
+    /// What dragging over a universe does in design mode
+    pub fn design_tool(&self) -> DesignTool {
+        self.inner
+            .string("design-tool")
+            .parse::<DesignTool>()
+            .unwrap_or_else(|error| {
+                glib::g_warning!(G_LOG_DOMAIN, "{}, using default", error);
+                DesignTool::default()
+            })
+    }
+
+    /// What drawing brings to life in WireWorld universes
+    pub fn wireworld_cell(&self) -> WireWorldCell {
+        self.inner
+            .string("wireworld-cell")
+            .parse::<WireWorldCell>()
+            .unwrap_or_else(|error| {
+                glib::g_warning!(G_LOG_DOMAIN, "{}, using default", error);
+                WireWorldCell::default()
+            })
+    }
+
+    /// The color of the cells drawing brings to life in universes of colored
+    /// rules
+    pub fn drawing_color(&self) -> u8 {
+        self.inner.uint("drawing-color") as u8
+    }
+
+    /// Whether drawing puts ants down instead of cells in universes of ant
+    /// rules
+    pub fn draws_ants(&self) -> bool {
+        self.inner.boolean("draw-ants")
+    }
+
+    /// The steps every ant takes in a generation in universes of ant rules
+    pub fn ant_steps(&self) -> u32 {
+        self.inner.uint("ant-steps")
+    }
+
+    /// How stamped patterns combine with the cells underneath
+    pub fn stamp_blend(&self) -> StampBlend {
+        self.inner
+            .string("stamp-blend")
+            .parse::<StampBlend>()
+            .unwrap_or_else(|error| {
+                glib::g_warning!(G_LOG_DOMAIN, "{}, using default", error);
+                StampBlend::default()
+            })
+    }
+
+    pub fn brush_size(&self) -> u32 {
+        self.inner.uint("brush-size")
+    }
+
+    pub fn stylus_brush_radius(&self) -> u32 {
+        self.inner.uint("stylus-brush-radius")
+    }
+
+    pub fn last_crash_report(&self) -> String {
+        self.inner.string("last-crash-report").to_string()
+    }
+
+    pub fn set_last_crash_report(&self, value: &str) {
+        self.inner
+            .set_string("last-crash-report", value)
+            .expect("Could not store last crash report")
+    }
+
+    pub fn rewind_depth(&self) -> u32 {
+        self.inner.uint("rewind-depth")
+    }
+
+    /// The number of generations the activity heatmap spans
+    pub fn heatmap_generations(&self) -> u32 {
+        self.inner.uint("heatmap-generations")
+    }
+
+    pub fn halt_when_settled(&self) -> bool {
+        self.inner.boolean("halt-when-settled")
+    }
+
+    pub fn detect_oscillators(&self) -> bool {
+        self.inner.boolean("detect-oscillators")
+    }
+
+    #[allow(dead_code)]
+    pub fn set_halt_when_settled(&self, value: bool) {
+        self.inner
+            .set_boolean("halt-when-settled", value)
+            .expect("Could not store halt when settled preference")
+    }
+
+    pub fn simulation_priority(&self) -> SimulationPriority {
+        let value = self.inner.string("simulation-priority");
+        value.parse::<SimulationPriority>().unwrap_or_else(|error| {
+            glib::g_warning!(G_LOG_DOMAIN, "{}, using default", error);
+            SimulationPriority::default()
+        })
+    }
+
+    pub fn set_simulation_priority(&self, value: SimulationPriority) {
+        self.inner
+            .set_string("simulation-priority", value.as_str())
+            .expect("Could not store simulation priority preference")
+    }
+
+    /// The rain applied to universes, or `None` if disabled
+    pub fn rain(&self) -> Option<UniverseRain> {
+        let rate = self.inner.double("rain-rate");
+        let placement = match self.inner.boolean("rain-at-edges") {
+            true => RainPlacement::Edges,
+            false => RainPlacement::Uniform,
+        };
+
+        match rate > 0.0 {
+            true => Some(UniverseRain::new(rate, placement)),
+            false => None,
+        }
+    }
+
+    pub fn set_rain(&self, value: Option<&UniverseRain>) {
+        self.inner
+            .set_double("rain-rate", value.map(|rain| rain.rate()).unwrap_or(0.0))
+            .expect("Could not store rain rate preference");
+        self.inner
+            .set_boolean(
+                "rain-at-edges",
+                value
+                    .map(|rain| rain.placement() == RainPlacement::Edges)
+                    .unwrap_or(false),
+            )
+            .expect("Could not store rain placement preference");
+    }
+
+    /// The probabilities births and survivals happen with and noise flips
+    /// cells with, or `None` if cells always evolve as the rule says
+    pub fn chance(&self) -> Option<UniverseChance> {
+        let birth_probability = self.inner.double("birth-probability");
+        let survival_probability = self.inner.double("survival-probability");
+        let temperature = self.inner.double("temperature");
+
+        match birth_probability < 1.0 || survival_probability < 1.0 || temperature > 0.0 {
+            true => Some(
+                UniverseChance::new(
+                    birth_probability,
+                    survival_probability,
+                    self.inner.uint("stochastic-seed") as u64,
+                )
+                .with_temperature(temperature),
+            ),
+            false => None,
+        }
+    }
+
+    /// How random universes are filled
+    pub fn soup(&self) -> UniverseSoup {
+        let seed = match self.inner.boolean("soup-fixed-seed") {
+            true => Some(self.inner.uint("soup-seed") as u64),
+            false => None,
+        };
+        let symmetry = self
+            .inner
+            .string("soup-symmetry")
+            .parse::<SoupSymmetry>()
+            .unwrap_or_else(|error| {
+                glib::g_warning!(G_LOG_DOMAIN, "{}, using default", error);
+                SoupSymmetry::default()
+            });
+        UniverseSoup::new(self.inner.double("soup-density"), seed).with_symmetry(symmetry)
+    }
+
+    /// Makes random seeding always draw the soup of `seed`
+    pub fn set_soup_seed(&self, seed: u32) {
+        self.inner
+            .set_uint("soup-seed", seed)
+            .expect("Could not store soup seed preference");
+        self.inner
+            .set_boolean("soup-fixed-seed", true)
+            .expect("Could not store soup fixed seed preference");
+    }
+
+    pub fn soup_selection_only(&self) -> bool {
+        self.inner.boolean("soup-selection-only")
+    }
+
+    pub fn turbo(&self) -> bool {
+        self.inner.boolean("turbo")
+    }
+
+    /// How far fast forwarding runs the simulation
+    pub fn fast_forward(&self) -> FastForward {
+        let generations = self.inner.uint("fast-forward-generations") as u64;
+        match self.inner.string("fast-forward-mode").as_str() {
+            "until" => FastForward::Until(generations),
+            _ => FastForward::For(generations),
+        }
+    }
+
+    /// The id of the library pattern injected from an edge, empty if disabled
+    pub fn injection_pattern(&self) -> String {
+        self.inner.string("injection-pattern").to_string()
+    }
+
+    pub fn set_injection_pattern(&self, value: &str) {
+        self.inner
+            .set_string("injection-pattern", value)
+            .expect("Could not store injection pattern preference")
+    }
+
+    pub fn injection_edge(&self) -> InjectionEdge {
+        let value = self.inner.string("injection-edge");
+        value.parse::<InjectionEdge>().unwrap_or_else(|error| {
+            glib::g_warning!(G_LOG_DOMAIN, "{}", error);
+            InjectionEdge::default()
+        })
+    }
+
+    pub fn set_injection_edge(&self, value: InjectionEdge) {
+        self.inner
+            .set_string("injection-edge", value.as_str())
+            .expect("Could not store injection edge preference")
+    }
+
+    /// The pattern injected from an edge of universes, or `None` if disabled
+    /// or if the pattern is not in the library
+    pub fn injection(&self) -> Option<UniverseInjection> {
+        let id = self.injection_pattern();
+        if id.is_empty() {
+            return None;
+        }
+
+        PatternLibrary::bundled()
+            .into_iter()
+            .find(|library_pattern| library_pattern.id() == id)
+            .map(|library_pattern| {
+                UniverseInjection::new(
+                    library_pattern.pattern().snapshot().clone(),
+                    self.injection_edge(),
+                    self.inner.uint("injection-period"),
+                )
+            })
+    }
+
+    pub fn reseed_when_stable(&self) -> bool {
+        self.inner.boolean("reseed-when-stable")
+    }
+
+    pub fn reseed_patience(&self) -> u32 {
+        self.inner.uint("reseed-patience")
+    }
+
+    pub fn ambient_palette_period(&self) -> u32 {
+        self.inner.uint("ambient-palette-period")
+    }
+
+    pub fn playlist(&self) -> Vec<PlaylistEntry> {
+        self.inner
+            .value("playlist")
+            .get::<Vec<(String, u32, u32, u32)>>()
+            .unwrap_or_default()
+            .into_iter()
+            .map(PlaylistEntry::from)
+            .collect()
+    }
+
+    pub fn set_playlist(&self, entries: &[PlaylistEntry]) {
+        let value: Vec<(String, u32, u32, u32)> =
+            entries.iter().map(|entry| entry.into()).collect();
+        self.inner
+            .set_value("playlist", &value.to_variant())
+            .expect("Could not store playlist")
+    }
+
+    pub fn shortcuts(&self) -> ShortcutBindings {
+        let overrides = self
+            .inner
+            .value("shortcuts")
+            .get::<HashMap<String, String>>()
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|(action_name, accelerator)| {
+                Shortcut::from_action_name(&action_name).map(|shortcut| (shortcut, accelerator))
+            })
+            .collect();
+        ShortcutBindings::new(overrides)
+    }
+
+    pub fn set_shortcuts(&self, bindings: &ShortcutBindings) {
+        let value: HashMap<String, String> = bindings
+            .overrides()
+            .iter()
+            .map(|(shortcut, accelerator)| (shortcut.action_name().to_owned(), accelerator.clone()))
+            .collect();
+        self.inner
+            .set_value("shortcuts", &value.to_variant())
+            .expect("Could not store shortcuts")
+    }
+
+    pub fn reset_shortcuts(&self) {
+        self.inner.reset("shortcuts");
+    }
+
     pub fn connect_changed<F>(&self, key: &str, f: F)
     where
         F: Fn(&gtk::gio::Settings, &str) + 'static,
@@ -169,4 +657,9 @@ impl GameOfLifeSettings {
     {
         self.inner.bind(key, object, property).build();
     }
+
+    /// A stateful action changing `key`, its state following the setting
+    pub fn create_action(&self, key: &str) -> gtk::gio::Action {
+        self.inner.create_action(key)
+    }
 }
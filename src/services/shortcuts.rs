@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+/// The actions whose keyboard shortcut can be rebound in the preferences
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shortcut {
+    Play,
+    SkipForwardOne,
+    StepBack,
+    ToggleDesignMode,
+    ToggleBrush,
+    ToggleSelection,
+    Cut,
+    Copy,
+    Paste,
+    Clear,
+    RandomSeed,
+    Undo,
+    Redo,
+    ZoomIn,
+    ZoomOut,
+    ZoomReset,
+    ZoomActualSize,
+    ZoomSelection,
+    InvertColors,
+    Fullscreen,
+    Presentation,
+    ToggleRecording,
+    New,
+    NewEmpty,
+    NewTab,
+    CloseTab,
+    PatternLibrary,
+    Snapshot,
+    OpenSnapshot,
+    SaveProject,
+    OpenProject,
+    Preferences,
+    Quit,
+}
+
+impl Shortcut {
+    pub const ALL: [Shortcut; 33] = [
+        Shortcut::Play,
+        Shortcut::SkipForwardOne,
+        Shortcut::StepBack,
+        Shortcut::ToggleDesignMode,
+        Shortcut::ToggleBrush,
+        Shortcut::ToggleSelection,
+        Shortcut::Cut,
+        Shortcut::Copy,
+        Shortcut::Paste,
+        Shortcut::Clear,
+        Shortcut::RandomSeed,
+        Shortcut::Undo,
+        Shortcut::Redo,
+        Shortcut::ZoomIn,
+        Shortcut::ZoomOut,
+        Shortcut::ZoomReset,
+        Shortcut::ZoomActualSize,
+        Shortcut::ZoomSelection,
+        Shortcut::InvertColors,
+        Shortcut::Fullscreen,
+        Shortcut::Presentation,
+        Shortcut::ToggleRecording,
+        Shortcut::New,
+        Shortcut::NewEmpty,
+        Shortcut::NewTab,
+        Shortcut::CloseTab,
+        Shortcut::PatternLibrary,
+        Shortcut::Snapshot,
+        Shortcut::OpenSnapshot,
+        Shortcut::SaveProject,
+        Shortcut::OpenProject,
+        Shortcut::Preferences,
+        Shortcut::Quit,
+    ];
+
+    /// The detailed name of the action triggered by the shortcut
+    pub fn action_name(&self) -> &'static str {
+        match self {
+            Shortcut::Play => "win.play",
+            Shortcut::SkipForwardOne => "win.skip-forward-one",
+            Shortcut::StepBack => "win.step-back",
+            Shortcut::ToggleDesignMode => "win.toggle-design-mode",
+            Shortcut::ToggleBrush => "win.toggle-instrument-brush",
+            Shortcut::ToggleSelection => "win.toggle-instrument-selection",
+            Shortcut::Cut => "win.cut",
+            Shortcut::Copy => "win.copy",
+            Shortcut::Paste => "win.paste",
+            Shortcut::Clear => "win.clear",
+            Shortcut::RandomSeed => "win.random-seed",
+            Shortcut::Undo => "win.undo",
+            Shortcut::Redo => "win.redo",
+            Shortcut::ZoomIn => "win.zoom-in",
+            Shortcut::ZoomOut => "win.zoom-out",
+            Shortcut::ZoomReset => "win.zoom-reset",
+            Shortcut::ZoomActualSize => "win.zoom-actual-size",
+            Shortcut::ZoomSelection => "win.zoom-selection",
+            Shortcut::InvertColors => "win.invert-colors",
+            Shortcut::Fullscreen => "win.toggle-fullscreen",
+            Shortcut::Presentation => "win.toggle-presentation",
+            Shortcut::ToggleRecording => "win.toggle-recording",
+            Shortcut::New => "win.new",
+            Shortcut::NewEmpty => "win.new-empty",
+            Shortcut::NewTab => "win.new-tab",
+            Shortcut::CloseTab => "win.close-tab",
+            Shortcut::PatternLibrary => "win.pattern-library",
+            Shortcut::Snapshot => "win.snapshot",
+            Shortcut::OpenSnapshot => "win.open-snapshot",
+            Shortcut::SaveProject => "win.save-project",
+            Shortcut::OpenProject => "win.open-project",
+            Shortcut::Preferences => "app.preferences",
+            Shortcut::Quit => "app.quit",
+        }
+    }
+
+    /// The accelerator bound to the action out of the box, empty if none is
+    pub fn default_accelerator(&self) -> &'static str {
+        match self {
+            Shortcut::Play => "space",
+            Shortcut::SkipForwardOne => "s",
+            Shortcut::StepBack => "comma",
+            Shortcut::ToggleDesignMode => "d",
+            Shortcut::ToggleBrush => "",
+            Shortcut::ToggleSelection => "",
+            Shortcut::Cut => "<Control>x",
+            Shortcut::Copy => "<Control>c",
+            Shortcut::Paste => "<Control>v",
+            Shortcut::Clear => "<Control>k",
+            Shortcut::RandomSeed => "<Control>r",
+            Shortcut::Undo => "<Control>z",
+            Shortcut::Redo => "<Control><Shift>z",
+            Shortcut::ZoomIn => "plus",
+            Shortcut::ZoomOut => "minus",
+            Shortcut::ZoomReset => "0",
+            Shortcut::ZoomActualSize => "1",
+            Shortcut::ZoomSelection => "3",
+            Shortcut::InvertColors => "<Control>i",
+            Shortcut::Fullscreen => "F11",
+            Shortcut::Presentation => "F5",
+            Shortcut::ToggleRecording => "<Control><Shift>r",
+            Shortcut::New => "<Control>n",
+            Shortcut::NewEmpty => "<Control>e",
+            Shortcut::NewTab => "<Control>t",
+            Shortcut::CloseTab => "<Control>w",
+            Shortcut::PatternLibrary => "<Control>l",
+            Shortcut::Snapshot => "<Control>s",
+            Shortcut::OpenSnapshot => "<Control>o",
+            Shortcut::SaveProject => "<Control><Shift>s",
+            Shortcut::OpenProject => "<Control><Shift>o",
+            Shortcut::Preferences => "<Control>comma",
+            Shortcut::Quit => "<Primary>q",
+        }
+    }
+
+    pub fn from_action_name(action_name: &str) -> Option<Shortcut> {
+        Shortcut::ALL
+            .into_iter()
+            .find(|shortcut| shortcut.action_name() == action_name)
+    }
+}
+
+/// The accelerators bound to every `Shortcut`, the defaults overridden by
+/// the ones chosen by the user. An empty accelerator leaves the action unbound.
+#[derive(Debug, Clone, Default)]
+pub struct ShortcutBindings {
+    overrides: HashMap<Shortcut, String>,
+}
+
+impl ShortcutBindings {
+    pub fn new(overrides: HashMap<Shortcut, String>) -> Self {
+        Self { overrides }
+    }
+
+    pub fn overrides(&self) -> &HashMap<Shortcut, String> {
+        &self.overrides
+    }
+
+    pub fn accelerator(&self, shortcut: Shortcut) -> &str {
+        self.overrides
+            .get(&shortcut)
+            .map(String::as_str)
+            .unwrap_or_else(|| shortcut.default_accelerator())
+    }
+
+    /// Binds `accelerator` to `shortcut`, forgetting the override if it
+    /// matches the default
+    pub fn set_accelerator(&mut self, shortcut: Shortcut, accelerator: &str) {
+        if same_accelerator(accelerator, shortcut.default_accelerator()) {
+            self.overrides.remove(&shortcut);
+        } else {
+            self.overrides.insert(shortcut, accelerator.to_owned());
+        }
+    }
+
+    /// The other shortcut already bound to `accelerator`, if any
+    pub fn conflict(&self, shortcut: Shortcut, accelerator: &str) -> Option<Shortcut> {
+        if accelerator.is_empty() {
+            return None;
+        }
+
+        Shortcut::ALL.into_iter().find(|other| {
+            *other != shortcut && same_accelerator(self.accelerator(*other), accelerator)
+        })
+    }
+}
+
+/// Whether two accelerators describe the same key combination, regardless of
+/// how their modifiers are spelled
+fn same_accelerator(first: &str, second: &str) -> bool {
+    match (
+        gtk::accelerator_parse(first),
+        gtk::accelerator_parse(second),
+    ) {
+        (Some(first), Some(second)) => first == second,
+        _ => first == second,
+    }
+}
@@ -0,0 +1,926 @@
+use super::{GameOfLifeSettings, SimulationPriority, StatisticsLog};
+use crate::models::{ObjectCensus, SparseUniverse, StabilizationDetector, Universe};
+use gtk::glib::{self, clone::Downgrade, Receiver, Sender};
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// How long a running simulation may go without a new generation, on top of the
+/// wait between generations, before it is reported as stalled
+const WATCHDOG_STALL_TIMEOUT: Duration = Duration::from_secs(5);
+const WATCHDOG_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The least time between two generations delivered in turbo mode, the
+/// generations computed meanwhile are skipped
+const TURBO_FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 30);
+
+/// The most generations per second run, and the least time between two
+/// generations delivered in turbo mode, while the system saves power
+const POWER_SAVING_MAX_SPEED: u32 = 10;
+const POWER_SAVING_FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 10);
+
+/// How long generations are counted for to measure the rate of the simulation
+const RATE_MEASURE_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Hands out the epochs identifying jobs, unique across workers so that ticks
+/// of an abandoned worker are never mistaken for those of its replacement
+static NEXT_EPOCH: AtomicU64 = AtomicU64::new(1);
+
+/// How often a fast forward reports the generation it reached
+const FAST_FORWARD_PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The universe a simulation evolves
+#[derive(Debug, Clone)]
+pub enum SimulatedUniverse {
+    Bounded(Universe),
+    Unbounded(SparseUniverse),
+}
+
+impl SimulatedUniverse {
+    fn tick(&mut self) {
+        match self {
+            Self::Bounded(universe) => {
+                universe.tick();
+            }
+            Self::Unbounded(universe) => universe.tick(),
+        }
+    }
+
+    fn population(&self) -> usize {
+        match self {
+            Self::Bounded(universe) => universe.alive_cells_count(),
+            Self::Unbounded(universe) => universe.alive_cells_count(),
+        }
+    }
+
+    fn state_hash(&self) -> u64 {
+        match self {
+            Self::Bounded(universe) => universe.state_hash(),
+            Self::Unbounded(universe) => universe.state_hash(),
+        }
+    }
+
+    fn generations(&self) -> u64 {
+        match self {
+            Self::Bounded(universe) => universe.generations(),
+            Self::Unbounded(universe) => universe.generations(),
+        }
+    }
+
+    /// Takes the census of the objects in the universe
+    pub fn census(&self) -> ObjectCensus {
+        match self {
+            Self::Bounded(universe) => ObjectCensus::take(&universe.snapshot()),
+            Self::Unbounded(universe) => universe
+                .living_snapshot()
+                .map(|snapshot| ObjectCensus::take(&snapshot))
+                .unwrap_or_default(),
+        }
+    }
+
+    fn stats(&self) -> SimulationStats {
+        let (births, deaths) = match self {
+            Self::Bounded(universe) => universe.last_changes(),
+            Self::Unbounded(universe) => universe.last_changes(),
+        };
+        let bounds = match self {
+            Self::Bounded(universe) => universe
+                .bounding_box()
+                .map(|(top, left, rows, columns)| (top as i64, left as i64, rows, columns)),
+            Self::Unbounded(universe) => universe.bounding_box(),
+        };
+        SimulationStats {
+            generation: self.generations(),
+            population: self.population(),
+            births,
+            deaths,
+            bounds,
+        }
+    }
+}
+
+/// What changed in a generation computed by the simulation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationStats {
+    generation: u64,
+    population: usize,
+    births: usize,
+    deaths: usize,
+    bounds: Option<(i64, i64, usize, usize)>,
+}
+
+impl SimulationStats {
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// The number of cells alive in this generation
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    /// The number of cells that came to life in this generation
+    pub fn births(&self) -> usize {
+        self.births
+    }
+
+    /// The number of cells that died in this generation
+    pub fn deaths(&self) -> usize {
+        self.deaths
+    }
+
+    /// The smallest `(top, left, rows, columns)` area containing the living
+    /// cells in this generation, or `None` if there are none
+    pub fn bounds(&self) -> Option<(i64, i64, usize, usize)> {
+        self.bounds
+    }
+}
+
+/// Why a running simulation halted by itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimulationHaltReason {
+    /// Every cell died, after the given number of generations
+    Extinct(u64),
+
+    /// The universe keeps repeating itself with the given period
+    Stabilized(usize),
+}
+
+impl SimulationHaltReason {
+    /// Records a new generation of `universe` in `detector`, telling whether
+    /// the universe settled down
+    fn observe(detector: &mut StabilizationDetector, universe: &SimulatedUniverse) -> Option<Self> {
+        if universe.population() == 0 {
+            return Some(Self::Extinct(universe.generations()));
+        }
+
+        match detector.observe(universe.state_hash()) {
+            true => detector.period().map(Self::Stabilized),
+            false => None,
+        }
+    }
+}
+
+/// How far a fast forward runs a universe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FastForward {
+    /// For the given number of generations
+    For(u64),
+
+    /// Until the given generation
+    Until(u64),
+}
+
+impl FastForward {
+    /// The generation reached by fast forwarding from `generation`
+    pub fn target(&self, generation: u64) -> u64 {
+        match self {
+            Self::For(generations) => generation.saturating_add(*generations),
+            Self::Until(target) => *target,
+        }
+    }
+}
+
+/// What a simulation tells the views observing it
+#[derive(Debug, Clone)]
+pub enum SimulationEvent {
+    /// A new generation was computed, shared by every listener as it is
+    /// never changed once published
+    Generation(Arc<SimulatedUniverse>),
+
+    /// What changed in the new generation, sent right before it
+    Stats(SimulationStats),
+
+    /// The simulation started or stopped
+    RunningChanged,
+
+    /// The simulation stopped, or resumed, delivering new generations
+    StalledChanged,
+
+    /// The simulation halted by itself, as the universe settled down
+    Halted(SimulationHaltReason),
+
+    /// A fast forward reached the given generation, without delivering the
+    /// generations before it
+    Progress(u64),
+
+    /// The number of generations computed per second was measured again
+    RateChanged,
+}
+
+/// Identifies a listener connected to a `SimulationController`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimulationListenerId(usize);
+
+/// What the worker hands back to the main thread
+enum SimulationTick {
+    Generation(Arc<SimulatedUniverse>, SimulationStats),
+    Settled(SimulationHaltReason),
+    Progress(u64),
+
+    /// A fast forward reached its target generation
+    Reached,
+}
+
+/// What the worker is asked to compute
+enum SimulationJob {
+    /// One generation every `wait`, or as fast as possible in turbo mode
+    /// delivering one every so often, until cancelled
+    Run {
+        universe: SimulatedUniverse,
+        wait: Duration,
+
+        /// The least time between two generations delivered in turbo mode
+        turbo: Option<Duration>,
+    },
+
+    /// As fast as possible until `target` generation, only delivering the
+    /// generation it stops at
+    RunUntil {
+        universe: SimulatedUniverse,
+        target: u64,
+    },
+}
+
+struct SimulationWorkerState {
+    /// The job waiting to be picked up by the worker
+    job: Option<(u64, SimulationJob, Option<StabilizationDetector>)>,
+}
+
+/// What the worker thread shares with the controller
+struct SimulationWorkerShared {
+    state: Mutex<SimulationWorkerState>,
+
+    /// Wakes the worker up when a job is submitted, cancelled, or the worker
+    /// shut down
+    wakeup: Condvar,
+
+    /// Identifies the current job, the worker abandons a job as soon as it
+    /// is not the current one anymore
+    epoch: AtomicU64,
+    shutdown: AtomicBool,
+}
+
+impl SimulationWorkerShared {
+    fn lock(&self) -> MutexGuard<'_, SimulationWorkerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn is_cancelled(&self, epoch: u64) -> bool {
+        self.shutdown.load(Ordering::SeqCst) || self.epoch.load(Ordering::SeqCst) != epoch
+    }
+
+    /// Waits until `deadline`, returning `false` if the job of `epoch` is
+    /// cancelled meanwhile
+    fn wait_until(&self, epoch: u64, deadline: Instant) -> bool {
+        let mut state = self.lock();
+        loop {
+            if self.is_cancelled(epoch) {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            state = self
+                .wakeup
+                .wait_timeout(state, deadline - now)
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .0;
+        }
+    }
+}
+
+/// A thread computing the jobs of a controller one after the other. Halting
+/// a simulation cancels its job, the worker then waits for the next one.
+struct SimulationWorker {
+    shared: Arc<SimulationWorkerShared>,
+
+    /// The priority the thread runs with, it can not be raised back once lowered
+    priority: SimulationPriority,
+}
+
+impl SimulationWorker {
+    fn spawn(sender: Sender<(u64, SimulationTick)>, priority: SimulationPriority) -> Self {
+        let shared = Arc::new(SimulationWorkerShared {
+            state: Mutex::new(SimulationWorkerState { job: None }),
+            wakeup: Condvar::new(),
+            epoch: AtomicU64::default(),
+            shutdown: AtomicBool::default(),
+        });
+
+        let worker_shared = shared.clone();
+        std::thread::spawn(move || {
+            priority.apply_to_current_thread();
+            Self::work(&worker_shared, &sender);
+        });
+
+        Self { shared, priority }
+    }
+
+    fn work(shared: &SimulationWorkerShared, sender: &Sender<(u64, SimulationTick)>) {
+        loop {
+            let (epoch, job, detector) = {
+                let mut state = shared.lock();
+                loop {
+                    if shared.shutdown.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    if let Some(job) = state.job.take() {
+                        break job;
+                    }
+                    state = shared
+                        .wakeup
+                        .wait(state)
+                        .unwrap_or_else(|poisoned| poisoned.into_inner());
+                }
+            };
+
+            let send = |tick| sender.send((epoch, tick)).is_ok();
+            let delivered = match job {
+                SimulationJob::Run {
+                    universe,
+                    wait,
+                    turbo,
+                } => Self::run(shared, epoch, universe, wait, turbo, detector, send),
+                SimulationJob::RunUntil { universe, target } => {
+                    Self::run_until(shared, epoch, universe, target, detector, send)
+                }
+            };
+
+            // The main context is gone along with the controller
+            if !delivered {
+                return;
+            }
+        }
+    }
+
+    /// Returns `false` once the main thread stopped listening
+    fn run(
+        shared: &SimulationWorkerShared,
+        epoch: u64,
+        mut universe: SimulatedUniverse,
+        wait: Duration,
+        turbo: Option<Duration>,
+        mut detector: Option<StabilizationDetector>,
+        send: impl Fn(SimulationTick) -> bool,
+    ) -> bool {
+        // Generations are paced from when the previous one was due, rather than
+        // from when it was done, so that computing them does not slow the pace
+        let mut due_at = Instant::now() + wait;
+        let mut delivered_at = Instant::now();
+        loop {
+            if turbo.is_none() && !shared.wait_until(epoch, due_at) {
+                return true;
+            }
+            universe.tick();
+            due_at = (due_at + wait).max(Instant::now());
+            if shared.is_cancelled(epoch) {
+                return true;
+            }
+
+            let halt_reason = detector
+                .as_mut()
+                .and_then(|detector| SimulationHaltReason::observe(detector, &universe));
+
+            // In turbo mode generations follow each other without waiting,
+            // only some of them being delivered
+            if let Some(frame_interval) = turbo {
+                if halt_reason.is_none() && delivered_at.elapsed() < frame_interval {
+                    continue;
+                }
+            }
+            delivered_at = Instant::now();
+
+            if !send(SimulationTick::Generation(
+                Arc::new(universe.clone()),
+                universe.stats(),
+            )) {
+                return false;
+            }
+            if let Some(halt_reason) = halt_reason {
+                return send(SimulationTick::Settled(halt_reason));
+            }
+        }
+    }
+
+    /// Returns `false` once the main thread stopped listening
+    fn run_until(
+        shared: &SimulationWorkerShared,
+        epoch: u64,
+        mut universe: SimulatedUniverse,
+        target: u64,
+        mut detector: Option<StabilizationDetector>,
+        send: impl Fn(SimulationTick) -> bool,
+    ) -> bool {
+        let mut reported_at = Instant::now();
+        let outcome = loop {
+            if universe.generations() >= target {
+                break Some(SimulationTick::Reached);
+            }
+            if shared.is_cancelled(epoch) {
+                break None;
+            }
+
+            universe.tick();
+            if let Some(halt_reason) = detector
+                .as_mut()
+                .and_then(|detector| SimulationHaltReason::observe(detector, &universe))
+            {
+                break Some(SimulationTick::Settled(halt_reason));
+            }
+
+            if reported_at.elapsed() >= FAST_FORWARD_PROGRESS_INTERVAL {
+                reported_at = Instant::now();
+                if !send(SimulationTick::Progress(universe.generations())) {
+                    return false;
+                }
+            }
+        };
+
+        // Halting midway still delivers the generation reached
+        let stats = universe.stats();
+        send(SimulationTick::Generation(Arc::new(universe), stats))
+            && match outcome {
+                Some(outcome) => send(outcome),
+                None => true,
+            }
+    }
+
+    /// Hands `job` to the worker, cancelling the current one. Returns the epoch
+    /// identifying the job.
+    fn submit(&self, job: SimulationJob, detector: Option<StabilizationDetector>) -> u64 {
+        let mut state = self.shared.lock();
+        let epoch = NEXT_EPOCH.fetch_add(1, Ordering::SeqCst);
+        self.shared.epoch.store(epoch, Ordering::SeqCst);
+        state.job = Some((epoch, job, detector));
+        self.shared.wakeup.notify_all();
+        epoch
+    }
+
+    /// Cancels the current job, the worker stops computing it as soon as it is
+    /// done with the generation at hand
+    fn cancel(&self) {
+        let mut state = self.shared.lock();
+        self.shared
+            .epoch
+            .store(NEXT_EPOCH.fetch_add(1, Ordering::SeqCst), Ordering::SeqCst);
+        state.job = None;
+        self.shared.wakeup.notify_all();
+    }
+
+    /// The epoch of the current job, ticks of other jobs are stale
+    fn epoch(&self) -> u64 {
+        self.shared.epoch.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for SimulationWorker {
+    fn drop(&mut self) {
+        let _state = self.shared.lock();
+        self.shared.shutdown.store(true, Ordering::SeqCst);
+        self.shared.wakeup.notify_all();
+    }
+}
+
+type SimulationListener = Rc<dyn Fn(&SimulationEvent)>;
+
+struct SimulationInner {
+    settings: GameOfLifeSettings,
+    sender: Sender<(u64, SimulationTick)>,
+
+    /// Computes the generations, spawned on the first run
+    worker: RefCell<Option<SimulationWorker>>,
+    running: Cell<bool>,
+
+    /// When the last generation computed by the worker came in
+    last_tick_at: Cell<Option<Instant>>,
+    watchdog: RefCell<Option<glib::SourceId>>,
+    stalled: Cell<bool>,
+    evolution_speed: Cell<u32>,
+    halts_when_settled: Cell<bool>,
+
+    /// Whether generations are computed as fast as possible, only some of them
+    /// being delivered
+    turbo: Cell<bool>,
+
+    /// Whether the system asks to save power, slowing running simulations down
+    power_saving: Cell<bool>,
+
+    /// When the generations of the running simulation started being counted,
+    /// and the generation it was at
+    rate_measure: Cell<Option<(Instant, u64)>>,
+    generations_per_second: Cell<Option<f64>>,
+
+    /// Why the simulation last halted by itself, until it is reported
+    halt_reason: Cell<Option<SimulationHaltReason>>,
+
+    /// The generations the ongoing fast forward started from and runs until
+    fast_forward: Cell<Option<(u64, u64)>>,
+
+    /// The last generation reported by the ongoing fast forward
+    fast_forward_generation: Cell<u64>,
+
+    /// The statistics of the generations delivered so far
+    stats_log: RefCell<StatisticsLog>,
+
+    listeners: RefCell<Vec<(SimulationListenerId, SimulationListener)>>,
+    next_listener_id: Cell<usize>,
+}
+
+/// Runs a universe on a worker thread, one generation after the other,
+/// and hands every generation to the views observing it on the main thread.
+/// Clones share the same simulation.
+#[derive(Clone)]
+pub struct SimulationController {
+    inner: Rc<SimulationInner>,
+}
+
+impl Default for SimulationController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SimulationController {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SimulationController")
+            .field("running", &self.is_running())
+            .field("stalled", &self.is_stalled())
+            .field("evolution_speed", &self.evolution_speed())
+            .finish()
+    }
+}
+
+impl SimulationController {
+    pub fn new() -> Self {
+        let (sender, receiver): (_, Receiver<(u64, SimulationTick)>) =
+            glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let controller = Self {
+            inner: Rc::new(SimulationInner {
+                settings: GameOfLifeSettings::default(),
+                sender,
+                worker: RefCell::default(),
+                running: Cell::default(),
+                last_tick_at: Cell::default(),
+                watchdog: RefCell::default(),
+                stalled: Cell::default(),
+                evolution_speed: Cell::new(1),
+                halts_when_settled: Cell::default(),
+                turbo: Cell::default(),
+                power_saving: Cell::default(),
+                rate_measure: Cell::default(),
+                generations_per_second: Cell::default(),
+                halt_reason: Cell::default(),
+                fast_forward: Cell::default(),
+                fast_forward_generation: Cell::default(),
+                stats_log: RefCell::default(),
+                listeners: RefCell::default(),
+                next_listener_id: Cell::default(),
+            }),
+        };
+
+        let inner = controller.inner.downgrade();
+        receiver.attach(None, move |(epoch, tick)| match Weak::upgrade(&inner) {
+            Some(inner) => {
+                Self { inner }.process_tick(epoch, tick);
+                glib::Continue(true)
+            }
+            None => glib::Continue(false),
+        });
+
+        controller
+    }
+
+    /// Calls `f` with every event of the simulation, until disconnected
+    pub fn connect_event<F: Fn(&SimulationEvent) + 'static>(&self, f: F) -> SimulationListenerId {
+        let id = SimulationListenerId(self.inner.next_listener_id.get());
+        self.inner.next_listener_id.set(id.0 + 1);
+        self.inner.listeners.borrow_mut().push((id, Rc::new(f)));
+        id
+    }
+
+    pub fn disconnect(&self, id: SimulationListenerId) {
+        self.inner
+            .listeners
+            .borrow_mut()
+            .retain(|(listener_id, _)| *listener_id != id);
+    }
+
+    fn emit(&self, event: SimulationEvent) {
+        // Listeners may connect others, or drive the simulation, while called
+        let listeners: Vec<SimulationListener> = self
+            .inner
+            .listeners
+            .borrow()
+            .iter()
+            .map(|(_, listener)| listener.clone())
+            .collect();
+        for listener in listeners {
+            listener(&event);
+        }
+    }
+
+    fn process_tick(&self, epoch: u64, tick: SimulationTick) {
+        // Generations computed before halting are still shown, anything else
+        // about a cancelled job is stale
+        let current = self
+            .inner
+            .worker
+            .borrow()
+            .as_ref()
+            .is_some_and(|worker| worker.epoch() == epoch);
+        match tick {
+            SimulationTick::Generation(universe, stats) => {
+                self.mark_tick();
+                self.measure_rate(stats.generation());
+                self.record_stats(stats);
+                self.emit(SimulationEvent::Generation(universe));
+            }
+            SimulationTick::Settled(reason) => {
+                if current && self.is_running() {
+                    self.inner.halt_reason.set(Some(reason));
+                    self.emit(SimulationEvent::Halted(reason));
+                    self.halt();
+                }
+            }
+            SimulationTick::Progress(generation) => {
+                self.mark_tick();
+                if current && self.inner.fast_forward.get().is_some() {
+                    self.inner.fast_forward_generation.set(generation);
+                    self.emit(SimulationEvent::Progress(generation));
+                }
+            }
+            SimulationTick::Reached => {
+                if current && self.is_running() {
+                    self.halt();
+                }
+            }
+        }
+    }
+
+    /// Logs the statistics of a new generation and hands them to listeners
+    fn record_stats(&self, stats: SimulationStats) {
+        self.inner.stats_log.borrow_mut().push(stats);
+        self.emit(SimulationEvent::Stats(stats));
+    }
+
+    /// The statistics of the generations delivered so far
+    pub fn stats_log(&self) -> StatisticsLog {
+        self.inner.stats_log.borrow().clone()
+    }
+
+    /// Updates the rate of the running simulation with a generation it computed
+    fn measure_rate(&self, generation: u64) {
+        if !self.is_running() {
+            return;
+        }
+
+        match self.inner.rate_measure.get() {
+            Some((started_at, first)) if started_at.elapsed() >= RATE_MEASURE_INTERVAL => {
+                let rate =
+                    generation.saturating_sub(first) as f64 / started_at.elapsed().as_secs_f64();
+                self.inner.generations_per_second.set(Some(rate));
+                self.inner
+                    .rate_measure
+                    .set(Some((Instant::now(), generation)));
+                self.emit(SimulationEvent::RateChanged);
+            }
+            Some(_) => (),
+            None => self
+                .inner
+                .rate_measure
+                .set(Some((Instant::now(), generation))),
+        }
+    }
+
+    /// The number of generations the running simulation computed per second
+    /// lately, once measured
+    pub fn generations_per_second(&self) -> Option<f64> {
+        self.inner.generations_per_second.get()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.inner.running.get()
+    }
+
+    /// Hands `job` to the worker, spawning it first if there is none yet or
+    /// if it does not run with the preferred priority anymore
+    fn submit(&self, job: SimulationJob) {
+        let priority = self.inner.settings.simulation_priority();
+        let detector = self
+            .halts_when_settled()
+            .then(|| StabilizationDetector::new(1));
+
+        let mut worker = self.inner.worker.borrow_mut();
+        if !worker
+            .as_ref()
+            .is_some_and(|worker| worker.priority == priority)
+        {
+            worker.replace(SimulationWorker::spawn(self.inner.sender.clone(), priority));
+        }
+        if let Some(worker) = worker.as_ref() {
+            worker.submit(job, detector);
+        }
+    }
+
+    /// Evolves `universe` on the worker thread until halted
+    pub fn run(&self, universe: SimulatedUniverse) {
+        self.inner.halt_reason.set(None);
+        self.inner.fast_forward.set(None);
+        self.inner.rate_measure.set(None);
+        self.inner.generations_per_second.set(None);
+
+        let frame_interval = match self.is_power_saving() {
+            true => POWER_SAVING_FRAME_INTERVAL,
+            false => TURBO_FRAME_INTERVAL,
+        };
+        self.submit(SimulationJob::Run {
+            universe,
+            wait: self.generation_interval(),
+            turbo: self.is_turbo().then_some(frame_interval),
+        });
+        self.inner.running.set(true);
+
+        self.start_watchdog();
+        self.emit(SimulationEvent::RunningChanged);
+    }
+
+    /// Evolves `universe` on the worker thread as fast as possible until
+    /// `target` generation, only delivering the generation it stops at.
+    /// Halting it midway delivers the generation reached.
+    pub fn run_until(&self, universe: SimulatedUniverse, target: u64) {
+        self.inner.halt_reason.set(None);
+        self.inner.rate_measure.set(None);
+        self.inner.generations_per_second.set(None);
+        self.inner
+            .fast_forward
+            .set(Some((universe.generations(), target)));
+        self.inner
+            .fast_forward_generation
+            .set(universe.generations());
+
+        self.submit(SimulationJob::RunUntil { universe, target });
+        self.inner.running.set(true);
+
+        self.start_watchdog();
+        self.emit(SimulationEvent::RunningChanged);
+    }
+
+    /// Abandons a worker stuck computing a generation and runs `universe` on
+    /// a new one. The abandoned worker exits as soon as it resumes.
+    pub fn restart(&self, universe: SimulatedUniverse) {
+        drop(self.inner.worker.take());
+        self.run(universe);
+    }
+
+    /// The generations the ongoing fast forward started from and runs until,
+    /// if one is ongoing
+    pub fn fast_forward(&self) -> Option<(u64, u64)> {
+        self.inner.fast_forward.get()
+    }
+
+    /// How far the ongoing fast forward got, from 0 to 1, if one is ongoing
+    pub fn fast_forward_progress(&self) -> Option<f64> {
+        let (from, target) = self.inner.fast_forward.get()?;
+        let done = self
+            .inner
+            .fast_forward_generation
+            .get()
+            .saturating_sub(from);
+        Some(match target.saturating_sub(from) {
+            0 => 1.0,
+            total => done as f64 / total as f64,
+        })
+    }
+
+    /// Cancels the job of the worker. Generations it already computed may
+    /// still come in afterwards.
+    pub fn halt(&self) {
+        if let Some(worker) = self.inner.worker.borrow().as_ref() {
+            worker.cancel();
+        }
+        self.inner.running.set(false);
+        self.inner.fast_forward.set(None);
+        self.inner.rate_measure.set(None);
+        self.inner.generations_per_second.set(None);
+        self.set_stalled(false);
+        self.emit(SimulationEvent::RunningChanged);
+    }
+
+    /// Computes the generation following `universe` right away, on the main thread
+    pub fn step(&self, universe: SimulatedUniverse) {
+        let mut universe = universe;
+        universe.tick();
+        self.record_stats(universe.stats());
+        self.emit(SimulationEvent::Generation(Arc::new(universe)));
+    }
+
+    /// Whether the running simulation stopped delivering new generations
+    pub fn is_stalled(&self) -> bool {
+        self.inner.stalled.get()
+    }
+
+    fn set_stalled(&self, value: bool) {
+        if self.inner.stalled.replace(value) != value {
+            self.emit(SimulationEvent::StalledChanged);
+        }
+    }
+
+    fn mark_tick(&self) {
+        self.inner.last_tick_at.set(Some(Instant::now()));
+        self.set_stalled(false);
+    }
+
+    /// Periodically checks that the worker keeps delivering generations,
+    /// until the simulation is halted
+    fn start_watchdog(&self) {
+        let inner = &self.inner;
+        inner.last_tick_at.set(Some(Instant::now()));
+        if inner.watchdog.borrow().is_some() {
+            return;
+        }
+
+        let weak_inner = inner.downgrade();
+        let source_id = glib::timeout_add_local(WATCHDOG_INTERVAL, move || {
+            let this = match Weak::upgrade(&weak_inner) {
+                Some(inner) => Self { inner },
+                None => return glib::Continue(false),
+            };
+            if !this.is_running() {
+                this.inner.watchdog.take();
+                return glib::Continue(false);
+            }
+
+            let limit = WATCHDOG_STALL_TIMEOUT + this.generation_interval();
+            if this
+                .inner
+                .last_tick_at
+                .get()
+                .is_some_and(|last_tick_at| last_tick_at.elapsed() > limit)
+            {
+                this.set_stalled(true);
+            }
+            glib::Continue(true)
+        });
+        inner.watchdog.replace(Some(source_id));
+    }
+
+    /// Whether running simulations halt by themselves once every cell died or
+    /// the universe keeps repeating itself
+    pub fn halts_when_settled(&self) -> bool {
+        self.inner.halts_when_settled.get()
+    }
+
+    pub fn set_halts_when_settled(&self, value: bool) {
+        self.inner.halts_when_settled.set(value);
+    }
+
+    /// Takes the reason why the simulation last halted by itself, if it did
+    pub fn take_halt_reason(&self) -> Option<SimulationHaltReason> {
+        self.inner.halt_reason.take()
+    }
+
+    /// Whether running simulations compute generations as fast as possible,
+    /// delivering only as many as can be shown
+    pub fn is_turbo(&self) -> bool {
+        self.inner.turbo.get()
+    }
+
+    pub fn set_turbo(&self, value: bool) {
+        self.inner.turbo.set(value);
+    }
+
+    /// Whether running simulations are slowed down to save power
+    pub fn is_power_saving(&self) -> bool {
+        self.inner.power_saving.get()
+    }
+
+    pub fn set_power_saving(&self, value: bool) {
+        self.inner.power_saving.set(value);
+    }
+
+    /// The time between two generations of a running simulation, not in turbo
+    /// mode
+    fn generation_interval(&self) -> Duration {
+        let mut speed = self.evolution_speed().max(1);
+        if self.is_power_saving() {
+            speed = speed.min(POWER_SAVING_MAX_SPEED);
+        }
+        Duration::from_millis(1000 / u64::from(speed))
+    }
+
+    /// The number of generations computed per second
+    pub fn evolution_speed(&self) -> u32 {
+        self.inner.evolution_speed.get()
+    }
+
+    pub fn set_evolution_speed(&self, value: u32) {
+        self.inner.evolution_speed.set(value);
+    }
+}
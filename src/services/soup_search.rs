@@ -0,0 +1,242 @@
+use crate::models::{
+    CensusObject, ObjectCensus, StabilizationDetector, Universe, UniverseRule, UniverseSoup,
+};
+use gtk::glib;
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+/// A batch of random soups, each evolved under the same rule for at most the
+/// same number of generations. Soup `n` is drawn from seed `first_seed + n`,
+/// so that any of them can be drawn again.
+#[derive(Clone, Debug)]
+pub struct SoupSearch {
+    soups: usize,
+    generations: u32,
+    universe_size: (usize, usize),
+    rule: UniverseRule,
+    soup: UniverseSoup,
+    first_seed: u32,
+}
+
+impl SoupSearch {
+    /// Creates a search through `soups` soups drawn like `soup`, whose own
+    /// seed is ignored
+    pub fn new(
+        soups: usize,
+        generations: u32,
+        universe_size: (usize, usize),
+        rule: UniverseRule,
+        soup: UniverseSoup,
+        first_seed: u32,
+    ) -> Self {
+        Self {
+            soups,
+            generations,
+            universe_size,
+            rule,
+            soup,
+            first_seed,
+        }
+    }
+
+    pub fn soups(&self) -> usize {
+        self.soups
+    }
+
+    /// The seed soup `index` is drawn from
+    pub fn seed(&self, index: usize) -> u32 {
+        self.first_seed.wrapping_add(index as u32)
+    }
+
+    /// Evolves soup `index` until it settles down or runs for the given number
+    /// of generations. Returns `None` if `cancelled` got set meanwhile.
+    fn run_soup(&self, index: usize, cancelled: &AtomicBool) -> Option<SoupSearchResult> {
+        let seed = self.seed(index);
+        let soup = UniverseSoup::new(self.soup.density(), Some(seed as u64))
+            .with_symmetry(self.soup.symmetry());
+        let (rows, columns) = self.universe_size;
+        let mut universe = Universe::new_soup(rows, columns, &soup);
+        universe.set_rule(self.rule.clone());
+
+        let mut detector = StabilizationDetector::new(1);
+        let mut settled = None;
+        for _ in 0..self.generations {
+            if cancelled.load(Ordering::Relaxed) {
+                return None;
+            }
+
+            universe.tick();
+            if detector.observe(universe.state_hash()) {
+                settled = Some(detector.period().unwrap_or(1));
+                break;
+            }
+        }
+
+        Some(SoupSearchResult {
+            seed,
+            population: universe.alive_cells_count(),
+            generations: universe.generations(),
+            settled,
+            census: ObjectCensus::take(&universe.snapshot()),
+        })
+    }
+}
+
+/// How a soup of a search ended up
+#[derive(Clone, Debug)]
+pub struct SoupSearchResult {
+    seed: u32,
+    population: usize,
+    generations: u64,
+    settled: Option<usize>,
+    census: ObjectCensus,
+}
+
+impl SoupSearchResult {
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    /// The number of cells alive once the soup stopped
+    pub fn population(&self) -> usize {
+        self.population
+    }
+
+    /// The number of generations the soup went through, until it settled down
+    /// if it did
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    /// The period the soup repeats with, 1 being a still life, or `None` if it
+    /// never settled down
+    pub fn period(&self) -> Option<usize> {
+        self.settled
+    }
+
+    /// The objects the soup ended up with
+    pub fn census(&self) -> &ObjectCensus {
+        &self.census
+    }
+}
+
+/// Reports from the threads running a soup search
+pub enum SoupSearchEvent {
+    Finished(SoupSearchResult),
+    /// Every soup was run through, or the search was cancelled
+    Done,
+}
+
+/// Runs the soups of `search` on as many worker threads as there are
+/// processors, until they are all done or `cancelled` is set. Soups finish in
+/// no particular order.
+pub fn run_soup_search(
+    search: SoupSearch,
+    cancelled: Arc<AtomicBool>,
+) -> glib::Receiver<SoupSearchEvent> {
+    let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+    let workers = std::thread::available_parallelism()
+        .map(|workers| workers.get())
+        .unwrap_or(1)
+        .min(search.soups())
+        .max(1);
+
+    std::thread::spawn(move || {
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..workers {
+                let sender = sender.clone();
+                scope.spawn(|| {
+                    let sender = sender;
+                    loop {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= search.soups() {
+                            break;
+                        }
+
+                        match search.run_soup(index, &cancelled) {
+                            Some(result) => {
+                                sender.send(SoupSearchEvent::Finished(result)).ok();
+                            }
+                            None => break,
+                        }
+                    }
+                });
+            }
+        });
+        sender.send(SoupSearchEvent::Done).ok();
+    });
+
+    receiver
+}
+
+/// Writes `results` as comma separated values, one soup per line, with a
+/// column counting each known object
+pub fn write_soup_results_csv(results: &[SoupSearchResult]) -> String {
+    let mut text = String::from("seed,population,generations,period");
+    for object in CensusObject::known() {
+        text.push(',');
+        text.push_str(object.name());
+    }
+    text.push_str(",Other\n");
+
+    for result in results {
+        text.push_str(&format!(
+            "{},{},{},{}",
+            result.seed,
+            result.population,
+            result.generations,
+            result
+                .settled
+                .map(|period| period.to_string())
+                .unwrap_or_default()
+        ));
+        for object in CensusObject::known() {
+            text.push_str(&format!(",{}", result.census.count(object)));
+        }
+        text.push_str(&format!(",{}\n", result.census.unrecognized()));
+    }
+    text
+}
+
+/// Writes `search` and its `results` as a JSON document. Censuses only list
+/// the objects that were found.
+pub fn write_soup_results_json(search: &SoupSearch, results: &[SoupSearchResult]) -> String {
+    let soups: Vec<String> = results
+        .iter()
+        .map(|result| {
+            let census: Vec<String> = result
+                .census
+                .counts()
+                .iter()
+                .map(|(object, count)| format!("\"{}\": {}", object.name(), count))
+                .chain(
+                    (result.census.unrecognized() > 0)
+                        .then(|| format!("\"Other\": {}", result.census.unrecognized())),
+                )
+                .collect();
+            format!(
+                "    {{\"seed\": {}, \"population\": {}, \"generations\": {}, \"period\": {}, \"census\": {{{}}}}}",
+                result.seed,
+                result.population,
+                result.generations,
+                result
+                    .settled
+                    .map(|period| period.to_string())
+                    .unwrap_or_else(|| "null".to_string()),
+                census.join(", ")
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"rule\": \"{}\",\n  \"generations\": {},\n  \"density\": {},\n  \"symmetry\": \"{}\",\n  \"soups\": [\n{}\n  ]\n}}\n",
+        search.rule,
+        search.generations,
+        search.soup.density(),
+        search.soup.symmetry().as_str(),
+        soups.join(",\n")
+    )
+}
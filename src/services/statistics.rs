@@ -0,0 +1,87 @@
+use super::SimulationStats;
+use std::collections::VecDeque;
+
+/// The most generations a statistics log keeps, older ones are dropped
+const STATISTICS_LOG_GENERATIONS: usize = 100_000;
+
+/// The statistics of the generations delivered by a simulation, oldest first,
+/// to analyze a run in spreadsheets. Generations skipped in turbo mode or
+/// while fast forwarding are left out, and going back to an earlier
+/// generation, as when the universe is replaced or rewound, starts over.
+#[derive(Clone, Debug, Default)]
+pub struct StatisticsLog {
+    generations: VecDeque<SimulationStats>,
+}
+
+impl StatisticsLog {
+    pub fn push(&mut self, stats: SimulationStats) {
+        if self
+            .generations
+            .back()
+            .is_some_and(|last| last.generation() >= stats.generation())
+        {
+            self.generations.clear();
+        }
+        if self.generations.len() == STATISTICS_LOG_GENERATIONS {
+            self.generations.pop_front();
+        }
+        self.generations.push_back(stats);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.generations.is_empty()
+    }
+
+    /// Writes the log as comma separated values, one generation per line.
+    /// Generations without living cells leave their bounding box empty.
+    pub fn to_csv(&self) -> String {
+        let mut text = String::from("generation,population,births,deaths,top,left,rows,columns\n");
+        for stats in self.generations.iter() {
+            let bounds = stats
+                .bounds()
+                .map(|(top, left, rows, columns)| format!("{},{},{},{}", top, left, rows, columns))
+                .unwrap_or_else(|| ",,,".to_string());
+            text.push_str(&format!(
+                "{},{},{},{},{}\n",
+                stats.generation(),
+                stats.population(),
+                stats.births(),
+                stats.deaths(),
+                bounds
+            ));
+        }
+        text
+    }
+
+    /// Writes the log as a JSON document
+    pub fn to_json(&self) -> String {
+        let generations: Vec<String> = self
+            .generations
+            .iter()
+            .map(|stats| {
+                let bounds = stats
+                    .bounds()
+                    .map(|(top, left, rows, columns)| {
+                        format!(
+                            "{{\"top\": {}, \"left\": {}, \"rows\": {}, \"columns\": {}}}",
+                            top, left, rows, columns
+                        )
+                    })
+                    .unwrap_or_else(|| "null".to_string());
+                format!(
+                    "    {{\"generation\": {}, \"population\": {}, \"births\": {}, \"deaths\": {}, \"bounds\": {}}}",
+                    stats.generation(),
+                    stats.population(),
+                    stats.births(),
+                    stats.deaths(),
+                    bounds
+                )
+            })
+            .collect();
+
+        format!(
+            "{{\n  \"generations\": [\n{}\n  ]\n}}\n",
+            generations.join(",\n")
+        )
+    }
+}
@@ -0,0 +1,562 @@
+use crate::config::G_LOG_DOMAIN;
+use crate::i18n::{i18n, i18n_f};
+use crate::models::{formats::write_rle, UniversePattern, UniverseRule};
+use crate::services::{
+    encode_surface_png, run_experiments, Experiment, ExperimentEvent, ExperimentOutputs,
+    ExperimentResult, HtmlReport, LibraryPattern, PatternLibrary, ReportSection,
+};
+use gtk::{cairo, gdk, gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::{Cell, RefCell};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// The side of experiment thumbnails in the results list
+const THUMBNAIL_SIZE: i32 = 64;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/experiments_dialog.ui")]
+    pub struct GameOfLifeExperimentsDialog {
+        #[template_child]
+        pub(super) queue_list: TemplateChild<gtk::ListBox>,
+
+        #[template_child]
+        pub(super) results_list: TemplateChild<gtk::ListBox>,
+
+        #[template_child]
+        pub(super) pattern_dropdown: TemplateChild<gtk::DropDown>,
+
+        #[template_child]
+        pub(super) rule_entry: TemplateChild<gtk::Entry>,
+
+        #[template_child]
+        pub(super) generations_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) thumbnail_check: TemplateChild<gtk::CheckButton>,
+
+        #[template_child]
+        pub(super) final_pattern_check: TemplateChild<gtk::CheckButton>,
+
+        #[template_child]
+        pub(super) add_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) run_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) export_button: TemplateChild<gtk::Button>,
+
+        pub(super) patterns: RefCell<Vec<LibraryPattern>>,
+
+        pub(super) experiments: RefCell<Vec<Experiment>>,
+
+        /// The finished experiments along with their results
+        pub(super) results: RefCell<Vec<(Experiment, ExperimentResult)>>,
+
+        /// The progress bars of the queue rows, in queue order
+        pub(super) progress_bars: RefCell<Vec<gtk::ProgressBar>>,
+
+        /// Set to cancel the queue while it runs
+        pub(super) cancelled: RefCell<Option<Arc<AtomicBool>>>,
+
+        /// The number of experiments the running queue finished
+        pub(super) finished: Cell<usize>,
+
+        pub(super) universe_size: Cell<(usize, usize)>,
+
+        /// The cell and background colors of thumbnails
+        pub(super) colors: RefCell<(String, String)>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeExperimentsDialog {
+        const NAME: &'static str = "GameOfLifeExperimentsDialog";
+        type Type = super::GameOfLifeExperimentsDialog;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeExperimentsDialog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.setup_widgets();
+            obj.connect_events();
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeExperimentsDialog {}
+    impl WindowImpl for GameOfLifeExperimentsDialog {}
+    impl DialogImpl for GameOfLifeExperimentsDialog {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeExperimentsDialog(ObjectSubclass<imp::GameOfLifeExperimentsDialog>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifeExperimentsDialog {
+    /// Creates a dialog whose experiments default to `rule`, run in universes
+    /// of `universe_size` and paint thumbnails with `colors`, as cell and
+    /// background colors
+    pub fn new(
+        rule: &UniverseRule,
+        universe_size: (usize, usize),
+        colors: (String, String),
+    ) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        let imp = dialog.imp();
+        imp.rule_entry.set_text(&rule.to_string());
+        imp.universe_size.set(universe_size);
+        imp.colors.replace(colors);
+        dialog
+    }
+
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+        let patterns = PatternLibrary::bundled();
+        let names: Vec<String> = patterns.iter().map(|pattern| pattern.name()).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        imp.pattern_dropdown
+            .set_model(Some(&gtk::StringList::new(&names)));
+        imp.add_button.set_sensitive(!patterns.is_empty());
+        imp.run_button.set_sensitive(false);
+        imp.patterns.replace(patterns);
+    }
+
+    fn connect_events(&self) {
+        let imp = self.imp();
+        imp.add_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.add_experiment();
+            }));
+
+        imp.run_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                match this.is_running() {
+                    true => this.cancel(),
+                    false => this.run(),
+                }
+            }));
+
+        imp.export_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.export_report();
+            }));
+
+        self.connect_response(|dialog, _| {
+            dialog.cancel();
+            dialog.close();
+        });
+    }
+
+    fn is_running(&self) -> bool {
+        self.imp().cancelled.borrow().is_some()
+    }
+
+    fn add_experiment(&self) {
+        let imp = self.imp();
+        let rule = match imp.rule_entry.text().parse::<UniverseRule>() {
+            Ok(rule) => rule,
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Invalid experiment rule: {:?}", error);
+                imp.rule_entry.add_css_class("error");
+                return;
+            }
+        };
+        imp.rule_entry.remove_css_class("error");
+
+        let experiment = match imp
+            .patterns
+            .borrow()
+            .get(imp.pattern_dropdown.selected() as usize)
+        {
+            Some(library_pattern) => Experiment::new(
+                &library_pattern.name(),
+                library_pattern.pattern().snapshot().clone(),
+                rule,
+                imp.generations_entry.value() as u32,
+                imp.universe_size.get(),
+                ExperimentOutputs::new(
+                    imp.thumbnail_check.is_active(),
+                    imp.final_pattern_check.is_active(),
+                ),
+            ),
+            None => return,
+        };
+
+        imp.experiments.borrow_mut().push(experiment);
+        self.rebuild_queue_rows();
+    }
+
+    fn remove_experiment(&self, index: usize) {
+        let mut experiments = self.imp().experiments.borrow_mut();
+        if index < experiments.len() {
+            experiments.remove(index);
+        }
+        drop(experiments);
+        self.rebuild_queue_rows();
+    }
+
+    fn rebuild_queue_rows(&self) {
+        let imp = self.imp();
+        while let Some(row) = imp.queue_list.row_at_index(0) {
+            imp.queue_list.remove(&row);
+        }
+
+        let mut progress_bars = vec![];
+        for (index, experiment) in imp.experiments.borrow().iter().enumerate() {
+            let (row, progress_bar) = self.build_queue_row(index, experiment);
+            imp.queue_list.append(&row);
+            progress_bars.push(progress_bar);
+        }
+        imp.progress_bars.replace(progress_bars);
+        imp.run_button
+            .set_sensitive(!imp.experiments.borrow().is_empty());
+    }
+
+    fn build_queue_row(
+        &self,
+        index: usize,
+        experiment: &Experiment,
+    ) -> (gtk::ListBoxRow, gtk::ProgressBar) {
+        let name_label = gtk::Label::builder()
+            .label(experiment.name())
+            .xalign(0.)
+            .build();
+        let details_label = gtk::Label::builder()
+            .label(&i18n_f(
+                "{} generations of {}",
+                &[
+                    &experiment.generations().to_string(),
+                    &experiment.rule().to_string(),
+                ],
+            ))
+            .xalign(0.)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+        let progress_bar = gtk::ProgressBar::new();
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        labels.set_hexpand(true);
+        labels.set_valign(gtk::Align::Center);
+        labels.append(&name_label);
+        labels.append(&details_label);
+        labels.append(&progress_bar);
+
+        let remove_button = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text(&i18n_f("Remove {} from the queue", &[experiment.name()]))
+            .valign(gtk::Align::Center)
+            .sensitive(!self.is_running())
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        remove_button.connect_clicked(clone!(@weak self as this => move |_| {
+            this.remove_experiment(index);
+        }));
+
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        content.set_margin_start(12);
+        content.set_margin_end(6);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.append(&labels);
+        content.append(&remove_button);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_child(Some(&content));
+        (row, progress_bar)
+    }
+
+    /// Runs the queued experiments, which leave the queue once finished
+    fn run(&self) {
+        let imp = self.imp();
+        let experiments = imp.experiments.borrow().clone();
+        if experiments.is_empty() {
+            return;
+        }
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        imp.cancelled.replace(Some(cancelled.clone()));
+        imp.finished.set(0);
+        self.set_running_state(true);
+
+        let receiver = run_experiments(experiments, cancelled);
+        receiver.attach(
+            None,
+            clone!(@weak self as this => @default-return Continue(false), move |event| {
+                let imp = this.imp();
+                match event {
+                    ExperimentEvent::Progress(index, generation) => {
+                        let experiments = imp.experiments.borrow();
+                        if let (Some(experiment), Some(progress_bar)) =
+                            (experiments.get(index), imp.progress_bars.borrow().get(index))
+                        {
+                            progress_bar.set_fraction(
+                                generation as f64 / experiment.generations().max(1) as f64,
+                            );
+                        }
+                    }
+                    ExperimentEvent::Finished(index, result) => {
+                        imp.finished.set(index + 1);
+                        if let Some(experiment) = imp.experiments.borrow().get(index) {
+                            imp.results_list
+                                .append(&this.build_result_row(experiment, &result));
+                            imp.results.borrow_mut().push((experiment.clone(), result));
+                            imp.export_button.set_sensitive(true);
+                        }
+                        if let Some(progress_bar) = imp.progress_bars.borrow().get(index) {
+                            progress_bar.set_fraction(1.);
+                        }
+                    }
+                    ExperimentEvent::Done => {
+                        imp.cancelled.replace(None);
+                        imp.experiments.borrow_mut().drain(..imp.finished.get());
+                        this.set_running_state(false);
+                        return Continue(false);
+                    }
+                }
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Stops the running queue after the current generation
+    fn cancel(&self) {
+        if let Some(cancelled) = self.imp().cancelled.borrow().as_ref() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn set_running_state(&self, running: bool) {
+        let imp = self.imp();
+        imp.run_button.set_label(&match running {
+            true => i18n("Cancel Queue"),
+            false => i18n("Run Queue"),
+        });
+        imp.add_button.set_sensitive(!running);
+        self.rebuild_queue_rows();
+    }
+
+    fn build_result_row(
+        &self,
+        experiment: &Experiment,
+        result: &ExperimentResult,
+    ) -> gtk::ListBoxRow {
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        content.set_margin_start(6);
+        content.set_margin_end(6);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+
+        let pixbuf = self.render_thumbnail(result).and_then(|surface| {
+            gdk::pixbuf_get_from_surface(&surface, 0, 0, surface.width(), surface.height())
+        });
+        if let Some(pixbuf) = pixbuf {
+            let texture = gdk::Texture::for_pixbuf(&pixbuf);
+            let picture = gtk::Picture::for_paintable(&texture);
+            picture.set_can_shrink(true);
+            picture.set_size_request(THUMBNAIL_SIZE, THUMBNAIL_SIZE);
+            content.append(&picture);
+        }
+
+        let settled = settled_description(result);
+        let name_label = gtk::Label::builder()
+            .label(&i18n_f(
+                "{} under {}",
+                &[experiment.name(), &experiment.rule().to_string()],
+            ))
+            .xalign(0.)
+            .build();
+        let stats_label = gtk::Label::builder()
+            .label(&i18n_f(
+                "{} cells alive after {} generations, {} at most. {}",
+                &[
+                    &result.population().to_string(),
+                    &experiment.generations().to_string(),
+                    &result.peak_population().to_string(),
+                    &settled,
+                ],
+            ))
+            .xalign(0.)
+            .wrap(true)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        labels.set_hexpand(true);
+        labels.set_valign(gtk::Align::Center);
+        labels.append(&name_label);
+        labels.append(&stats_label);
+        content.append(&labels);
+
+        if let Some(snapshot) = result.final_pattern() {
+            let mut pattern = UniversePattern::new(snapshot.clone());
+            pattern.set_name(Some(experiment.name().to_string()));
+            pattern.set_rule(Some(experiment.rule().clone()));
+
+            let save_button = gtk::Button::builder()
+                .icon_name("document-save-symbolic")
+                .tooltip_text(&i18n("Save the final pattern"))
+                .valign(gtk::Align::Center)
+                .css_classes(vec!["flat".to_string()])
+                .build();
+            save_button.connect_clicked(clone!(@weak self as this => move |_| {
+                this.save_pattern(&pattern);
+            }));
+            content.append(&save_button);
+        }
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_child(Some(&content));
+        row
+    }
+
+    /// Asks for a file and saves the results as an HTML report
+    fn export_report(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export experiments report"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&i18n("HTML pages")));
+        filter.add_mime_type("text/html");
+        filter.add_pattern("*.html");
+        dialog.add_filter(&filter);
+        dialog.set_current_name(&format!("{}.html", i18n("Experiments")));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as this => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    let content = this.build_report().to_html();
+                    if let Err(error) = file.replace_contents(content.as_bytes(), None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                    }
+                }
+            })
+        );
+
+        dialog.show();
+    }
+
+    /// Paints the thumbnail of `result` with the colors of the dialog, if the
+    /// experiment produced one
+    fn render_thumbnail(&self, result: &ExperimentResult) -> Option<cairo::ImageSurface> {
+        let colors = self.imp().colors.borrow();
+        match result.thumbnail()?.render(&colors.0, &colors.1) {
+            Ok(surface) => Some(surface),
+            Err(error) => {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Could not render experiment thumbnail: {}",
+                    error
+                );
+                None
+            }
+        }
+    }
+
+    fn build_report(&self) -> HtmlReport {
+        let mut report = HtmlReport::new(&i18n("Game of Life Experiments"));
+        for (experiment, result) in self.imp().results.borrow().iter() {
+            let mut section = ReportSection::new(experiment.name());
+            section.add_stat(&i18n("Rule"), &experiment.rule().to_string());
+            section.add_stat(&i18n("Generations"), &experiment.generations().to_string());
+            section.add_stat(&i18n("Final population"), &result.population().to_string());
+            section.add_stat(
+                &i18n("Peak population"),
+                &result.peak_population().to_string(),
+            );
+            section.add_stat(&i18n("Outcome"), &settled_description(result));
+            section.set_chart(
+                &i18n("Population by generation"),
+                result.populations().to_vec(),
+            );
+
+            if let Some(surface) = self.render_thumbnail(result) {
+                match encode_surface_png(&surface) {
+                    Ok(image) => section.set_thumbnail(image),
+                    Err(error) => glib::g_warning!(
+                        G_LOG_DOMAIN,
+                        "Could not encode experiment thumbnail: {}",
+                        error
+                    ),
+                }
+            }
+            if let Some(snapshot) = result.final_pattern() {
+                let mut pattern = UniversePattern::new(snapshot.clone());
+                pattern.set_rule(Some(experiment.rule().clone()));
+                section.set_pattern(&write_rle(&pattern));
+            }
+            report.add_section(section);
+        }
+        report
+    }
+
+    fn save_pattern(&self, pattern: &UniversePattern) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Save"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Save final pattern"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        dialog.set_current_name(&format!("{}.rle", pattern.name().unwrap_or_default()));
+
+        let content = write_rle(pattern);
+        dialog.connect_response(
+            clone!(@strong dialog => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    if let Err(error) = file.replace_contents(content.as_bytes(), None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                    }
+                }
+            })
+        );
+
+        dialog.show();
+    }
+}
+
+/// Describes whether and how the universe of an experiment settled down
+fn settled_description(result: &ExperimentResult) -> String {
+    match result.settled() {
+        Some((generation, 1)) => i18n_f("Still from generation {}", &[&generation.to_string()]),
+        Some((generation, period)) => i18n_f(
+            "Period {} from generation {}",
+            &[&period.to_string(), &generation.to_string()],
+        ),
+        None => i18n("Never settled"),
+    }
+}
@@ -1,9 +1,27 @@
+mod experiments_dialog;
 mod new_universe_view;
+mod palette;
+mod pattern_library;
+mod playlist_editor;
 mod preferences_window;
+mod resize_universe_view;
+mod rule_editor;
+mod soup_search_dialog;
+mod stats_panel;
 mod universe_controls;
 mod universe_grid;
+mod viewport;
 
+pub use experiments_dialog::*;
 pub use new_universe_view::*;
+pub use palette::*;
+pub use pattern_library::*;
+pub use playlist_editor::*;
 pub use preferences_window::*;
+pub use resize_universe_view::*;
+pub use rule_editor::*;
+pub use soup_search_dialog::*;
+pub use stats_panel::*;
 pub use universe_controls::*;
 pub use universe_grid::*;
+pub use viewport::*;
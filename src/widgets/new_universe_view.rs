@@ -1,4 +1,9 @@
-use crate::{config::G_LOG_DOMAIN, services::GameOfLifeSettings};
+use crate::{
+    config::G_LOG_DOMAIN,
+    i18n::i18n,
+    models::{Engine, UNIVERSE_MAX_SIZE},
+    services::GameOfLifeSettings,
+};
 use gtk::{gio, glib, glib::clone};
 use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
 
@@ -36,6 +41,8 @@ mod imp {
         pub(super) template_check: TemplateChild<gtk::CheckButton>,
         #[template_child]
         pub(super) template_list_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub(super) unbounded_check: TemplateChild<gtk::CheckButton>,
     }
 
     #[glib::object_subclass]
@@ -112,7 +119,7 @@ impl GameOfLifeNewUniverseView {
 
         let row_adjust = gtk::Adjustment::builder()
             .lower(10.)
-            .upper(1000.)
+            .upper(UNIVERSE_MAX_SIZE as f64)
             .step_increment(1.0)
             .page_increment(10.)
             .value(settings.universe_width().into())
@@ -120,7 +127,7 @@ impl GameOfLifeNewUniverseView {
 
         let column_adjust = gtk::Adjustment::builder()
             .lower(10.)
-            .upper(1000.)
+            .upper(UNIVERSE_MAX_SIZE as f64)
             .step_increment(1.0)
             .page_increment(10.)
             .value(settings.universe_height().into())
@@ -145,6 +152,26 @@ impl GameOfLifeNewUniverseView {
         self.imp()
             .template_list_dropdown
             .set_sensitive(self.imp().template_check.is_active());
+
+        // Rules the unbounded engine cannot simulate leave only bounded universes
+        let rule = settings.rule();
+        if !Engine::Unbounded.capabilities().supports_rule(&rule) {
+            let unbounded_check = &self.imp().unbounded_check;
+            unbounded_check.set_sensitive(false);
+            unbounded_check.set_tooltip_text(Some(&if rule.is_birth(0) {
+                i18n("Rules with births on zero neighbours would fill an unbounded universe")
+            } else if rule.has_dying_states() {
+                i18n("Unbounded universes cannot simulate dying states")
+            } else if rule.is_colored() {
+                i18n("Unbounded universes cannot tell cells apart by color")
+            } else if rule.is_ant() {
+                i18n("Unbounded universes cannot be walked by ants")
+            } else if rule.is_elementary() {
+                i18n("Unbounded universes cannot scroll the line of elementary rules")
+            } else {
+                i18n("Unbounded universes only count the eight cells around each cell")
+            }));
+        }
     }
 
     fn connect_events(&self) {
@@ -186,6 +213,10 @@ impl GameOfLifeNewUniverseView {
         }
     }
 
+    pub fn unbounded(&self) -> bool {
+        self.imp().unbounded_check.is_active()
+    }
+
     pub fn size(&self) -> (f64, f64) {
         (
             self.imp().rows_entry.value(),
@@ -0,0 +1,136 @@
+/// The colors the grid paints with, resolved from the settings and the style
+/// of the desktop. Everything drawn over the universe takes its colors from
+/// here, so that overlays always match the cells and background they lie on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniverseGridPalette {
+    cell: gtk::gdk::RGBA,
+    background: gtk::gdk::RGBA,
+    age_gradient: Option<UniverseGridAgeGradient>,
+    dying: Option<gtk::gdk::RGBA>,
+}
+
+impl Default for UniverseGridPalette {
+    fn default() -> Self {
+        Self::new(
+            gtk::gdk::RGBA::new(0.0, 0.0, 0.0, 1.0),
+            gtk::gdk::RGBA::new(1.0, 1.0, 1.0, 1.0),
+        )
+    }
+}
+
+impl UniverseGridPalette {
+    pub fn new(cell: gtk::gdk::RGBA, background: gtk::gdk::RGBA) -> Self {
+        Self {
+            cell,
+            background,
+            age_gradient: None,
+            dying: None,
+        }
+    }
+
+    /// Colors living cells by how many generations they survived for, or with
+    /// the cell color alone if `None`
+    pub fn with_age_gradient(mut self, age_gradient: Option<UniverseGridAgeGradient>) -> Self {
+        self.age_gradient = age_gradient;
+        self
+    }
+
+    /// Starts dying cells of Generations rules from `dying`, or from the cell
+    /// color if `None`
+    pub fn with_dying_color(mut self, dying: Option<gtk::gdk::RGBA>) -> Self {
+        self.dying = dying;
+        self
+    }
+
+    /// The same palette with the cell and background colors swapped, as for
+    /// dark rooms
+    pub fn inverted(self) -> Self {
+        Self {
+            cell: self.background,
+            background: self.cell,
+            ..self
+        }
+    }
+
+    /// The color of living cells, before aging
+    pub fn cell(&self) -> gtk::gdk::RGBA {
+        self.cell
+    }
+
+    pub fn background(&self) -> gtk::gdk::RGBA {
+        self.background
+    }
+
+    pub fn age_gradient(&self) -> Option<UniverseGridAgeGradient> {
+        self.age_gradient
+    }
+
+    /// The color dying cells start from
+    pub fn dying(&self) -> gtk::gdk::RGBA {
+        self.dying.unwrap_or(self.cell)
+    }
+
+    /// The lines between cells, standing out lightly from the background
+    /// whether it is light or dark
+    pub fn grid_lines(&self) -> gtk::gdk::RGBA {
+        let background = self.background;
+        let luminance =
+            0.2126 * background.red() + 0.7152 * background.green() + 0.0722 * background.blue();
+        match luminance < 0.5 {
+            true => gtk::gdk::RGBA::new(1.0, 1.0, 1.0, 0.12),
+            false => gtk::gdk::RGBA::new(0.0, 0.0, 0.0, 0.12),
+        }
+    }
+
+    /// The outlines drawn around every cell, a shade lighter than the
+    /// background
+    pub fn outline(&self) -> gtk::gdk::RGBA {
+        let mut outline = self.background;
+        outline.set_red(outline.red() + 0.1);
+        outline.set_green(outline.green() + 0.1);
+        outline.set_blue(outline.blue() + 0.1);
+        outline
+    }
+
+    /// The edges of the selected area and of the cell edited from the
+    /// keyboard, filled with a light tint of the same color
+    pub fn selection(&self) -> gtk::gdk::RGBA {
+        self.cell
+    }
+
+    /// The cells previewed before being placed or drawn
+    pub fn ghost(&self) -> gtk::gdk::RGBA {
+        let mut ghost = self.cell;
+        ghost.set_alpha(ghost.alpha() * 0.5);
+        ghost
+    }
+}
+
+/// Living cells shift from the cell color toward `color` as they survive,
+/// reaching it once they survived for `span` generations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniverseGridAgeGradient {
+    color: gtk::gdk::RGBA,
+    span: u32,
+}
+
+impl UniverseGridAgeGradient {
+    pub fn new(color: gtk::gdk::RGBA, span: u32) -> Self {
+        Self {
+            color,
+            span: span.max(1),
+        }
+    }
+
+    /// The color of a living cell that survived for `age` generations
+    pub fn color_at(&self, cell_color: &gtk::gdk::RGBA, age: u32) -> gtk::gdk::RGBA {
+        let progress = age.min(self.span) as f32 / self.span as f32;
+        let mix = |from: f32, to: f32| from + (to - from) * progress;
+        gtk::gdk::RGBA::new(
+            mix(cell_color.red(), self.color.red()),
+            mix(cell_color.green(), self.color.green()),
+            mix(cell_color.blue(), self.color.blue()),
+            mix(cell_color.alpha(), self.color.alpha()),
+        )
+    }
+}
@@ -0,0 +1,274 @@
+use crate::i18n::i18n_f;
+use crate::models::{UniversePattern, UniversePointMatrix};
+use crate::services::{GameOfLifeSettings, LibraryPattern, PatternLibrary};
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::{Cell, RefCell};
+
+const THUMBNAIL_SIZE: i32 = 48;
+
+/// Draws a pattern scaled to fit the drawing area, keeping cells square
+fn draw_thumbnail(
+    area: &gtk::DrawingArea,
+    context: &gtk::cairo::Context,
+    width: i32,
+    height: i32,
+    pattern: &UniversePattern,
+) {
+    let snapshot = pattern.snapshot();
+    let cell_size = f64::min(
+        width as f64 / snapshot.columns() as f64,
+        height as f64 / snapshot.rows() as f64,
+    );
+    let offset_x = (width as f64 - cell_size * snapshot.columns() as f64) / 2.0;
+    let offset_y = (height as f64 - cell_size * snapshot.rows() as f64) / 2.0;
+
+    let color = area.style_context().color();
+    context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64,
+    );
+
+    for (row, column) in snapshot.iter_alive() {
+        context.rectangle(
+            offset_x + column as f64 * cell_size,
+            offset_y + row as f64 * cell_size,
+            cell_size,
+            cell_size,
+        );
+    }
+    context.fill().ok();
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/pattern_library.ui")]
+    pub struct GameOfLifePatternLibrary {
+        #[template_child]
+        pub(super) search_entry: TemplateChild<gtk::SearchEntry>,
+
+        #[template_child]
+        pub(super) patterns_list: TemplateChild<gtk::ListBox>,
+
+        #[template_child]
+        pub(super) row_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) column_entry: TemplateChild<gtk::SpinButton>,
+
+        pub(super) patterns: RefCell<Vec<LibraryPattern>>,
+
+        pub(super) universe_size: Cell<(usize, usize)>,
+
+        pub(super) settings: GameOfLifeSettings,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifePatternLibrary {
+        const NAME: &'static str = "GameOfLifePatternLibrary";
+        type Type = super::GameOfLifePatternLibrary;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifePatternLibrary {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.setup_widgets();
+            obj.connect_events();
+        }
+    }
+
+    impl WidgetImpl for GameOfLifePatternLibrary {}
+    impl WindowImpl for GameOfLifePatternLibrary {}
+    impl DialogImpl for GameOfLifePatternLibrary {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifePatternLibrary(ObjectSubclass<imp::GameOfLifePatternLibrary>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifePatternLibrary {
+    /// Creates a library dialog stamping onto a universe of `rows`x`columns` cells
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        dialog.imp().universe_size.set((rows, columns));
+        dialog
+            .imp()
+            .row_entry
+            .set_range(0., rows.saturating_sub(1) as f64);
+        dialog
+            .imp()
+            .column_entry
+            .set_range(0., columns.saturating_sub(1) as f64);
+        dialog
+    }
+
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+        imp.row_entry.set_increments(1., 10.);
+        imp.column_entry.set_increments(1., 10.);
+        self.set_response_sensitive(gtk::ResponseType::Ok, false);
+        self.set_response_sensitive(gtk::ResponseType::Apply, false);
+
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&imp.settings.create_action("stamp-blend"));
+        self.insert_action_group("library", Some(&actions));
+
+        let patterns = PatternLibrary::all();
+        for library_pattern in patterns.iter() {
+            imp.patterns_list.append(&Self::build_row(library_pattern));
+        }
+        imp.patterns.replace(patterns);
+
+        imp.patterns_list.set_filter_func(
+            clone!(@weak self as this => @default-return true, move |row| {
+                this.matches_search(row)
+            }),
+        );
+    }
+
+    fn build_row(library_pattern: &LibraryPattern) -> gtk::ListBoxRow {
+        let pattern = library_pattern.pattern().clone();
+        let thumbnail = gtk::DrawingArea::builder()
+            .content_width(THUMBNAIL_SIZE)
+            .content_height(THUMBNAIL_SIZE)
+            .build();
+        thumbnail.set_draw_func(move |area, context, width, height| {
+            draw_thumbnail(area, context, width, height, &pattern);
+        });
+
+        let name_label = gtk::Label::builder()
+            .label(&library_pattern.name())
+            .xalign(0.)
+            .build();
+        let snapshot = library_pattern.pattern().snapshot();
+        let size = format!("{} × {}", snapshot.columns(), snapshot.rows());
+        let size_label = gtk::Label::builder()
+            .label(&match library_pattern.is_user() {
+                true => i18n_f("{}, saved by you", &[&size]),
+                false => size,
+            })
+            .xalign(0.)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        labels.set_valign(gtk::Align::Center);
+        labels.append(&name_label);
+        labels.append(&size_label);
+
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        content.set_margin_start(6);
+        content.set_margin_end(6);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.append(&thumbnail);
+        content.append(&labels);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_child(Some(&content));
+        if let Some(description) = library_pattern.pattern().comments().first() {
+            row.set_tooltip_text(Some(description));
+        }
+        row
+    }
+
+    fn connect_events(&self) {
+        let imp = self.imp();
+        imp.search_entry
+            .connect_search_changed(clone!(@weak self as this => move |_| {
+                this.imp().patterns_list.invalidate_filter();
+            }));
+
+        imp.patterns_list
+            .connect_row_selected(clone!(@weak self as this => move |_, row| {
+                this.on_row_selected(row);
+            }));
+
+        imp.patterns_list
+            .connect_row_activated(clone!(@weak self as this => move |_, _| {
+                this.response(gtk::ResponseType::Ok);
+            }));
+    }
+
+    fn matches_search(&self, row: &gtk::ListBoxRow) -> bool {
+        let query = self.imp().search_entry.text().to_lowercase();
+        if query.is_empty() {
+            return true;
+        }
+
+        let patterns = self.imp().patterns.borrow();
+        match patterns.get(row.index() as usize) {
+            Some(library_pattern) => {
+                library_pattern.name().to_lowercase().contains(&query)
+                    || library_pattern
+                        .pattern()
+                        .comments()
+                        .iter()
+                        .any(|comment| comment.to_lowercase().contains(&query))
+            }
+            None => false,
+        }
+    }
+
+    /// Centers the selected pattern in the universe by default
+    fn on_row_selected(&self, row: Option<&gtk::ListBoxRow>) {
+        let imp = self.imp();
+        self.set_response_sensitive(gtk::ResponseType::Ok, row.is_some());
+        self.set_response_sensitive(gtk::ResponseType::Apply, row.is_some());
+
+        if let Some(library_pattern) =
+            row.and_then(|row| imp.patterns.borrow().get(row.index() as usize).cloned())
+        {
+            let (rows, columns) = imp.universe_size.get();
+            let snapshot = library_pattern.pattern().snapshot();
+            imp.row_entry
+                .set_value(rows.saturating_sub(snapshot.rows()) as f64 / 2.);
+            imp.column_entry
+                .set_value(columns.saturating_sub(snapshot.columns()) as f64 / 2.);
+        }
+    }
+
+    /// Selects the pattern identified by `id`, scrolling it into view
+    pub fn select_pattern(&self, id: &str) {
+        let imp = self.imp();
+        let index = imp
+            .patterns
+            .borrow()
+            .iter()
+            .position(|library_pattern| library_pattern.id() == id);
+        if let Some(row) = index.and_then(|index| imp.patterns_list.row_at_index(index as i32)) {
+            imp.patterns_list.select_row(Some(&row));
+            row.grab_focus();
+        }
+    }
+
+    pub fn selected_pattern(&self) -> Option<LibraryPattern> {
+        let imp = self.imp();
+        let row = imp.patterns_list.selected_row()?;
+        imp.patterns.borrow().get(row.index() as usize).cloned()
+    }
+
+    /// The row and column the top left corner of the pattern should be stamped at
+    pub fn location(&self) -> (usize, usize) {
+        (
+            self.imp().row_entry.value() as usize,
+            self.imp().column_entry.value() as usize,
+        )
+    }
+}
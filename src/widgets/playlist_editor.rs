@@ -0,0 +1,196 @@
+use crate::i18n::i18n_f;
+use crate::services::{LibraryPattern, PatternLibrary, PlaylistEntry};
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::RefCell;
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/playlist_editor.ui")]
+    pub struct GameOfLifePlaylistEditor {
+        #[template_child]
+        pub(super) entries_list: TemplateChild<gtk::ListBox>,
+
+        #[template_child]
+        pub(super) pattern_dropdown: TemplateChild<gtk::DropDown>,
+
+        #[template_child]
+        pub(super) generations_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) speed_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) zoom_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) add_button: TemplateChild<gtk::Button>,
+
+        pub(super) patterns: RefCell<Vec<LibraryPattern>>,
+
+        pub(super) entries: RefCell<Vec<PlaylistEntry>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifePlaylistEditor {
+        const NAME: &'static str = "GameOfLifePlaylistEditor";
+        type Type = super::GameOfLifePlaylistEditor;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifePlaylistEditor {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.setup_widgets();
+            obj.connect_events();
+        }
+    }
+
+    impl WidgetImpl for GameOfLifePlaylistEditor {}
+    impl WindowImpl for GameOfLifePlaylistEditor {}
+    impl DialogImpl for GameOfLifePlaylistEditor {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifePlaylistEditor(ObjectSubclass<imp::GameOfLifePlaylistEditor>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifePlaylistEditor {
+    /// Creates an editor showing `entries`
+    pub fn new(entries: Vec<PlaylistEntry>) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        dialog.imp().entries.replace(entries);
+        dialog.rebuild_rows();
+        dialog
+    }
+
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+        let patterns = PatternLibrary::bundled();
+        let names: Vec<String> = patterns.iter().map(|pattern| pattern.name()).collect();
+        let names: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        imp.pattern_dropdown
+            .set_model(Some(&gtk::StringList::new(&names)));
+        imp.add_button.set_sensitive(!patterns.is_empty());
+        imp.patterns.replace(patterns);
+    }
+
+    fn connect_events(&self) {
+        self.imp()
+            .add_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.add_entry();
+            }));
+    }
+
+    fn add_entry(&self) {
+        let imp = self.imp();
+        let pattern_id = match imp
+            .patterns
+            .borrow()
+            .get(imp.pattern_dropdown.selected() as usize)
+        {
+            Some(library_pattern) => library_pattern.id().to_string(),
+            None => return,
+        };
+
+        imp.entries.borrow_mut().push(PlaylistEntry::new(
+            &pattern_id,
+            imp.generations_entry.value() as u32,
+            imp.speed_entry.value() as u32,
+            imp.zoom_entry.value() as u32,
+        ));
+        self.rebuild_rows();
+    }
+
+    fn remove_entry(&self, index: usize) {
+        let mut entries = self.imp().entries.borrow_mut();
+        if index < entries.len() {
+            entries.remove(index);
+        }
+        drop(entries);
+        self.rebuild_rows();
+    }
+
+    fn rebuild_rows(&self) {
+        let imp = self.imp();
+        while let Some(row) = imp.entries_list.row_at_index(0) {
+            imp.entries_list.remove(&row);
+        }
+
+        for (index, entry) in imp.entries.borrow().iter().enumerate() {
+            imp.entries_list.append(&self.build_row(index, entry));
+        }
+    }
+
+    fn build_row(&self, index: usize, entry: &PlaylistEntry) -> gtk::ListBoxRow {
+        let name = self
+            .imp()
+            .patterns
+            .borrow()
+            .iter()
+            .find(|library_pattern| library_pattern.id() == entry.pattern_id())
+            .map(|library_pattern| library_pattern.name())
+            .unwrap_or_else(|| entry.pattern_id().to_string());
+
+        let name_label = gtk::Label::builder().label(&name).xalign(0.).build();
+        let details_label = gtk::Label::builder()
+            .label(&i18n_f(
+                "{} generations at {} gen/s, zoom {}×",
+                &[
+                    &entry.generations().to_string(),
+                    &entry.speed().to_string(),
+                    &entry.zoom().to_string(),
+                ],
+            ))
+            .xalign(0.)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        labels.set_hexpand(true);
+        labels.set_valign(gtk::Align::Center);
+        labels.append(&name_label);
+        labels.append(&details_label);
+
+        let remove_button = gtk::Button::builder()
+            .icon_name("user-trash-symbolic")
+            .tooltip_text(&i18n_f("Remove {} from the playlist", &[&name]))
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        remove_button.connect_clicked(clone!(@weak self as this => move |_| {
+            this.remove_entry(index);
+        }));
+
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        content.set_margin_start(12);
+        content.set_margin_end(6);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.append(&labels);
+        content.append(&remove_button);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_child(Some(&content));
+        row
+    }
+
+    pub fn entries(&self) -> Vec<PlaylistEntry> {
+        self.imp().entries.borrow().clone()
+    }
+}
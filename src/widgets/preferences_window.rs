@@ -1,9 +1,26 @@
-use crate::services::GameOfLifeSettings;
+use crate::{
+    i18n::{i18n, i18n_f},
+    models::{InjectionEdge, Neighbourhood, RulePreset, UniverseRule},
+    services::{
+        GameOfLifeSettings, PatternLibrary, RecordingFormat, Shortcut, SimulationPriority, UserRule,
+    },
+    widgets::GameOfLifeRuleEditor,
+};
 use adw::{
+    prelude::{
+        ActionRowExt, ComboRowExt, EntryRowExt, MessageDialogExt, MessageDialogExtManual,
+        PreferencesGroupExt, PreferencesRowExt, PreferencesWindowExt,
+    },
     subclass::{preferences_window::PreferencesWindowImpl, window::AdwWindowImpl},
     PreferencesWindow,
 };
-use gtk::{gdk::RGBA, gio, glib, prelude::*, subclass::prelude::*, CompositeTemplate};
+use gtk::{
+    gdk, gdk::RGBA, gio, glib, glib::clone, prelude::*, subclass::prelude::*, CompositeTemplate,
+};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 mod imp {
     use super::*;
@@ -28,12 +45,36 @@ mod imp {
         #[template_child]
         pub(super) background_color_dark_picker: TemplateChild<gtk::ColorButton>,
 
+        #[template_child]
+        pub(super) reset_colors_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) cell_accent_color: TemplateChild<gtk::Switch>,
+
         #[template_child]
         pub(super) draw_cells_outline: TemplateChild<gtk::Switch>,
 
+        #[template_child]
+        pub(super) show_grid_coordinates: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) cell_size_adjustment: TemplateChild<gtk::Adjustment>,
+
         #[template_child]
         pub(super) fade_out_dead_cells: TemplateChild<gtk::Switch>,
 
+        #[template_child]
+        pub(super) death_trail_length_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) cell_age_coloring: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) cell_age_color_picker: TemplateChild<gtk::ColorButton>,
+
+        #[template_child]
+        pub(super) cell_age_span_adjustment: TemplateChild<gtk::Adjustment>,
+
         #[template_child]
         pub(super) allow_render_on_resize: TemplateChild<gtk::Switch>,
 
@@ -42,6 +83,123 @@ mod imp {
 
         #[template_child]
         pub(super) show_design_hint: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) rule_preset: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) rule_entry: TemplateChild<adw::EntryRow>,
+
+        #[template_child]
+        pub(super) rule_editor_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) rule_neighbourhood: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) universe_width_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) universe_height_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) restore_session: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) pause_on_unfocus: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) pause_when_hidden: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) throttle_when_saving_power: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) write_crash_reports: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) screenshot_when_stable: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) screenshot_folder_row: TemplateChild<adw::ActionRow>,
+
+        #[template_child]
+        pub(super) screenshot_folder_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) recording_format: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) recording_interval_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) halt_when_settled: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) detect_oscillators: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) stylus_brush_radius_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) simulation_priority: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) rain_rate_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) rain_at_edges: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) birth_probability_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) survival_probability_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) temperature_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) stochastic_seed_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) injection_pattern: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) injection_edge: TemplateChild<adw::ComboRow>,
+
+        #[template_child]
+        pub(super) injection_period_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) reseed_when_stable: TemplateChild<gtk::Switch>,
+
+        #[template_child]
+        pub(super) reseed_patience_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) ambient_palette_period_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) rewind_depth_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) heatmap_generations_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) shortcuts_group: TemplateChild<adw::PreferencesGroup>,
+
+        #[template_child]
+        pub(super) reset_shortcuts_button: TemplateChild<gtk::Button>,
+
+        pub(super) shortcut_labels: RefCell<Vec<(Shortcut, gtk::ShortcutLabel)>>,
+
+        /// The rules saved by the user, listed after the presets
+        pub(super) user_rules: RefCell<Vec<UserRule>>,
+
+        /// Whether the rows of the rules saved by the user are being replaced,
+        /// selecting other rows on the way
+        pub(super) reloading_user_rules: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -63,6 +221,7 @@ mod imp {
         fn constructed(&self) {
             self.parent_constructed();
             self.obj().setup_bindings();
+            self.obj().setup_shortcuts();
         }
 
         fn properties() -> &'static [glib::ParamSpec] {
@@ -173,6 +332,311 @@ impl GameOfLifePreferencesWindow {
         glib::Object::new::<Self>(&[])
     }
 
+    fn select_screenshot_folder(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Select"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Screenshots folder"))
+            .transient_for(self)
+            .action(gtk::FileChooserAction::SelectFolder)
+            .build();
+
+        dialog.connect_response(clone!(@strong dialog => move |_, response| {
+            if response != gtk::ResponseType::Accept {
+                return;
+            }
+
+            if let Some(path) = dialog.file().and_then(|file| file.path()) {
+                GameOfLifeSettings::default().set_screenshot_folder(&path);
+            }
+        }));
+
+        dialog.show();
+    }
+
+    /// Lists the rebindable shortcuts, each row opening a dialog to change it
+    fn setup_shortcuts(&self) {
+        let settings = GameOfLifeSettings::default();
+        let imp = self.imp();
+
+        for shortcut in Shortcut::ALL {
+            let label = gtk::ShortcutLabel::new("");
+            label.set_disabled_text(&i18n("Disabled"));
+            label.set_valign(gtk::Align::Center);
+
+            let row = adw::ActionRow::new();
+            row.set_title(&shortcut_title(shortcut));
+            row.set_activatable(true);
+            row.add_suffix(&label);
+            row.connect_activated(clone!(@weak self as this => move |_| {
+                this.edit_shortcut(shortcut);
+            }));
+
+            imp.shortcuts_group.add(&row);
+            imp.shortcut_labels.borrow_mut().push((shortcut, label));
+        }
+
+        self.update_shortcut_labels(&settings);
+        settings.connect_changed(
+            "shortcuts",
+            clone!(@weak self as this, @strong settings => move |_, _| {
+                this.update_shortcut_labels(&settings);
+            }),
+        );
+        imp.reset_shortcuts_button
+            .connect_clicked(clone!(@strong settings => move |_| {
+                settings.reset_shortcuts();
+            }));
+    }
+
+    /// Lists the rules saved by the user after the presets, before custom
+    /// rules
+    fn reload_user_rules(&self, settings: &GameOfLifeSettings) {
+        let imp = self.imp();
+        let user_rules = UserRule::all();
+        let names: Vec<&str> = user_rules
+            .iter()
+            .map(|user_rule| user_rule.name())
+            .collect();
+        if let Some(model) = imp
+            .rule_preset
+            .model()
+            .and_then(|model| model.downcast::<gtk::StringList>().ok())
+        {
+            imp.reloading_user_rules.set(true);
+            let previous = imp.user_rules.borrow().len() as u32;
+            model.splice(RulePreset::ALL.len() as u32, previous, &names);
+            imp.reloading_user_rules.set(false);
+        }
+        imp.user_rules.replace(user_rules);
+        self.update_rule_preset(settings);
+    }
+
+    /// The rule saved by the user that the current rule is, if it is no preset
+    fn current_user_rule(&self, settings: &GameOfLifeSettings) -> Option<UserRule> {
+        let rule = settings.rule();
+        self.imp()
+            .user_rules
+            .borrow()
+            .iter()
+            .find(|user_rule| *user_rule.rule() == rule)
+            .cloned()
+    }
+
+    /// Selects the preset the rule is, or the rule saved by the user, describing
+    /// it
+    fn update_rule_preset(&self, settings: &GameOfLifeSettings) {
+        let imp = self.imp();
+        let row = &imp.rule_preset;
+        let preset = RulePreset::from_rule(&settings.rule());
+        let user_rule = self.current_user_rule(settings);
+        let user_rules = imp.user_rules.borrow();
+        row.set_selected(match (preset, &user_rule) {
+            (Some(preset), _) => RulePreset::ALL
+                .iter()
+                .position(|other| *other == preset)
+                .unwrap_or_default() as u32,
+            (None, Some(user_rule)) => {
+                let position = user_rules
+                    .iter()
+                    .position(|other| other.id() == user_rule.id())
+                    .unwrap_or_default();
+                (RulePreset::ALL.len() + position) as u32
+            }
+            (None, None) => (RulePreset::ALL.len() + user_rules.len()) as u32,
+        });
+        if preset.is_none() && user_rule.is_some() {
+            row.set_subtitle(&i18n("A rule you saved from the rule editor"));
+            return;
+        }
+        row.set_subtitle(&match preset {
+            Some(RulePreset::Conway) => {
+                i18n("The Game of Life, where cells need company but not too much")
+            }
+            Some(RulePreset::Seeds) => {
+                i18n("Every living cell dies at once, leaving explosive growth behind")
+            }
+            Some(RulePreset::DayAndNight) => {
+                i18n("Living and dead cells behave alike, so patterns work inverted too")
+            }
+            Some(RulePreset::LifeWithoutDeath) => {
+                i18n("Cells never die, growing ladders and blots of ink")
+            }
+            Some(RulePreset::BriansBrain) => {
+                i18n("Firing cells rest for a generation, sending sparks around")
+            }
+            Some(RulePreset::StarWars) => {
+                i18n("Cells fade through two states, building ships and walls")
+            }
+            Some(RulePreset::HexagonalLife) => {
+                i18n("Cells are hexagons with six neighbours, a world of gliders of its own")
+            }
+            Some(RulePreset::BoscosRule) => {
+                i18n("Cells count their neighbours five cells away, growing bugs and blobs")
+            }
+            Some(RulePreset::Immigration) => {
+                i18n("Two colors of cells compete, newborns taking the color of most parents")
+            }
+            Some(RulePreset::QuadLife) => {
+                i18n("Four colors of cells compete, newborns of three colors taking the fourth")
+            }
+            Some(RulePreset::WireWorld) => {
+                i18n("Electrons travel along wires, to build logic circuits")
+            }
+            Some(RulePreset::LangtonsAnt) => {
+                i18n("An ant turns on the cells it walks over, building a highway after some chaos")
+            }
+            Some(RulePreset::Rule30) => {
+                i18n("A single line of cells grows chaotic triangles, each generation a row below")
+            }
+            None => i18n("A rule of your own, typed below or made in the rule editor"),
+        });
+    }
+
+    /// Opens the rule editor on the current rule, storing the rule it makes
+    /// and saving it under its name when asked to
+    fn edit_rule_dialog(&self) {
+        let settings = GameOfLifeSettings::default();
+        let user_rule = self.current_user_rule(&settings);
+        let dialog = GameOfLifeRuleEditor::new(
+            &settings.rule(),
+            user_rule.as_ref().map(|user_rule| user_rule.name()),
+        );
+        dialog.set_modal(true);
+        dialog.set_transient_for(Some(self));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as this => move |_, response| {
+                let rule = dialog.rule();
+                match response {
+                    gtk::ResponseType::Ok => {
+                        if let Err(error) = UserRule::save(&dialog.name(), &rule) {
+                            this.add_toast(&adw::Toast::new(&i18n_f(
+                                "Could not save the rule: {}",
+                                &[&error.to_string()],
+                            )));
+                        }
+                        settings.set_rule(&rule);
+                        this.reload_user_rules(&settings);
+                    }
+                    gtk::ResponseType::Apply => settings.set_rule(&rule),
+                    _ => (),
+                }
+                dialog.close();
+            }),
+        );
+        dialog.show();
+    }
+
+    /// Selects the neighbourhood of the rule, describing how far it reaches
+    fn update_rule_neighbourhood(&self, settings: &GameOfLifeSettings) {
+        let row = &self.imp().rule_neighbourhood;
+        let rule = settings.rule();
+        row.set_sensitive(
+            !rule.is_wireworld() && !rule.is_colored() && !rule.is_ant() && !rule.is_elementary(),
+        );
+        row.set_selected(
+            Neighbourhood::ALL
+                .iter()
+                .position(|neighbourhood| *neighbourhood == rule.neighbourhood())
+                .unwrap_or_default() as u32,
+        );
+        let size = rule.neighbourhood_size().to_string();
+        row.set_subtitle(&match rule.range() {
+            1 => i18n_f("{} neighbours touching each cell", &[&size]),
+            range => i18n_f(
+                "{} neighbours up to {} cells away",
+                &[&size, &range.to_string()],
+            ),
+        });
+    }
+
+    fn update_shortcut_labels(&self, settings: &GameOfLifeSettings) {
+        let bindings = settings.shortcuts();
+        for (shortcut, label) in self.imp().shortcut_labels.borrow().iter() {
+            label.set_accelerator(bindings.accelerator(*shortcut));
+        }
+    }
+
+    /// Waits for the new key combination of `shortcut`. Combinations already
+    /// bound to another action are only taken over once confirmed.
+    fn edit_shortcut(&self, shortcut: Shortcut) {
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Set Shortcut")),
+            Some(&i18n_f(
+                "Press the new shortcut for “{}”, or Backspace to disable it",
+                &[&shortcut_title(shortcut)],
+            )),
+        );
+        dialog.add_responses(&[("cancel", &i18n("_Cancel")), ("replace", &i18n("_Replace"))]);
+        dialog.set_response_appearance("replace", adw::ResponseAppearance::Destructive);
+        dialog.set_response_enabled("replace", false);
+        dialog.set_close_response("cancel");
+
+        let pending: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+        // Keys are caught before the dialog buttons see them, Escape still closes it
+        let controller = gtk::EventControllerKey::new();
+        controller.set_propagation_phase(gtk::PropagationPhase::Capture);
+        controller.connect_key_pressed(
+            clone!(@weak dialog, @strong pending => @default-return glib::signal::Inhibit(false), move |_, key, _, modifiers| {
+                let modifiers = modifiers & gtk::accelerator_get_default_mod_mask();
+                let key = key.to_lower();
+                let accelerator = match (key, modifiers.is_empty()) {
+                    (gdk::Key::Escape, true) => return glib::signal::Inhibit(false),
+                    (gdk::Key::BackSpace, true) => String::new(),
+                    _ if gtk::accelerator_valid(key, modifiers) => {
+                        gtk::accelerator_name(key, modifiers).to_string()
+                    }
+                    _ => return glib::signal::Inhibit(true),
+                };
+
+                let settings = GameOfLifeSettings::default();
+                let mut bindings = settings.shortcuts();
+                match bindings.conflict(shortcut, &accelerator) {
+                    Some(other) => {
+                        dialog.set_body(&i18n_f(
+                            "{} is already used by “{}”. Replace it to move the shortcut here.",
+                            &[
+                                &gtk::accelerator_get_label(key, modifiers),
+                                &shortcut_title(other),
+                            ],
+                        ));
+                        dialog.set_response_enabled("replace", true);
+                        pending.replace(Some(accelerator));
+                    }
+                    None => {
+                        bindings.set_accelerator(shortcut, &accelerator);
+                        settings.set_shortcuts(&bindings);
+                        dialog.close();
+                    }
+                }
+                glib::signal::Inhibit(true)
+            }),
+        );
+        dialog.add_controller(&controller);
+
+        dialog.connect_response(None, move |_, response| {
+            if response != "replace" {
+                return;
+            }
+
+            if let Some(accelerator) = pending.take() {
+                let settings = GameOfLifeSettings::default();
+                let mut bindings = settings.shortcuts();
+                if let Some(other) = bindings.conflict(shortcut, &accelerator) {
+                    bindings.set_accelerator(other, "");
+                }
+                bindings.set_accelerator(shortcut, &accelerator);
+                settings.set_shortcuts(&bindings);
+            }
+        });
+
+        dialog.present();
+    }
+
     fn setup_bindings(&self) {
         let settings = GameOfLifeSettings::default();
         let imp = self.imp();
@@ -183,7 +647,32 @@ impl GameOfLifePreferencesWindow {
             &imp.draw_cells_outline.get(),
             "active",
         );
+        settings.bind(
+            "show-grid-coordinates",
+            &imp.show_grid_coordinates.get(),
+            "active",
+        );
+        settings.bind("cell-size", &imp.cell_size_adjustment.get(), "value");
         settings.bind("fade-out-cells", &imp.fade_out_dead_cells.get(), "active");
+        settings.bind(
+            "death-trail-length",
+            &imp.death_trail_length_adjustment.get(),
+            "value",
+        );
+        settings.bind("cell-accent-color", &imp.cell_accent_color.get(), "active");
+        settings.bind("cell-age-coloring", &imp.cell_age_coloring.get(), "active");
+        settings.bind(
+            "cell-age-span",
+            &imp.cell_age_span_adjustment.get(),
+            "value",
+        );
+        if let Ok(age_color) = RGBA::parse(settings.cell_age_color().as_str()) {
+            imp.cell_age_color_picker.set_rgba(&age_color);
+        }
+        imp.cell_age_color_picker
+            .connect_color_set(clone!(@strong settings => move |picker| {
+                settings.set_cell_age_color(picker.rgba().to_string());
+            }));
         settings.bind(
             "allow-render-during-resize",
             &imp.allow_render_on_resize.get(),
@@ -195,6 +684,264 @@ impl GameOfLifePreferencesWindow {
             &imp.evolution_speed_adjustment.get(),
             "value",
         );
+        settings.bind(
+            "universe-width",
+            &imp.universe_width_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "universe-height",
+            &imp.universe_height_adjustment.get(),
+            "value",
+        );
+        settings.bind("restore-session", &imp.restore_session.get(), "active");
+        settings.bind("pause-on-unfocus", &imp.pause_on_unfocus.get(), "active");
+        settings.bind("pause-when-hidden", &imp.pause_when_hidden.get(), "active");
+        settings.bind(
+            "throttle-when-saving-power",
+            &imp.throttle_when_saving_power.get(),
+            "active",
+        );
+        settings.bind("halt-when-settled", &imp.halt_when_settled.get(), "active");
+        settings.bind(
+            "detect-oscillators",
+            &imp.detect_oscillators.get(),
+            "active",
+        );
+        settings.bind(
+            "stylus-brush-radius",
+            &imp.stylus_brush_radius_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "write-crash-reports",
+            &imp.write_crash_reports.get(),
+            "active",
+        );
+
+        // Rows follow the order of SimulationPriority::ALL
+        let priority = settings.simulation_priority();
+        imp.simulation_priority.set_selected(
+            SimulationPriority::ALL
+                .iter()
+                .position(|candidate| *candidate == priority)
+                .unwrap_or_default() as u32,
+        );
+        imp.simulation_priority
+            .connect_selected_notify(clone!(@strong settings => move |row| {
+                if let Some(priority) = SimulationPriority::ALL.get(row.selected() as usize) {
+                    settings.set_simulation_priority(*priority);
+                }
+            }));
+        settings.bind("rain-rate", &imp.rain_rate_adjustment.get(), "value");
+        settings.bind("rain-at-edges", &imp.rain_at_edges.get(), "active");
+        settings.bind(
+            "birth-probability",
+            &imp.birth_probability_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "survival-probability",
+            &imp.survival_probability_adjustment.get(),
+            "value",
+        );
+        settings.bind("temperature", &imp.temperature_adjustment.get(), "value");
+        settings.bind(
+            "stochastic-seed",
+            &imp.stochastic_seed_adjustment.get(),
+            "value",
+        );
+
+        // The first row disables injection, the others follow the library order
+        let library = PatternLibrary::bundled();
+        let pattern_names = gtk::StringList::new(&[&i18n("None")]);
+        for library_pattern in library.iter() {
+            pattern_names.append(&library_pattern.name());
+        }
+        imp.injection_pattern.set_model(Some(&pattern_names));
+        let injection_pattern = settings.injection_pattern();
+        imp.injection_pattern.set_selected(
+            library
+                .iter()
+                .position(|library_pattern| library_pattern.id() == injection_pattern)
+                .map(|position| position + 1)
+                .unwrap_or_default() as u32,
+        );
+        imp.injection_pattern
+            .connect_selected_notify(clone!(@strong settings => move |row| {
+                let id = match row.selected() as usize {
+                    0 => "",
+                    position => library
+                        .get(position - 1)
+                        .map(|library_pattern| library_pattern.id())
+                        .unwrap_or_default(),
+                };
+                settings.set_injection_pattern(id);
+            }));
+
+        // Rows follow the order of InjectionEdge::ALL
+        let edge = settings.injection_edge();
+        imp.injection_edge.set_selected(
+            InjectionEdge::ALL
+                .iter()
+                .position(|candidate| *candidate == edge)
+                .unwrap_or_default() as u32,
+        );
+        imp.injection_edge
+            .connect_selected_notify(clone!(@strong settings => move |row| {
+                if let Some(edge) = InjectionEdge::ALL.get(row.selected() as usize) {
+                    settings.set_injection_edge(*edge);
+                }
+            }));
+        settings.bind(
+            "injection-period",
+            &imp.injection_period_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "reseed-when-stable",
+            &imp.reseed_when_stable.get(),
+            "active",
+        );
+        settings.bind("rewind-depth", &imp.rewind_depth_adjustment.get(), "value");
+        settings.bind(
+            "heatmap-generations",
+            &imp.heatmap_generations_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "reseed-patience",
+            &imp.reseed_patience_adjustment.get(),
+            "value",
+        );
+        settings.bind(
+            "ambient-palette-period",
+            &imp.ambient_palette_period_adjustment.get(),
+            "value",
+        );
+
+        imp.reset_colors_button
+            .connect_clicked(clone!(@strong settings => move |_| {
+                settings.reset_colors();
+            }));
+
+        settings.bind(
+            "screenshot-when-stable",
+            &imp.screenshot_when_stable.get(),
+            "active",
+        );
+        imp.screenshot_folder_row
+            .set_subtitle(&settings.screenshot_folder().to_string_lossy());
+        settings.connect_changed(
+            "screenshot-folder",
+            clone!(@weak self as this, @strong settings => move |_, _| {
+                this.imp()
+                    .screenshot_folder_row
+                    .set_subtitle(&settings.screenshot_folder().to_string_lossy());
+            }),
+        );
+        imp.screenshot_folder_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.select_screenshot_folder();
+            }));
+
+        // Rows follow the order of RecordingFormat::ALL
+        let format = settings.recording_format();
+        imp.recording_format.set_selected(
+            RecordingFormat::ALL
+                .iter()
+                .position(|candidate| *candidate == format)
+                .unwrap_or_default() as u32,
+        );
+        imp.recording_format
+            .connect_selected_notify(clone!(@strong settings => move |row| {
+                if let Some(format) = RecordingFormat::ALL.get(row.selected() as usize) {
+                    settings.set_recording_format(*format);
+                }
+            }));
+        settings.bind(
+            "recording-interval",
+            &imp.recording_interval_adjustment.get(),
+            "value",
+        );
+
+        // Rules are validated before being stored
+        imp.rule_entry.set_text(&settings.rule().to_string());
+        imp.rule_entry
+            .connect_apply(clone!(@strong settings => move |entry| {
+                match entry.text().parse::<UniverseRule>() {
+                    Ok(rule) => {
+                        entry.remove_css_class("error");
+                        entry.set_text(&rule.to_string());
+                        settings.set_rule(&rule);
+                    }
+                    Err(_) => entry.add_css_class("error"),
+                }
+            }));
+        imp.rule_entry.connect_changed(|entry| {
+            entry.remove_css_class("error");
+        });
+
+        // Rows follow the order of RulePreset::ALL, then the rules saved by
+        // the user, with custom rules last. Picking a preset also picks the
+        // colors it looks best with.
+        self.reload_user_rules(&settings);
+        settings.connect_changed(
+            "rule",
+            clone!(@weak self as this, @strong settings => move |_, _| {
+                this.imp().rule_entry.set_text(&settings.rule().to_string());
+                this.update_rule_preset(&settings);
+                this.update_rule_neighbourhood(&settings);
+            }),
+        );
+        imp.rule_preset.connect_selected_notify(
+            clone!(@weak self as this, @strong settings => move |row| {
+                let imp = this.imp();
+                if imp.reloading_user_rules.get() {
+                    return;
+                }
+                let selected = row.selected() as usize;
+                if let Some(preset) = RulePreset::ALL.get(selected) {
+                    if settings.rule() != preset.rule() {
+                        settings.set_dying_color(preset.dying_color().unwrap_or_default());
+                        settings.set_rule(&preset.rule());
+                    }
+                    return;
+                }
+                let user_rule = selected
+                    .checked_sub(RulePreset::ALL.len())
+                    .and_then(|index| imp.user_rules.borrow().get(index).cloned());
+                if let Some(user_rule) = user_rule {
+                    if settings.rule() != *user_rule.rule() {
+                        settings.set_rule(user_rule.rule());
+                    }
+                }
+            }),
+        );
+        imp.rule_editor_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.edit_rule_dialog();
+            }));
+
+        // Rows follow the order of Neighbourhood::ALL. The range is kept as
+        // far as the neighbourhood reaches, it is typed in the rule.
+        self.update_rule_neighbourhood(&settings);
+        imp.rule_neighbourhood
+            .connect_selected_notify(clone!(@strong settings => move |row| {
+                let Some(neighbourhood) = Neighbourhood::ALL.get(row.selected() as usize) else {
+                    return;
+                };
+                let rule = settings.rule();
+                if !rule.is_wireworld()
+                    && !rule.is_colored()
+                    && !rule.is_ant()
+                    && !rule.is_elementary()
+                    && rule.neighbourhood() != *neighbourhood
+                {
+                    let range = rule.range();
+                    settings.set_rule(&rule.with_neighbourhood(*neighbourhood, range));
+                }
+            }));
 
         // Proxy colors to this widget, to convert from RGBA to string
         settings.bind("fg-color", instance.as_ref(), "universe-cell-color");
@@ -237,3 +984,42 @@ impl GameOfLifePreferencesWindow {
         );
     }
 }
+
+/// The name of the action triggered by `shortcut`, as listed in the preferences
+fn shortcut_title(shortcut: Shortcut) -> String {
+    match shortcut {
+        Shortcut::Play => i18n("Run or stop the simulation"),
+        Shortcut::SkipForwardOne => i18n("Step forward one generation"),
+        Shortcut::StepBack => i18n("Step back one generation"),
+        Shortcut::ToggleDesignMode => i18n("Toggle design mode"),
+        Shortcut::ToggleBrush => i18n("Brush tool"),
+        Shortcut::ToggleSelection => i18n("Selection tool"),
+        Shortcut::Cut => i18n("Cut the selection"),
+        Shortcut::Copy => i18n("Copy the selection"),
+        Shortcut::Paste => i18n("Paste a pattern"),
+        Shortcut::Clear => i18n("Clear the universe"),
+        Shortcut::RandomSeed => i18n("Random seed"),
+        Shortcut::Undo => i18n("Undo the last edit"),
+        Shortcut::Redo => i18n("Redo the last undone edit"),
+        Shortcut::ZoomIn => i18n("Zoom in"),
+        Shortcut::ZoomOut => i18n("Zoom out"),
+        Shortcut::ZoomReset => i18n("Fit the universe"),
+        Shortcut::ZoomActualSize => i18n("One pixel per cell"),
+        Shortcut::ZoomSelection => i18n("Zoom to the selection"),
+        Shortcut::InvertColors => i18n("Invert colors"),
+        Shortcut::Fullscreen => i18n("Toggle fullscreen"),
+        Shortcut::Presentation => i18n("Presentation mode"),
+        Shortcut::ToggleRecording => i18n("Start or stop recording"),
+        Shortcut::New => i18n("New universe"),
+        Shortcut::NewEmpty => i18n("New empty universe"),
+        Shortcut::NewTab => i18n("New tab"),
+        Shortcut::CloseTab => i18n("Close tab"),
+        Shortcut::PatternLibrary => i18n("Pattern library"),
+        Shortcut::Snapshot => i18n("Save a snapshot"),
+        Shortcut::OpenSnapshot => i18n("Open a snapshot"),
+        Shortcut::SaveProject => i18n("Save the project"),
+        Shortcut::OpenProject => i18n("Open a project"),
+        Shortcut::Preferences => i18n("Preferences"),
+        Shortcut::Quit => i18n("Quit"),
+    }
+}
@@ -0,0 +1,69 @@
+use crate::models::ResizeAnchor;
+use gtk::{gio, glib};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/resize_universe_view.ui")]
+    pub struct GameOfLifeResizeUniverseView {
+        #[template_child]
+        pub(super) rows_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub(super) columns_adjustment: TemplateChild<gtk::Adjustment>,
+        #[template_child]
+        pub(super) top_left_check: TemplateChild<gtk::CheckButton>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeResizeUniverseView {
+        const NAME: &'static str = "GameOfLifeResizeUniverseView";
+        type Type = super::GameOfLifeResizeUniverseView;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeResizeUniverseView {}
+    impl WidgetImpl for GameOfLifeResizeUniverseView {}
+    impl WindowImpl for GameOfLifeResizeUniverseView {}
+    impl DialogImpl for GameOfLifeResizeUniverseView {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeResizeUniverseView(ObjectSubclass<imp::GameOfLifeResizeUniverseView>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifeResizeUniverseView {
+    /// Creates a dialog starting from the current `rows`x`columns` size
+    pub fn new(rows: usize, columns: usize) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        dialog.imp().rows_adjustment.set_value(rows as f64);
+        dialog.imp().columns_adjustment.set_value(columns as f64);
+        dialog
+    }
+
+    /// The chosen `(rows, columns)`
+    pub fn size(&self) -> (usize, usize) {
+        (
+            self.imp().rows_adjustment.value() as usize,
+            self.imp().columns_adjustment.value() as usize,
+        )
+    }
+
+    pub fn anchor(&self) -> ResizeAnchor {
+        match self.imp().top_left_check.is_active() {
+            true => ResizeAnchor::TopLeft,
+            false => ResizeAnchor::Center,
+        }
+    }
+}
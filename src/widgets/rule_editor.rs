@@ -0,0 +1,241 @@
+use crate::i18n::i18n_f;
+use crate::models::{
+    hensel_arrangement, hensel_arrangements, hensel_letters, HenselConditions, UniverseRule,
+    HENSEL_NEIGHBOURS,
+};
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::{Cell, RefCell};
+
+const ARRANGEMENT_SIZE: i32 = 24;
+
+/// Draws the cell in the middle and its neighbours, the living ones of
+/// `neighbours` filled in
+fn draw_arrangement(
+    area: &gtk::DrawingArea,
+    context: &gtk::cairo::Context,
+    width: i32,
+    height: i32,
+    neighbours: u8,
+    alive: bool,
+) {
+    let cell_size = f64::min(width as f64, height as f64) / 3.0;
+    let offset_x = (width as f64 - cell_size * 3.0) / 2.0;
+    let offset_y = (height as f64 - cell_size * 3.0) / 2.0;
+    let color = area.style_context().color();
+
+    let mut cells = vec![((0, 0), alive)];
+    cells.extend(
+        HENSEL_NEIGHBOURS
+            .iter()
+            .enumerate()
+            .map(|(bit, offset)| (*offset, neighbours >> bit & 1 == 1)),
+    );
+    for ((delta_row, delta_column), alive) in cells {
+        context.set_source_rgba(
+            color.red() as f64,
+            color.green() as f64,
+            color.blue() as f64,
+            color.alpha() as f64 * if alive { 1.0 } else { 0.15 },
+        );
+        context.rectangle(
+            offset_x + (1 + delta_column) as f64 * cell_size + 1.0,
+            offset_y + (1 + delta_row) as f64 * cell_size + 1.0,
+            cell_size - 2.0,
+            cell_size - 2.0,
+        );
+        context.fill().ok();
+    }
+}
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/rule_editor.ui")]
+    pub struct GameOfLifeRuleEditor {
+        #[template_child]
+        pub(super) birth_grid: TemplateChild<gtk::Grid>,
+
+        #[template_child]
+        pub(super) survival_grid: TemplateChild<gtk::Grid>,
+
+        #[template_child]
+        pub(super) rule_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) name_entry: TemplateChild<gtk::Entry>,
+
+        #[template_child]
+        pub(super) save_button: TemplateChild<gtk::Button>,
+
+        /// The rule being edited, whose number of states is kept
+        pub(super) base_rule: RefCell<UniverseRule>,
+
+        pub(super) birth: Cell<HenselConditions>,
+
+        pub(super) survival: Cell<HenselConditions>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeRuleEditor {
+        const NAME: &'static str = "GameOfLifeRuleEditor";
+        type Type = super::GameOfLifeRuleEditor;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeRuleEditor {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().connect_events();
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeRuleEditor {}
+    impl WindowImpl for GameOfLifeRuleEditor {}
+    impl DialogImpl for GameOfLifeRuleEditor {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeRuleEditor(ObjectSubclass<imp::GameOfLifeRuleEditor>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifeRuleEditor {
+    /// Creates an editor starting from `rule`, offering to save it under
+    /// `name`. Only rules counting the eight cells around can be edited, other
+    /// ones are turned into one. WireWorld, colored, ant and elementary rules
+    /// are not edited, the default rule being offered instead.
+    pub fn new(rule: &UniverseRule, name: Option<&str>) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        let imp = dialog.imp();
+        let editable =
+            !rule.is_wireworld() && !rule.is_colored() && !rule.is_ant() && !rule.is_elementary();
+        let rule = match editable {
+            true => rule.clone(),
+            false => UniverseRule::default(),
+        };
+        imp.birth.set(rule.birth_conditions());
+        imp.survival.set(rule.survival_conditions());
+        imp.base_rule.replace(rule);
+        imp.name_entry.set_text(name.unwrap_or_default());
+
+        dialog.fill_grid(&imp.birth_grid, true);
+        dialog.fill_grid(&imp.survival_grid, false);
+        dialog.update_rule_label();
+        dialog
+    }
+
+    fn connect_events(&self) {
+        self.imp()
+            .name_entry
+            .connect_changed(clone!(@weak self as this => move |_| {
+                this.imp().save_button.set_sensitive(!this.name().is_empty());
+            }));
+    }
+
+    /// Adds a row of toggles per count of living neighbours to `grid`, one
+    /// per arrangement, led by a button toggling them all
+    fn fill_grid(&self, grid: &gtk::Grid, birth: bool) {
+        let conditions = match birth {
+            true => self.imp().birth.get(),
+            false => self.imp().survival.get(),
+        };
+
+        for count in 0..=8u8 {
+            let toggles: Vec<gtk::ToggleButton> = (0..hensel_arrangements(count))
+                .map(|index| {
+                    let toggle = self.build_toggle(birth, count, index);
+                    toggle.set_active(conditions.contains(count, index));
+                    grid.attach(&toggle, index as i32 + 1, count as i32, 1, 1);
+                    toggle
+                })
+                .collect();
+
+            let count_button = gtk::Button::builder()
+                .label(&count.to_string())
+                .tooltip_text(&i18n_f(
+                    "All arrangements of {} living neighbours",
+                    &[&count.to_string()],
+                ))
+                .valign(gtk::Align::Center)
+                .css_classes(vec!["flat".to_string(), "heading".to_string()])
+                .build();
+            count_button.connect_clicked(move |_| {
+                let active = !toggles.iter().all(|toggle| toggle.is_active());
+                for toggle in toggles.iter() {
+                    toggle.set_active(active);
+                }
+            });
+            grid.attach(&count_button, 0, count as i32, 1, 1);
+        }
+    }
+
+    fn build_toggle(&self, birth: bool, count: u8, index: usize) -> gtk::ToggleButton {
+        let neighbours = hensel_arrangement(count, index);
+        let arrangement = gtk::DrawingArea::builder()
+            .content_width(ARRANGEMENT_SIZE)
+            .content_height(ARRANGEMENT_SIZE)
+            .build();
+        arrangement.set_draw_func(move |area, context, width, height| {
+            draw_arrangement(area, context, width, height, neighbours, !birth);
+        });
+
+        let letter = hensel_letters(count)
+            .get(index..index + 1)
+            .unwrap_or_default();
+        let name = format!("{}{}", count, letter);
+        let label = gtk::Label::builder()
+            .label(&name)
+            .css_classes(vec!["caption".to_string()])
+            .build();
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        content.append(&arrangement);
+        content.append(&label);
+
+        let toggle = gtk::ToggleButton::builder()
+            .child(&content)
+            .tooltip_text(&name)
+            .build();
+        toggle.connect_toggled(clone!(@weak self as this => move |toggle| {
+            let imp = this.imp();
+            let conditions = match birth {
+                true => &imp.birth,
+                false => &imp.survival,
+            };
+            let mut value = conditions.get();
+            value.set(count, index, toggle.is_active());
+            conditions.set(value);
+            this.update_rule_label();
+        }));
+        toggle
+    }
+
+    fn update_rule_label(&self) {
+        self.imp().rule_label.set_label(&self.rule().to_string());
+    }
+
+    /// The edited rule
+    pub fn rule(&self) -> UniverseRule {
+        let imp = self.imp();
+        imp.base_rule
+            .borrow()
+            .clone()
+            .with_conditions(imp.birth.get(), imp.survival.get())
+    }
+
+    /// The name to save the rule under, empty if none was given
+    pub fn name(&self) -> String {
+        self.imp().name_entry.text().trim().to_string()
+    }
+}
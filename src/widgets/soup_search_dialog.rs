@@ -0,0 +1,359 @@
+use crate::config::G_LOG_DOMAIN;
+use crate::i18n::{i18n, i18n_f};
+use crate::models::{UniverseRule, UniverseSoup};
+use crate::services::{
+    run_soup_search, write_soup_results_csv, write_soup_results_json, GameOfLifeSettings,
+    SoupSearch, SoupSearchEvent, SoupSearchResult,
+};
+use gtk::{gio, glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::{Cell, RefCell};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/soup_search_dialog.ui")]
+    pub struct GameOfLifeSoupSearchDialog {
+        #[template_child]
+        pub(super) results_list: TemplateChild<gtk::ListBox>,
+
+        #[template_child]
+        pub(super) progress_bar: TemplateChild<gtk::ProgressBar>,
+
+        #[template_child]
+        pub(super) soups_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) rule_entry: TemplateChild<gtk::Entry>,
+
+        #[template_child]
+        pub(super) generations_entry: TemplateChild<gtk::SpinButton>,
+
+        #[template_child]
+        pub(super) run_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) export_csv_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) export_json_button: TemplateChild<gtk::Button>,
+
+        /// The last search run, along with the results of its finished soups
+        pub(super) search: RefCell<Option<SoupSearch>>,
+
+        pub(super) results: RefCell<Vec<SoupSearchResult>>,
+
+        /// Set to cancel the search while it runs
+        pub(super) cancelled: RefCell<Option<Arc<AtomicBool>>>,
+
+        pub(super) universe_size: Cell<(usize, usize)>,
+
+        /// How soups are drawn, whose seed is that of the first soup if fixed
+        pub(super) soup: Cell<UniverseSoup>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeSoupSearchDialog {
+        const NAME: &'static str = "GameOfLifeSoupSearchDialog";
+        type Type = super::GameOfLifeSoupSearchDialog;
+        type ParentType = gtk::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeSoupSearchDialog {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().connect_events();
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeSoupSearchDialog {}
+    impl WindowImpl for GameOfLifeSoupSearchDialog {}
+    impl DialogImpl for GameOfLifeSoupSearchDialog {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeSoupSearchDialog(ObjectSubclass<imp::GameOfLifeSoupSearchDialog>)
+        @extends gtk::Widget, gtk::Window, gtk::Dialog,
+        @implements gio::ActionGroup, gio::ActionMap;
+}
+
+impl GameOfLifeSoupSearchDialog {
+    /// Creates a dialog whose searches default to `rule`, run in universes of
+    /// `universe_size` and draw soups like `soup`
+    pub fn new(rule: &UniverseRule, universe_size: (usize, usize), soup: UniverseSoup) -> Self {
+        let dialog = glib::Object::new::<Self>(&[]);
+        let imp = dialog.imp();
+        imp.rule_entry.set_text(&rule.to_string());
+        imp.universe_size.set(universe_size);
+        imp.soup.set(soup);
+        dialog
+    }
+
+    fn connect_events(&self) {
+        let imp = self.imp();
+        imp.run_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                match this.is_running() {
+                    true => this.cancel(),
+                    false => this.run(),
+                }
+            }));
+
+        imp.export_csv_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                let content = write_soup_results_csv(&this.imp().results.borrow());
+                this.export_results(&i18n("Comma separated values"), "text/csv", "csv", content);
+            }));
+
+        imp.export_json_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                let imp = this.imp();
+                let content = match imp.search.borrow().as_ref() {
+                    Some(search) => write_soup_results_json(search, &imp.results.borrow()),
+                    None => return,
+                };
+                this.export_results(&i18n("JSON documents"), "application/json", "json", content);
+            }));
+
+        self.connect_response(|dialog, _| {
+            dialog.cancel();
+            dialog.close();
+        });
+    }
+
+    fn is_running(&self) -> bool {
+        self.imp().cancelled.borrow().is_some()
+    }
+
+    /// Runs a new search, replacing the results of the previous one
+    fn run(&self) {
+        let imp = self.imp();
+        let rule = match imp.rule_entry.text().parse::<UniverseRule>() {
+            Ok(rule) => rule,
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Invalid soup search rule: {:?}", error);
+                imp.rule_entry.add_css_class("error");
+                return;
+            }
+        };
+        imp.rule_entry.remove_css_class("error");
+
+        let soup = imp.soup.get();
+        let first_seed = soup
+            .seed()
+            .map(|seed| seed as u32)
+            .unwrap_or_else(rand::random::<u32>);
+        let search = SoupSearch::new(
+            imp.soups_entry.value() as usize,
+            imp.generations_entry.value() as u32,
+            imp.universe_size.get(),
+            rule,
+            soup,
+            first_seed,
+        );
+
+        while let Some(row) = imp.results_list.row_at_index(0) {
+            imp.results_list.remove(&row);
+        }
+        imp.results.borrow_mut().clear();
+        imp.search.replace(Some(search.clone()));
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        imp.cancelled.replace(Some(cancelled.clone()));
+        self.set_running_state(true);
+        self.update_progress();
+
+        let receiver = run_soup_search(search, cancelled);
+        receiver.attach(
+            None,
+            clone!(@weak self as this => @default-return Continue(false), move |event| {
+                let imp = this.imp();
+                match event {
+                    SoupSearchEvent::Finished(result) => {
+                        imp.results_list.append(&this.build_result_row(&result));
+                        imp.results.borrow_mut().push(result);
+                        this.update_progress();
+                    }
+                    SoupSearchEvent::Done => {
+                        imp.cancelled.replace(None);
+                        this.set_running_state(false);
+                        return Continue(false);
+                    }
+                }
+                Continue(true)
+            }),
+        );
+    }
+
+    /// Stops the running search once each worker is done with its generation
+    fn cancel(&self) {
+        if let Some(cancelled) = self.imp().cancelled.borrow().as_ref() {
+            cancelled.store(true, Ordering::Relaxed);
+        }
+    }
+
+    fn set_running_state(&self, running: bool) {
+        let imp = self.imp();
+        imp.run_button.set_label(&match running {
+            true => i18n("Cancel Search"),
+            false => i18n("Run Search"),
+        });
+        imp.soups_entry.set_sensitive(!running);
+        imp.rule_entry.set_sensitive(!running);
+        imp.generations_entry.set_sensitive(!running);
+
+        let has_results = !running && !imp.results.borrow().is_empty();
+        imp.export_csv_button.set_sensitive(has_results);
+        imp.export_json_button.set_sensitive(has_results);
+    }
+
+    fn update_progress(&self) {
+        let imp = self.imp();
+        let soups = imp
+            .search
+            .borrow()
+            .as_ref()
+            .map(|search| search.soups())
+            .unwrap_or_default();
+        let finished = imp.results.borrow().len();
+        imp.progress_bar
+            .set_fraction(finished as f64 / soups.max(1) as f64);
+        imp.progress_bar.set_text(Some(&i18n_f(
+            "{} of {} soups",
+            &[&finished.to_string(), &soups.to_string()],
+        )));
+    }
+
+    fn build_result_row(&self, result: &SoupSearchResult) -> gtk::ListBoxRow {
+        let seed_label = gtk::Label::builder()
+            .label(&i18n_f("Seed {}", &[&result.seed().to_string()]))
+            .xalign(0.)
+            .build();
+        let stats_label = gtk::Label::builder()
+            .label(&i18n_f(
+                "{} cells alive after {} generations. {}",
+                &[
+                    &result.population().to_string(),
+                    &result.generations().to_string(),
+                    &settled_description(result),
+                ],
+            ))
+            .xalign(0.)
+            .wrap(true)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+        let census_label = gtk::Label::builder()
+            .label(&census_description(result))
+            .xalign(0.)
+            .wrap(true)
+            .css_classes(vec!["dim-label".to_string(), "caption".to_string()])
+            .build();
+
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 3);
+        labels.set_hexpand(true);
+        labels.set_valign(gtk::Align::Center);
+        labels.append(&seed_label);
+        labels.append(&stats_label);
+        labels.append(&census_label);
+
+        let seed = result.seed();
+        let use_button = gtk::Button::builder()
+            .label(&i18n("Use Seed"))
+            .tooltip_text(&i18n("Always draw this soup when randomly seeding"))
+            .valign(gtk::Align::Center)
+            .css_classes(vec!["flat".to_string()])
+            .build();
+        use_button.connect_clicked(move |_| {
+            GameOfLifeSettings::default().set_soup_seed(seed);
+        });
+
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        content.set_margin_start(12);
+        content.set_margin_end(6);
+        content.set_margin_top(6);
+        content.set_margin_bottom(6);
+        content.append(&labels);
+        content.append(&use_button);
+
+        let row = gtk::ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_child(Some(&content));
+        row
+    }
+
+    /// Asks for a file and saves `content` to it
+    fn export_results(&self, filter_name: &str, mime_type: &str, extension: &str, content: String) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export soup search results"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(filter_name));
+        filter.add_mime_type(mime_type);
+        filter.add_pattern(&format!("*.{}", extension));
+        dialog.add_filter(&filter);
+        dialog.set_current_name(&format!("{}.{}", i18n("Soup Search"), extension));
+
+        dialog.connect_response(
+            clone!(@strong dialog => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    if let Err(error) = file.replace_contents(content.as_bytes(), None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                    }
+                }
+            })
+        );
+
+        dialog.show();
+    }
+}
+
+/// Describes whether and how a soup settled down
+fn settled_description(result: &SoupSearchResult) -> String {
+    match result.period() {
+        Some(1) => i18n("Still"),
+        Some(period) => i18n_f("Period {}", &[&period.to_string()]),
+        None => i18n("Never settled"),
+    }
+}
+
+/// Lists the objects a soup ended up with, the most common first among each
+/// kind
+fn census_description(result: &SoupSearchResult) -> String {
+    let census = result.census();
+    let mut objects: Vec<String> = census
+        .counts()
+        .iter()
+        .map(|(object, count)| format!("{} × {}", count, i18n(object.name())))
+        .collect();
+    if census.unrecognized() > 0 {
+        objects.push(format!("{} × {}", census.unrecognized(), i18n("Other")));
+    }
+
+    match objects.is_empty() {
+        true => i18n("No objects"),
+        false => objects.join(", "),
+    }
+}
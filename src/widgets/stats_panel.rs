@@ -0,0 +1,313 @@
+use crate::i18n::i18n;
+use crate::models::ObjectCensus;
+use crate::services::{
+    SimulatedUniverse, SimulationController, SimulationEvent, SimulationListenerId, SimulationStats,
+};
+use gtk::{glib, glib::clone};
+use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How many of the most recent generations are charted
+const STATS_PANEL_GENERATIONS: usize = 200;
+
+/// The colors of the births and deaths charts, from the GNOME palette. The
+/// population is charted with the text color.
+const BIRTHS_COLOR: (f64, f64, f64) = (0.18, 0.76, 0.49);
+const DEATHS_COLOR: (f64, f64, f64) = (0.88, 0.11, 0.14);
+
+/// How often the census is taken at most while generations come in, as it
+/// looks at every cell of the universe
+const CENSUS_INTERVAL: Duration = Duration::from_millis(1000);
+
+mod imp {
+    use super::*;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/stats_panel.ui")]
+    pub struct GameOfLifeStatsPanel {
+        #[template_child]
+        pub(super) population_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) population_chart: TemplateChild<gtk::DrawingArea>,
+        #[template_child]
+        pub(super) births_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) births_chart: TemplateChild<gtk::DrawingArea>,
+        #[template_child]
+        pub(super) deaths_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) deaths_chart: TemplateChild<gtk::DrawingArea>,
+        #[template_child]
+        pub(super) objects_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) census_list: TemplateChild<gtk::ListBox>,
+
+        /// The stats of the most recent generations, oldest first
+        pub(super) history: RefCell<VecDeque<SimulationStats>>,
+
+        /// The simulation charted, with the listener feeding the panel
+        pub(super) controller: RefCell<Option<(SimulationController, SimulationListenerId)>>,
+
+        /// The latest generation, until its census is taken
+        pub(super) census_universe: RefCell<Option<Arc<SimulatedUniverse>>>,
+
+        /// Takes the census of the latest generation once due
+        pub(super) census_timeout: RefCell<Option<glib::SourceId>>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeStatsPanel {
+        const NAME: &'static str = "GameOfLifeStatsPanel";
+        type Type = super::GameOfLifeStatsPanel;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeStatsPanel {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.setup_charts();
+            obj.setup_census();
+        }
+
+        fn dispose(&self) {
+            if let Some((controller, listener)) = self.controller.take() {
+                controller.disconnect(listener);
+            }
+            if let Some(source_id) = self.census_timeout.take() {
+                source_id.remove();
+            }
+        }
+    }
+
+    impl WidgetImpl for GameOfLifeStatsPanel {}
+    impl BoxImpl for GameOfLifeStatsPanel {}
+}
+
+glib::wrapper! {
+    pub struct GameOfLifeStatsPanel(ObjectSubclass<imp::GameOfLifeStatsPanel>)
+        @extends gtk::Widget, gtk::Box,
+        @implements gtk::Orientable;
+}
+
+impl Default for GameOfLifeStatsPanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameOfLifeStatsPanel {
+    pub fn new() -> Self {
+        glib::Object::new::<Self>(&[])
+    }
+
+    fn setup_charts(&self) {
+        let imp = self.imp();
+        imp.population_chart.set_draw_func(
+            clone!(@weak self as this => move |area, cairo_context, width, height| {
+                let color = area.style_context().color();
+                let color = (color.red() as f64, color.green() as f64, color.blue() as f64);
+                this.draw_chart(cairo_context, width, height, color, SimulationStats::population);
+            }),
+        );
+        imp.births_chart.set_draw_func(
+            clone!(@weak self as this => move |_, cairo_context, width, height| {
+                this.draw_chart(cairo_context, width, height, BIRTHS_COLOR, SimulationStats::births);
+            }),
+        );
+        imp.deaths_chart.set_draw_func(
+            clone!(@weak self as this => move |_, cairo_context, width, height| {
+                this.draw_chart(cairo_context, width, height, DEATHS_COLOR, SimulationStats::deaths);
+            }),
+        );
+    }
+
+    /// Charts the stats of `controller` from now on, starting over
+    pub fn set_controller(&self, controller: &SimulationController) {
+        let imp = self.imp();
+        if let Some((previous, listener)) = imp.controller.take() {
+            previous.disconnect(listener);
+        }
+
+        let listener = controller.connect_event(clone!(@weak self as this => move |event| {
+            match event {
+                SimulationEvent::Stats(stats) => this.push(*stats),
+                SimulationEvent::Generation(universe) => {
+                    this.imp().census_universe.replace(Some(universe.clone()));
+                    this.queue_census();
+                }
+                _ => (),
+            }
+        }));
+        imp.controller.replace(Some((controller.clone(), listener)));
+        self.clear();
+    }
+
+    /// Adds the stats of a new generation. Going back to an earlier generation,
+    /// as when a universe is replaced, starts the charts over.
+    pub fn push(&self, stats: SimulationStats) {
+        let imp = self.imp();
+        {
+            let mut history = imp.history.borrow_mut();
+            if history
+                .back()
+                .is_some_and(|last| last.generation() >= stats.generation())
+            {
+                history.clear();
+            }
+            if history.len() == STATS_PANEL_GENERATIONS {
+                history.pop_front();
+            }
+            history.push_back(stats);
+        }
+
+        imp.population_label
+            .set_label(&stats.population().to_string());
+        imp.births_label.set_label(&stats.births().to_string());
+        imp.deaths_label.set_label(&stats.deaths().to_string());
+        self.queue_charts_draw();
+    }
+
+    pub fn clear(&self) {
+        let imp = self.imp();
+        imp.history.borrow_mut().clear();
+        for label in [&imp.population_label, &imp.births_label, &imp.deaths_label] {
+            label.set_label("0");
+        }
+        self.queue_charts_draw();
+        imp.census_universe.replace(None);
+        self.show_census(&ObjectCensus::default());
+    }
+
+    fn setup_census(&self) {
+        let placeholder = gtk::Label::new(Some(&i18n("No objects")));
+        placeholder.add_css_class("dim-label");
+        placeholder.add_css_class("caption");
+        placeholder.set_margin_top(12);
+        placeholder.set_margin_bottom(12);
+        self.imp().census_list.set_placeholder(Some(&placeholder));
+
+        // Generations that came in while hidden are counted once shown
+        self.connect_map(|this| this.queue_census());
+    }
+
+    /// Takes the census of the latest generation, if not done yet, once
+    /// `CENSUS_INTERVAL` passed since the last one
+    fn queue_census(&self) {
+        let imp = self.imp();
+        if !self.is_mapped()
+            || imp.census_timeout.borrow().is_some()
+            || imp.census_universe.borrow().is_none()
+        {
+            return;
+        }
+
+        let source_id = glib::timeout_add_local_once(
+            CENSUS_INTERVAL,
+            clone!(@weak self as this => move || {
+                this.imp().census_timeout.take();
+                if let Some(universe) = this.imp().census_universe.take() {
+                    this.show_census(&universe.census());
+                }
+            }),
+        );
+        imp.census_timeout.replace(Some(source_id));
+    }
+
+    fn show_census(&self, census: &ObjectCensus) {
+        let imp = self.imp();
+        imp.objects_label.set_label(&census.total().to_string());
+
+        let list = &imp.census_list;
+        while let Some(row) = list.row_at_index(0) {
+            list.remove(&row);
+        }
+
+        let rows = census
+            .counts()
+            .iter()
+            .map(|(object, count)| (i18n(object.name()), *count))
+            .chain((census.unrecognized() > 0).then(|| (i18n("Other"), census.unrecognized())));
+        for (name, count) in rows {
+            let name_label = gtk::Label::new(Some(&name));
+            name_label.set_xalign(0.0);
+            name_label.set_hexpand(true);
+            name_label.add_css_class("caption");
+
+            let count_label = gtk::Label::new(Some(&count.to_string()));
+            count_label.add_css_class("caption");
+            count_label.add_css_class("numeric");
+
+            let row = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+            row.set_margin_start(12);
+            row.set_margin_end(12);
+            row.set_margin_top(6);
+            row.set_margin_bottom(6);
+            row.append(&name_label);
+            row.append(&count_label);
+            list.append(&row);
+        }
+    }
+
+    fn queue_charts_draw(&self) {
+        let imp = self.imp();
+        imp.population_chart.queue_draw();
+        imp.births_chart.queue_draw();
+        imp.deaths_chart.queue_draw();
+    }
+
+    /// Draws the `value` of every generation in the history as a filled line,
+    /// scaled so that the largest value reaches the top of the chart
+    fn draw_chart(
+        &self,
+        cairo_context: &gtk::cairo::Context,
+        width: i32,
+        height: i32,
+        (red, green, blue): (f64, f64, f64),
+        value: fn(&SimulationStats) -> usize,
+    ) {
+        let history = self.imp().history.borrow();
+        if history.len() < 2 {
+            return;
+        }
+
+        let (width, height) = (width as f64, height as f64);
+        let max = history.iter().map(value).max().unwrap_or_default().max(1) as f64;
+        let step = width / (STATS_PANEL_GENERATIONS - 1) as f64;
+        let point = |index: usize, stats: &SimulationStats| {
+            (
+                index as f64 * step,
+                height - (value(stats) as f64 / max) * (height - 2.0) - 1.0,
+            )
+        };
+
+        cairo_context.move_to(0.0, height);
+        for (index, stats) in history.iter().enumerate() {
+            let (x, y) = point(index, stats);
+            cairo_context.line_to(x, y);
+        }
+        cairo_context.line_to((history.len() - 1) as f64 * step, height);
+        cairo_context.close_path();
+        cairo_context.set_source_rgba(red, green, blue, 0.2);
+        cairo_context.fill().unwrap();
+
+        for (index, stats) in history.iter().enumerate() {
+            let (x, y) = point(index, stats);
+            cairo_context.line_to(x, y);
+        }
+        cairo_context.set_source_rgb(red, green, blue);
+        cairo_context.set_line_width(1.5);
+        cairo_context.stroke().unwrap();
+    }
+}
@@ -1,3 +1,5 @@
+use crate::i18n::i18n_f;
+use crate::services::GameOfLifeSettings;
 use gtk::{gio, glib};
 use gtk::{prelude::*, subclass::prelude::*, CompositeTemplate};
 
@@ -10,14 +12,53 @@ mod imp {
     #[template(resource = "/com/github/sixpounder/GameOfLife/universe_controls.ui")]
     pub struct GameOfLifeUniverseControls {
         #[template_child]
-        pub(super) run_button: TemplateChild<gtk::Button>,
+        pub(super) run_button: TemplateChild<gtk::ToggleButton>,
 
         #[template_child]
-        pub(super) random_seed_button: TemplateChild<gtk::Button>,
+        pub(super) random_seed_button: TemplateChild<adw::SplitButton>,
+
+        #[template_child]
+        pub(super) soup_density_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) soup_fixed_seed: TemplateChild<gtk::CheckButton>,
+
+        #[template_child]
+        pub(super) soup_seed_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) soup_selection_only: TemplateChild<gtk::CheckButton>,
+
+        #[template_child]
+        pub(super) fast_forward_popover: TemplateChild<gtk::Popover>,
+
+        #[template_child]
+        pub(super) fast_forward_generations_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) rate_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) turbo_button: TemplateChild<gtk::ToggleButton>,
+
+        #[template_child]
+        pub(super) record_button: TemplateChild<gtk::ToggleButton>,
+
+        #[template_child]
+        pub(super) start_box: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) center_box: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) end_box: TemplateChild<gtk::Box>,
 
         pub(super) playing: std::cell::Cell<bool>,
+        pub(super) recording: std::cell::Cell<bool>,
         pub(super) reveal_tools: std::cell::Cell<bool>,
         pub(super) brush_mode: std::cell::Cell<bool>,
+        pub(super) selection_mode: std::cell::Cell<bool>,
+        pub(super) compact: std::cell::Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -37,13 +78,22 @@ mod imp {
     }
 
     impl ObjectImpl for GameOfLifeUniverseControls {
+        fn constructed(&self) {
+            self.parent_constructed();
+            self.obj().bind_soup_settings();
+            self.obj().bind_fast_forward_settings();
+        }
+
         fn properties() -> &'static [ParamSpec] {
             static PROPERTIES: Lazy<Vec<ParamSpec>> = Lazy::new(|| {
                 vec![
                     ParamSpecBoolean::new("playing", "", "", false, ParamFlags::READWRITE),
                     ParamSpecBoolean::new("stopped", "", "", true, ParamFlags::READABLE),
+                    ParamSpecBoolean::new("recording", "", "", false, ParamFlags::READWRITE),
                     ParamSpecBoolean::new("reveal-tools", "", "", false, ParamFlags::READWRITE),
                     ParamSpecBoolean::new("brush-mode", "", "", false, ParamFlags::READWRITE),
+                    ParamSpecBoolean::new("selection-mode", "", "", false, ParamFlags::READWRITE),
+                    ParamSpecBoolean::new("compact", "", "", false, ParamFlags::READWRITE),
                     ParamSpecString::new(
                         "run-button-icon-name",
                         "",
@@ -62,10 +112,13 @@ mod imp {
             match pspec.name() {
                 "playing" => imp.playing.get().to_value(),
                 "stopped" => (!imp.playing.get()).to_value(),
+                "recording" => imp.recording.get().to_value(),
                 "reveal-tools" => imp.reveal_tools.get().to_value(),
                 "brush-mode" => imp.brush_mode.get().to_value(),
+                "selection-mode" => imp.selection_mode.get().to_value(),
+                "compact" => imp.compact.get().to_value(),
                 "run-button-icon-name" => match obj.property("playing") {
-                    true => "media-playback-stop-symbolic",
+                    true => "media-playback-pause-symbolic",
                     false => "media-playback-start-symbolic",
                 }
                 .to_value(),
@@ -82,6 +135,9 @@ mod imp {
                     let run_button = self.run_button.get();
                     let run_button_style = run_button.style_context();
 
+                    // Clicking the toggle flips it even when the run could not start
+                    run_button.set_active(now_playing);
+
                     if now_playing != was_playing {
                         self.playing.set(now_playing);
 
@@ -98,9 +154,19 @@ mod imp {
                         obj.notify("stopped");
                     }
                 }
+                "recording" => {
+                    let recording = value.get::<bool>().unwrap();
+                    self.recording.set(recording);
+
+                    // Clicking the toggle flips it even when the recording could not start
+                    self.record_button.set_active(recording);
+                }
                 "reveal-tools" => {
                     obj.imp().reveal_tools.set(value.get::<bool>().unwrap());
                 }
+                "compact" => {
+                    obj.set_compact(value.get::<bool>().unwrap());
+                }
                 _ => unimplemented!(),
             }
         }
@@ -120,6 +186,82 @@ impl GameOfLifeUniverseControls {
         glib::Object::new::<Self>(&[("application", application)])
     }
 
+    /// Keeps the random seed options in sync with the preferences
+    fn bind_soup_settings(&self) {
+        let imp = self.imp();
+        let settings = GameOfLifeSettings::default();
+        settings.bind("soup-density", &imp.soup_density_adjustment.get(), "value");
+        settings.bind("soup-fixed-seed", &imp.soup_fixed_seed.get(), "active");
+        settings.bind("soup-seed", &imp.soup_seed_adjustment.get(), "value");
+        settings.bind(
+            "soup-selection-only",
+            &imp.soup_selection_only.get(),
+            "active",
+        );
+
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&settings.create_action("soup-symmetry"));
+        self.insert_action_group("soup", Some(&actions));
+    }
+
+    /// Keeps the fast forward options in sync with the preferences
+    fn bind_fast_forward_settings(&self) {
+        let imp = self.imp();
+        let settings = GameOfLifeSettings::default();
+        settings.bind(
+            "fast-forward-generations",
+            &imp.fast_forward_generations_adjustment.get(),
+            "value",
+        );
+
+        settings.bind("turbo", &imp.turbo_button.get(), "active");
+
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&settings.create_action("fast-forward-mode"));
+        actions.add_action(&settings.create_action("turbo"));
+        self.insert_action_group("fast-forward", Some(&actions));
+    }
+
+    /// Fits the controls in narrow windows, moving the less used buttons
+    /// into a menu
+    pub fn set_compact(&self, value: bool) {
+        let imp = self.imp();
+        if imp.compact.replace(value) == value {
+            return;
+        }
+
+        let (spacing, margin) = match value {
+            true => (6, 0),
+            false => (24, 24),
+        };
+        imp.start_box.set_spacing(spacing);
+        imp.start_box.set_margin_start(margin);
+        imp.center_box.set_spacing(spacing);
+        imp.end_box.set_spacing(spacing);
+        imp.end_box.set_margin_end(margin);
+        if value {
+            imp.rate_label.set_visible(false);
+        }
+        self.notify("compact");
+    }
+
+    /// Shows the number of generations computed per second, or hides it
+    pub fn set_generations_per_second(&self, value: Option<f64>) {
+        let rate_label = self.imp().rate_label.get();
+        match value.filter(|_| !self.imp().compact.get()) {
+            Some(rate) => {
+                rate_label.set_label(&i18n_f("{} generations/s", &[&format!("{:.0}", rate)]));
+                rate_label.set_visible(true);
+            }
+            None => rate_label.set_visible(false),
+        }
+    }
+
+    /// Closes the fast forward options, once fast forwarding started
+    pub fn close_fast_forward(&self) {
+        self.imp().fast_forward_popover.popdown();
+    }
+
     pub fn set_tools_revealed(&self, value: bool) {
         self.imp().reveal_tools.set(value);
         self.notify("reveal-tools");
@@ -137,4 +279,25 @@ impl GameOfLifeUniverseControls {
     pub fn brush(&self) -> bool {
         self.imp().brush_mode.get()
     }
+
+    pub fn set_brush(&self, value: bool) {
+        if self.imp().brush_mode.replace(value) != value {
+            self.notify("brush-mode");
+        }
+    }
+
+    pub fn toggle_selection(&self) {
+        self.imp().selection_mode.set(!self.selection());
+        self.notify("selection-mode");
+    }
+
+    pub fn selection(&self) -> bool {
+        self.imp().selection_mode.get()
+    }
+
+    pub fn set_selection(&self, value: bool) {
+        if self.imp().selection_mode.replace(value) != value {
+            self.notify("selection-mode");
+        }
+    }
 }
@@ -1,19 +1,116 @@
 use crate::config::G_LOG_DOMAIN;
+use crate::i18n::i18n_f;
 use crate::models::{
-    Universe, UniverseCell, UniverseGridMode, UniversePoint, UniversePointMatrix, UniverseSnapshot,
+    formats::parse_pattern, ActivityHeatmap, AntHeading, CellDelta, DesignTool, Engine,
+    Neighbourhood, ResizeAnchor, SnapshotTransform, SparseUniverse, SparseViewport,
+    StabilizationDetector, StampBlend, Universe, UniverseArea, UniverseCell, UniverseChance,
+    UniverseHistory, UniverseInjection, UniversePattern, UniversePoint, UniversePointMatrix,
+    UniverseRain, UniverseRule, UniverseSnapshot, UniverseSoup, WireWorldCell, BRUSH_MAX_SIZE,
 };
-use crate::services::GameOfLifeSettings;
-use gtk::{
-    gio,
-    glib::{clone, Receiver, Sender},
-    prelude::*,
-    subclass::prelude::*,
-    CompositeTemplate,
+use crate::services::{
+    record_event, set_engine_state, GameOfLifeSettings, SimulatedUniverse, SimulationController,
+    SimulationEvent, SimulationHaltReason, SimulationListenerId,
 };
+use crate::widgets::{UniverseGridPalette, UniverseGridViewport};
+use gtk::{gio, glib::clone, prelude::*, subclass::prelude::*, CompositeTemplate};
 
 use std::cell::{Cell, RefCell};
 use std::str::FromStr;
 
+/// How long it takes to fade cells out, or back in, when transitioning to a new universe
+const TRANSITION_FADE_DURATION: std::time::Duration = std::time::Duration::from_millis(600);
+
+/// How long drawing a frame may take before the following frames skip details
+const FRAME_BUDGET: std::time::Duration = std::time::Duration::from_millis(12);
+
+/// How many frames skip details once a frame ran over budget, before trying
+/// to draw them again
+const REDUCED_DETAIL_FRAMES: u32 = 30;
+
+/// The number of cells along each side of the textures the cells layer is
+/// uploaded as
+const CELLS_TILE_SIZE: usize = 64;
+
+/// The layout of the premultiplied native endian ARGB pixels of the cells layer
+#[cfg(target_endian = "little")]
+const CELLS_TEXTURE_FORMAT: gtk::gdk::MemoryFormat = gtk::gdk::MemoryFormat::B8g8r8a8Premultiplied;
+#[cfg(target_endian = "big")]
+const CELLS_TEXTURE_FORMAT: gtk::gdk::MemoryFormat = gtk::gdk::MemoryFormat::A8r8g8b8Premultiplied;
+
+/// How long the widget has to keep its size before rendering again, unless
+/// rendering during resizes is allowed
+const RESIZE_SETTLE_DELAY: std::time::Duration = std::time::Duration::from_millis(200);
+
+/// The opacity of the shade over the last frame, while frozen
+const FROZEN_DIM_OPACITY: f32 = 0.3;
+
+/// The distance between the hatching lines of freeze zones, in pixels
+const FREEZE_ZONE_HATCH_SPACING: f64 = 8.0;
+
+/// How large cells have to be, in logical pixels, for grid lines to be drawn
+/// between them
+const GRID_LINES_MIN_CELL_SIZE: f64 = 12.0;
+
+/// The least distance between two coordinate labels, in logical pixels
+const GRID_COORDINATES_SPACING: f64 = 28.0;
+
+/// The number of shades the activity heatmap tints cells with, and how
+/// opaque they are
+const HEATMAP_SHADES: usize = 12;
+const HEATMAP_OPACITY: f64 = 0.6;
+
+/// How much each zoom step enlarges cells
+const ZOOM_STEP: f64 = 1.25;
+
+/// The most zoom steps cells can be enlarged by
+const ZOOM_MAX_LEVEL: u32 = 12;
+
+/// The largest cells can be drawn at a fixed size, in logical pixels
+const ZOOM_MAX_CELL_SIZE: f64 = 256.0;
+
+/// How many cells an unbounded universe is panned by per scroll step, once
+/// the view cannot scroll any further
+const UNBOUNDED_SCROLL_CELLS: f64 = 4.0;
+
+/// The most edits that can be undone
+const UNDO_DEPTH: usize = 50;
+
+/// The viewport showing `universe` on the whole widget area, zoomed in around
+/// the center of the widget
+fn widget_viewport(
+    drawing_area: &GameOfLifeUniverseGrid,
+    universe: &Universe,
+) -> UniverseGridViewport {
+    let (width, height) = (drawing_area.width() as f64, drawing_area.height() as f64);
+    let viewport = UniverseGridViewport::new(width, height, universe.rows(), universe.columns())
+        .with_scale_factor(drawing_area.scale_factor() as f64)
+        .with_hexagonal(universe.rule().neighbourhood() == Neighbourhood::Hexagonal);
+    let viewport = match drawing_area.imp().zoom.get() {
+        UniverseGridZoom::Fit(level) => viewport.with_zoom(ZOOM_STEP.powi(level as i32)),
+        UniverseGridZoom::CellSize(size) => viewport.with_cell_size(Some(size)),
+    };
+
+    // Keeps the focused cell at the center, as long as the universe still
+    // covers the whole widget, and centers the universe along the axes it
+    // does not cover
+    let (cell_width, cell_height) = viewport.cell_size();
+    let (focus_row, focus_column) = drawing_area.imp().zoom_focus.get().unwrap_or((
+        universe.rows() as f64 / 2.0,
+        universe.columns() as f64 / 2.0,
+    ));
+    let pan = |size: f64, cells: usize, cell_size: f64, focus: f64| {
+        let extent = cells as f64 * cell_size;
+        match extent >= size {
+            true => (size / 2.0 - focus * cell_size).clamp(size - extent, 0.0),
+            false => (size - extent) / 2.0,
+        }
+    };
+    viewport.with_pan(
+        pan(width, universe.rows(), cell_width, focus_row),
+        pan(height, universe.columns(), cell_height, focus_column),
+    )
+}
+
 /// Maps a point on the widget area onto a cell in a given universe
 fn widget_area_point_to_universe_cell(
     drawing_area: &GameOfLifeUniverseGrid,
@@ -21,16 +118,75 @@ fn widget_area_point_to_universe_cell(
     x: f64,
     y: f64,
 ) -> Option<UniversePoint> {
-    if let Some(universe) = universe {
-        let (widget_width, widget_height) = (drawing_area.width(), drawing_area.height());
-        let (universe_width, universe_height) = (universe.rows(), universe.columns());
+    let universe = universe?;
+    let (row, column) = widget_viewport(drawing_area, universe).cell_at(x, y)?;
+    universe.get(row, column)
+}
 
-        let universe_row = ((x.round() as i32) * universe_width as i32) / widget_width;
-        let universe_column = ((y.round() as i32) * universe_height as i32) / widget_height;
+/// The cells within `radius` of `center`, as `(row, column)`, leaving out those
+/// past the edges of a `rows`x`columns` universe
+fn brush_cells(
+    center: (usize, usize),
+    radius: usize,
+    rows: usize,
+    columns: usize,
+) -> Vec<(usize, usize)> {
+    let radius = radius as i64;
+    let (center_row, center_column) = (center.0 as i64, center.1 as i64);
+    let mut cells = vec![];
+    for delta_row in -radius..=radius {
+        for delta_column in -radius..=radius {
+            let (row, column) = (center_row + delta_row, center_column + delta_column);
+            if delta_row * delta_row + delta_column * delta_column <= radius * radius
+                && (0..rows as i64).contains(&row)
+                && (0..columns as i64).contains(&column)
+            {
+                cells.push((row as usize, column as usize));
+            }
+        }
+    }
+    cells
+}
 
-        universe.get(universe_row as usize, universe_column as usize)
-    } else {
-        None
+/// The cells of a square `size` cells wide over `center`, as `(row, column)`,
+/// leaving out those past the edges of a `rows`x`columns` universe. Even sizes
+/// reach one cell further towards the bottom right.
+fn square_brush_cells(
+    center: (usize, usize),
+    size: usize,
+    rows: usize,
+    columns: usize,
+) -> Vec<(usize, usize)> {
+    let size = size.clamp(1, BRUSH_MAX_SIZE);
+    let first_row = center.0.saturating_sub((size - 1) / 2);
+    let first_column = center.1.saturating_sub((size - 1) / 2);
+    let last_row = (center.0 + size / 2).min(rows.saturating_sub(1));
+    let last_column = (center.1 + size / 2).min(columns.saturating_sub(1));
+    let mut cells = vec![];
+    for row in first_row..=last_row {
+        for column in first_column..=last_column {
+            cells.push((row, column));
+        }
+    }
+    cells
+}
+
+/// The cells a stroke covers around each cell it passes over
+#[derive(Clone, Copy, Debug)]
+enum Brush {
+    /// A square this many cells wide, as set for the design tools
+    Square(usize),
+
+    /// A disk of this radius, as drawn by a stylus
+    Disk(usize),
+}
+
+impl Brush {
+    fn cells(self, center: (usize, usize), rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        match self {
+            Brush::Square(size) => square_brush_cells(center, size, rows, columns),
+            Brush::Disk(radius) => brush_cells(center, radius, rows, columns),
+        }
     }
 }
 
@@ -39,204 +195,1343 @@ fn snapshot_grid(
     snapshot: &gtk::Snapshot,
     bounds: &gtk::graphene::Rect,
 ) {
-    // Determine colors
-    let fg_color = widget.fg_color.get().unwrap();
-    let bg_color = widget.bg_color.get().unwrap();
-    let wants_outlines = widget.draw_cells_outline.get();
-    let fades_dead_cells = widget.fades_dead_cells.get();
-
-    let mut outline_color = bg_color;
-    outline_color.set_red(outline_color.red() + 0.1);
-    outline_color.set_green(outline_color.green() + 0.1);
-    outline_color.set_blue(outline_color.blue() + 0.1);
+    // Determine colors, overlays fading along with the cells
+    let palette = match widget.inverted_colors.get() {
+        true => widget.palette.get().inverted(),
+        false => widget.palette.get(),
+    };
+    let opacity = widget.transition_opacity.get().unwrap_or(1.0);
+    let faded = |mut color: gtk::gdk::RGBA| {
+        color.set_alpha(color.alpha() * opacity as f32);
+        color
+    };
+    let fg_color = faded(palette.cell());
+    let selection_color = faded(palette.selection());
+    let ghost_color = faded(palette.ghost());
+    // Outlines and hatching are skipped while frames run over budget
+    let reduced_detail = widget.reduced_detail_frames.get() > 0;
+    let wants_outlines = widget.draw_cells_outline.get() && !reduced_detail;
+    let cell_style = UniverseGridCellStyle {
+        palette,
+        fades_dead_cells: widget.fades_dead_cells.get(),
+        death_trail: widget.death_trail.get(),
+    };
+    let outline_color = palette.outline();
 
     // Paint the background
-    snapshot.append_color(&bg_color, bounds);
+    snapshot.append_color(&palette.background(), bounds);
 
-    // Create a utility cairo context
+    // Create a utility cairo context, for what lies below the cells
     let cairo_context = snapshot.append_cairo(bounds);
 
-    // Get a lock on the universe object
-    let universe = widget.universe.borrow();
+    // Get a lock on the universe object, or on the reference one while comparing
+    let comparing = widget.comparing.get() && widget.reference.borrow().is_some();
+    let universe = match comparing {
+        true => widget.reference.borrow(),
+        false => widget.universe.borrow(),
+    };
     if let Some(universe) = universe.as_ref() {
-        let (width, height) = (
-            bounds.width() as f64 / universe.columns() as f64,
-            bounds.height() as f64 / universe.rows() as f64,
-        );
+        let viewport = widget_viewport(&widget.obj(), universe);
+
+        if wants_outlines {
+            cairo_context.set_line_width(1.0);
+            cairo_context.set_source_rgba(
+                outline_color.red() as f64,
+                outline_color.green() as f64,
+                outline_color.blue() as f64,
+                outline_color.alpha() as f64,
+            );
+            if viewport.is_hexagonal() {
+                trace_hexagons(&cairo_context, &viewport);
+            } else {
+                for w in 0..=universe.rows() {
+                    let (x, y, _, height) = viewport.area_rect(w, 0, 0, universe.columns());
+                    cairo_context.move_to(x, y);
+                    cairo_context.line_to(x, y + height);
+                }
+                for h in 0..=universe.columns() {
+                    let (x, y, width, _) = viewport.area_rect(0, h, universe.rows(), 0);
+                    cairo_context.move_to(x, y);
+                    cairo_context.line_to(x + width, y);
+                }
+            }
+            cairo_context.stroke().unwrap();
+        }
+
+        // The reference is not tracked by damage, repaint it as a whole
+        let damage = match comparing {
+            true => UniverseGridDamage::Full,
+            false => widget.damage.replace(UniverseGridDamage::None),
+        };
+        let mut cells_layer = widget.cells_layer.borrow_mut();
+        cells_layer.update(universe, damage, &cell_style);
+
+        snapshot.push_opacity(opacity);
+        match viewport.is_hexagonal() {
+            true => cells_layer.append_hexagons(&snapshot.append_cairo(bounds), &viewport),
+            false => cells_layer.append_tiles(snapshot, &viewport, bounds),
+        }
+        snapshot.pop();
+
+        // Overlays are drawn on top of the cells
+        let cairo_context = snapshot.append_cairo(bounds);
+        let (cell_width, cell_height) = viewport.cell_size();
+
+        if let Some(heatmap) = widget.heatmap.borrow().as_ref() {
+            draw_heatmap(&cairo_context, &viewport, heatmap);
+        }
+        draw_ants(&cairo_context, &viewport, universe);
+
+        // Separate cells once they are large enough to be edited one by one
+        let zoomed_in = cell_width.min(cell_height) >= GRID_LINES_MIN_CELL_SIZE;
+        if zoomed_in && !reduced_detail {
+            let line_color = palette.grid_lines();
+            if !wants_outlines {
+                draw_grid_lines(&cairo_context, &viewport, universe, &line_color);
+            }
+            if widget.shows_grid_coordinates.get() {
+                draw_grid_coordinates(&cairo_context, &viewport, universe, &line_color);
+            }
+        }
+
+        // Hatch the freeze zones
+        let freeze_zones = match reduced_detail {
+            true => vec![],
+            false => widget.freeze_zones.borrow().clone(),
+        };
+        for zone in freeze_zones.iter() {
+            let (x, y, width, height) =
+                viewport.area_rect(zone.row(), zone.column(), zone.rows(), zone.columns());
+            cairo_context.save().unwrap();
+            cairo_context.rectangle(x, y, width, height);
+            cairo_context.clip_preserve();
+            cairo_context.set_source_rgba(
+                fg_color.red() as f64,
+                fg_color.green() as f64,
+                fg_color.blue() as f64,
+                0.5,
+            );
+            cairo_context.set_line_width(1.0);
+            cairo_context.stroke().unwrap();
+            let mut offset = 0.0;
+            while offset < width + height {
+                cairo_context.move_to(x + offset, y);
+                cairo_context.line_to(x + offset - height, y + height);
+                offset += FREEZE_ZONE_HATCH_SPACING;
+            }
+            cairo_context.set_source_rgba(
+                fg_color.red() as f64,
+                fg_color.green() as f64,
+                fg_color.blue() as f64,
+                0.25,
+            );
+            cairo_context.stroke().unwrap();
+            cairo_context.restore().unwrap();
+        }
+
+        // Highlight the selected area
+        if let Some(area) = widget.selection.get() {
+            let selection_rect =
+                viewport.area_rect(area.row(), area.column(), area.rows(), area.columns());
+            cairo_context.rectangle(
+                selection_rect.0,
+                selection_rect.1,
+                selection_rect.2,
+                selection_rect.3,
+            );
+            cairo_context.set_source_rgba(
+                selection_color.red() as f64,
+                selection_color.green() as f64,
+                selection_color.blue() as f64,
+                0.15,
+            );
+            cairo_context.fill_preserve().unwrap();
+            cairo_context.set_line_width(2.0);
+            cairo_context.set_dash(&[6.0, 4.0], 0.0);
+            cairo_context.set_source_rgba(
+                selection_color.red() as f64,
+                selection_color.green() as f64,
+                selection_color.blue() as f64,
+                0.9,
+            );
+            cairo_context.stroke().unwrap();
+            cairo_context.set_dash(&[], 0.0);
+        }
 
-        for el in universe.iter_cells() {
-            let w = el.row();
-            let h = el.column();
-            let coords: (f64, f64) = ((w as f64) * width, (h as f64) * height);
+        // Preview the pattern being placed under the pointer
+        if let (Some(pattern), Some((origin_row, origin_column))) = (
+            widget.placing.borrow().as_ref(),
+            widget.obj().placement_origin(),
+        ) {
+            for (pattern_row, pattern_column) in pattern.iter_alive() {
+                let (x, y, width, height) = viewport.cell_rect(
+                    (origin_row + pattern_row) % universe.rows(),
+                    (origin_column + pattern_column) % universe.columns(),
+                );
+                let ghost_rect_bounds =
+                    gtk::graphene::Rect::new(x as f32, y as f32, width as f32, height as f32);
+                snapshot.append_color(&ghost_color, &ghost_rect_bounds);
+            }
+        }
 
-            if wants_outlines {
-                cairo_context.rectangle(coords.0, coords.1, width, height);
-                cairo_context.set_line_width(1.0);
+        // Outline the cell edited from the keyboard
+        if let Some((row, column)) = widget.obj().keyboard_cursor() {
+            if widget.obj().has_visible_focus() {
+                let (x, y, width, height) = viewport.cell_rect(row, column);
+                cairo_context.rectangle(x, y, width, height);
+                cairo_context.set_line_width(2.0);
                 cairo_context.set_source_rgba(
-                    outline_color.red() as f64,
-                    outline_color.green() as f64,
-                    outline_color.blue() as f64,
-                    outline_color.alpha() as f64,
+                    selection_color.red() as f64,
+                    selection_color.green() as f64,
+                    selection_color.blue() as f64,
+                    0.9,
                 );
                 cairo_context.stroke().unwrap();
             }
-            if el.cell().is_alive() {
-                let cell_rect_bounds = gtk::graphene::Rect::new(
-                    coords.0 as f32,
-                    coords.1 as f32,
-                    width as f32,
-                    height as f32,
-                );
-                snapshot.append_color(&fg_color, &cell_rect_bounds);
-            } else if fades_dead_cells {
-                let transparency_factor = el.corpse_heat();
-                if transparency_factor > 0.0 {
-                    let cell_rect_bounds = gtk::graphene::Rect::new(
-                        coords.0 as f32,
-                        coords.1 as f32,
-                        width as f32,
-                        height as f32,
-                    );
-                    let mut fade_color = fg_color;
-                    fade_color.set_alpha(fade_color.alpha() * transparency_factor as f32);
-                    snapshot.append_color(&fade_color, &cell_rect_bounds);
-                }
+        }
+
+        // Preview the shape being drawn with the design tool
+        if let Some((from, to)) = widget.shape_stroke.get() {
+            for (row, column) in widget.tool.get().shape_cells(from, to) {
+                let (x, y, width, height) = viewport.cell_rect(row, column);
+                let ghost_rect_bounds =
+                    gtk::graphene::Rect::new(x as f32, y as f32, width as f32, height as f32);
+                snapshot.append_color(&ghost_color, &ghost_rect_bounds);
             }
         }
     } else {
         glib::warn!("No universe to render");
     }
+
+    if widget.shows_performance_hud.get() {
+        draw_performance_hud(&snapshot.append_cairo(bounds), widget.render_stats.get());
+    }
 }
 
-#[derive(Debug)]
-pub enum UniverseGridRequest {
-    /// Restores normal rendering operations
-    #[allow(dead_code)]
-    Unfreeze,
+/// Tints every visible cell that was alive over the generations of `heatmap`
+/// by how often it was, from blue for cells seldom alive to red for those
+/// always alive
+fn draw_heatmap(
+    cairo_context: &gtk::cairo::Context,
+    viewport: &UniverseGridViewport,
+    heatmap: &ActivityHeatmap,
+) {
+    let mut shades = vec![vec![]; HEATMAP_SHADES];
+    let (rows, columns) = viewport.visible_cells();
+    for row in rows {
+        for column in columns.clone() {
+            let heat = heatmap.heat(row, column);
+            if heat > 0.0 {
+                let shade = ((heat * HEATMAP_SHADES as f64).ceil() as usize).min(HEATMAP_SHADES);
+                shades[shade - 1].push((row, column));
+            }
+        }
+    }
 
-    /// Requests the grid to redraw itself. If the value is Some(universe) the contained
-    /// value will replace the current model inside the widget
-    Redraw(Option<Universe>),
+    cairo_context.save().unwrap();
+    for (shade, cells) in shades.iter().enumerate() {
+        if cells.is_empty() {
+            continue;
+        }
+        let hue = (HEATMAP_SHADES - 1 - shade) as f32 / (HEATMAP_SHADES - 1) as f32 * 0.66;
+        let (red, green, blue) = gtk::hsv_to_rgb(hue, 1.0, 1.0);
+        cairo_context.set_source_rgba(red as f64, green as f64, blue as f64, HEATMAP_OPACITY);
+        for (row, column) in cells {
+            match viewport.is_hexagonal() {
+                true => trace_polygon(cairo_context, &viewport.cell_hexagon(*row, *column)),
+                false => {
+                    let (x, y, width, height) = viewport.cell_rect(*row, *column);
+                    cairo_context.rectangle(x, y, width, height);
+                }
+            }
+        }
+        cairo_context.fill().unwrap();
+    }
+    cairo_context.restore().unwrap();
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[repr(u8)]
-enum UniverseGridInteractionState {
-    Idle,
-    Ongoing,
+/// Draws each ant of `universe` as a triangle pointing the way it faces
+fn draw_ants(
+    cairo_context: &gtk::cairo::Context,
+    viewport: &UniverseGridViewport,
+    universe: &Universe,
+) {
+    let ants = universe.ants();
+    if ants.is_empty() {
+        return;
+    }
+
+    cairo_context.save().unwrap();
+    let color = colored_cell_color(1);
+    cairo_context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64,
+    );
+    for (row, column, heading) in ants {
+        let (x, y, width, height) = viewport.cell_rect(row, column);
+        let (center_x, center_y) = (x + width / 2.0, y + height / 2.0);
+        // Rows run along the x axis, as everywhere on the grid
+        let (row_offset, column_offset) = heading.offset();
+        let (toward_x, toward_y) = (row_offset as f64 * width, column_offset as f64 * height);
+        let (across_x, across_y) = (column_offset as f64 * width, row_offset as f64 * height);
+        trace_polygon(
+            cairo_context,
+            &[
+                (center_x + toward_x * 0.4, center_y + toward_y * 0.4),
+                (
+                    center_x - toward_x * 0.3 + across_x * 0.35,
+                    center_y - toward_y * 0.3 + across_y * 0.35,
+                ),
+                (
+                    center_x - toward_x * 0.3 - across_x * 0.35,
+                    center_y - toward_y * 0.3 - across_y * 0.35,
+                ),
+            ],
+        );
+    }
+    cairo_context.fill().unwrap();
+    cairo_context.restore().unwrap();
 }
 
-impl Default for UniverseGridInteractionState {
-    fn default() -> Self {
-        Self::Idle
+/// Draws lines between the cells of `universe`, over living cells too
+fn draw_grid_lines(
+    cairo_context: &gtk::cairo::Context,
+    viewport: &UniverseGridViewport,
+    universe: &Universe,
+    color: &gtk::gdk::RGBA,
+) {
+    cairo_context.save().unwrap();
+    cairo_context.set_line_width(1.0);
+    cairo_context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64,
+    );
+    if viewport.is_hexagonal() {
+        trace_hexagons(cairo_context, viewport);
+        cairo_context.stroke().unwrap();
+        cairo_context.restore().unwrap();
+        return;
+    }
+
+    // Lines are centered on pixels so that they stay one pixel wide
+    for row in 0..=universe.rows() {
+        let (x, y, _, height) = viewport.area_rect(row, 0, 0, universe.columns());
+        cairo_context.move_to(x + 0.5, y);
+        cairo_context.line_to(x + 0.5, y + height);
+    }
+    for column in 0..=universe.columns() {
+        let (x, y, width, _) = viewport.area_rect(0, column, universe.rows(), 0);
+        cairo_context.move_to(x, y + 0.5);
+        cairo_context.line_to(x + width, y + 0.5);
     }
+    cairo_context.stroke().unwrap();
+    cairo_context.restore().unwrap();
 }
 
-mod imp {
-    use super::*;
-    use glib::{
-        types::StaticType, ParamFlags, ParamSpec, ParamSpecBoolean, ParamSpecEnum, ParamSpecUInt,
+/// Adds the outlines of the hexagonal cells visible on the widget to the
+/// current path
+fn trace_hexagons(cairo_context: &gtk::cairo::Context, viewport: &UniverseGridViewport) {
+    let (rows, columns) = viewport.visible_cells();
+    for column in columns {
+        for row in rows.clone() {
+            trace_polygon(cairo_context, &viewport.cell_hexagon(row, column));
+        }
+    }
+}
+
+fn trace_polygon(cairo_context: &gtk::cairo::Context, corners: &[(f64, f64)]) {
+    for (index, (x, y)) in corners.iter().enumerate() {
+        match index {
+            0 => cairo_context.move_to(*x, *y),
+            _ => cairo_context.line_to(*x, *y),
+        }
+    }
+    cairo_context.close_path();
+}
+
+/// Labels rows along the top edge and columns along the left edge, as far
+/// apart as needed for the labels not to overlap
+fn draw_grid_coordinates(
+    cairo_context: &gtk::cairo::Context,
+    viewport: &UniverseGridViewport,
+    universe: &Universe,
+    color: &gtk::gdk::RGBA,
+) {
+    let (cell_width, cell_height) = viewport.cell_size();
+    let spacing = |cell_size: f64| {
+        [1, 2, 5, 10, 20, 50, 100, 200, 500]
+            .into_iter()
+            .find(|step| *step as f64 * cell_size >= GRID_COORDINATES_SPACING)
+            .unwrap_or(1000)
     };
-    use once_cell::sync::Lazy;
 
-    #[derive(Debug, Default, CompositeTemplate)]
-    #[template(resource = "/com/github/sixpounder/GameOfLife/universe_grid.ui")]
-    pub struct GameOfLifeUniverseGrid {
-        pub(super) settings: GameOfLifeSettings,
+    cairo_context.save().unwrap();
+    cairo_context.select_font_face(
+        "monospace",
+        gtk::cairo::FontSlant::Normal,
+        gtk::cairo::FontWeight::Normal,
+    );
+    cairo_context.set_font_size(10.0);
+    cairo_context.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        (color.alpha() as f64 * 5.0).min(1.0),
+    );
+    for row in (0..universe.rows()).step_by(spacing(cell_width)) {
+        let (x, y, _, _) = viewport.cell_rect(row, 0);
+        cairo_context.move_to(x + 2.0, y + 11.0);
+        cairo_context.show_text(&row.to_string()).unwrap();
+    }
+    // The first column is already labeled by the first row
+    for column in (0..universe.columns())
+        .step_by(spacing(cell_height))
+        .skip(1)
+    {
+        let (x, y, _, _) = viewport.cell_rect(0, column);
+        cairo_context.move_to(x + 2.0, y + 11.0);
+        cairo_context.show_text(&column.to_string()).unwrap();
+    }
+    cairo_context.restore().unwrap();
+}
 
-        pub(super) frozen: Cell<bool>,
+/// How long frames take to draw, and how many had to skip details
+#[derive(Debug, Default, Clone, Copy)]
+struct UniverseGridRenderStats {
+    frames: u64,
+    reduced_frames: u64,
+    last_frame_time: std::time::Duration,
+}
 
-        pub(super) mode: Cell<UniverseGridMode>,
+/// Overlays the rendering statistics in the top left corner
+fn draw_performance_hud(cairo_context: &gtk::cairo::Context, stats: UniverseGridRenderStats) {
+    let lines = [
+        format!(
+            "{:.1} ms / {} ms",
+            stats.last_frame_time.as_secs_f64() * 1000.0,
+            FRAME_BUDGET.as_millis()
+        ),
+        format!("{} frames, {} reduced", stats.frames, stats.reduced_frames),
+    ];
 
-        pub(super) universe: RefCell<Option<Universe>>,
+    cairo_context.save().unwrap();
+    cairo_context.select_font_face(
+        "monospace",
+        gtk::cairo::FontSlant::Normal,
+        gtk::cairo::FontWeight::Normal,
+    );
+    cairo_context.set_font_size(12.0);
+    cairo_context.rectangle(6.0, 6.0, 220.0, 40.0);
+    cairo_context.set_source_rgba(0.0, 0.0, 0.0, 0.6);
+    cairo_context.fill().unwrap();
+    cairo_context.set_source_rgba(1.0, 1.0, 1.0, 0.9);
+    for (index, line) in lines.iter().enumerate() {
+        cairo_context.move_to(12.0, 22.0 + index as f64 * 16.0);
+        cairo_context.show_text(line).unwrap();
+    }
+    cairo_context.restore().unwrap();
+}
 
-        pub(super) receiver: RefCell<Option<Receiver<UniverseGridRequest>>>,
+/// The color of a cell in `state` in a WireWorld universe, after the usual
+/// yellow wires with blue electron heads and red tails
+fn wireworld_color(state: u8) -> gtk::gdk::RGBA {
+    match WireWorldCell::ALL
+        .into_iter()
+        .find(|cell| cell.state() == state)
+    {
+        Some(WireWorldCell::Conductor) => gtk::gdk::RGBA::new(0.965, 0.827, 0.176, 1.0),
+        Some(WireWorldCell::Head) => gtk::gdk::RGBA::new(0.208, 0.518, 0.894, 1.0),
+        Some(WireWorldCell::Tail) => gtk::gdk::RGBA::new(0.878, 0.106, 0.141, 1.0),
+        None => gtk::gdk::RGBA::new(0.0, 0.0, 0.0, 0.0),
+    }
+}
 
-        pub(super) sender: Option<Sender<UniverseGridRequest>>,
+/// The color of a living cell of `color` under a colored rule, blue, red,
+/// green or yellow as in QuadLife. The same colors are set in the style sheet
+/// for the palette of the design tools.
+fn colored_cell_color(color: u8) -> gtk::gdk::RGBA {
+    match color {
+        0 => gtk::gdk::RGBA::new(0.208, 0.518, 0.894, 1.0),
+        1 => gtk::gdk::RGBA::new(0.878, 0.106, 0.141, 1.0),
+        2 => gtk::gdk::RGBA::new(0.180, 0.761, 0.494, 1.0),
+        _ => gtk::gdk::RGBA::new(0.961, 0.761, 0.067, 1.0),
+    }
+}
 
-        pub(super) render_thread_stopper: RefCell<Option<std::sync::mpsc::Receiver<()>>>,
+/// The color of a cell of `color` under an ant rule with `colors` colors: the
+/// first one is left empty, the second one is the cell color and the others
+/// go around the color wheel
+fn ant_cell_color(cell_color: &gtk::gdk::RGBA, color: u8, colors: u8) -> gtk::gdk::RGBA {
+    match color {
+        0 => gtk::gdk::RGBA::new(0.0, 0.0, 0.0, 0.0),
+        1 => *cell_color,
+        _ => {
+            let hue = (color - 2) as f32 / (colors - 2) as f32;
+            let (red, green, blue) = gtk::hsv_to_rgb(hue, 0.7, 0.9);
+            gtk::gdk::RGBA::new(red, green, blue, cell_color.alpha())
+        }
+    }
+}
 
-        pub(super) allow_draw_on_resize: Cell<bool>,
+/// The opacity of a cell in the dying `state` of a Generations rule with
+/// `states` states, lower the closer it is to death
+fn dying_opacity(state: u8, states: u8) -> f32 {
+    (states - state) as f32 / (states - 1) as f32
+}
 
-        pub(super) fg_color: Cell<Option<gtk::gdk::RGBA>>,
+/// How the cells layer paints cells
+#[derive(Debug)]
+struct UniverseGridCellStyle {
+    palette: UniverseGridPalette,
+    fades_dead_cells: bool,
 
-        pub(super) bg_color: Cell<Option<gtk::gdk::RGBA>>,
+    /// The number of generations dead cells fade out over, 0 for none
+    death_trail: u32,
+}
 
-        pub(super) point_under_pointing_device: Cell<Option<UniversePoint>>,
+impl UniverseGridCellStyle {
+    /// The color of the cell at `point`, including its opacity
+    fn color_at(&self, universe: &Universe, point: &UniversePoint) -> gtk::gdk::RGBA {
+        let (row, column) = (point.row(), point.column());
+        if universe.rule().is_wireworld() {
+            return wireworld_color(universe.cell_state(row, column));
+        }
 
-        pub(super) evolution_speed: Cell<u32>,
+        if point.cell().is_alive() && universe.rule().is_colored() {
+            return colored_cell_color(universe.cell_color(row, column));
+        }
 
-        pub(super) animated: Cell<bool>,
+        if universe.rule().is_ant() {
+            return ant_cell_color(
+                &self.palette.cell(),
+                universe.cell_color(row, column),
+                universe.rule().ant_colors(),
+            );
+        }
 
-        pub(super) draw_cells_outline: Cell<bool>,
+        if point.cell().is_alive() {
+            return match self.palette.age_gradient() {
+                Some(gradient) => {
+                    gradient.color_at(&self.palette.cell(), universe.cell_age(row, column))
+                }
+                None => self.palette.cell(),
+            };
+        }
 
-        pub(super) fades_dead_cells: Cell<bool>,
+        // Dying cells fade out state after state, whatever the other effects
+        let state = universe.cell_state(row, column);
+        if state > 1 {
+            let states = universe.rule().states();
+            let mut color = self.palette.dying();
+            color.set_alpha(color.alpha() * dying_opacity(state, states));
+            return color;
+        }
 
-        pub(super) interaction_state: Cell<UniverseGridInteractionState>,
+        let corpse_opacity = match self.fades_dead_cells {
+            true => point.corpse_heat().clamp(0.0, 1.0),
+            false => 0.0,
+        };
+        let trail_opacity = match universe.last_alive_generation(row, column) {
+            Some(last_alive) if self.death_trail > 0 => {
+                let dead_for = universe.generations().saturating_sub(last_alive) as f64;
+                (1.0 - dead_for / (self.death_trail as f64 + 1.0)).max(0.0)
+            }
+            _ => 0.0,
+        };
+
+        let mut color = self.palette.cell();
+        color.set_alpha(color.alpha() * corpse_opacity.max(trail_opacity) as f32);
+        color
     }
+}
 
-    #[glib::object_subclass]
-    impl ObjectSubclass for GameOfLifeUniverseGrid {
-        const NAME: &'static str = "GameOfLifeUniverseGrid";
-        type Type = super::GameOfLifeUniverseGrid;
-        type ParentType = gtk::Widget;
+/// What changed in the universe since the grid was last drawn
+#[derive(Debug, Default)]
+enum UniverseGridDamage {
+    /// Nothing, the cells can be drawn as they were
+    None,
 
-        fn new() -> Self {
-            let (sender, r) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
-            let receiver = RefCell::new(Some(r));
+    /// Only these cells changed
+    Cells(Vec<UniversePoint>),
+
+    /// Anything may have changed
+    #[default]
+    Full,
+}
 
-            let mut this = Self::default();
+/// A square of cells of the cells layer, uploaded as a texture at the device
+/// resolution it is shown at
+#[derive(Debug, Default)]
+struct UniverseGridCellsTile {
+    texture: Option<gtk::gdk::Texture>,
 
-            this.universe.replace(Some(Universe::new_random(
-                this.settings.universe_width() as usize,
-                this.settings.universe_height() as usize,
-            )));
+    /// The `(x, y, width, height)` device pixels the texture was scaled to
+    rect: (i32, i32, i32, i32),
 
-            this.receiver = receiver;
-            this.sender = Some(sender);
+    /// Bumped whenever a cell of the tile changes
+    version: u64,
 
-            // Start universe in running mode
-            this.mode.set(UniverseGridMode::Locked);
+    /// The version the texture was scaled from
+    texture_version: u64,
+}
 
-            // Defaults to light color scheme
-            this.fg_color.set(Some(
-                gtk::gdk::RGBA::from_str(&this.settings.fg_color()).unwrap(),
-            ));
+/// A tile to scale on the render worker, with a copy of its cells
+#[derive(Debug)]
+struct UniverseGridTileJob {
+    epoch: u64,
+    index: usize,
+    version: u64,
+    rect: (i32, i32, i32, i32),
+    rows: usize,
+    cells: Vec<u8>,
 
-            this.bg_color.set(Some(
-                gtk::gdk::RGBA::from_str(&this.settings.bg_color()).unwrap(),
-            ));
+    /// The device pixel each row and each column of cells starts at, relative
+    /// to the tile, followed by where the last one ends
+    row_edges: Vec<usize>,
+    column_edges: Vec<usize>,
+}
 
-            this
+impl UniverseGridTileJob {
+    /// Scales the cells up to the device pixels of the tile, each cell
+    /// covering the pixels between its edges
+    fn scale(&self) -> Vec<u8> {
+        let (width, height) = (self.rect.2 as usize, self.rect.3 as usize);
+        let mut bytes = vec![0u8; width * height * 4];
+        for (column, edges) in self.column_edges.windows(2).enumerate() {
+            for y in edges[0]..edges[1].min(height) {
+                for (row, row_edges) in self.row_edges.windows(2).enumerate() {
+                    let source = (column * self.rows + row) * 4;
+                    let cell = &self.cells[source..source + 4];
+                    for x in row_edges[0].min(width)..row_edges[1].min(width) {
+                        let target = (y * width + x) * 4;
+                        bytes[target..target + 4].copy_from_slice(cell);
+                    }
+                }
+            }
         }
+        bytes
+    }
+}
 
-        fn class_init(klass: &mut Self::Class) {
-            Self::bind_template(klass);
-            klass.set_layout_manager_type::<gtk::BinLayout>();
+/// A tile scaled by the render worker
+#[derive(Debug)]
+struct UniverseGridTileRender {
+    epoch: u64,
+    index: usize,
+    version: u64,
+    rect: (i32, i32, i32, i32),
+    bytes: Vec<u8>,
+}
+
+impl UniverseGridTileRender {
+    fn texture(self) -> gtk::gdk::Texture {
+        gtk::gdk::MemoryTexture::new(
+            self.rect.2,
+            self.rect.3,
+            CELLS_TEXTURE_FORMAT,
+            &glib::Bytes::from_owned(self.bytes),
+            self.rect.2 as usize * 4,
+        )
+        .upcast()
+    }
+}
+
+/// Scales tiles on a thread of its own, so that drawing a frame never waits for
+/// them: until a tile comes back, its previous texture is shown in its place.
+/// The thread ends once the worker is dropped.
+#[derive(Debug)]
+struct UniverseGridRenderWorker {
+    jobs: std::sync::mpsc::Sender<Vec<UniverseGridTileJob>>,
+}
+
+impl UniverseGridRenderWorker {
+    /// Starts the thread, handing the scaled tiles to `receive` on the main thread
+    fn spawn<F: FnMut(Vec<UniverseGridTileRender>) + 'static>(receive: F) -> Self {
+        let (jobs, job_receiver) = std::sync::mpsc::channel::<Vec<UniverseGridTileJob>>();
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        std::thread::spawn(move || {
+            for batch in job_receiver.iter() {
+                let renders: Vec<UniverseGridTileRender> = batch
+                    .into_iter()
+                    .map(|job| UniverseGridTileRender {
+                        bytes: job.scale(),
+                        epoch: job.epoch,
+                        index: job.index,
+                        version: job.version,
+                        rect: job.rect,
+                    })
+                    .collect();
+                if sender.send(renders).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut receive = receive;
+        receiver.attach(None, move |renders| {
+            receive(renders);
+            glib::Continue(true)
+        });
+        Self { jobs }
+    }
+}
+
+/// The cells of a universe rendered offscreen at one pixel per cell, so that
+/// each generation only patches the cells that changed instead of repainting
+/// the whole universe.
+///
+/// The GPU composites the cells as textures of `CELLS_TILE_SIZE` cells square,
+/// scaled up to the widget size on the CPU as GTK only filters textures
+/// linearly. Each tile is only scaled again when one of its cells changed or
+/// when the viewport moved it, so still areas cost nothing to draw. With a
+/// render worker, tiles are scaled off the main thread and swapped in once
+/// ready, the previous textures being drawn meanwhile.
+#[derive(Debug, Default)]
+struct UniverseGridCellsLayer {
+    rows: usize,
+    columns: usize,
+    pixels: Vec<u8>,
+
+    /// The uploaded tiles, row of tiles by row of tiles along the vertical axis
+    tiles: Vec<UniverseGridCellsTile>,
+
+    /// Bumped whenever the tiles are laid out anew, telling apart the tiles
+    /// scaled for an earlier layout
+    epoch: u64,
+
+    worker: Option<UniverseGridRenderWorker>,
+
+    /// Whether tiles sent to the worker are yet to come back
+    pending: bool,
+}
+
+impl UniverseGridCellsLayer {
+    fn update(
+        &mut self,
+        universe: &Universe,
+        damage: UniverseGridDamage,
+        style: &UniverseGridCellStyle,
+    ) {
+        let resized = (self.rows, self.columns) != (universe.rows(), universe.columns());
+        match damage {
+            UniverseGridDamage::None if !resized => (),
+            UniverseGridDamage::Cells(changes) if !resized => {
+                for point in changes.iter() {
+                    self.paint(point, &style.color_at(universe, point));
+                }
+            }
+            _ if !resized => {
+                for point in universe.iter_cells() {
+                    self.paint(&point, &style.color_at(universe, &point));
+                }
+            }
+            _ => {
+                self.rows = universe.rows();
+                self.columns = universe.columns();
+                self.pixels = vec![0; self.rows * self.columns * 4];
+                self.tiles = std::iter::repeat_with(UniverseGridCellsTile::default)
+                    .take(self.tiles_across() * self.tiles_down())
+                    .collect();
+                self.epoch += 1;
+                self.pending = false;
+                for point in universe.iter_cells() {
+                    self.paint(&point, &style.color_at(universe, &point));
+                }
+            }
         }
+    }
 
-        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
-            obj.init_template();
+    fn set_worker(&mut self, worker: UniverseGridRenderWorker) {
+        self.worker = Some(worker);
+    }
+
+    /// The number of tiles along the horizontal axis, where rows are laid
+    fn tiles_across(&self) -> usize {
+        self.rows.div_ceil(CELLS_TILE_SIZE)
+    }
+
+    fn tiles_down(&self) -> usize {
+        self.columns.div_ceil(CELLS_TILE_SIZE)
+    }
+
+    /// Stores the premultiplied native endian ARGB pixel of a cell. Rows are laid
+    /// along the horizontal axis, matching the grid.
+    fn paint(&mut self, point: &UniversePoint, cell_color: &gtk::gdk::RGBA) {
+        let alpha = cell_color.alpha() as f64;
+        let channel = |value: f32| (value as f64 * alpha * 255.0).round() as u32;
+        let pixel = ((alpha * 255.0).round() as u32) << 24
+            | channel(cell_color.red()) << 16
+            | channel(cell_color.green()) << 8
+            | channel(cell_color.blue());
+
+        let index = (point.column() * self.rows + point.row()) * 4;
+        if let Some(bytes) = self.pixels.get_mut(index..index + 4) {
+            if bytes != pixel.to_ne_bytes() {
+                bytes.copy_from_slice(&pixel.to_ne_bytes());
+                let tile = (point.column() / CELLS_TILE_SIZE) * self.tiles_across()
+                    + point.row() / CELLS_TILE_SIZE;
+                if let Some(tile) = self.tiles.get_mut(tile) {
+                    tile.version += 1;
+                }
+            }
         }
     }
 
-    impl ObjectImpl for GameOfLifeUniverseGrid {
-        fn constructed(&self) {
-            self.parent_constructed();
-            let obj = self.obj();
-            obj.setup_drawing_area();
-            obj.setup_channel();
+    /// Swaps in the tiles scaled by the worker. Tiles scaled for an earlier
+    /// layout are dropped.
+    fn receive(&mut self, renders: Vec<UniverseGridTileRender>) {
+        self.pending = false;
+        for render in renders {
+            if render.epoch != self.epoch {
+                continue;
+            }
+            if let Some(tile) = self.tiles.get_mut(render.index) {
+                tile.rect = render.rect;
+                tile.texture_version = render.version;
+                tile.texture = Some(render.texture());
+            }
         }
+    }
 
-        fn properties() -> &'static [glib::ParamSpec] {
-            static PROPERTIES: Lazy<Vec<ParamSpec>> = Lazy::new(|| {
-                vec![
-                    ParamSpecEnum::new(
-                        "mode",
-                        "",
-                        "",
-                        UniverseGridMode::static_type(),
-                        1,
+    /// Copies the cells of a tile, with the device pixels they cover in `rect`
+    fn tile_job(
+        &self,
+        index: usize,
+        viewport: &UniverseGridViewport,
+        rect: (i32, i32, i32, i32),
+    ) -> UniverseGridTileJob {
+        let tiles_across = self.tiles_across();
+        let row = (index % tiles_across) * CELLS_TILE_SIZE;
+        let column = (index / tiles_across) * CELLS_TILE_SIZE;
+        let rows = CELLS_TILE_SIZE.min(self.rows - row);
+        let columns = CELLS_TILE_SIZE.min(self.columns - column);
+
+        let scale_factor = viewport.scale_factor();
+        let edges = |count: usize, edge: &dyn Fn(usize) -> f64, start: i32| {
+            (0..=count)
+                .map(|index| ((edge(index) * scale_factor).round() as i32 - start).max(0) as usize)
+                .collect::<Vec<usize>>()
+        };
+
+        let mut cells = Vec::with_capacity(rows * columns * 4);
+        for tile_column in 0..columns {
+            let start = ((column + tile_column) * self.rows + row) * 4;
+            cells.extend_from_slice(&self.pixels[start..start + rows * 4]);
+        }
+
+        UniverseGridTileJob {
+            epoch: self.epoch,
+            index,
+            version: self.tiles[index].version,
+            rect,
+            rows,
+            cells,
+            row_edges: edges(
+                rows,
+                &|index| viewport.area_rect(row + index, column, 0, 0).0,
+                rect.0,
+            ),
+            column_edges: edges(
+                columns,
+                &|index| viewport.area_rect(row, column + index, 0, 0).1,
+                rect.1,
+            ),
+        }
+    }
+
+    /// Draws the visible cells as hexagons, in the colors painted for the
+    /// tiles. Cells of the same color are filled together.
+    fn append_hexagons(
+        &self,
+        cairo_context: &gtk::cairo::Context,
+        viewport: &UniverseGridViewport,
+    ) {
+        let (rows, columns) = viewport.visible_cells();
+        let mut filling = None;
+        for column in columns {
+            for row in rows.clone() {
+                let index = (column * self.rows + row) * 4;
+                let Some(bytes) = self.pixels.get(index..index + 4) else {
+                    continue;
+                };
+                let pixel = u32::from_ne_bytes(bytes.try_into().unwrap());
+                if pixel == 0 {
+                    continue;
+                }
+
+                if filling != Some(pixel) {
+                    if filling.is_some() {
+                        cairo_context.fill().unwrap();
+                    }
+                    let alpha = (pixel >> 24) as f64;
+                    let channel = |shift: u32| (pixel >> shift & 0xff) as f64 / alpha;
+                    cairo_context.set_source_rgba(
+                        channel(16),
+                        channel(8),
+                        channel(0),
+                        alpha / 255.0,
+                    );
+                    filling = Some(pixel);
+                }
+                trace_polygon(cairo_context, &viewport.cell_hexagon(row, column));
+            }
+        }
+        if filling.is_some() {
+            cairo_context.fill().unwrap();
+        }
+    }
+
+    /// Appends the tiles visible within `bounds` to `snapshot`. Tiles that
+    /// changed are sent to the worker, if any, and drawn as they were until
+    /// they come back; those never drawn yet are scaled right away.
+    fn append_tiles(
+        &mut self,
+        snapshot: &gtk::Snapshot,
+        viewport: &UniverseGridViewport,
+        bounds: &gtk::graphene::Rect,
+    ) {
+        let scale_factor = viewport.scale_factor();
+        let device = |value: f64| (value * scale_factor).round() as i32;
+        let tiles_across = self.tiles_across();
+        let mut batch = vec![];
+        for index in 0..self.tiles.len() {
+            let row = (index % tiles_across) * CELLS_TILE_SIZE;
+            let column = (index / tiles_across) * CELLS_TILE_SIZE;
+            let rows = CELLS_TILE_SIZE.min(self.rows - row);
+            let columns = CELLS_TILE_SIZE.min(self.columns - column);
+
+            let (x, y, width, height) = viewport.area_rect(row, column, rows, columns);
+            let visible = x < bounds.width() as f64
+                && y < bounds.height() as f64
+                && x + width > 0.0
+                && y + height > 0.0;
+            let rect = (device(x), device(y), device(x + width), device(y + height));
+            let rect = (rect.0, rect.1, rect.2 - rect.0, rect.3 - rect.1);
+            if !visible || rect.2 <= 0 || rect.3 <= 0 {
+                continue;
+            }
+
+            let tile = &self.tiles[index];
+            let outdated = tile.texture_version != tile.version || tile.rect != rect;
+            if tile.texture.is_none() || (outdated && self.worker.is_none()) {
+                let job = self.tile_job(index, viewport, rect);
+                let render = UniverseGridTileRender {
+                    bytes: job.scale(),
+                    epoch: job.epoch,
+                    index,
+                    version: job.version,
+                    rect,
+                };
+                let tile = &mut self.tiles[index];
+                tile.rect = rect;
+                tile.texture_version = render.version;
+                tile.texture = Some(render.texture());
+            } else if outdated && !self.pending {
+                batch.push(self.tile_job(index, viewport, rect));
+            }
+
+            if let Some(texture) = self.tiles[index].texture.as_ref() {
+                snapshot.append_texture(
+                    texture,
+                    &gtk::graphene::Rect::new(
+                        (rect.0 as f64 / scale_factor) as f32,
+                        (rect.1 as f64 / scale_factor) as f32,
+                        (rect.2 as f64 / scale_factor) as f32,
+                        (rect.3 as f64 / scale_factor) as f32,
+                    ),
+                );
+            }
+        }
+
+        if let (false, Some(worker)) = (batch.is_empty(), self.worker.as_ref()) {
+            self.pending = worker.jobs.send(batch).is_ok();
+        }
+    }
+}
+
+#[derive(Clone, Debug, glib::Enum, Copy, PartialEq)]
+#[enum_type(name = "UniverseGridMode")]
+pub enum UniverseGridMode {
+    /// The grid can receive interactive inputs, such as mouse clicks
+    Unlocked = 0,
+
+    /// The grid will not receive interactive inputs
+    Locked = 1,
+
+    /// Dragging on the grid selects a rectangular area of the universe
+    Select = 2,
+}
+
+impl Default for UniverseGridMode {
+    fn default() -> Self {
+        Self::Locked
+    }
+}
+
+#[derive(Debug)]
+pub enum UniverseGridRequest {
+    /// Restores normal rendering operations
+    Unfreeze,
+
+    /// Requests the grid to redraw itself. If the value is Some(universe) the contained
+    /// value will replace the current model inside the widget
+    Redraw(Option<Universe>),
+
+    /// Sets the colors the grid paints with and redraws it
+    PaletteChanged(UniverseGridPalette),
+
+    /// Same as `Redraw`, but carries the full state of an unbounded universe. The grid
+    /// will store it and render its current viewport projection
+    RedrawUnbounded(SparseUniverse),
+}
+
+/// A transformation of a pattern, as seen on the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UniverseGridTransform {
+    RotateClockwise,
+    RotateCounterClockwise,
+    FlipHorizontally,
+    FlipVertically,
+}
+
+impl UniverseGridTransform {
+    /// The grid draws rows along the horizontal axis and columns along the vertical
+    /// one, so the universe appears transposed: rotations are reversed and flips
+    /// swap axes when mapped to the universe rows and columns
+    fn snapshot_transform(self) -> SnapshotTransform {
+        match self {
+            Self::RotateClockwise => SnapshotTransform::RotateCounterClockwise,
+            Self::RotateCounterClockwise => SnapshotTransform::RotateClockwise,
+            Self::FlipHorizontally => SnapshotTransform::FlipRows,
+            Self::FlipVertically => SnapshotTransform::FlipColumns,
+        }
+    }
+}
+
+/// How large the grid draws cells
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UniverseGridZoom {
+    /// Cells stretch to fit the whole universe in the widget, enlarged by
+    /// `ZOOM_STEP` per level
+    Fit(u32),
+
+    /// Square cells of a fixed size, in logical pixels
+    CellSize(f64),
+}
+
+impl Default for UniverseGridZoom {
+    fn default() -> Self {
+        Self::Fit(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+enum UniverseGridInteractionState {
+    Idle,
+    Ongoing,
+}
+
+impl Default for UniverseGridInteractionState {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+mod imp {
+    use super::*;
+    use glib::{
+        types::StaticType, ParamFlags, ParamSpec, ParamSpecBoolean, ParamSpecDouble, ParamSpecEnum,
+        ParamSpecOverride, ParamSpecUInt, ParamSpecUInt64,
+    };
+    use once_cell::sync::Lazy;
+
+    #[derive(Debug, Default, CompositeTemplate)]
+    #[template(resource = "/com/github/sixpounder/GameOfLife/universe_grid.ui")]
+    pub struct GameOfLifeUniverseGrid {
+        #[template_child]
+        pub(super) selection_popover: TemplateChild<gtk::PopoverMenu>,
+        #[template_child]
+        pub(super) context_popover: TemplateChild<gtk::PopoverMenu>,
+
+        /// The cell the context menu was opened on
+        pub(super) context_cell: Cell<Option<(usize, usize)>>,
+
+        /// The pattern stamped last, which can be stamped again from the context menu
+        pub(super) last_stamped: RefCell<Option<UniverseSnapshot>>,
+
+        /// How stamped patterns combine with the cells underneath
+        pub(super) stamp_blend: Cell<StampBlend>,
+
+        /// The cell kept at the center of the widget when zoomed in, if not the
+        /// center of the universe
+        pub(super) zoom_focus: Cell<Option<(f64, f64)>>,
+
+        pub(super) settings: GameOfLifeSettings,
+
+        /// Set while resizing, showing the last frame dimmed instead of
+        /// rendering every intermediate size
+        pub(super) frozen: Cell<bool>,
+
+        /// The last frame rendered, shown while frozen
+        pub(super) last_frame: RefCell<Option<gtk::gsk::RenderNode>>,
+
+        /// Unfreezes the widget once it kept its size for `RESIZE_SETTLE_DELAY`
+        pub(super) unfreeze_source: RefCell<Option<glib::SourceId>>,
+
+        pub(super) allocated_size: Cell<(i32, i32)>,
+
+        pub(super) mode: Cell<UniverseGridMode>,
+
+        /// The radius of the brush at full stylus pressure, in cells
+        pub(super) stylus_brush_radius: Cell<usize>,
+
+        /// What strokes of the primary button do in design mode
+        pub(super) tool: Cell<DesignTool>,
+
+        /// The width of the square brush of the design tools, in cells
+        pub(super) brush_size: Cell<usize>,
+
+        /// What drawing brings to life in WireWorld universes
+        pub(super) wireworld_cell: Cell<WireWorldCell>,
+
+        /// The color of the cells drawing brings to life under colored rules
+        pub(super) drawing_color: Cell<u8>,
+
+        /// Whether drawing puts ants down instead of cells under ant rules
+        pub(super) draws_ants: Cell<bool>,
+
+        /// The steps every ant takes in a generation under ant rules
+        pub(super) ant_steps: Cell<u32>,
+
+        /// The cells the drag of a shape tool started from and is over
+        pub(super) shape_stroke: Cell<Option<((usize, usize), (usize, usize))>>,
+
+        /// The cell edited from the keyboard, once the grid got the focus
+        pub(super) keyboard_cursor: Cell<Option<(usize, usize)>>,
+
+        pub(super) universe: RefCell<Option<Universe>>,
+
+        pub(super) sparse_universe: RefCell<Option<SparseUniverse>>,
+
+        pub(super) rule: RefCell<UniverseRule>,
+
+        pub(super) rain: Cell<Option<UniverseRain>>,
+
+        pub(super) chance: RefCell<Option<UniverseChance>>,
+
+        pub(super) injection: RefCell<Option<UniverseInjection>>,
+
+        pub(super) freeze_zones: RefCell<Vec<UniverseArea>>,
+
+        pub(super) reference: RefCell<Option<Universe>>,
+
+        pub(super) comparing: Cell<bool>,
+
+        pub(super) placing: RefCell<Option<UniverseSnapshot>>,
+
+        /// The quarter turns applied to the pattern being placed by the
+        /// ongoing rotate gesture
+        pub(super) placement_turns: Cell<i32>,
+
+        /// The zoom and the fractional `(row, column)` under the fingers when
+        /// a pinch began
+        pub(super) pinch_start: Cell<Option<(UniverseGridZoom, (f64, f64))>>,
+
+        /// The `(rows, columns)` a middle button drag panned an unbounded
+        /// universe by so far
+        pub(super) pan_drag_cells: Cell<(i64, i64)>,
+
+        /// The fractions of cells scrolling panned an unbounded universe by,
+        /// carried over to the next scroll
+        pub(super) scroll_pan_remainder: Cell<(f64, f64)>,
+
+        pub(super) selection: Cell<Option<UniverseArea>>,
+
+        /// The cell a selection drag started from
+        pub(super) selection_anchor: Cell<Option<(usize, usize)>>,
+
+        pub(super) stabilization: RefCell<Option<StabilizationDetector>>,
+
+        /// Watches for repeating states to report oscillators, if enabled
+        pub(super) oscillation: RefCell<Option<StabilizationDetector>>,
+
+        /// The opacity of cells while transitioning to a new universe
+        pub(super) transition_opacity: Cell<Option<f64>>,
+
+        /// The universe to show once cells faded out
+        pub(super) transition_target: RefCell<Option<Universe>>,
+
+        pub(super) damage: RefCell<UniverseGridDamage>,
+
+        pub(super) cells_layer: RefCell<UniverseGridCellsLayer>,
+
+        /// Runs the universe shown by this grid, possibly along with other grids
+        pub(super) controller: RefCell<SimulationController>,
+
+        pub(super) controller_listener: Cell<Option<SimulationListenerId>>,
+
+        /// The recent generations of the bounded universe, to rewind it
+        pub(super) history: RefCell<UniverseHistory>,
+
+        pub(super) allow_draw_on_resize: Cell<bool>,
+
+        /// The colors cells, the background and overlays are painted with
+        pub(super) palette: Cell<UniverseGridPalette>,
+
+        /// Whether cells are painted with the background color and vice versa
+        pub(super) inverted_colors: Cell<bool>,
+
+        pub(super) point_under_pointing_device: Cell<Option<UniversePoint>>,
+
+        pub(super) animated: Cell<bool>,
+
+        pub(super) draw_cells_outline: Cell<bool>,
+
+        pub(super) fades_dead_cells: Cell<bool>,
+
+        /// The number of generations dead cells fade out over
+        pub(super) death_trail: Cell<u32>,
+
+        /// How large cells are drawn
+        pub(super) zoom: Cell<UniverseGridZoom>,
+
+        /// The universes as they were before each edit, most recent last
+        pub(super) undo_stack: RefCell<Vec<Universe>>,
+
+        /// The universes as they were before each undo, most recent last
+        pub(super) redo_stack: RefCell<Vec<Universe>>,
+
+        /// Whether rows and columns are labeled when zoomed in
+        pub(super) shows_grid_coordinates: Cell<bool>,
+
+        pub(super) render_stats: Cell<UniverseGridRenderStats>,
+
+        /// How many more frames are drawn without details, after one ran over budget
+        pub(super) reduced_detail_frames: Cell<u32>,
+
+        pub(super) shows_performance_hud: Cell<bool>,
+
+        /// How often each cell was alive lately, while the heatmap is shown
+        pub(super) heatmap: RefCell<Option<ActivityHeatmap>>,
+
+        /// The number of generations the heatmap spans
+        pub(super) heatmap_generations: Cell<usize>,
+
+        pub(super) interaction_state: Cell<UniverseGridInteractionState>,
+
+        /// The size of cells in logical pixels zooming resets to, 0 to fit
+        /// the universe
+        pub(super) fixed_cell_size: Cell<u32>,
+
+        /// The adjustments of the scrolled window the grid is in, with their
+        /// value-changed handler
+        pub(super) hadjustment: RefCell<Option<(gtk::Adjustment, glib::SignalHandlerId)>>,
+        pub(super) vadjustment: RefCell<Option<(gtk::Adjustment, glib::SignalHandlerId)>>,
+
+        pub(super) hscroll_policy: Cell<Option<gtk::ScrollablePolicy>>,
+        pub(super) vscroll_policy: Cell<Option<gtk::ScrollablePolicy>>,
+
+        /// Whether the adjustments are being set to the zoom focus, rather
+        /// than scrolled
+        pub(super) updating_adjustments: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for GameOfLifeUniverseGrid {
+        const NAME: &'static str = "GameOfLifeUniverseGrid";
+        type Type = super::GameOfLifeUniverseGrid;
+        type ParentType = gtk::Widget;
+        type Interfaces = (gtk::Scrollable,);
+
+        fn new() -> Self {
+            let this = Self::default();
+
+            this.universe.replace(Some(Universe::new_random(
+                this.settings.universe_width() as usize,
+                this.settings.universe_height() as usize,
+            )));
+
+            // Start universe in running mode
+            this.mode.set(UniverseGridMode::Locked);
+            this.brush_size.set(1);
+
+            // Defaults to light color scheme
+            this.palette.set(UniverseGridPalette::new(
+                gtk::gdk::RGBA::from_str(&this.settings.fg_color()).unwrap(),
+                gtk::gdk::RGBA::from_str(&this.settings.bg_color()).unwrap(),
+            ));
+
+            this
+        }
+
+        fn class_init(klass: &mut Self::Class) {
+            Self::bind_template(klass);
+            klass.set_layout_manager_type::<gtk::BinLayout>();
+
+            klass.install_action("grid.paste-here", None, move |grid, _, _| {
+                grid.paste_at_context_cell();
+            });
+
+            klass.install_action("grid.stamp-here", None, move |grid, _, _| {
+                let pattern = grid.imp().last_stamped.borrow().clone();
+                if let Some(pattern) = pattern {
+                    grid.stamp_at_context_cell(&pattern);
+                }
+            });
+
+            klass.install_action("grid.clear-selection", None, move |grid, _, _| {
+                grid.clear_selection();
+            });
+
+            klass.install_action("grid.center-here", None, move |grid, _, _| {
+                if let Some((row, column)) = grid.imp().context_cell.get() {
+                    grid.set_zoom_focus(Some((row as f64 + 0.5, column as f64 + 0.5)));
+                }
+            });
+
+            klass.install_action("grid.copy-coordinates", None, move |grid, _, _| {
+                if let Some((row, column)) = grid.imp().context_cell.get() {
+                    grid.clipboard().set_text(&format!("{}, {}", row, column));
+                }
+            });
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for GameOfLifeUniverseGrid {
+        fn constructed(&self) {
+            self.parent_constructed();
+            let obj = self.obj();
+            obj.setup_drawing_area();
+            obj.setup_controller();
+            obj.setup_render_worker();
+        }
+
+        fn dispose(&self) {
+            self.selection_popover.unparent();
+            self.context_popover.unparent();
+            if let Some(listener) = self.controller_listener.take() {
+                self.controller.borrow().disconnect(listener);
+            }
+        }
+
+        fn properties() -> &'static [glib::ParamSpec] {
+            static PROPERTIES: Lazy<Vec<ParamSpec>> = Lazy::new(|| {
+                vec![
+                    ParamSpecEnum::new(
+                        "mode",
+                        "",
+                        "",
+                        UniverseGridMode::static_type(),
+                        1,
                         ParamFlags::READWRITE,
                     ),
                     ParamSpecBoolean::new(
@@ -254,8 +1549,46 @@ mod imp {
                         ParamFlags::READWRITE,
                     ),
                     ParamSpecBoolean::new("running", "", "", false, ParamFlags::READABLE),
+                    ParamSpecBoolean::new("unbounded", "", "", false, ParamFlags::READWRITE),
+                    ParamSpecBoolean::new("stable", "", "", false, ParamFlags::READABLE),
+                    ParamSpecUInt::new("period", "", "", 0, u32::MAX, 0, ParamFlags::READABLE),
+                    ParamSpecBoolean::new("stalled", "", "", false, ParamFlags::READABLE),
                     ParamSpecBoolean::new("animated", "", "", true, ParamFlags::READWRITE),
+                    ParamSpecBoolean::new("inverted-colors", "", "", false, ParamFlags::READWRITE),
                     ParamSpecUInt::new("evolution-speed", "", "", 1, 100, 5, ParamFlags::READWRITE),
+                    ParamSpecUInt64::new(
+                        "generation",
+                        "",
+                        "",
+                        0,
+                        u64::MAX,
+                        0,
+                        ParamFlags::READABLE,
+                    ),
+                    ParamSpecDouble::new(
+                        "fast-forward-progress",
+                        "",
+                        "",
+                        0.0,
+                        1.0,
+                        0.0,
+                        ParamFlags::READABLE,
+                    ),
+                    ParamSpecDouble::new(
+                        "generations-per-second",
+                        "",
+                        "",
+                        0.0,
+                        f64::MAX,
+                        0.0,
+                        ParamFlags::READABLE,
+                    ),
+                    ParamSpecBoolean::new("turbo", "", "", false, ParamFlags::READWRITE),
+                    ParamSpecBoolean::new("placing", "", "", false, ParamFlags::READABLE),
+                    ParamSpecOverride::for_interface::<gtk::Scrollable>("hadjustment"),
+                    ParamSpecOverride::for_interface::<gtk::Scrollable>("vadjustment"),
+                    ParamSpecOverride::for_interface::<gtk::Scrollable>("hscroll-policy"),
+                    ParamSpecOverride::for_interface::<gtk::Scrollable>("vscroll-policy"),
                 ]
             });
             PROPERTIES.as_ref()
@@ -279,9 +1612,30 @@ mod imp {
                 "animated" => {
                     obj.set_animated(value.get::<bool>().unwrap());
                 }
+                "inverted-colors" => {
+                    obj.set_inverted_colors(value.get::<bool>().unwrap());
+                }
                 "evolution-speed" => {
                     obj.set_evolution_speed(value.get::<u32>().unwrap_or(5));
                 }
+                "unbounded" => {
+                    obj.set_unbounded(value.get::<bool>().unwrap());
+                }
+                "turbo" => {
+                    obj.set_turbo(value.get::<bool>().unwrap());
+                }
+                "hadjustment" => {
+                    obj.set_adjustment(gtk::Orientation::Horizontal, value.get().unwrap());
+                }
+                "vadjustment" => {
+                    obj.set_adjustment(gtk::Orientation::Vertical, value.get().unwrap());
+                }
+                "hscroll-policy" => {
+                    self.hscroll_policy.set(Some(value.get().unwrap()));
+                }
+                "vscroll-policy" => {
+                    self.vscroll_policy.set(Some(value.get().unwrap()));
+                }
                 _ => unimplemented!(),
             }
         }
@@ -294,8 +1648,30 @@ mod imp {
                 "draw-cells-outline" => obj.draw_cells_outline().to_value(),
                 "fades-dead-cells" => obj.fades_dead_cells().to_value(),
                 "animated" => obj.animated().to_value(),
+                "inverted-colors" => obj.inverted_colors().to_value(),
                 "evolution-speed" => obj.evolution_speed().to_value(),
                 "running" => obj.is_running().to_value(),
+                "unbounded" => obj.unbounded().to_value(),
+                "generation" => obj.generation().to_value(),
+                "stable" => obj.is_stable().to_value(),
+                "period" => (obj.period().unwrap_or(0) as u32).to_value(),
+                "stalled" => obj.is_stalled().to_value(),
+                "fast-forward-progress" => obj.fast_forward_progress().unwrap_or(0.0).to_value(),
+                "generations-per-second" => obj.generations_per_second().unwrap_or(0.0).to_value(),
+                "turbo" => obj.is_turbo().to_value(),
+                "placing" => obj.is_placing().to_value(),
+                "hadjustment" => obj.adjustment(gtk::Orientation::Horizontal).to_value(),
+                "vadjustment" => obj.adjustment(gtk::Orientation::Vertical).to_value(),
+                "hscroll-policy" => self
+                    .hscroll_policy
+                    .get()
+                    .unwrap_or(gtk::ScrollablePolicy::Minimum)
+                    .to_value(),
+                "vscroll-policy" => self
+                    .vscroll_policy
+                    .get()
+                    .unwrap_or(gtk::ScrollablePolicy::Minimum)
+                    .to_value(),
                 _ => unimplemented!(),
             }
         }
@@ -308,33 +1684,163 @@ mod imp {
                 self.obj().width() as f32,
                 self.obj().height() as f32,
             );
-            snapshot_grid(self, snapshot, &widget_bounds);
+            if self.frozen.get() {
+                if let Some(frame) = self.last_frame.borrow().as_ref() {
+                    snapshot.push_clip(&widget_bounds);
+                    snapshot.append_node(frame);
+                    snapshot.pop();
+                    snapshot.append_color(
+                        &gtk::gdk::RGBA::new(0.0, 0.0, 0.0, FROZEN_DIM_OPACITY),
+                        &widget_bounds,
+                    );
+                    return;
+                }
+            }
+
+            let started_at = std::time::Instant::now();
+            let frame_snapshot = gtk::Snapshot::new();
+            snapshot_grid(self, &frame_snapshot, &widget_bounds);
+            let frame = frame_snapshot.to_node();
+            if let Some(frame) = frame.as_ref() {
+                snapshot.append_node(frame);
+            }
+            self.last_frame.replace(frame);
+            self.obj().record_frame(started_at.elapsed());
+        }
+
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+            let resized = self.allocated_size.replace((width, height)) != (width, height);
+            if resized && !self.allow_draw_on_resize.get() && self.last_frame.borrow().is_some() {
+                self.obj().freeze_until_resize_settles();
+            }
+            self.selection_popover.present();
+            self.context_popover.present();
+            self.obj().update_adjustments();
         }
     }
+
+    impl ScrollableImpl for GameOfLifeUniverseGrid {}
 }
 
 glib::wrapper! {
     pub struct GameOfLifeUniverseGrid(ObjectSubclass<imp::GameOfLifeUniverseGrid>)
         @extends gtk::Widget,
-        @implements gio::ActionGroup, gio::ActionMap;
+        @implements gio::ActionGroup, gio::ActionMap, gtk::Scrollable;
+}
+
+impl Default for GameOfLifeUniverseGrid {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl GameOfLifeUniverseGrid {
-    pub fn new<P: glib::IsA<gtk::Application>>(application: &P) -> Self {
-        glib::Object::new::<Self>(&[("application", application)])
+    pub fn new() -> Self {
+        glib::Object::new::<Self>(&[])
     }
 
-    fn setup_channel(&self) {
-        let receiver = self.imp().receiver.borrow_mut().take().unwrap();
-        receiver.attach(
-            None,
-            clone!(@strong self as this => move |action| this.process_action(action)),
-        );
+    /// Scales the cells layer on a worker thread, redrawing as tiles come back
+    fn setup_render_worker(&self) {
+        let worker = UniverseGridRenderWorker::spawn(clone!(@weak self as this => move |renders| {
+            this.imp().cells_layer.borrow_mut().receive(renders);
+            this.queue_draw();
+        }));
+        self.imp().cells_layer.borrow_mut().set_worker(worker);
+    }
+
+    fn setup_controller(&self) {
+        let listener = self
+            .controller()
+            .connect_event(clone!(@weak self as this => move |event| {
+                this.on_simulation_event(event);
+            }));
+        self.imp().controller_listener.set(Some(listener));
+    }
+
+    /// The simulation running the universe shown by this grid
+    pub fn controller(&self) -> SimulationController {
+        self.imp().controller.borrow().clone()
+    }
+
+    /// Shows the generations of `controller` from now on, along with any other
+    /// grid attached to it. The simulation previously shown is left running.
+    pub fn set_controller(&self, controller: &SimulationController) {
+        let imp = self.imp();
+        if let Some(listener) = imp.controller_listener.take() {
+            imp.controller.borrow().disconnect(listener);
+        }
+        imp.controller.replace(controller.clone());
+        self.setup_controller();
+        self.notify("running");
+        self.notify("stalled");
+    }
+
+    /// Shows the universe of `grid` and follows its simulation, keeping its own
+    /// size and rendering options
+    pub fn observe(&self, grid: &GameOfLifeUniverseGrid) {
+        let imp = self.imp();
+        let source = grid.imp();
+        imp.rule.replace(grid.rule());
+        imp.rain.set(grid.rain());
+        imp.chance.replace(grid.chance());
+        imp.injection.replace(grid.injection());
+        imp.freeze_zones.replace(grid.freeze_zones());
+        imp.universe.replace(source.universe.borrow().clone());
+        imp.sparse_universe
+            .replace(source.sparse_universe.borrow().clone());
+        imp.history.borrow_mut().clear();
+        self.record_history();
+        self.reset_stabilization();
+        self.set_controller(&grid.controller());
+        self.notify("generation");
+        self.redraw();
+    }
+
+    fn on_simulation_event(&self, event: &SimulationEvent) {
+        match event {
+            SimulationEvent::Generation(universe) => match universe.as_ref() {
+                SimulatedUniverse::Bounded(universe) => {
+                    self.process_action(UniverseGridRequest::Redraw(Some(universe.clone())));
+                }
+                SimulatedUniverse::Unbounded(sparse_universe) => {
+                    self.process_action(UniverseGridRequest::RedrawUnbounded(
+                        sparse_universe.clone(),
+                    ));
+                }
+            },
+            SimulationEvent::Stats(_) => (),
+            SimulationEvent::RunningChanged => self.notify("running"),
+            SimulationEvent::StalledChanged => self.notify("stalled"),
+            SimulationEvent::Halted(reason) => {
+                self.record_crash_event(&format!("Settled: {:?}", reason));
+            }
+            SimulationEvent::Progress(_) => self.notify("fast-forward-progress"),
+            SimulationEvent::RateChanged => self.notify("generations-per-second"),
+        }
     }
 
     /// Initializes the inner drawing area with callbacks, controllers etc...
     fn setup_drawing_area(&self) {
         let drawing_area = self.imp().obj();
+        drawing_area.set_focusable(true);
+
+        let key_controller = gtk::EventControllerKey::new();
+        key_controller.connect_key_pressed(
+            clone!(@strong self as this => move |_, key, _, modifiers| {
+                this.on_drawing_area_key_pressed(key, modifiers)
+            }),
+        );
+        drawing_area.add_controller(&key_controller);
+
+        let focus_controller = gtk::EventControllerFocus::new();
+        focus_controller.connect_enter(clone!(@strong self as this => move |_| {
+            this.on_drawing_area_focus_enter();
+        }));
+        focus_controller.connect_leave(clone!(@strong self as this => move |_| {
+            this.queue_draw();
+        }));
+        drawing_area.add_controller(&focus_controller);
 
         let left_click_gesture_controller = gtk::GestureClick::new();
         left_click_gesture_controller.set_button(gtk::gdk::ffi::GDK_BUTTON_PRIMARY as u32);
@@ -399,6 +1905,12 @@ impl GameOfLifeUniverseGrid {
                 this.on_drawing_area_drag_move(gesture, events, Some(UniverseCell::Alive))
             }),
         );
+
+        left_drag_gesture_controller.connect_end(
+            clone!(@strong self as this => move |gesture, events| {
+                this.on_drawing_area_drag_end(gesture, events)
+            }),
+        );
         drawing_area.add_controller(&left_drag_gesture_controller);
 
         let right_drag_gesture_controller = gtk::GestureDrag::new();
@@ -430,288 +1942,2325 @@ impl GameOfLifeUniverseGrid {
         }));
 
         drawing_area.add_controller(&motion_controller);
+
+        // Dragging with the middle button pans over the plane of unbounded universes
+        let middle_drag_gesture_controller = gtk::GestureDrag::new();
+        middle_drag_gesture_controller.set_button(gtk::gdk::ffi::GDK_BUTTON_MIDDLE as u32);
+        middle_drag_gesture_controller.connect_begin(
+            clone!(@strong self as this => move |gesture, _| {
+                if this.unbounded() {
+                    gesture.set_state(gtk::EventSequenceState::Claimed);
+                    this.imp().pan_drag_cells.set((0, 0));
+                }
+            }),
+        );
+        middle_drag_gesture_controller.connect_update(
+            clone!(@strong self as this => move |gesture, _| {
+                this.on_pan_drag_move(gesture);
+            }),
+        );
+        drawing_area.add_controller(&middle_drag_gesture_controller);
+
+        // Scrolling past the edges of unbounded universes pans over the plane
+        let scroll_controller =
+            gtk::EventControllerScroll::new(gtk::EventControllerScrollFlags::BOTH_AXES);
+        scroll_controller.connect_scroll(
+            clone!(@strong self as this => move |controller, dx, dy| {
+                let modifiers = controller.current_event_state();
+                glib::signal::Inhibit(
+                    !modifiers.contains(gtk::gdk::ModifierType::CONTROL_MASK)
+                        && this.on_drawing_area_scrolled(dx, dy),
+                )
+            }),
+        );
+        drawing_area.add_controller(&scroll_controller);
+
+        // Turning two fingers orients the pattern being placed by quarter turns
+        let rotate_gesture_controller = gtk::GestureRotate::new();
+        rotate_gesture_controller.connect_begin(clone!(@strong self as this => move |gesture, _| {
+            if this.is_placing() {
+                gesture.set_state(gtk::EventSequenceState::Claimed);
+                this.imp().placement_turns.set(0);
+            }
+        }));
+        rotate_gesture_controller.connect_angle_changed(
+            clone!(@strong self as this => move |_, angle, _| {
+                this.on_placement_rotated(angle);
+            }),
+        );
+        drawing_area.add_controller(&rotate_gesture_controller);
+
+        // Pinching zooms around the fingers, which pan the view as they move
+        let zoom_gesture_controller = gtk::GestureZoom::new();
+        zoom_gesture_controller.connect_begin(clone!(@strong self as this => move |gesture, _| {
+            this.on_pinch_begin(gesture);
+        }));
+        zoom_gesture_controller.connect_update(clone!(@strong self as this => move |gesture, _| {
+            this.on_pinch_update(gesture);
+        }));
+        zoom_gesture_controller.connect_end(clone!(@strong self as this => move |_, _| {
+            this.imp().pinch_start.set(None);
+            this.center_unbounded_viewport_on_focus();
+        }));
+        drawing_area.add_controller(&zoom_gesture_controller);
+
+        let long_press_gesture_controller = gtk::GestureLongPress::new();
+        long_press_gesture_controller.set_touch_only(true);
+        long_press_gesture_controller.connect_pressed(
+            clone!(@strong self as this => move |gesture, x, y| {
+                this.on_drawing_area_long_pressed(gesture, x, y);
+            }),
+        );
+        drawing_area.add_controller(&long_press_gesture_controller);
     }
 
     fn process_action(&self, action: UniverseGridRequest) -> glib::Continue {
         match action {
             UniverseGridRequest::Unfreeze => self.set_frozen(false),
             UniverseGridRequest::Redraw(new_universe_state) => {
-                if let Some(new_universe_state) = new_universe_state {
-                    self.imp().universe.replace(Some(new_universe_state));
+                let Some(new_universe_state) = new_universe_state else {
+                    self.redraw();
+                    return glib::Continue(true);
+                };
+
+                let state_hash = self
+                    .observes_states()
+                    .then(|| new_universe_state.state_hash());
+
+                // A generation following the current one only needs its changes drawn
+                let follows_current =
+                    self.imp()
+                        .universe
+                        .borrow()
+                        .as_ref()
+                        .is_some_and(|current| {
+                            current.generations() + 1 == new_universe_state.generations()
+                                && current.rows() == new_universe_state.rows()
+                                && current.columns() == new_universe_state.columns()
+                        });
+                // Survivors change color with age and dead cells keep fading,
+                // every cell has to be repainted
+                let changes = (follows_current
+                    && self.imp().palette.get().age_gradient().is_none()
+                    && self.imp().death_trail.get() == 0)
+                    .then(|| new_universe_state.last_delta());
+
+                self.imp().universe.replace(Some(new_universe_state));
+                self.record_history();
+                self.record_heatmap();
+                self.notify("generation");
+                if let Some(state_hash) = state_hash {
+                    self.observe_state(state_hash);
+                }
+
+                match changes {
+                    Some(changes) => self.redraw_changes(changes),
+                    None => self.redraw(),
+                }
+            }
+            UniverseGridRequest::PaletteChanged(palette) => {
+                if self.imp().palette.replace(palette) != palette {
+                    self.redraw();
+                }
+            }
+            UniverseGridRequest::RedrawUnbounded(mut sparse_universe) => {
+                // The viewport is panned on the grid, the simulation only
+                // evolves the cells
+                if let Some(current) = self.imp().sparse_universe.borrow().as_ref() {
+                    let viewport = current.viewport();
+                    sparse_universe.set_viewport(viewport.top, viewport.left);
+                }
+                let state_hash = self.observes_states().then(|| sparse_universe.state_hash());
+                self.imp().universe.replace(Some(sparse_universe.project()));
+                self.imp().sparse_universe.replace(Some(sparse_universe));
+                self.record_heatmap();
+                self.notify("generation");
+                if let Some(state_hash) = state_hash {
+                    self.observe_state(state_hash);
                 }
                 self.redraw();
             }
         }
 
-        glib::Continue(true)
+        glib::Continue(true)
+    }
+
+    fn on_drawing_area_clicked(
+        &self,
+        gesture: &gtk::GestureClick,
+        _n_press: i32,
+        x: f64,
+        y: f64,
+        alter_state: Option<UniverseCell>,
+    ) {
+        if self.is_placing() {
+            match alter_state {
+                // A touch places the pattern when lifted, unless a second
+                // finger joins in to rotate it
+                Some(UniverseCell::Alive) if gesture.current_sequence().is_some() => {
+                    self.set_point_under_pointing_device(x, y);
+                }
+                Some(UniverseCell::Alive) => self.stamp_placement(),
+                _ => self.cancel_placement(),
+            }
+            return;
+        }
+
+        if self.mode() == UniverseGridMode::Select {
+            match alter_state {
+                Some(UniverseCell::Alive) => {
+                    self.imp()
+                        .interaction_state
+                        .set(UniverseGridInteractionState::Ongoing);
+                    self.imp().selection_anchor.set(None);
+                    self.extend_selection(x, y);
+                }
+                _ => self.popup_selection_menu(x, y),
+            }
+            return;
+        }
+
+        if self.mode() == UniverseGridMode::Locked && alter_state == Some(UniverseCell::Dead) {
+            self.popup_context_menu(x, y);
+            return;
+        }
+
+        if self.mode() == UniverseGridMode::Unlocked {
+            self.grab_focus();
+            self.imp()
+                .interaction_state
+                .set(UniverseGridInteractionState::Ongoing);
+            // A whole stroke is undone at once
+            self.checkpoint();
+            if alter_state == Some(UniverseCell::Alive) && self.tool().draws_shapes() {
+                self.begin_shape(x, y);
+            } else {
+                let (alter_state, brush) = self.stroke_brush(gesture, alter_state);
+                self.alter_universe_point(x, y, alter_state, brush);
+            }
+        }
+    }
+
+    fn on_drawing_area_click_released(
+        &self,
+        gesture: &gtk::GestureClick,
+        _n_press: i32,
+        x: f64,
+        y: f64,
+    ) {
+        if self.is_placing() && gesture.current_sequence().is_some() {
+            self.set_point_under_pointing_device(x, y);
+            self.stamp_placement();
+        }
+        self.end_shape();
+
+        self.imp()
+            .interaction_state
+            .set(UniverseGridInteractionState::Idle);
+    }
+
+    fn on_drawing_area_click_unpaired_released(
+        &self,
+        _gesture: &gtk::GestureClick,
+        _x: f64,
+        _y: f64,
+        _button: u32,
+        _events: Option<&gtk::gdk::EventSequence>,
+    ) {
+        self.imp()
+            .interaction_state
+            .set(UniverseGridInteractionState::Idle);
+    }
+
+    fn on_drawing_area_drag_begin(
+        &self,
+        gesture: &gtk::GestureDrag,
+        _events: Option<&gtk::gdk::EventSequence>,
+        alter_state: Option<UniverseCell>,
+    ) {
+        if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
+            if let Some(point) = gesture.start_point() {
+                if self.mode() == UniverseGridMode::Select {
+                    self.extend_selection(point.0, point.1);
+                } else if self.imp().shape_stroke.get().is_some() {
+                    self.extend_shape(point.0, point.1);
+                } else {
+                    let (alter_state, brush) = self.stroke_brush(gesture, alter_state);
+                    self.alter_universe_point(point.0, point.1, alter_state, brush);
+                }
+            }
+        }
+    }
+
+    fn on_drawing_area_drag_move(
+        &self,
+        gesture: &gtk::GestureDrag,
+        _events: Option<&gtk::gdk::EventSequence>,
+        alter_state: Option<UniverseCell>,
+    ) {
+        if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
+            if let Some(point) = gesture.offset() {
+                let origin = gesture.start_point().unwrap();
+                if self.mode() == UniverseGridMode::Select {
+                    self.extend_selection(origin.0 + point.0, origin.1 + point.1);
+                } else if self.imp().shape_stroke.get().is_some() {
+                    self.extend_shape(origin.0 + point.0, origin.1 + point.1);
+                } else {
+                    let (alter_state, brush) = self.stroke_brush(gesture, alter_state);
+                    self.alter_universe_point(
+                        origin.0 + point.0,
+                        origin.1 + point.1,
+                        alter_state,
+                        brush,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Moves the keyboard cursor with the arrow keys, ten cells at a time with
+    /// Shift, and toggles the cell under it with Space or Enter in design mode.
+    /// Rows go along the horizontal axis, columns along the vertical one.
+    fn on_drawing_area_key_pressed(
+        &self,
+        key: gtk::gdk::Key,
+        modifiers: gtk::gdk::ModifierType,
+    ) -> glib::signal::Inhibit {
+        use gtk::gdk::{Key, ModifierType};
+
+        if modifiers.intersects(ModifierType::CONTROL_MASK | ModifierType::ALT_MASK) {
+            return glib::signal::Inhibit(false);
+        }
+
+        let step = match modifiers.contains(ModifierType::SHIFT_MASK) {
+            true => 10,
+            false => 1,
+        };
+        let (row_offset, column_offset) = match key {
+            Key::Left | Key::KP_Left => (-step, 0),
+            Key::Right | Key::KP_Right => (step, 0),
+            Key::Up | Key::KP_Up => (0, -step),
+            Key::Down | Key::KP_Down => (0, step),
+            Key::space | Key::Return | Key::KP_Enter => {
+                return glib::signal::Inhibit(self.toggle_keyboard_cursor_cell());
+            }
+            _ => return glib::signal::Inhibit(false),
+        };
+        self.move_keyboard_cursor(row_offset, column_offset);
+        glib::signal::Inhibit(true)
+    }
+
+    fn on_drawing_area_focus_enter(&self) {
+        if self.imp().keyboard_cursor.get().is_none() {
+            self.imp()
+                .keyboard_cursor
+                .set(Some((self.rows() / 2, self.columns() / 2)));
+        }
+        self.announce_keyboard_cursor();
+        self.queue_draw();
+    }
+
+    fn on_drawing_area_drag_end(
+        &self,
+        _gesture: &gtk::GestureDrag,
+        _events: Option<&gtk::gdk::EventSequence>,
+    ) {
+        self.end_shape();
+    }
+
+    /// Pans an unbounded universe for the cells under the pointer to follow
+    /// a middle button drag
+    fn on_pan_drag_move(&self, gesture: &gtk::GestureDrag) {
+        let imp = self.imp();
+        let (cell_width, cell_height) = match imp.universe.borrow().as_ref() {
+            Some(universe) => widget_viewport(self, universe).cell_size(),
+            None => return,
+        };
+        let (offset_x, offset_y) = match gesture.offset() {
+            Some(offset) if cell_width > 0.0 && cell_height > 0.0 => offset,
+            _ => return,
+        };
+
+        let cells = (
+            -(offset_x / cell_width).round() as i64,
+            -(offset_y / cell_height).round() as i64,
+        );
+        let (panned_rows, panned_columns) = imp.pan_drag_cells.replace(cells);
+        self.pan_unbounded(cells.0 - panned_rows, cells.1 - panned_columns);
+    }
+
+    /// Pans an unbounded universe by `UNBOUNDED_SCROLL_CELLS` per scroll step,
+    /// once the view is scrolled to its edge along every axis scrolled along.
+    /// Returns whether the scroll was taken over.
+    fn on_drawing_area_scrolled(&self, dx: f64, dy: f64) -> bool {
+        if !self.unbounded() {
+            return false;
+        }
+
+        let at_edge = |orientation, delta: f64| match self.adjustment(orientation) {
+            _ if delta == 0.0 => true,
+            Some(adjustment) if delta < 0.0 => adjustment.value() <= adjustment.lower(),
+            Some(adjustment) => adjustment.value() + adjustment.page_size() >= adjustment.upper(),
+            None => true,
+        };
+        if !at_edge(gtk::Orientation::Horizontal, dx) || !at_edge(gtk::Orientation::Vertical, dy) {
+            return false;
+        }
+
+        let imp = self.imp();
+        let (remainder_rows, remainder_columns) = imp.scroll_pan_remainder.get();
+        let rows = remainder_rows + dx * UNBOUNDED_SCROLL_CELLS;
+        let columns = remainder_columns + dy * UNBOUNDED_SCROLL_CELLS;
+        imp.scroll_pan_remainder
+            .set((rows.fract(), columns.fract()));
+        self.pan_unbounded(rows.trunc() as i64, columns.trunc() as i64);
+        true
+    }
+
+    fn on_drawing_area_mouse_position(
+        &self,
+        _controller: &gtk::EventControllerMotion,
+        x: f64,
+        y: f64,
+    ) {
+        self.set_point_under_pointing_device(x, y);
+    }
+
+    fn set_point_under_pointing_device(&self, x: f64, y: f64) {
+        self.imp()
+            .point_under_pointing_device
+            .set(widget_area_point_to_universe_cell(
+                &self.imp().obj(),
+                self.imp().universe.borrow().as_ref(),
+                x,
+                y,
+            ));
+
+        if self.is_placing() {
+            self.queue_draw();
+        }
+    }
+
+    /// Rotates the pattern being placed to the quarter turn closest to `angle`,
+    /// in radians clockwise since the rotate gesture began
+    fn on_placement_rotated(&self, angle: f64) {
+        if !self.is_placing() {
+            return;
+        }
+
+        let turns = (angle / std::f64::consts::FRAC_PI_2).round() as i32;
+        let applied = self.imp().placement_turns.replace(turns);
+        for _ in applied..turns {
+            self.transform_placement(UniverseGridTransform::RotateClockwise);
+        }
+        for _ in turns..applied {
+            self.transform_placement(UniverseGridTransform::RotateCounterClockwise);
+        }
+    }
+
+    /// The fractional `(row, column)` position of the point at `x`, `y` on the
+    /// widget area
+    fn fractional_cell_at(&self, x: f64, y: f64) -> Option<(f64, f64)> {
+        let universe = self.imp().universe.borrow();
+        let viewport = widget_viewport(self, universe.as_ref()?);
+        let (cell_width, cell_height) = viewport.cell_size();
+        if cell_width <= 0.0 || cell_height <= 0.0 {
+            return None;
+        }
+        let (origin_x, origin_y) = viewport.origin();
+        Some(((x - origin_x) / cell_width, (y - origin_y) / cell_height))
+    }
+
+    /// Takes over the fingers for a pinch, unless they rotate the pattern being
+    /// placed
+    fn on_pinch_begin(&self, gesture: &gtk::GestureZoom) {
+        if self.is_placing() {
+            return;
+        }
+
+        let anchor = gesture
+            .bounding_box_center()
+            .and_then(|(x, y)| self.fractional_cell_at(x, y));
+        if let Some(anchor) = anchor {
+            gesture.set_state(gtk::EventSequenceState::Claimed);
+            self.imp()
+                .interaction_state
+                .set(UniverseGridInteractionState::Idle);
+            self.imp()
+                .pinch_start
+                .set(Some((self.imp().zoom.get(), anchor)));
+        }
+    }
+
+    /// Scales cells by how far the fingers spread since the pinch began,
+    /// keeping the cell that was under them there. Fitted universes zoom by
+    /// whole steps.
+    fn on_pinch_update(&self, gesture: &gtk::GestureZoom) {
+        let imp = self.imp();
+        let ((zoom, (anchor_row, anchor_column)), (x, y)) =
+            match (imp.pinch_start.get(), gesture.bounding_box_center()) {
+                (Some(start), Some(center)) => (start, center),
+                _ => return,
+            };
+
+        let scale = gesture.scale_delta().max(f64::EPSILON);
+        imp.zoom.set(match zoom {
+            UniverseGridZoom::Fit(level) => UniverseGridZoom::Fit(
+                (level as f64 + scale.ln() / ZOOM_STEP.ln())
+                    .round()
+                    .clamp(0.0, ZOOM_MAX_LEVEL as f64) as u32,
+            ),
+            UniverseGridZoom::CellSize(size) => UniverseGridZoom::CellSize(
+                (size * scale).clamp(1.0 / self.scale_factor() as f64, ZOOM_MAX_CELL_SIZE),
+            ),
+        });
+
+        let (cell_width, cell_height) = match imp.universe.borrow().as_ref() {
+            Some(universe) => widget_viewport(self, universe).cell_size(),
+            None => return,
+        };
+        imp.zoom_focus.set(Some((
+            anchor_row - (x - self.width() as f64 / 2.0) / cell_width,
+            anchor_column - (y - self.height() as f64 / 2.0) / cell_height,
+        )));
+        self.update_adjustments();
+        self.queue_draw();
+    }
+
+    /// Toggles the cell under a long touch from what it was before the touch,
+    /// which already drew on it when it began
+    fn on_drawing_area_long_pressed(&self, gesture: &gtk::GestureLongPress, x: f64, y: f64) {
+        if self.mode() != UniverseGridMode::Unlocked
+            || self.is_placing()
+            || self.tool().draws_shapes()
+        {
+            return;
+        }
+
+        let imp = self.imp();
+        let point =
+            match widget_area_point_to_universe_cell(self, imp.universe.borrow().as_ref(), x, y) {
+                Some(point) => point,
+                None => return,
+            };
+        let (row, column) = (point.row(), point.column());
+        let before = imp
+            .undo_stack
+            .borrow()
+            .last()
+            .and_then(|universe| universe.get(row, column))
+            .map(|before| *before.cell())
+            .unwrap_or(*point.cell());
+
+        gesture.set_state(gtk::EventSequenceState::Claimed);
+        imp.interaction_state
+            .set(UniverseGridInteractionState::Idle);
+        self.set_cells(&[(row, column)], !before);
+    }
+
+    fn on_drawing_area_mouse_leave(&self, _controller: &gtk::EventControllerMotion) {
+        self.imp().point_under_pointing_device.set(None);
+
+        if self.is_placing() {
+            self.queue_draw();
+        }
+    }
+
+    /// Alters the universe cell visually located at `x` and `y` coordinates. If `Some(value)`
+    /// is provided it will be used as the new cell value, else the opposite value of the current
+    /// one will be set
+    /// Sets the cell at `x`x`y`, along with the other cells under `brush`
+    fn alter_universe_point(&self, x: f64, y: f64, value: Option<UniverseCell>, brush: Brush) {
+        let drawing_area = self.imp().obj();
+        let universe_borrow = self.imp().universe.borrow();
+
+        if let Some(universe_point) =
+            widget_area_point_to_universe_cell(&drawing_area, universe_borrow.as_ref(), x, y)
+        {
+            // NONE value means invert the cell value, SOME value sets it
+            let next_value = match value {
+                Some(v) => v,
+                None => !(*universe_point.cell()),
+            };
+
+            let universe = universe_borrow.as_ref().unwrap();
+            let brushed = brush.cells(
+                (universe_point.row(), universe_point.column()),
+                universe.rows(),
+                universe.columns(),
+            );
+            drop(universe_borrow);
+            self.set_cells(&brushed, next_value);
+        }
+    }
+
+    /// Sets the cells at `cells`, as `(row, column)`, to `value`
+    fn set_cells(&self, cells: &[(usize, usize)], value: UniverseCell) {
+        let wireworld_cell = self.wireworld_cell();
+        let drawing_color = self.drawing_color();
+        let mut universe_borrow = self.imp().universe.borrow_mut();
+        let universe = match universe_borrow.as_mut() {
+            Some(universe) => universe,
+            None => return,
+        };
+
+        // WireWorld cells are drawn with the selected cell of its palette, and
+        // ants may be put down instead of cells
+        let wireworld = value.is_alive() && universe.rule().is_wireworld();
+        let ants = value.is_alive() && universe.rule().is_ant() && self.draws_ants();
+        let mut changed_points = Vec::with_capacity(cells.len());
+        for &(row, column) in cells.iter() {
+            match (wireworld, ants) {
+                (true, _) => universe.set_cell_state(row, column, wireworld_cell.state()),
+                (_, true) => universe.place_ant(row, column, AntHeading::North),
+                _ => universe.set_cell(row, column, value),
+            }
+            // Cells of colored rules are drawn in the selected color
+            universe.set_cell_color(row, column, drawing_color);
+            changed_points.extend(universe.get(row, column));
+        }
+        drop(universe_borrow);
+
+        if let Some(sparse_universe) = self.imp().sparse_universe.borrow_mut().as_mut() {
+            for &(row, column) in cells.iter() {
+                sparse_universe.set(row, column, value).ok();
+            }
+        }
+
+        self.redraw_changes(changed_points);
+    }
+
+    /// The cell edited from the keyboard, kept within the universe
+    pub fn keyboard_cursor(&self) -> Option<(usize, usize)> {
+        let (row, column) = self.imp().keyboard_cursor.get()?;
+        let universe = self.imp().universe.borrow();
+        let universe = universe.as_ref()?;
+        Some((
+            row.min(universe.rows().saturating_sub(1)),
+            column.min(universe.columns().saturating_sub(1)),
+        ))
+    }
+
+    /// Moves the keyboard cursor by `row_offset` rows and `column_offset`
+    /// columns, stopping at the edges
+    fn move_keyboard_cursor(&self, row_offset: isize, column_offset: isize) {
+        let (row, column) = match self.keyboard_cursor() {
+            Some(cursor) => cursor,
+            None => return,
+        };
+        let moved = |value: usize, offset: isize, size: usize| {
+            (value as isize + offset).clamp(0, size.saturating_sub(1) as isize) as usize
+        };
+        let cursor = (
+            moved(row, row_offset, self.rows()),
+            moved(column, column_offset, self.columns()),
+        );
+        if self.imp().keyboard_cursor.replace(Some(cursor)) != Some(cursor) {
+            self.announce_keyboard_cursor();
+            self.queue_draw();
+        }
+    }
+
+    /// Toggles the cell under the keyboard cursor, as a click would in design
+    /// mode. Returns `false` outside of design mode.
+    fn toggle_keyboard_cursor_cell(&self) -> bool {
+        let cursor = match self.keyboard_cursor() {
+            Some(cursor) if self.mode() == UniverseGridMode::Unlocked => cursor,
+            _ => return false,
+        };
+        let cell = match self.imp().universe.borrow().as_ref() {
+            Some(universe) => *universe.get_cell(cursor.0, cursor.1).0,
+            None => return false,
+        };
+
+        self.checkpoint();
+        self.set_cells(&[cursor], !cell);
+        self.announce_keyboard_cursor();
+        true
+    }
+
+    /// Tells assistive technologies where the keyboard cursor is, and whether
+    /// the cell under it is alive
+    fn announce_keyboard_cursor(&self) {
+        let (row, column) = match self.keyboard_cursor() {
+            Some(cursor) => cursor,
+            None => return,
+        };
+        let alive = match self.imp().universe.borrow().as_ref() {
+            Some(universe) => universe.get_cell(row, column).0.is_alive(),
+            None => return,
+        };
+
+        let (row, column) = (row.to_string(), column.to_string());
+        let label = match alive {
+            true => i18n_f("Cell {}, {}, alive", &[&row, &column]),
+            false => i18n_f("Cell {}, {}, dead", &[&row, &column]),
+        };
+        self.update_property(&[gtk::accessible::Property::Label(&label)]);
+    }
+
+    /// Starts drawing the shape of the design tool from the cell visually
+    /// located at `x` and `y` coordinates
+    fn begin_shape(&self, x: f64, y: f64) {
+        let point = widget_area_point_to_universe_cell(
+            &self.imp().obj(),
+            self.imp().universe.borrow().as_ref(),
+            x,
+            y,
+        );
+        if let Some(point) = point {
+            let cell = (point.row(), point.column());
+            self.imp().shape_stroke.set(Some((cell, cell)));
+            self.queue_draw();
+        }
+    }
+
+    /// Stretches the shape being drawn up to the cell visually located at `x`
+    /// and `y` coordinates
+    fn extend_shape(&self, x: f64, y: f64) {
+        let point = widget_area_point_to_universe_cell(
+            &self.imp().obj(),
+            self.imp().universe.borrow().as_ref(),
+            x,
+            y,
+        );
+        if let (Some((from, to)), Some(point)) = (self.imp().shape_stroke.get(), point) {
+            let cell = (point.row(), point.column());
+            if cell != to {
+                self.imp().shape_stroke.set(Some((from, cell)));
+                self.queue_draw();
+            }
+        }
+    }
+
+    /// Draws the shape being drawn into the universe, if any
+    fn end_shape(&self) {
+        if let Some((from, to)) = self.imp().shape_stroke.take() {
+            let tool = self.tool();
+            self.set_cells(&tool.shape_cells(from, to), tool.cell());
+        }
+    }
+
+    /// Adapts a stroke to the design tool and to the stylus drawing it, if
+    /// any. The primary button applies the tool, others erase. The eraser tip
+    /// kills cells, and the brush grows with the pressure and tilt of the pen
+    /// up to the stylus brush radius. Other devices draw with the square brush
+    /// of the design tools.
+    fn stroke_brush(
+        &self,
+        gesture: &impl IsA<gtk::EventController>,
+        value: Option<UniverseCell>,
+    ) -> (Option<UniverseCell>, Brush) {
+        let value = match value {
+            Some(UniverseCell::Alive) => Some(self.tool().cell()),
+            value => value,
+        };
+        let square = Brush::Square(self.brush_size());
+        let event = match gesture.current_event() {
+            Some(event) => event,
+            None => return (value, square),
+        };
+
+        let value = match event.device_tool() {
+            Some(tool) if tool.tool_type() == gtk::gdk::DeviceToolType::Eraser => {
+                Some(UniverseCell::Dead)
+            }
+            _ => value,
+        };
+
+        let brush = match event.axis(gtk::gdk::AxisUse::Pressure) {
+            Some(pressure) => {
+                // A tilted pen lays a wider stroke, as a pencil would
+                let tilt = match (
+                    event.axis(gtk::gdk::AxisUse::Xtilt),
+                    event.axis(gtk::gdk::AxisUse::Ytilt),
+                ) {
+                    (Some(x_tilt), Some(y_tilt)) => x_tilt.hypot(y_tilt).min(1.0),
+                    _ => 0.0,
+                };
+                let strength = (pressure.clamp(0.0, 1.0) * (1.0 + tilt)).min(1.0);
+                Brush::Disk(
+                    (strength * self.imp().stylus_brush_radius.get() as f64).round() as usize,
+                )
+            }
+            None => square,
+        };
+
+        (value, brush)
+    }
+
+    pub fn mode(&self) -> UniverseGridMode {
+        self.imp().mode.get()
+    }
+
+    pub fn set_mode(&self, value: UniverseGridMode) {
+        if !self.is_running() {
+            if value != UniverseGridMode::Select {
+                self.set_selection(None);
+            }
+            if self.imp().shape_stroke.take().is_some() {
+                self.queue_draw();
+            }
+            self.imp().mode.set(value);
+            self.notify("mode");
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.controller().is_running()
+    }
+
+    pub fn set_frozen(&self, value: bool) {
+        self.imp().frozen.set(value);
+        if !value {
+            self.queue_draw();
+        }
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.imp().frozen.get()
+    }
+
+    /// Shows the last frame dimmed until the widget keeps its size for
+    /// `RESIZE_SETTLE_DELAY`
+    fn freeze_until_resize_settles(&self) {
+        let imp = self.imp();
+        imp.frozen.set(true);
+        if let Some(source) = imp.unfreeze_source.take() {
+            source.remove();
+        }
+        let source = glib::timeout_add_local_once(
+            RESIZE_SETTLE_DELAY,
+            clone!(@weak self as this => move || {
+                this.imp().unfreeze_source.take();
+                this.process_action(UniverseGridRequest::Unfreeze);
+            }),
+        );
+        imp.unfreeze_source.replace(Some(source));
+    }
+
+    pub fn allow_draw_on_resize(&self) -> bool {
+        self.imp().allow_draw_on_resize.get()
+    }
+
+    pub fn set_allow_render_on_resize(&self, value: bool) {
+        self.imp().allow_draw_on_resize.set(value);
+    }
+
+    /// A copy of the universe shown, to be evolved by the controller
+    fn simulated_universe(&self) -> Option<SimulatedUniverse> {
+        let imp = self.imp();
+        match imp.sparse_universe.borrow().as_ref() {
+            Some(sparse_universe) => Some(SimulatedUniverse::Unbounded(sparse_universe.clone())),
+            None => imp
+                .universe
+                .borrow()
+                .clone()
+                .map(SimulatedUniverse::Bounded),
+        }
+    }
+
+    pub fn run(&self) {
+        let Some(universe) = self.simulated_universe() else {
+            glib::warn!("No universe to run");
+            return;
+        };
+
+        self.controller().run(universe);
+        self.record_crash_event("Simulation started");
+    }
+
+    /// Runs the simulation as fast as possible until `target` generation,
+    /// showing only the generation it stops at. A running simulation is
+    /// restarted to fast forward.
+    pub fn run_until(&self, target: u64) {
+        if target <= self.generation() {
+            return;
+        }
+        if self.is_running() {
+            self.halt();
+        }
+
+        let Some(universe) = self.simulated_universe() else {
+            glib::warn!("No universe to run");
+            return;
+        };
+
+        self.controller().run_until(universe, target);
+        self.record_crash_event(&format!("Fast forwarding to generation {}", target));
+    }
+
+    /// How far the ongoing fast forward got, from 0 to 1, if one is ongoing
+    pub fn fast_forward_progress(&self) -> Option<f64> {
+        self.controller().fast_forward_progress()
+    }
+
+    pub fn halt(&self) {
+        self.controller().halt();
+        self.record_crash_event("Simulation halted");
+    }
+
+    /// Lists `event` in crash reports, along with the current state of the grid
+    fn record_crash_event(&self, event: &str) {
+        record_event(event);
+
+        let imp = self.imp();
+        let summary = match imp.universe.borrow().as_ref() {
+            Some(universe) => format!(
+                "{}x{} {} universe at generation {}, {} alive, rule {}, {}",
+                universe.rows(),
+                universe.columns(),
+                self.engine(),
+                self.generation(),
+                universe.alive_cells_count(),
+                self.rule(),
+                match self.is_running() {
+                    true => "running",
+                    false => "stopped",
+                },
+            ),
+            None => String::from("No universe"),
+        };
+        set_engine_state(summary);
+    }
+
+    /// Whether the running simulation stopped delivering new generations
+    pub fn is_stalled(&self) -> bool {
+        self.controller().is_stalled()
+    }
+
+    /// Abandons the current worker and starts a new one from the last
+    /// generation received. The abandoned worker exits as soon as it resumes.
+    pub fn restart_engine(&self) {
+        glib::g_warning!(G_LOG_DOMAIN, "Restarting a stalled simulation");
+        self.record_crash_event("Restarting a stalled simulation");
+        let Some(universe) = self.simulated_universe() else {
+            glib::warn!("No universe to run");
+            return;
+        };
+
+        self.controller().restart(universe);
+    }
+
+    pub fn toggle_run(&self) {
+        if self.is_running() {
+            self.halt();
+        } else {
+            self.run();
+        }
+    }
+
+    pub fn get_universe_snapshot(&self) -> UniverseSnapshot {
+        let imp = self.imp();
+        imp.universe.borrow().as_ref().unwrap().snapshot()
+    }
+
+    /// A copy of the current universe along with its generation. For unbounded
+    /// universes, this is the visible viewport.
+    pub fn get_universe(&self) -> Universe {
+        let mut universe = self.imp().universe.borrow().as_ref().unwrap().clone();
+        let cells = universe.cells().clone();
+        let dying = universe.dying_states().map(<[u8]>::to_vec);
+        universe.restore(self.generation(), cells);
+        if let Some(dying) = dying {
+            universe.restore_dying_states(&dying);
+        }
+        universe
+    }
+
+    /// The number of living cells, including those outside the viewport of an
+    /// unbounded universe
+    pub fn population(&self) -> usize {
+        let imp = self.imp();
+        if let Some(sparse_universe) = imp.sparse_universe.borrow().as_ref() {
+            return sparse_universe.alive_cells_count();
+        }
+
+        imp.universe
+            .borrow()
+            .as_ref()
+            .map(Universe::alive_cells_count)
+            .unwrap_or_default()
+    }
+
+    /// Stamps `pattern` with its top left corner at `row`x`column`, combined with
+    /// the cells underneath as told by the stamp blend. A running simulation is
+    /// restarted from the stamped state.
+    pub fn stamp(&self, pattern: &UniverseSnapshot, row: usize, column: usize) {
+        let imp = self.imp();
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+
+        self.checkpoint();
+        imp.last_stamped.replace(Some(pattern.clone()));
+
+        let blend = self.stamp_blend();
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.stamp_blended(pattern, row, column, blend);
+        }
+
+        if let Some(sparse_universe) = imp.sparse_universe.borrow_mut().as_mut() {
+            for pattern_row in 0..pattern.rows() {
+                for pattern_column in 0..pattern.columns() {
+                    let (target_row, target_column) = (row + pattern_row, column + pattern_column);
+                    let (Some(stamped), Some(existing)) = (
+                        pattern.get(pattern_row, pattern_column),
+                        sparse_universe.get(target_row, target_column),
+                    ) else {
+                        continue;
+                    };
+                    sparse_universe
+                        .set(
+                            target_row,
+                            target_column,
+                            blend.blend(*existing.cell(), *stamped.cell()),
+                        )
+                        .ok();
+                }
+            }
+        }
+
+        self.redraw();
+
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// Enters place mode: `pattern` follows the pointer as a translucent preview,
+    /// stamped by every primary click until a secondary click cancels the
+    /// placement
+    pub fn begin_placement(&self, pattern: UniverseSnapshot) {
+        self.imp().placing.replace(Some(pattern));
+        self.notify("placing");
+        self.queue_draw();
+    }
+
+    pub fn cancel_placement(&self) {
+        if self.imp().placing.take().is_some() {
+            self.notify("placing");
+            self.queue_draw();
+        }
+    }
+
+    pub fn is_placing(&self) -> bool {
+        self.imp().placing.borrow().is_some()
+    }
+
+    /// Transforms the pattern being placed
+    pub fn transform_placement(&self, transform: UniverseGridTransform) {
+        let mut placing = self.imp().placing.borrow_mut();
+        if let Some(pattern) = placing.as_mut() {
+            *pattern = pattern.transformed(transform.snapshot_transform());
+            drop(placing);
+            self.queue_draw();
+        }
+    }
+
+    /// Stamps the pattern being placed under the pointer, staying in place
+    /// mode to stamp it again
+    fn stamp_placement(&self) {
+        let origin = self.placement_origin();
+        let pattern = self.imp().placing.borrow().clone();
+        if let (Some(pattern), Some((row, column))) = (pattern, origin) {
+            self.stamp(&pattern, row, column);
+        }
+    }
+
+    /// Where the pattern being placed would be stamped, so that it is centered
+    /// on the pointer
+    fn placement_origin(&self) -> Option<(usize, usize)> {
+        let imp = self.imp();
+        let point = imp.point_under_pointing_device.get()?;
+        let placing = imp.placing.borrow();
+        let pattern = placing.as_ref()?;
+        let (rows, columns) = (self.rows(), self.columns());
+        Some((
+            (point.row() + rows - (pattern.rows() / 2) % rows) % rows,
+            (point.column() + columns - (pattern.columns() / 2) % columns) % columns,
+        ))
+    }
+
+    pub fn selection(&self) -> Option<UniverseArea> {
+        self.imp().selection.get()
+    }
+
+    pub fn set_selection(&self, value: Option<UniverseArea>) {
+        if self.imp().selection.replace(value) != value {
+            self.queue_draw();
+        }
+    }
+
+    pub fn select_all(&self) {
+        self.set_selection(Some(UniverseArea::new(0, 0, self.rows(), self.columns())));
+    }
+
+    /// Stretches the selection from the cell a selection drag started from up to
+    /// the cell visually located at `x` and `y` coordinates
+    fn extend_selection(&self, x: f64, y: f64) {
+        let point = widget_area_point_to_universe_cell(
+            &self.imp().obj(),
+            self.imp().universe.borrow().as_ref(),
+            x,
+            y,
+        );
+
+        if let Some(point) = point {
+            let corner = (point.row(), point.column());
+            let anchor = match self.imp().selection_anchor.get() {
+                Some(anchor) => anchor,
+                None => {
+                    self.imp().selection_anchor.set(Some(corner));
+                    corner
+                }
+            };
+            self.set_selection(Some(UniverseArea::from_corners(anchor, corner)));
+        }
+    }
+
+    /// Opens the context menu on the cell under `x`, `y`, enabling the actions
+    /// that apply to it
+    fn popup_context_menu(&self, x: f64, y: f64) {
+        let imp = self.imp();
+        let cell = widget_area_point_to_universe_cell(self, imp.universe.borrow().as_ref(), x, y)
+            .map(|point| (point.row(), point.column()));
+        imp.context_cell.set(cell);
+
+        let on_cell = cell.is_some();
+        let clipboard_has_text = self.clipboard().formats().contains_type(glib::Type::STRING);
+        self.action_set_enabled("grid.paste-here", on_cell && clipboard_has_text);
+        self.action_set_enabled(
+            "grid.stamp-here",
+            on_cell && imp.last_stamped.borrow().is_some(),
+        );
+        self.action_set_enabled("grid.clear-selection", self.selection().is_some());
+        self.action_set_enabled(
+            "grid.center-here",
+            on_cell && imp.zoom.get() != UniverseGridZoom::Fit(0),
+        );
+        self.action_set_enabled("grid.copy-coordinates", on_cell);
+
+        let popover = imp.context_popover.get();
+        popover.set_pointing_to(Some(&gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+        popover.popup();
+    }
+
+    /// Stamps `pattern` centered on the cell the context menu was opened on
+    fn stamp_at_context_cell(&self, pattern: &UniverseSnapshot) {
+        if let Some((row, column)) = self.imp().context_cell.get() {
+            let (rows, columns) = (self.rows(), self.columns());
+            self.stamp(
+                pattern,
+                (row + rows - (pattern.rows() / 2) % rows) % rows,
+                (column + columns - (pattern.columns() / 2) % columns) % columns,
+            );
+        }
+    }
+
+    /// Reads a pattern from the clipboard and stamps it on the cell the context
+    /// menu was opened on
+    fn paste_at_context_cell(&self) {
+        self.clipboard().read_text_async(
+            gio::Cancellable::NONE,
+            clone!(@weak self as this => move |result| {
+                match result.ok().flatten().map(|text| parse_pattern(&text)) {
+                    Some(Ok(pattern)) => this.stamp_at_context_cell(pattern.snapshot()),
+                    _ => glib::g_warning!(G_LOG_DOMAIN, "The clipboard does not contain a pattern"),
+                }
+            }),
+        );
+    }
+
+    fn popup_selection_menu(&self, x: f64, y: f64) {
+        let popover = self.imp().selection_popover.get();
+        popover.set_pointing_to(Some(&gtk::gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+        popover.popup();
+    }
+
+    /// The cells in the selection, or the living cells of the whole universe
+    /// cropped to their bounding box if nothing is selected
+    pub fn selected_pattern(&self) -> UniversePattern {
+        match self.selection() {
+            Some(area) => {
+                let snapshot = self.get_universe_snapshot();
+                let mut pattern = UniversePattern::new(snapshot.crop(
+                    area.row(),
+                    area.column(),
+                    area.rows(),
+                    area.columns(),
+                ));
+                pattern.set_rule(Some(self.rule()));
+                pattern
+            }
+            None => self.current_pattern(),
+        }
+    }
+
+    /// Kills every cell in the selection
+    pub fn clear_selection(&self) {
+        if let Some(area) = self.selection() {
+            self.edit_area(&area, |universe| universe.clear_area(&area));
+        }
+    }
+
+    /// Seeds the selection with random cells drawn from `soup`
+    pub fn randomize_selection(&self, soup: &UniverseSoup) {
+        if let Some(area) = self.selection() {
+            self.edit_area(&area, |universe| universe.fill_soup(&area, soup));
+        }
+    }
+
+    /// Transforms the cells in the selection in place, keeping its top left corner.
+    /// The selection is resized to fit the transformed cells.
+    pub fn transform_selection(&self, transform: UniverseGridTransform) {
+        if let Some(area) = self.selection() {
+            let transformed = self
+                .get_universe_snapshot()
+                .crop(area.row(), area.column(), area.rows(), area.columns())
+                .transformed(transform.snapshot_transform());
+
+            self.edit_area(&area, |universe| universe.clear_area(&area));
+            self.stamp(&transformed, area.row(), area.column());
+            self.set_selection(Some(UniverseArea::new(
+                area.row(),
+                area.column(),
+                transformed.rows().min(self.rows() - area.row()),
+                transformed.columns().min(self.columns() - area.column()),
+            )));
+        }
+    }
+
+    /// Applies `edit` to the current universe, then carries the new state of `area`
+    /// over to the unbounded universe, if any. A running simulation is restarted
+    /// from the edited state.
+    fn edit_area<F: FnOnce(&mut Universe)>(&self, area: &UniverseArea, edit: F) {
+        let imp = self.imp();
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+
+        self.checkpoint();
+
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            edit(universe);
+
+            if let Some(sparse_universe) = imp.sparse_universe.borrow_mut().as_mut() {
+                for (row, column) in area.iter() {
+                    if let Some(point) = universe.get(row, column) {
+                        sparse_universe.set(row, column, *point.cell()).ok();
+                    }
+                }
+            }
+        }
+
+        self.redraw();
+
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// The living cells of the current universe, cropped to their bounding box
+    pub fn current_pattern(&self) -> UniversePattern {
+        let snapshot = self.get_universe_snapshot();
+        let cropped = match snapshot.bounding_box() {
+            Some((top, left, rows, columns)) => snapshot.crop(top, left, rows, columns),
+            None => snapshot.crop(0, 0, 0, 0),
+        };
+        let mut pattern = UniversePattern::new(cropped);
+        pattern.set_rule(Some(self.rule()));
+        pattern
+    }
+
+    /// Stores the current state as the reference to compare against
+    pub fn set_reference_from_current(&self) {
+        let current = self.imp().universe.borrow().clone();
+        self.imp().reference.replace(current);
+    }
+
+    pub fn has_reference(&self) -> bool {
+        self.imp().reference.borrow().is_some()
+    }
+
+    /// While comparing, the grid shows the reference state instead of the live one
+    pub fn set_comparing(&self, value: bool) {
+        if self.imp().comparing.replace(value) != value {
+            self.redraw();
+        }
+    }
+
+    pub fn is_comparing(&self) -> bool {
+        self.imp().comparing.get()
+    }
+
+    /// What changed from the reference to the current state, if a reference is set
+    pub fn reference_diff(&self) -> Option<CellDelta> {
+        let reference = self.imp().reference.borrow().as_ref()?.snapshot();
+        Some(reference.diff(&self.get_universe_snapshot()))
+    }
+
+    /// Replaces the universe with one of the same size filled from `soup`
+    pub fn random_seed(&self, soup: &UniverseSoup) {
+        let current_universe = self.imp().universe.borrow();
+        let (rows, cols) = match current_universe.as_ref() {
+            Some(universe) => (universe.rows(), universe.columns()),
+            None => (200, 200),
+        };
+
+        drop(current_universe);
+
+        let new_universe = Universe::new_soup(rows, cols, soup);
+        self.set_universe(new_universe);
+    }
+
+    /// Grows or crops the universe to `rows`x`columns` around `anchor`, keeping
+    /// its cells. A running simulation is restarted from the resized universe.
+    pub fn resize_universe(&self, rows: usize, columns: usize, anchor: ResizeAnchor) {
+        self.reframe_universe(|universe| universe.resize(rows, columns, anchor));
+    }
+
+    /// Moves the living cells to the middle of the universe. Returns `false`
+    /// if there are none.
+    pub fn center_pattern(&self) -> bool {
+        let (top, left, rows, columns) = match self.get_universe_snapshot().bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return false,
+        };
+
+        let row_offset = ((self.rows() - rows) / 2) as isize - top as isize;
+        let column_offset = ((self.columns() - columns) / 2) as isize - left as isize;
+        self.reframe_universe(|universe| universe.shift(row_offset, column_offset));
+        true
+    }
+
+    /// Crops the universe to the living cells, keeping `margin` cells around
+    /// them. Returns `false` if there are none.
+    pub fn crop_to_pattern(&self, margin: usize) -> bool {
+        let (top, left, rows, columns) = match self.get_universe_snapshot().bounding_box() {
+            Some(bounding_box) => bounding_box,
+            None => return false,
+        };
+
+        self.reframe_universe(|universe| {
+            universe.crop_to(
+                top as isize - margin as isize,
+                left as isize - margin as isize,
+                rows + 2 * margin,
+                columns + 2 * margin,
+            )
+        });
+        true
+    }
+
+    /// Applies `reframe` to the universe as an edit that can be undone,
+    /// restarting a running simulation on the result
+    fn reframe_universe(&self, reframe: impl FnOnce(&mut Universe)) {
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+
+        let universe = self.imp().universe.borrow().clone();
+        if let Some(mut universe) = universe {
+            reframe(&mut universe);
+            self.set_universe(universe);
+        }
+
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// Replaces the universe with an empty one of the same size
+    pub fn clear(&self) {
+        let current_universe = self.imp().universe.borrow();
+        let (rows, cols) = match current_universe.as_ref() {
+            Some(universe) => (universe.rows(), universe.columns()),
+            None => (200, 200),
+        };
+
+        drop(current_universe);
+
+        self.set_universe(Universe::new_empty(rows, cols));
+    }
+
+    /// Computes the next generation, on the main thread, through the controller
+    /// so that every grid attached to it follows
+    pub fn skip_forward_one(&self) {
+        if let Some(universe) = self.simulated_universe() {
+            self.controller().step(universe);
+        }
+    }
+
+    pub fn set_universe(&self, universe: Universe) {
+        self.checkpoint();
+        self.replace_universe(universe);
+    }
+
+    /// Records the current universe, so that the edit about to be made can be
+    /// undone. Anything undone before cannot be redone anymore.
+    fn checkpoint(&self) {
+        let imp = self.imp();
+        if let Some(universe) = imp.universe.borrow().as_ref() {
+            let mut undo_stack = imp.undo_stack.borrow_mut();
+            if undo_stack.len() == UNDO_DEPTH {
+                undo_stack.remove(0);
+            }
+            undo_stack.push(universe.clone());
+            imp.redo_stack.borrow_mut().clear();
+        }
+    }
+
+    /// Brings the universe back to how it was before the last edit. Unbounded
+    /// universes are brought back to what the grid showed of them.
+    pub fn undo(&self) {
+        let imp = self.imp();
+        let previous = imp.undo_stack.borrow_mut().pop();
+        if let (Some(previous), Some(current)) = (previous, imp.universe.borrow().clone()) {
+            imp.redo_stack.borrow_mut().push(current);
+            self.replace_universe(previous);
+        }
+    }
+
+    /// Makes the last undone edit again
+    pub fn redo(&self) {
+        let imp = self.imp();
+        let next = imp.redo_stack.borrow_mut().pop();
+        if let (Some(next), Some(current)) = (next, imp.universe.borrow().clone()) {
+            imp.undo_stack.borrow_mut().push(current);
+            self.replace_universe(next);
+        }
+    }
+
+    fn replace_universe(&self, mut universe: Universe) {
+        universe.set_rule(self.rule());
+        universe.set_rain(self.rain());
+        universe.set_chance(self.chance());
+        universe.set_ant_steps(self.ant_steps());
+        universe.set_injection(self.injection());
+        universe.set_freeze_zones(self.freeze_zones());
+        if self.unbounded() {
+            self.imp()
+                .sparse_universe
+                .replace(Some(SparseUniverse::from(&universe)));
+        }
+        self.imp().universe.replace(Some(universe));
+        self.imp().history.borrow_mut().clear();
+        self.record_history();
+        if let Some(heatmap) = self.imp().heatmap.borrow_mut().as_mut() {
+            heatmap.clear();
+        }
+        self.record_heatmap();
+        self.notify("generation");
+        self.reset_stabilization();
+        self.redraw();
+        self.record_crash_event("Universe replaced");
+    }
+
+    /// Sets how many past generations are kept to rewind to
+    pub fn set_history_depth(&self, depth: usize) {
+        self.imp().history.borrow_mut().set_capacity(depth);
+    }
+
+    /// Records the current generation of the bounded universe. Unbounded
+    /// universes cannot be rewound.
+    fn record_history(&self) {
+        let imp = self.imp();
+        if imp.sparse_universe.borrow().is_some() {
+            return;
+        }
+
+        if let Some(universe) = imp.universe.borrow().as_ref() {
+            imp.history.borrow_mut().record(
+                universe.generations(),
+                universe.cells(),
+                universe.dying_states(),
+            );
+        }
+    }
+
+    /// The `(first, last)` generations that can be rewound to, if any
+    pub fn history_range(&self) -> Option<(u64, u64)> {
+        self.imp().history.borrow().range()
+    }
+
+    /// Brings the universe back to a recorded `generation`, halting the
+    /// simulation. Returns `false` if the generation is not recorded.
+    pub fn rewind_to(&self, generation: u64) -> bool {
+        let imp = self.imp();
+        let (cells, dying) = match imp.history.borrow().get(generation) {
+            Some((cells, dying)) => (cells.clone(), dying.map(<[u8]>::to_vec)),
+            None => return false,
+        };
+
+        if self.is_running() {
+            self.halt();
+        }
+
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.restore(generation, cells);
+            if let Some(dying) = dying {
+                universe.restore_dying_states(&dying);
+            }
+        }
+        self.record_crash_event(&format!("Rewound to generation {}", generation));
+        self.notify("generation");
+        self.reset_stabilization();
+        self.redraw();
+        true
+    }
+
+    /// Brings the universe back to the previous generation, if recorded
+    pub fn step_back(&self) -> bool {
+        match self.generation().checked_sub(1) {
+            Some(generation) => self.rewind_to(generation),
+            None => false,
+        }
+    }
+
+    /// Enables stabilization detection, reporting a `stable` universe once it has been
+    /// settled for `patience` generations. `None` disables detection.
+    pub fn set_stabilization_patience(&self, patience: Option<usize>) {
+        self.imp()
+            .stabilization
+            .replace(patience.map(StabilizationDetector::new));
+        self.notify("stable");
+    }
+
+    pub fn is_stable(&self) -> bool {
+        self.imp()
+            .stabilization
+            .borrow()
+            .as_ref()
+            .map(|detector| detector.is_stable())
+            .unwrap_or(false)
+    }
+
+    /// Enables reporting the period of repeating states through `period`
+    pub fn set_detects_oscillators(&self, value: bool) {
+        let had_period = self.period().is_some();
+        self.imp()
+            .oscillation
+            .replace(value.then(|| StabilizationDetector::new(1)));
+        if had_period {
+            self.notify("period");
+        }
+    }
+
+    /// The number of generations after which the current state repeats, 1 being
+    /// a still life, or `None` if it does not repeat a recent state or
+    /// oscillators are not detected
+    pub fn period(&self) -> Option<usize> {
+        self.imp()
+            .oscillation
+            .borrow()
+            .as_ref()
+            .and_then(|detector| detector.period())
+    }
+
+    /// Whether new generations need to be hashed for a detector
+    fn observes_states(&self) -> bool {
+        self.imp().stabilization.borrow().is_some() || self.imp().oscillation.borrow().is_some()
+    }
+
+    fn observe_state(&self, state_hash: u64) {
+        let was_stable = self.is_stable();
+        let is_stable = match self.imp().stabilization.borrow_mut().as_mut() {
+            Some(detector) => detector.observe(state_hash),
+            None => false,
+        };
+
+        let period = self.period();
+        if let Some(detector) = self.imp().oscillation.borrow_mut().as_mut() {
+            detector.observe(state_hash);
+        }
+
+        if was_stable != is_stable {
+            self.notify("stable");
+        }
+        if period != self.period() {
+            self.notify("period");
+        }
+    }
+
+    fn reset_stabilization(&self) {
+        let was_stable = self.is_stable();
+        if let Some(detector) = self.imp().stabilization.borrow_mut().as_mut() {
+            detector.reset();
+        }
+
+        let had_period = self.period().is_some();
+        if let Some(detector) = self.imp().oscillation.borrow_mut().as_mut() {
+            detector.reset();
+        }
+
+        if was_stable {
+            self.notify("stable");
+        }
+        if had_period {
+            self.notify("period");
+        }
+    }
+
+    pub fn in_transition(&self) -> bool {
+        self.imp().transition_opacity.get().is_some()
+    }
+
+    /// Fades the cells out, replaces the universe with `universe` and fades them
+    /// back in. A running simulation keeps running through the transition.
+    pub fn transition_to(&self, universe: Universe) {
+        let imp = self.imp();
+        imp.transition_target.replace(Some(universe));
+        if imp.transition_opacity.get().is_some() {
+            // The ongoing transition picks up the new target
+            return;
+        }
+
+        let started = std::time::Instant::now();
+        imp.transition_opacity.set(Some(1.0));
+        glib::timeout_add_local(
+            std::time::Duration::from_millis(16),
+            clone!(@weak self as this => @default-return glib::Continue(false), move || {
+                let imp = this.imp();
+                let progress =
+                    started.elapsed().as_secs_f64() / TRANSITION_FADE_DURATION.as_secs_f64();
+
+                if progress >= 1.0 {
+                    if let Some(target) = imp.transition_target.take() {
+                        let was_running = this.is_running();
+                        this.halt();
+                        this.set_universe(target);
+                        if was_running {
+                            this.run();
+                        }
+                    }
+                }
+
+                let continues = progress < 2.0;
+                imp.transition_opacity.set(match continues {
+                    true => Some((1.0 - progress).abs()),
+                    false => None,
+                });
+                this.queue_draw();
+
+                glib::Continue(continues)
+            }),
+        );
+    }
+
+    /// The number of generations computed for the current universe
+    pub fn generation(&self) -> u64 {
+        let imp = self.imp();
+        if let Some(sparse_universe) = imp.sparse_universe.borrow().as_ref() {
+            return sparse_universe.generations();
+        }
+
+        imp.universe
+            .borrow()
+            .as_ref()
+            .map(|universe| universe.generations())
+            .unwrap_or(0)
+    }
+
+    pub fn rule(&self) -> UniverseRule {
+        self.imp().rule.borrow().clone()
+    }
+
+    /// Sets the rule for the current universe and any universe set afterwards
+    pub fn set_rule(&self, value: UniverseRule) {
+        let imp = self.imp();
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_rule(value.clone());
+        }
+        if let Some(sparse_universe) = imp.sparse_universe.borrow_mut().as_mut() {
+            sparse_universe.set_rule(value.clone());
+        }
+        imp.rule.replace(value);
+        // Cells may have been put to rest, or be colored after other states
+        self.redraw();
+    }
+
+    pub fn rain(&self) -> Option<UniverseRain> {
+        self.imp().rain.get()
+    }
+
+    /// Sets the rain for the current universe and any universe set afterwards.
+    /// A running simulation is restarted to pick it up.
+    pub fn set_rain(&self, value: Option<UniverseRain>) {
+        let imp = self.imp();
+        if imp.rain.replace(value) == value {
+            return;
+        }
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_rain(value);
+        }
+        if let Some(sparse_universe) = imp.sparse_universe.borrow_mut().as_mut() {
+            sparse_universe.set_rain(value);
+        }
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// The steps every ant takes in a generation under ant rules
+    pub fn ant_steps(&self) -> u32 {
+        self.imp().ant_steps.get().max(1)
+    }
+
+    /// Sets the steps ants take in a generation, for the current universe and
+    /// any universe set afterwards. A running simulation is restarted to pick
+    /// them up.
+    pub fn set_ant_steps(&self, value: u32) {
+        let imp = self.imp();
+        if imp.ant_steps.replace(value) == value {
+            return;
+        }
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_ant_steps(value);
+        }
+        if was_running {
+            self.run();
+        }
+    }
+
+    pub fn chance(&self) -> Option<UniverseChance> {
+        self.imp().chance.borrow().clone()
+    }
+
+    /// Makes births and survivals of the current universe and any universe set
+    /// afterwards happen with the probabilities of `value`, drawing from the start
+    /// of its seeded sequence. A running simulation is restarted to pick it up.
+    pub fn set_chance(&self, value: Option<UniverseChance>) {
+        let imp = self.imp();
+        if *imp.chance.borrow() == value {
+            return;
+        }
+        imp.chance.replace(value.clone());
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_chance(value.clone());
+        }
+        if let Some(sparse_universe) = imp.sparse_universe.borrow_mut().as_mut() {
+            sparse_universe.set_chance(value);
+        }
+        if was_running {
+            self.run();
+        }
+    }
+
+    pub fn freeze_zones(&self) -> Vec<UniverseArea> {
+        self.imp().freeze_zones.borrow().clone()
+    }
+
+    /// Sets the areas of the current universe and any universe set afterwards
+    /// whose cells never change. Unbounded universes do not support freeze zones.
+    /// A running simulation is restarted to pick them up.
+    fn set_freeze_zones(&self, value: Vec<UniverseArea>) {
+        let imp = self.imp();
+        imp.freeze_zones.replace(value.clone());
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_freeze_zones(value);
+        }
+        if was_running {
+            self.run();
+        }
+        self.queue_draw();
+    }
+
+    /// Freezes the cells in the selection
+    pub fn freeze_selection(&self) {
+        if let Some(area) = self.selection() {
+            let mut zones = self.freeze_zones();
+            zones.push(area);
+            self.set_freeze_zones(zones);
+        }
+    }
+
+    /// Lets the cells of the freeze zones touching the selection evolve again
+    pub fn unfreeze_selection(&self) {
+        if let Some(area) = self.selection() {
+            let mut zones = self.freeze_zones();
+            zones.retain(|zone| !zone.intersects(&area));
+            self.set_freeze_zones(zones);
+        }
+    }
+
+    pub fn injection(&self) -> Option<UniverseInjection> {
+        self.imp().injection.borrow().clone()
+    }
+
+    /// Sets the pattern stamped on an edge of the current universe and any
+    /// universe set afterwards. Unbounded universes have no edges and are not
+    /// injected into. A running simulation is restarted to pick it up.
+    pub fn set_injection(&self, value: Option<UniverseInjection>) {
+        let imp = self.imp();
+        if *imp.injection.borrow() == value {
+            return;
+        }
+        imp.injection.replace(value.clone());
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        if let Some(universe) = imp.universe.borrow_mut().as_mut() {
+            universe.set_injection(value);
+        }
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// Whether the universe is unbounded, in which case the grid only shows
+    /// a viewport over it instead of wrapping around its edges
+    pub fn unbounded(&self) -> bool {
+        self.imp().sparse_universe.borrow().is_some()
+    }
+
+    /// Moves the viewport of an unbounded universe by `rows` and `columns`
+    /// over the plane. Bounded universes are left as they are.
+    pub fn pan_unbounded(&self, rows: i64, columns: i64) {
+        if (rows, columns) == (0, 0) {
+            return;
+        }
+
+        let viewport = match self.imp().sparse_universe.borrow().as_ref() {
+            Some(sparse_universe) => sparse_universe.viewport().panned(rows, columns),
+            None => return,
+        };
+        self.set_unbounded_viewport(viewport);
+    }
+
+    /// The region of the plane shown for an unbounded universe, in world
+    /// coordinates, or `None` for bounded universes
+    pub fn unbounded_viewport(&self) -> Option<SparseViewport> {
+        self.imp()
+            .sparse_universe
+            .borrow()
+            .as_ref()
+            .map(|sparse_universe| sparse_universe.viewport())
+    }
+
+    /// Shows the region of the plane at the top left corner of `viewport`,
+    /// for an unbounded universe. A running simulation keeps going, its next
+    /// generations being shown through the new viewport, while its rain and
+    /// noise stay on the viewport it was started with.
+    pub fn set_unbounded_viewport(&self, viewport: SparseViewport) {
+        let imp = self.imp();
+        let projection = match imp.sparse_universe.borrow_mut().as_mut() {
+            Some(sparse_universe) if sparse_universe.viewport() != viewport => {
+                sparse_universe.set_viewport(viewport.top, viewport.left);
+                sparse_universe.project()
+            }
+            _ => return,
+        };
+        imp.universe.replace(Some(projection));
+        self.redraw();
+    }
+
+    /// Moves the viewport of an unbounded universe for the focused cell to be
+    /// at its center, so that the plane all around it can be scrolled to
+    fn center_unbounded_viewport_on_focus(&self) {
+        let imp = self.imp();
+        let (focus_row, focus_column) = match imp.zoom_focus.get() {
+            Some(focus) if self.unbounded() => focus,
+            _ => return,
+        };
+
+        let rows = (focus_row - self.rows() as f64 / 2.0).round();
+        let columns = (focus_column - self.columns() as f64 / 2.0).round();
+        imp.zoom_focus
+            .set(Some((focus_row - rows, focus_column - columns)));
+        self.pan_unbounded(rows as i64, columns as i64);
+    }
+
+    /// The engine evolving the current universe
+    pub fn engine(&self) -> Engine {
+        match self.unbounded() {
+            true => Engine::Unbounded,
+            false => Engine::Bounded,
+        }
+    }
+
+    pub fn set_unbounded(&self, value: bool) {
+        if value == self.unbounded() || self.is_running() {
+            return;
+        }
+
+        let sparse_universe = match value {
+            true => self
+                .imp()
+                .universe
+                .borrow()
+                .as_ref()
+                .map(SparseUniverse::from),
+            false => None,
+        };
+        self.imp().sparse_universe.replace(sparse_universe);
+        self.notify("unbounded");
+    }
+
+    /// Redraws the grid, repainting every cell
+    pub fn redraw(&self) {
+        self.imp().damage.replace(UniverseGridDamage::Full);
+        self.update_adjustments();
+        self.queue_draw();
     }
 
-    fn on_drawing_area_clicked(
-        &self,
-        _gesture: &gtk::GestureClick,
-        _n_press: i32,
-        x: f64,
-        y: f64,
-        alter_state: Option<UniverseCell>,
-    ) {
-        if self.mode() == UniverseGridMode::Unlocked {
-            self.imp()
-                .interaction_state
-                .set(UniverseGridInteractionState::Ongoing);
-            self.alter_universe_point(x, y, alter_state);
+    /// Redraws the grid, repainting only the cells in `changes`
+    fn redraw_changes(&self, changes: Vec<UniversePoint>) {
+        let mut damage = self.imp().damage.borrow_mut();
+        match &mut *damage {
+            UniverseGridDamage::None => *damage = UniverseGridDamage::Cells(changes),
+            UniverseGridDamage::Cells(pending) => pending.extend(changes),
+            UniverseGridDamage::Full => (),
         }
+        drop(damage);
+        self.queue_draw();
     }
 
-    fn on_drawing_area_click_released(
-        &self,
-        _gesture: &gtk::GestureClick,
-        _n_press: i32,
-        _x: f64,
-        _y: f64,
-    ) {
-        self.imp()
-            .interaction_state
-            .set(UniverseGridInteractionState::Idle);
+    /// Applies new colors, redrawing the grid once if they changed
+    pub fn set_palette(&self, palette: UniverseGridPalette) {
+        self.process_action(UniverseGridRequest::PaletteChanged(palette));
     }
 
-    fn on_drawing_area_click_unpaired_released(
-        &self,
-        _gesture: &gtk::GestureClick,
-        _x: f64,
-        _y: f64,
-        _button: u32,
-        _events: Option<&gtk::gdk::EventSequence>,
-    ) {
-        self.imp()
-            .interaction_state
-            .set(UniverseGridInteractionState::Idle);
+    pub fn inverted_colors(&self) -> bool {
+        self.imp().inverted_colors.get()
     }
 
-    fn on_drawing_area_drag_begin(
-        &self,
-        gesture: &gtk::GestureDrag,
-        _events: Option<&gtk::gdk::EventSequence>,
-        alter_state: Option<UniverseCell>,
-    ) {
-        if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
-            if let Some(point) = gesture.start_point() {
-                self.alter_universe_point(point.0, point.1, alter_state);
-            }
+    /// Swaps the cell and background colors, as for dark rooms
+    pub fn set_inverted_colors(&self, value: bool) {
+        if self.imp().inverted_colors.replace(value) != value {
+            self.notify("inverted-colors");
+            self.redraw();
         }
     }
 
-    fn on_drawing_area_drag_move(
-        &self,
-        gesture: &gtk::GestureDrag,
-        _events: Option<&gtk::gdk::EventSequence>,
-        alter_state: Option<UniverseCell>,
-    ) {
-        if self.imp().interaction_state.get() == UniverseGridInteractionState::Ongoing {
-            if let Some(point) = gesture.offset() {
-                let origin = gesture.start_point().unwrap();
-                self.alter_universe_point(origin.0 + point.0, origin.1 + point.1, alter_state);
-            }
-        }
+    pub fn rows(&self) -> usize {
+        self.imp().universe.borrow().as_ref().unwrap().rows()
     }
 
-    fn on_drawing_area_mouse_position(
-        &self,
-        _controller: &gtk::EventControllerMotion,
-        x: f64,
-        y: f64,
-    ) {
-        self.imp()
-            .point_under_pointing_device
-            .set(widget_area_point_to_universe_cell(
-                &self.imp().obj(),
-                self.imp().universe.borrow().as_ref(),
-                x,
-                y,
-            ));
+    pub fn columns(&self) -> usize {
+        self.imp().universe.borrow().as_ref().unwrap().columns()
     }
 
-    fn on_drawing_area_mouse_leave(&self, _controller: &gtk::EventControllerMotion) {
-        self.imp().point_under_pointing_device.set(None);
+    pub fn draw_cells_outline(&self) -> bool {
+        self.imp().draw_cells_outline.get()
     }
 
-    /// Alters the universe cell visually located at `x` and `y` coordinates. If `Some(value)`
-    /// is provided it will be used as the new cell value, else the opposite value of the current
-    /// one will be set
-    fn alter_universe_point(&self, x: f64, y: f64, value: Option<UniverseCell>) {
-        let drawing_area = self.imp().obj();
-        let universe_borrow = self.imp().universe.borrow();
+    pub fn set_draw_cells_outline(&self, value: bool) {
+        let current = self.imp().draw_cells_outline.get();
+        if value != current {
+            self.imp().draw_cells_outline.set(value);
+            self.redraw();
+        }
+    }
 
-        if let Some(universe_point) =
-            widget_area_point_to_universe_cell(&drawing_area, universe_borrow.as_ref(), x, y)
-        {
-            // If a point is found, set its cell value
-            drop(universe_borrow);
-            let mut universe_mut_borrow = self.imp().universe.borrow_mut();
-            let mut_borrow = universe_mut_borrow.as_mut().unwrap();
+    pub fn set_stylus_brush_radius(&self, value: usize) {
+        self.imp().stylus_brush_radius.set(value);
+    }
 
-            // NONE value means invert the cell value, SOME value sets it
-            let next_value = match value {
-                Some(v) => v,
-                None => !(*universe_point.cell()),
-            };
+    /// What strokes of the primary button do in design mode
+    pub fn tool(&self) -> DesignTool {
+        self.imp().tool.get()
+    }
 
-            mut_borrow.set_cell(universe_point.row(), universe_point.column(), next_value);
-            self.redraw();
-        }
+    pub fn set_tool(&self, value: DesignTool) {
+        self.imp().tool.set(value);
     }
 
-    pub fn mode(&self) -> UniverseGridMode {
-        self.imp().mode.get()
+    /// The width of the square brush of the design tools, in cells
+    pub fn brush_size(&self) -> usize {
+        self.imp().brush_size.get()
     }
 
-    pub fn set_mode(&self, value: UniverseGridMode) {
-        if !self.is_running() {
-            self.imp().mode.set(value);
-            self.notify("mode");
-        }
+    pub fn set_brush_size(&self, value: usize) {
+        self.imp().brush_size.set(value.clamp(1, BRUSH_MAX_SIZE));
     }
 
-    pub fn is_running(&self) -> bool {
-        self.imp().render_thread_stopper.borrow().is_some()
+    /// What drawing brings to life in WireWorld universes
+    pub fn wireworld_cell(&self) -> WireWorldCell {
+        self.imp().wireworld_cell.get()
     }
 
-    pub fn set_frozen(&self, value: bool) {
-        self.imp().frozen.set(value);
-        if !value {
-            self.queue_draw();
-        }
+    pub fn set_wireworld_cell(&self, value: WireWorldCell) {
+        self.imp().wireworld_cell.set(value);
     }
 
-    pub fn frozen(&self) -> bool {
-        self.imp().frozen.get()
+    /// The color of the cells drawing brings to life under colored rules
+    pub fn drawing_color(&self) -> u8 {
+        self.imp().drawing_color.get()
     }
 
-    pub fn allow_draw_on_resize(&self) -> bool {
-        self.imp().allow_draw_on_resize.get()
+    pub fn set_drawing_color(&self, value: u8) {
+        self.imp().drawing_color.set(value);
     }
 
-    pub fn set_allow_render_on_resize(&self, value: bool) {
-        self.imp().allow_draw_on_resize.set(value);
+    /// Whether drawing puts ants down instead of cells under ant rules
+    pub fn draws_ants(&self) -> bool {
+        self.imp().draws_ants.get()
     }
 
-    fn get_sender(&self) -> Sender<UniverseGridRequest> {
-        self.imp().sender.as_ref().unwrap().clone()
+    pub fn set_draws_ants(&self, value: bool) {
+        self.imp().draws_ants.set(value);
     }
 
-    pub fn run(&self) {
-        // self.set_mode(UniverseGridMode::Run);
-        let local_sender = self.get_sender();
+    /// How stamped patterns combine with the cells underneath
+    pub fn stamp_blend(&self) -> StampBlend {
+        self.imp().stamp_blend.get()
+    }
 
-        let (thread_render_stopper_sender, thread_render_stopper_receiver) =
-            std::sync::mpsc::channel::<()>();
+    pub fn set_stamp_blend(&self, value: StampBlend) {
+        self.imp().stamp_blend.set(value);
+    }
 
-        // Drop this to stop ticking thread
-        self.imp()
-            .render_thread_stopper
-            .replace(Some(thread_render_stopper_receiver));
-
-        let thread_universe = self.imp().universe.borrow();
-        if let Some(universe) = thread_universe.as_ref() {
-            let mut thread_universe = universe.clone();
-            let wait: u64 = 1000 / u64::from(self.evolution_speed());
-            std::thread::spawn(move || {
-                while thread_render_stopper_sender.send(()).is_ok() {
-                    std::thread::sleep(std::time::Duration::from_millis(wait));
-                    thread_universe.tick();
-                    local_sender
-                        .send(UniverseGridRequest::Redraw(Some(thread_universe.clone())))
-                        .unwrap();
-                }
+    pub fn fades_dead_cells(&self) -> bool {
+        self.imp().fades_dead_cells.get()
+    }
+
+    /// Accounts for a frame that took `frame_time` to draw, skipping details in
+    /// the following frames if it ran over budget
+    fn record_frame(&self, frame_time: std::time::Duration) {
+        let imp = self.imp();
+        let reduced_detail_frames = imp.reduced_detail_frames.get();
+        let mut stats = imp.render_stats.get();
+        stats.frames += 1;
+        if reduced_detail_frames > 0 {
+            stats.reduced_frames += 1;
+        }
+        stats.last_frame_time = frame_time;
+        imp.render_stats.set(stats);
+
+        imp.reduced_detail_frames
+            .set(match frame_time > FRAME_BUDGET {
+                true => REDUCED_DETAIL_FRAMES,
+                false => reduced_detail_frames.saturating_sub(1),
             });
 
-            self.notify("running");
-        } else {
-            glib::warn!("No universe to run");
+        // Details come back with a frame of their own once the simulation
+        // stops asking for new ones
+        if reduced_detail_frames == 1 {
+            glib::idle_add_local_once(clone!(@weak self as this => move || {
+                this.queue_draw();
+            }));
         }
     }
 
-    pub fn halt(&self) {
-        let inner = self.imp().render_thread_stopper.take();
-        drop(inner);
-        self.notify("running");
+    pub fn shows_performance_hud(&self) -> bool {
+        self.imp().shows_performance_hud.get()
     }
 
-    pub fn toggle_run(&self) {
-        if self.is_running() {
-            self.halt();
-        } else {
-            self.run();
+    /// Overlays how long frames take to draw and how many skipped details
+    pub fn set_shows_performance_hud(&self, value: bool) {
+        self.imp().shows_performance_hud.set(value);
+        self.queue_draw();
+    }
+
+    pub fn shows_heatmap(&self) -> bool {
+        self.imp().heatmap.borrow().is_some()
+    }
+
+    /// Tints cells by how often they were alive over the last generations,
+    /// counting from the one shown when turned on
+    pub fn set_shows_heatmap(&self, value: bool) {
+        let imp = self.imp();
+        match value {
+            true if imp.heatmap.borrow().is_none() => {
+                imp.heatmap
+                    .replace(Some(ActivityHeatmap::new(imp.heatmap_generations.get())));
+                self.record_heatmap();
+            }
+            true => (),
+            false => drop(imp.heatmap.take()),
         }
+        self.queue_draw();
     }
 
-    pub fn get_universe_snapshot(&self) -> UniverseSnapshot {
+    /// Spans the heatmap over the last `value` generations
+    pub fn set_heatmap_generations(&self, value: usize) {
         let imp = self.imp();
-        imp.universe.borrow().as_ref().unwrap().snapshot()
+        imp.heatmap_generations.set(value);
+        if let Some(heatmap) = imp.heatmap.borrow_mut().as_mut() {
+            heatmap.set_window(value);
+        }
+        self.queue_draw();
     }
 
-    pub fn random_seed(&self) {
-        let current_universe = self.imp().universe.borrow();
-        let (rows, cols) = match current_universe.as_ref() {
-            Some(universe) => (universe.rows(), universe.columns()),
-            None => (200, 200),
-        };
+    /// Counts the cells alive in the generation shown, while the heatmap is
+    fn record_heatmap(&self) {
+        let imp = self.imp();
+        if let (Some(heatmap), Some(universe)) = (
+            imp.heatmap.borrow_mut().as_mut(),
+            imp.universe.borrow().as_ref(),
+        ) {
+            heatmap.record(universe.generations(), universe.cells());
+        }
+    }
 
-        drop(current_universe);
+    pub fn shows_grid_coordinates(&self) -> bool {
+        self.imp().shows_grid_coordinates.get()
+    }
+
+    /// Labels rows and columns along the edges of the grid, once cells are
+    /// large enough for grid lines to be drawn between them
+    pub fn set_shows_grid_coordinates(&self, value: bool) {
+        if self.imp().shows_grid_coordinates.replace(value) != value {
+            self.queue_draw();
+        }
+    }
 
-        let new_universe = Universe::new_random(rows, cols);
-        self.process_action(UniverseGridRequest::Redraw(Some(new_universe)));
+    pub fn zoom_in(&self) {
+        self.set_zoom(match self.imp().zoom.get() {
+            UniverseGridZoom::Fit(level) => UniverseGridZoom::Fit((level + 1).min(ZOOM_MAX_LEVEL)),
+            UniverseGridZoom::CellSize(size) => {
+                UniverseGridZoom::CellSize((size * ZOOM_STEP).min(ZOOM_MAX_CELL_SIZE))
+            }
+        });
     }
 
-    pub fn skip_forward_one(&self) {
-        if let Ok(mut borrow) = self.imp().universe.try_borrow_mut() {
-            if let Some(current_universe) = borrow.as_mut() {
-                current_universe.tick();
-                self.redraw();
+    /// Shrinks cells by a step. Cells of a fixed size shrink down to a device
+    /// pixel, or go back to fitting the universe once they would be smaller
+    /// than that takes.
+    pub fn zoom_out(&self) {
+        self.set_zoom(match self.imp().zoom.get() {
+            UniverseGridZoom::Fit(level) => UniverseGridZoom::Fit(level.saturating_sub(1)),
+            UniverseGridZoom::CellSize(size) => {
+                let fit_size = self.fit_cell_size();
+                let pixel = 1.0 / self.scale_factor() as f64;
+                match size > fit_size && size / ZOOM_STEP <= fit_size {
+                    true => UniverseGridZoom::Fit(0),
+                    false => UniverseGridZoom::CellSize((size / ZOOM_STEP).max(pixel)),
+                }
             }
+        });
+    }
+
+    /// Fits the whole universe in the widget again. Unbounded universes are
+    /// centered on their living cells, wherever they went on the plane.
+    pub fn reset_zoom(&self) {
+        self.imp().zoom_focus.set(None);
+        let living_center =
+            self.imp()
+                .sparse_universe
+                .borrow()
+                .as_ref()
+                .and_then(|sparse_universe| {
+                    let (top, left, rows, columns) = sparse_universe.bounding_box()?;
+                    Some(
+                        sparse_universe
+                            .viewport()
+                            .centered_on(top + rows as i64 / 2, left + columns as i64 / 2),
+                    )
+                });
+        if let Some(viewport) = living_center {
+            self.set_unbounded_viewport(viewport);
         }
+        self.set_zoom(UniverseGridZoom::Fit(0));
     }
 
-    pub fn set_universe(&self, universe: Universe) {
-        self.imp().universe.replace(Some(universe));
-        self.redraw();
+    /// Draws each cell on a single device pixel, keeping the focused cell
+    /// at the center
+    pub fn zoom_to_actual_size(&self) {
+        self.set_zoom(UniverseGridZoom::CellSize(1.0 / self.scale_factor() as f64));
     }
 
-    pub fn redraw(&self) {
+    /// Enlarges cells for the selection to fill the widget, centered on it.
+    /// Does nothing without a selection.
+    pub fn zoom_to_selection(&self) {
+        let area = match self.selection() {
+            Some(area) => area,
+            None => return,
+        };
+        let size = f64::min(
+            self.width() as f64 / area.rows().max(1) as f64,
+            self.height() as f64 / area.columns().max(1) as f64,
+        );
+        self.set_zoom_focus(Some((
+            area.row() as f64 + area.rows() as f64 / 2.0,
+            area.column() as f64 + area.columns() as f64 / 2.0,
+        )));
+        self.set_zoom(UniverseGridZoom::CellSize(size.min(ZOOM_MAX_CELL_SIZE)));
+    }
+
+    /// The size of the smaller side of cells when fitting the universe
+    fn fit_cell_size(&self) -> f64 {
+        match self.imp().universe.borrow().as_ref() {
+            Some(universe) if universe.rows() > 0 && universe.columns() > 0 => f64::min(
+                self.width() as f64 / universe.rows() as f64,
+                self.height() as f64 / universe.columns() as f64,
+            ),
+            _ => 0.0,
+        }
+    }
+
+    /// Keeps the cell at `focus`, in fractional `(row, column)` coordinates, at
+    /// the center of the widget while zoomed in. `None` centers the universe.
+    pub fn set_zoom_focus(&self, focus: Option<(f64, f64)>) {
+        self.imp().zoom_focus.set(focus);
+        self.center_unbounded_viewport_on_focus();
+        self.update_adjustments();
         self.queue_draw();
     }
 
-    pub fn set_cell_color(&self, color: Option<gtk::gdk::RGBA>) {
-        self.imp().fg_color.set(color);
-        self.redraw();
+    fn set_zoom(&self, value: UniverseGridZoom) {
+        if self.imp().zoom.replace(value) != value {
+            self.center_unbounded_viewport_on_focus();
+            self.update_adjustments();
+            self.queue_draw();
+        }
     }
 
-    pub fn set_background_color(&self, color: Option<gtk::gdk::RGBA>) {
-        self.imp().bg_color.set(color);
-        self.redraw();
+    /// Draws cells `size` logical pixels large, scrolling over universes
+    /// larger than the widget, or fits the universe in the widget with 0
+    pub fn set_fixed_cell_size(&self, size: u32) {
+        if self.imp().fixed_cell_size.replace(size) != size {
+            self.set_zoom(match size {
+                0 => UniverseGridZoom::Fit(0),
+                size => UniverseGridZoom::CellSize(size as f64),
+            });
+        }
     }
 
-    pub fn rows(&self) -> usize {
-        self.imp().universe.borrow().as_ref().unwrap().rows()
+    fn adjustment(&self, orientation: gtk::Orientation) -> Option<gtk::Adjustment> {
+        let slot = match orientation {
+            gtk::Orientation::Horizontal => &self.imp().hadjustment,
+            _ => &self.imp().vadjustment,
+        };
+        slot.borrow()
+            .as_ref()
+            .map(|(adjustment, _)| adjustment.clone())
     }
 
-    pub fn columns(&self) -> usize {
-        self.imp().universe.borrow().as_ref().unwrap().columns()
+    /// Scrolls along `orientation` with `adjustment`, as set by the scrolled
+    /// window the grid is in
+    fn set_adjustment(&self, orientation: gtk::Orientation, adjustment: Option<gtk::Adjustment>) {
+        let slot = match orientation {
+            gtk::Orientation::Horizontal => &self.imp().hadjustment,
+            _ => &self.imp().vadjustment,
+        };
+        if let Some((previous, handler)) = slot.take() {
+            previous.disconnect(handler);
+        }
+        if let Some(adjustment) = adjustment {
+            let handler = adjustment.connect_value_changed(clone!(@weak self as this => move |_| {
+                this.on_adjustment_value_changed();
+            }));
+            slot.replace(Some((adjustment, handler)));
+        }
+        self.update_adjustments();
     }
 
-    pub fn draw_cells_outline(&self) -> bool {
-        self.imp().draw_cells_outline.get()
+    /// Sets the adjustments to the part of the universe shown, their upper
+    /// bound being the size of the whole universe
+    fn update_adjustments(&self) {
+        let imp = self.imp();
+        let (viewport, rows, columns) = match imp.universe.borrow().as_ref() {
+            Some(universe) => (
+                widget_viewport(self, universe),
+                universe.rows(),
+                universe.columns(),
+            ),
+            None => return,
+        };
+        let (width, height) = (self.width() as f64, self.height() as f64);
+        let (cell_width, cell_height) = viewport.cell_size();
+        let (origin_x, origin_y) = viewport.origin();
+
+        imp.updating_adjustments.set(true);
+        for (orientation, size, extent, origin, cell_size) in [
+            (
+                gtk::Orientation::Horizontal,
+                width,
+                rows as f64 * cell_width,
+                origin_x,
+                cell_width,
+            ),
+            (
+                gtk::Orientation::Vertical,
+                height,
+                columns as f64 * cell_height,
+                origin_y,
+                cell_height,
+            ),
+        ] {
+            if let Some(adjustment) = self.adjustment(orientation) {
+                let (value, upper) = ((-origin).max(0.0), extent.max(size));
+                if (
+                    adjustment.value(),
+                    adjustment.upper(),
+                    adjustment.page_size(),
+                ) != (value, upper, size)
+                {
+                    adjustment.configure(value, 0.0, upper, cell_size.max(1.0), size * 0.9, size);
+                }
+            }
+        }
+        imp.updating_adjustments.set(false);
     }
 
-    pub fn set_draw_cells_outline(&self, value: bool) {
-        let current = self.imp().draw_cells_outline.get();
-        if value != current {
-            self.imp().draw_cells_outline.set(value);
-            self.redraw();
+    /// Focuses the zoom on the cell at the center of the part of the universe
+    /// scrolled to
+    fn on_adjustment_value_changed(&self) {
+        let imp = self.imp();
+        if imp.updating_adjustments.get() {
+            return;
+        }
+
+        let (cell_width, cell_height) = match imp.universe.borrow().as_ref() {
+            Some(universe) => widget_viewport(self, universe).cell_size(),
+            None => return,
+        };
+        if cell_width <= 0.0 || cell_height <= 0.0 {
+            return;
         }
+        let value = |orientation| {
+            self.adjustment(orientation)
+                .map(|adjustment| adjustment.value())
+                .unwrap_or(0.0)
+        };
+        imp.zoom_focus.set(Some((
+            (value(gtk::Orientation::Horizontal) + self.width() as f64 / 2.0) / cell_width,
+            (value(gtk::Orientation::Vertical) + self.height() as f64 / 2.0) / cell_height,
+        )));
+        self.queue_draw();
     }
 
-    pub fn fades_dead_cells(&self) -> bool {
-        self.imp().fades_dead_cells.get()
+    pub fn death_trail(&self) -> u32 {
+        self.imp().death_trail.get()
+    }
+
+    /// Keeps dead cells visible, fading out over `value` generations, so that
+    /// moving patterns leave a trail behind. 0 disables the trail.
+    pub fn set_death_trail(&self, value: u32) {
+        if self.imp().death_trail.replace(value) != value {
+            if !self.is_running() {
+                self.redraw();
+            } else {
+                self.imp().damage.replace(UniverseGridDamage::Full);
+            }
+        }
     }
 
     pub fn set_fades_dead_cells(&self, value: bool) {
@@ -721,16 +4270,80 @@ impl GameOfLifeUniverseGrid {
 
             if !self.is_running() {
                 self.redraw();
+            } else {
+                // Repaint every cell on the next generation
+                self.imp().damage.replace(UniverseGridDamage::Full);
             }
         }
     }
 
+    /// Whether running simulations halt by themselves once every cell died or
+    /// the universe keeps repeating itself
+    pub fn halts_when_settled(&self) -> bool {
+        self.controller().halts_when_settled()
+    }
+
+    pub fn set_halts_when_settled(&self, value: bool) {
+        self.controller().set_halts_when_settled(value);
+    }
+
+    /// Takes the reason why the simulation last halted by itself, if it did
+    pub fn take_halt_reason(&self) -> Option<SimulationHaltReason> {
+        self.controller().take_halt_reason()
+    }
+
     pub fn evolution_speed(&self) -> u32 {
-        self.imp().evolution_speed.get()
+        self.controller().evolution_speed()
     }
 
     pub fn set_evolution_speed(&self, value: u32) {
-        self.imp().evolution_speed.set(value);
+        self.controller().set_evolution_speed(value);
+    }
+
+    /// Whether the simulation computes generations as fast as possible, only
+    /// redrawing some of them
+    pub fn is_turbo(&self) -> bool {
+        self.controller().is_turbo()
+    }
+
+    /// A running simulation is restarted to pick it up.
+    pub fn set_turbo(&self, value: bool) {
+        if self.is_turbo() == value {
+            return;
+        }
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        self.controller().set_turbo(value);
+        if was_running {
+            self.run();
+        }
+        self.notify("turbo");
+    }
+
+    /// Whether running simulations are slowed down because the system asks to
+    /// save power. A running simulation is restarted to pick it up.
+    pub fn set_power_saving(&self, value: bool) {
+        if self.controller().is_power_saving() == value {
+            return;
+        }
+
+        let was_running = self.is_running();
+        if was_running {
+            self.halt();
+        }
+        self.controller().set_power_saving(value);
+        if was_running {
+            self.run();
+        }
+    }
+
+    /// The number of generations the running simulation computed per second
+    /// lately, once measured
+    pub fn generations_per_second(&self) -> Option<f64> {
+        self.controller().generations_per_second()
     }
 
     pub fn animated(&self) -> bool {
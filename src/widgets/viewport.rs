@@ -0,0 +1,249 @@
+use std::ops::Range;
+
+/// Maps universe cells to widget coordinates and back. Every conversion between
+/// the two goes through here, so that what is drawn and what is clicked always
+/// agree.
+///
+/// The grid lays rows along the horizontal axis and columns along the vertical
+/// one. By default cells stretch to fill the widget; with letterboxing they stay
+/// square and the universe is centered. The zoom factor scales cells around the
+/// top left corner of the universe, which the pan offset then moves, in widget
+/// coordinates. A fixed cell size instead draws square cells of that size,
+/// whatever the size of the widget. Cell edges are snapped to device pixels for the scale factor of
+/// the display.
+///
+/// Hexagonal cells are laid out in offset columns: cells of odd columns are
+/// shifted by half a cell along the rows, and each cell is the hexagon with
+/// pointed ends inscribed in its rectangle, reaching a sixth of a cell into
+/// the columns above and below.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UniverseGridViewport {
+    width: f64,
+    height: f64,
+    rows: usize,
+    columns: usize,
+    zoom: f64,
+    cell_size: Option<f64>,
+    pan: (f64, f64),
+    letterbox: bool,
+    scale_factor: f64,
+    hexagonal: bool,
+}
+
+impl UniverseGridViewport {
+    /// Creates a viewport showing a `rows`x`columns` universe on a widget
+    /// of `width`x`height` logical pixels
+    pub fn new(width: f64, height: f64, rows: usize, columns: usize) -> Self {
+        Self {
+            width,
+            height,
+            rows,
+            columns,
+            zoom: 1.0,
+            cell_size: None,
+            pan: (0.0, 0.0),
+            letterbox: false,
+            scale_factor: 1.0,
+            hexagonal: false,
+        }
+    }
+
+    pub fn with_zoom(mut self, zoom: f64) -> Self {
+        self.zoom = zoom.max(f64::EPSILON);
+        self
+    }
+
+    /// Draws square cells of `size` logical pixels instead of fitting the
+    /// universe in the widget, the zoom factor being left out
+    pub fn with_cell_size(mut self, size: Option<f64>) -> Self {
+        self.cell_size = size.map(|size| size.max(f64::EPSILON));
+        self
+    }
+
+    pub fn with_pan(mut self, x: f64, y: f64) -> Self {
+        self.pan = (x, y);
+        self
+    }
+
+    #[allow(dead_code)]
+    pub fn with_letterbox(mut self, letterbox: bool) -> Self {
+        self.letterbox = letterbox;
+        self
+    }
+
+    /// Sets the number of device pixels per logical pixel
+    pub fn with_scale_factor(mut self, scale_factor: f64) -> Self {
+        self.scale_factor = scale_factor.max(1.0);
+        self
+    }
+
+    /// Lays cells out as hexagons
+    pub fn with_hexagonal(mut self, hexagonal: bool) -> Self {
+        self.hexagonal = hexagonal;
+        self
+    }
+
+    pub fn is_hexagonal(&self) -> bool {
+        self.hexagonal
+    }
+
+    /// The number of device pixels per logical pixel
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor
+    }
+
+    /// The `(width, height)` of a cell, in logical pixels
+    pub fn cell_size(&self) -> (f64, f64) {
+        if self.rows == 0 || self.columns == 0 {
+            return (0.0, 0.0);
+        }
+        if let Some(size) = self.cell_size {
+            return (size, size);
+        }
+
+        let (width, height) = (
+            self.width / self.rows as f64,
+            self.height / self.columns as f64,
+        );
+        match self.letterbox {
+            true => (width.min(height) * self.zoom, width.min(height) * self.zoom),
+            false => (width * self.zoom, height * self.zoom),
+        }
+    }
+
+    /// Where the top left corner of the universe lies on the widget
+    pub fn origin(&self) -> (f64, f64) {
+        let (cell_width, cell_height) = self.cell_size();
+        let (x, y) = match self.letterbox && self.cell_size.is_none() {
+            true => (
+                (self.width - cell_width * self.rows as f64 / self.zoom) / 2.0,
+                (self.height - cell_height * self.columns as f64 / self.zoom) / 2.0,
+            ),
+            false => (0.0, 0.0),
+        };
+        (x + self.pan.0, y + self.pan.1)
+    }
+
+    /// Snaps a logical coordinate to the closest device pixel
+    fn snap(&self, value: f64) -> f64 {
+        (value * self.scale_factor).round() / self.scale_factor
+    }
+
+    /// The `(x, y, width, height)` rectangle covered by the cells from `row`x`column`
+    /// spanning `rows`x`columns` cells
+    pub fn area_rect(
+        &self,
+        row: usize,
+        column: usize,
+        rows: usize,
+        columns: usize,
+    ) -> (f64, f64, f64, f64) {
+        let (cell_width, cell_height) = self.cell_size();
+        let (origin_x, origin_y) = self.origin();
+        let left = self.snap(origin_x + row as f64 * cell_width);
+        let top = self.snap(origin_y + column as f64 * cell_height);
+        let right = self.snap(origin_x + (row + rows) as f64 * cell_width);
+        let bottom = self.snap(origin_y + (column + columns) as f64 * cell_height);
+        (left, top, right - left, bottom - top)
+    }
+
+    /// The rows and columns of the cells visible on the widget, counting
+    /// those only partly visible
+    pub fn visible_cells(&self) -> (Range<usize>, Range<usize>) {
+        let (cell_width, cell_height) = self.cell_size();
+        if cell_width <= 0.0 || cell_height <= 0.0 {
+            return (0..0, 0..0);
+        }
+
+        // One more cell on each side covers the shifted and pointed hexagons
+        let (origin_x, origin_y) = self.origin();
+        let range = |from: f64, to: f64, count: usize| {
+            (from.floor().max(0.0) as usize).min(count)..(to.ceil().max(0.0) as usize).min(count)
+        };
+        (
+            range(
+                -origin_x / cell_width - 1.0,
+                (self.width - origin_x) / cell_width + 1.0,
+                self.rows,
+            ),
+            range(
+                -origin_y / cell_height - 1.0,
+                (self.height - origin_y) / cell_height + 1.0,
+                self.columns,
+            ),
+        )
+    }
+
+    /// The `(x, y, width, height)` rectangle covered by the cell at `row`x`column`.
+    /// Hexagonal cells reach a little past it above and below.
+    pub fn cell_rect(&self, row: usize, column: usize) -> (f64, f64, f64, f64) {
+        let (x, y, width, height) = self.area_rect(row, column, 1, 1);
+        match self.hexagonal && column % 2 == 1 {
+            true => (self.snap(x + width / 2.0), y, width, height),
+            false => (x, y, width, height),
+        }
+    }
+
+    /// The corners of the hexagonal cell at `row`x`column`, clockwise from
+    /// the top one
+    pub fn cell_hexagon(&self, row: usize, column: usize) -> [(f64, f64); 6] {
+        let (x, y, width, height) = self.cell_rect(row, column);
+        [
+            (x + width / 2.0, y - height / 6.0),
+            (x + width, y + height / 6.0),
+            (x + width, y + height * 5.0 / 6.0),
+            (x + width / 2.0, y + height * 7.0 / 6.0),
+            (x, y + height * 5.0 / 6.0),
+            (x, y + height / 6.0),
+        ]
+    }
+
+    /// The `(row, column)` of the cell under the widget point at `x`, `y`,
+    /// or `None` if the point is out of the universe
+    pub fn cell_at(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        let (cell_width, cell_height) = self.cell_size();
+        if cell_width <= 0.0 || cell_height <= 0.0 {
+            return None;
+        }
+
+        let (origin_x, origin_y) = self.origin();
+        let column = ((y - origin_y) / cell_height).floor();
+        let cell_in_column = |column: f64| {
+            let shift = match self.hexagonal && column.rem_euclid(2.0) == 1.0 {
+                true => cell_width / 2.0,
+                false => 0.0,
+            };
+            let row = ((x - origin_x - shift) / cell_width).floor();
+            match row >= 0.0
+                && column >= 0.0
+                && (row as usize) < self.rows
+                && (column as usize) < self.columns
+            {
+                true => Some((row as usize, column as usize)),
+                false => None,
+            }
+        };
+
+        if !self.hexagonal {
+            return cell_in_column(column);
+        }
+
+        // The pointed ends of hexagons reach into the columns above and below
+        [column, column - 1.0, column + 1.0]
+            .into_iter()
+            .filter_map(cell_in_column)
+            .find(|(row, column)| polygon_contains(&self.cell_hexagon(*row, *column), x, y))
+            .or_else(|| cell_in_column(column))
+    }
+}
+
+/// Whether the convex `polygon`, with its corners given clockwise, contains
+/// the point at `x`, `y`
+fn polygon_contains(polygon: &[(f64, f64)], x: f64, y: f64) -> bool {
+    polygon
+        .iter()
+        .zip(polygon.iter().cycle().skip(1))
+        .all(|((from_x, from_y), (to_x, to_y))| {
+            (to_x - from_x) * (y - from_y) - (to_y - from_y) * (x - from_x) >= 0.0
+        })
+}
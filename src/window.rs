@@ -1,18 +1,92 @@
-use std::{io::prelude::*, str::FromStr};
+use std::{
+    io::prelude::*,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
-use crate::i18n::i18n;
-use adw::prelude::AdwApplicationExt;
+use crate::i18n::{i18n, i18n_f};
+use adw::prelude::{AdwApplicationExt, AdwWindowExt, MessageDialogExt, MessageDialogExtManual};
 use gtk::prelude::*;
 use gtk::subclass::prelude::*;
 use gtk::{gio, glib, glib::clone, CompositeTemplate};
 
 use crate::{
-    config::{APPLICATION_G_PATH, G_LOG_DOMAIN},
-    models::{Universe, UniverseGridMode, UniverseSnapshot},
-    services::{GameOfLifeSettings, Template},
-    widgets::{GameOfLifeNewUniverseView, NewUniverseType},
+    config::{APPLICATION_G_PATH, G_LOG_DOMAIN, VERSION},
+    models::{
+        formats::{
+            parse_pattern, write_rle, PatternFormat, PatternMetadata, PATTERN_METADATA_MIME_TYPE,
+        },
+        Universe, UniversePattern, UniversePointMatrix, UniverseRule, UniverseSnapshot,
+    },
+    services::{
+        crash_reports_dir, encode_surface_png, is_safe_mode, latest_crash_report,
+        render_snapshot_surface, session_project_path, set_crash_reports_enabled, user_data_dir,
+        AmbientPlayer, ControlReply, ControlRequest, ControlService, GameOfLifeProject,
+        GameOfLifeSettings, LibraryPattern, PatternLibrary, PatternNotes, PlaylistPlayer,
+        PowerMonitor, ProjectColors, Recording, RecordingFormat, ScenePreset, SimulationHaltReason,
+        Template, WorkspaceBundle, IMAGE_MAX_SIZE, RECORDING_MAX_FRAMES,
+    },
+    widgets::{
+        GameOfLifeExperimentsDialog, GameOfLifeNewUniverseView, GameOfLifePatternLibrary,
+        GameOfLifePlaylistEditor, GameOfLifeResizeUniverseView, GameOfLifeSoupSearchDialog,
+        GameOfLifeUniverseGrid, NewUniverseType, UniverseGridAgeGradient, UniverseGridMode,
+        UniverseGridPalette, UniverseGridTransform,
+    },
 };
 
+/// The most bytes of pattern metadata read from the clipboard
+const PATTERN_METADATA_MAX_SIZE: usize = 4096;
+
+/// Reports from the thread encoding a recording
+enum EncodingProgress {
+    /// The number of frames encoded so far
+    Frame(usize),
+    /// The encoded animation, or `None` if encoding was cancelled
+    Finished(Option<Vec<u8>>),
+}
+
+/// A universe hosted in a tab of the window, with where it was loaded from
+#[derive(Debug)]
+struct UniverseTab {
+    grid: GameOfLifeUniverseGrid,
+
+    /// Identifies the pattern loaded in the tab (a template, a library pattern
+    /// or a file), used to attach notes to it and to title the tab
+    pattern_key: Option<String>,
+
+    /// The project file the universe was last opened from or saved to
+    project_file: Option<gio::File>,
+
+    /// The state of the universe when the project was last opened or saved
+    saved_project_state: u64,
+}
+
+const COMPARE_KEY: gtk::gdk::Key = gtk::gdk::Key::c;
+const ROTATE_KEY: gtk::gdk::Key = gtk::gdk::Key::r;
+const FLIP_KEY: gtk::gdk::Key = gtk::gdk::Key::f;
+
+/// The largest side of screenshots of stable universes, in pixels
+const SCREENSHOT_MAX_SIZE: usize = 1024;
+
+/// The number of pixels per cell initially offered when exporting images
+const EXPORT_IMAGE_DEFAULT_SCALE: usize = 8;
+
+/// The dead cells kept around the living ones when cropping to them
+const CROP_TO_PATTERN_MARGIN: usize = 2;
+
+/// The width below which the window switches to its narrow layout, fitting
+/// phone screens
+const NARROW_LAYOUT_WIDTH: i32 = 560;
+
+/// How long the controls stay once the pointer rests, while fullscreen
+const CONTROLS_HIDE_DELAY: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// How long typing has to pause for before edited notes are written to disk
+const NOTES_SAVE_DELAY: std::time::Duration = std::time::Duration::from_secs(1);
+
 mod imp {
     use super::*;
     use glib::{ParamFlags, ParamSpec, ParamSpecBoolean, ParamSpecString};
@@ -26,13 +100,134 @@ mod imp {
         pub(super) toast_overlay: TemplateChild<adw::ToastOverlay>,
 
         #[template_child]
-        pub(super) universe_grid: TemplateChild<crate::widgets::GameOfLifeUniverseGrid>,
+        pub(super) tab_view: TemplateChild<adw::TabView>,
 
         #[template_child]
         pub(super) controls: TemplateChild<crate::widgets::GameOfLifeUniverseControls>,
 
+        #[template_child]
+        pub(super) main_box: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) tab_bar: TemplateChild<adw::TabBar>,
+
+        #[template_child]
+        pub(super) content_box: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) tools_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) controls_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) notes_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) notes_view: TemplateChild<gtk::TextView>,
+
+        #[template_child]
+        pub(super) notes_subject_label: TemplateChild<gtk::Label>,
+
+        #[template_child]
+        pub(super) stats_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) stats_panel: TemplateChild<crate::widgets::GameOfLifeStatsPanel>,
+
+        #[template_child]
+        pub(super) brush_size_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) wireworld_palette: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) colors_palette: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) quadlife_colors: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) ant_palette: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) ant_steps_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) elementary_palette: TemplateChild<gtk::Box>,
+
+        #[template_child]
+        pub(super) elementary_rule_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) placement_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) timeline_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) timeline_adjustment: TemplateChild<gtk::Adjustment>,
+
+        #[template_child]
+        pub(super) fast_forward_revealer: TemplateChild<gtk::Revealer>,
+
+        #[template_child]
+        pub(super) fast_forward_progress_bar: TemplateChild<gtk::ProgressBar>,
+
+        /// Set while the timeline follows the grid, as opposed to the user moving it
+        pub(super) updating_timeline: std::cell::Cell<bool>,
+
         pub(super) mode: std::cell::Cell<UniverseGridMode>,
 
+        /// The universes open in the window, one per tab
+        pub(super) tabs: std::cell::RefCell<Vec<UniverseTab>>,
+
+        /// The grid of the selected tab
+        pub(super) selected_grid: std::cell::RefCell<Option<GameOfLifeUniverseGrid>>,
+
+        pub(super) notes: PatternNotes,
+
+        pub(super) loading_notes: std::cell::Cell<bool>,
+
+        /// Writes the edited notes once typing paused for `NOTES_SAVE_DELAY`
+        pub(super) notes_save_source: std::cell::RefCell<Option<glib::SourceId>>,
+
+        /// Set while the simulation is paused as the window is unfocused or hidden
+        pub(super) paused_while_away: std::cell::Cell<bool>,
+
+        /// The demo playlist being played, if any
+        pub(super) playlist: std::cell::RefCell<Option<PlaylistPlayer>>,
+
+        /// Reseeds the universe once it settles down, while in ambient mode
+        pub(super) ambient: std::cell::RefCell<Option<AmbientPlayer>>,
+
+        /// The run being recorded, if any
+        pub(super) recording: std::cell::RefCell<Option<Recording>>,
+
+        /// The D-Bus control interface, once exported
+        pub(super) control: std::cell::RefCell<Option<ControlService>>,
+
+        /// The dialog reporting a stalled simulation, while shown
+        pub(super) stall_dialog: std::cell::RefCell<Option<adw::MessageDialog>>,
+
+        /// Whether the system runs on battery or in power saver mode
+        pub(super) power_monitor: PowerMonitor,
+
+        /// Set while running simulations are slowed down to save power
+        pub(super) saving_power: std::cell::Cell<bool>,
+
+        /// Set while the window is narrower than `NARROW_LAYOUT_WIDTH`
+        pub(super) narrow: std::cell::Cell<bool>,
+
+        /// Set while only the universe is shown, fullscreen
+        pub(super) presenting: std::cell::Cell<bool>,
+
+        /// Hides the controls once the pointer rested long enough, while
+        /// fullscreen
+        pub(super) controls_hide_source: std::cell::RefCell<Option<glib::SourceId>>,
+
+        pub(super) pointer_over_controls: std::cell::Cell<bool>,
+
         pub(super) provider: gtk::CssProvider,
 
         pub(super) style_manager: adw::StyleManager,
@@ -49,9 +244,48 @@ mod imp {
         fn new() -> Self {
             Self {
                 toast_overlay: TemplateChild::default(),
-                universe_grid: TemplateChild::default(),
+                tab_view: TemplateChild::default(),
                 controls: TemplateChild::default(),
+                main_box: TemplateChild::default(),
+                tab_bar: TemplateChild::default(),
+                content_box: TemplateChild::default(),
+                tools_revealer: TemplateChild::default(),
+                controls_revealer: TemplateChild::default(),
+                notes_revealer: TemplateChild::default(),
+                notes_view: TemplateChild::default(),
+                notes_subject_label: TemplateChild::default(),
+                stats_revealer: TemplateChild::default(),
+                stats_panel: TemplateChild::default(),
+                brush_size_adjustment: TemplateChild::default(),
+                wireworld_palette: TemplateChild::default(),
+                colors_palette: TemplateChild::default(),
+                quadlife_colors: TemplateChild::default(),
+                ant_palette: TemplateChild::default(),
+                ant_steps_adjustment: TemplateChild::default(),
+                elementary_palette: TemplateChild::default(),
+                elementary_rule_adjustment: TemplateChild::default(),
+                placement_revealer: TemplateChild::default(),
+                timeline_revealer: TemplateChild::default(),
+                timeline_adjustment: TemplateChild::default(),
+                updating_timeline: std::cell::Cell::default(),
                 mode: std::cell::Cell::default(),
+                tabs: std::cell::RefCell::default(),
+                selected_grid: std::cell::RefCell::default(),
+                notes: PatternNotes::default(),
+                loading_notes: std::cell::Cell::default(),
+                notes_save_source: std::cell::RefCell::default(),
+                paused_while_away: std::cell::Cell::default(),
+                playlist: std::cell::RefCell::default(),
+                ambient: std::cell::RefCell::default(),
+                recording: std::cell::RefCell::default(),
+                control: std::cell::RefCell::default(),
+                stall_dialog: std::cell::RefCell::default(),
+                power_monitor: PowerMonitor::new(),
+                saving_power: std::cell::Cell::default(),
+                narrow: std::cell::Cell::default(),
+                presenting: std::cell::Cell::default(),
+                controls_hide_source: std::cell::RefCell::default(),
+                pointer_over_controls: std::cell::Cell::default(),
                 provider: gtk::CssProvider::new(),
                 settings: GameOfLifeSettings::default(),
                 style_manager: adw::StyleManager::default(),
@@ -65,6 +299,18 @@ mod imp {
                 win.new_universe_dialog();
             });
 
+            klass.install_action("win.new-tab", None, move |win, _, _| {
+                win.add_universe_tab();
+            });
+
+            klass.install_action("win.open-view", None, move |win, _, _| {
+                win.open_universe_view();
+            });
+
+            klass.install_action("win.close-tab", None, move |win, _, _| {
+                win.close_selected_tab();
+            });
+
             klass.install_action("win.random-seed", None, move |win, _, _| {
                 win.seed_universe();
             });
@@ -73,6 +319,73 @@ mod imp {
                 win.skip_forward_one();
             });
 
+            klass.install_action("win.fast-forward", None, move |win, _, _| {
+                win.fast_forward();
+            });
+
+            klass.install_action("win.resize-universe", None, move |win, _, _| {
+                win.resize_universe_dialog();
+            });
+
+            klass.install_action("win.center-pattern", None, move |win, _, _| {
+                if !win.universe_grid().center_pattern() {
+                    win.add_toast(i18n("There are no living cells to center"));
+                }
+            });
+
+            klass.install_action("win.crop-to-pattern", None, move |win, _, _| {
+                if !win.universe_grid().crop_to_pattern(CROP_TO_PATTERN_MARGIN) {
+                    win.add_toast(i18n("There are no living cells to crop to"));
+                }
+            });
+
+            klass.install_action("win.finish-placement", None, move |win, _, _| {
+                win.universe_grid().cancel_placement();
+            });
+
+            klass.install_action("win.invert-colors", None, move |win, _, _| {
+                let grid = win.universe_grid();
+                grid.set_inverted_colors(!grid.inverted_colors());
+            });
+
+            klass.install_action("win.step-back", None, move |win, _, _| {
+                if !win.universe_grid().step_back() {
+                    win.add_toast(i18n("No earlier generation to step back to"));
+                }
+            });
+
+            klass.install_action("win.clear", None, move |win, _, _| {
+                win.clear_universe();
+            });
+
+            klass.install_action("win.undo", None, move |win, _, _| {
+                win.universe_grid().undo();
+            });
+
+            klass.install_action("win.redo", None, move |win, _, _| {
+                win.universe_grid().redo();
+            });
+
+            klass.install_action("win.zoom-in", None, move |win, _, _| {
+                win.universe_grid().zoom_in();
+            });
+
+            klass.install_action("win.zoom-out", None, move |win, _, _| {
+                win.universe_grid().zoom_out();
+            });
+
+            klass.install_action("win.zoom-reset", None, move |win, _, _| {
+                win.universe_grid().reset_zoom();
+            });
+
+            klass.install_action("win.zoom-actual-size", None, move |win, _, _| {
+                win.universe_grid().zoom_to_actual_size();
+            });
+
+            klass.install_action("win.zoom-selection", None, move |win, _, _| {
+                win.universe_grid().zoom_to_selection();
+            });
+
             klass.install_action("win.play", None, move |win, _, _| {
                 win.toggle_run();
             });
@@ -85,6 +398,38 @@ mod imp {
                 win.select_and_load_snapshot();
             });
 
+            klass.install_action("win.open-project", None, move |win, _, _| {
+                win.select_and_open_project();
+            });
+
+            klass.install_action("win.save-project", None, move |win, _, _| {
+                win.save_project();
+            });
+
+            klass.install_action("win.save-project-as", None, move |win, _, _| {
+                win.save_project_as();
+            });
+
+            klass.install_action("win.toggle-recording", None, move |win, _, _| {
+                win.toggle_recording();
+            });
+
+            klass.install_action("win.export-image", None, move |win, _, _| {
+                win.export_image();
+            });
+
+            klass.install_action("win.export-bundle", None, move |win, _, _| {
+                win.export_bundle();
+            });
+
+            klass.install_action("win.export-statistics", None, move |win, _, _| {
+                win.export_statistics();
+            });
+
+            klass.install_action("win.import-bundle", None, move |win, _, _| {
+                win.import_bundle();
+            });
+
             klass.install_action("win.toggle-design-mode", None, move |win, _, _| {
                 win.toggle_edit_mode();
             });
@@ -92,6 +437,138 @@ mod imp {
             klass.install_action("win.toggle-instrument-brush", None, move |win, _, _| {
                 win.toggle_instrument_brush();
             });
+
+            klass.install_action("win.pattern-library", None, move |win, _, _| {
+                win.pattern_library_dialog(None);
+            });
+
+            klass.install_action("win.add-to-library", None, move |win, _, _| {
+                win.add_to_library();
+            });
+
+            klass.install_action("win.toggle-instrument-selection", None, move |win, _, _| {
+                win.toggle_instrument_selection();
+            });
+
+            klass.install_action("win.cut", None, move |win, _, _| {
+                win.cut_pattern();
+            });
+
+            klass.install_action("win.copy", None, move |win, _, _| {
+                win.copy_pattern();
+            });
+
+            klass.install_action("win.clear-selection", None, move |win, _, _| {
+                win.universe_grid().clear_selection();
+            });
+
+            klass.install_action("win.randomize-selection", None, move |win, _, _| {
+                win.universe_grid()
+                    .randomize_selection(&win.imp().settings.soup());
+            });
+
+            klass.install_action("win.freeze-selection", None, move |win, _, _| {
+                win.universe_grid().freeze_selection();
+            });
+
+            klass.install_action("win.unfreeze-selection", None, move |win, _, _| {
+                win.universe_grid().unfreeze_selection();
+            });
+
+            klass.install_action("win.rotate-selection-clockwise", None, move |win, _, _| {
+                win.universe_grid()
+                    .transform_selection(UniverseGridTransform::RotateClockwise);
+            });
+
+            klass.install_action(
+                "win.rotate-selection-counter-clockwise",
+                None,
+                move |win, _, _| {
+                    win.universe_grid()
+                        .transform_selection(UniverseGridTransform::RotateCounterClockwise);
+                },
+            );
+
+            klass.install_action("win.flip-selection-horizontally", None, move |win, _, _| {
+                win.universe_grid()
+                    .transform_selection(UniverseGridTransform::FlipHorizontally);
+            });
+
+            klass.install_action("win.flip-selection-vertically", None, move |win, _, _| {
+                win.universe_grid()
+                    .transform_selection(UniverseGridTransform::FlipVertically);
+            });
+
+            klass.install_action("win.select-all", None, move |win, _, _| {
+                win.universe_grid().select_all();
+            });
+
+            klass.install_action("win.deselect", None, move |win, _, _| {
+                win.universe_grid().set_selection(None);
+            });
+
+            klass.install_action("win.paste", None, move |win, _, _| {
+                win.paste_pattern();
+            });
+
+            klass.install_action("win.switch-rule", Some("s"), move |win, _, parameter| {
+                let rule = parameter
+                    .and_then(|parameter| parameter.get::<String>())
+                    .and_then(|rule| rule.parse::<UniverseRule>().ok());
+                if let Some(rule) = rule {
+                    win.imp().settings.set_rule(&rule);
+                }
+            });
+
+            klass.install_action("win.toggle-playlist", None, move |win, _, _| {
+                win.toggle_playlist();
+            });
+
+            klass.install_action("win.toggle-ambient", None, move |win, _, _| {
+                win.toggle_ambient();
+            });
+
+            klass.install_action("win.edit-playlist", None, move |win, _, _| {
+                win.edit_playlist_dialog();
+            });
+
+            klass.install_action("win.experiments", None, move |win, _, _| {
+                win.experiments_dialog();
+            });
+
+            klass.install_action("win.soup-search", None, move |win, _, _| {
+                win.soup_search_dialog();
+            });
+
+            klass.install_action("win.set-reference", None, move |win, _, _| {
+                win.set_reference();
+            });
+
+            klass.install_action("win.toggle-notes", None, move |win, _, _| {
+                win.toggle_notes();
+            });
+
+            klass.install_action("win.toggle-stats", None, move |win, _, _| {
+                win.toggle_stats();
+            });
+
+            klass.install_action("win.toggle-performance-hud", None, move |win, _, _| {
+                let grid = win.universe_grid();
+                grid.set_shows_performance_hud(!grid.shows_performance_hud());
+            });
+
+            klass.install_action("win.toggle-heatmap", None, move |win, _, _| {
+                let grid = win.universe_grid();
+                grid.set_shows_heatmap(!grid.shows_heatmap());
+            });
+
+            klass.install_action("win.toggle-fullscreen", None, move |win, _, _| {
+                win.toggle_fullscreen();
+            });
+
+            klass.install_action("win.toggle-presentation", None, move |win, _, _| {
+                win.toggle_presentation();
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -107,6 +584,8 @@ mod imp {
             obj.setup_widgets();
             obj.restore_window_state();
             obj.connect_events();
+            obj.load_pattern_notes();
+            obj.setup_control();
         }
 
         fn properties() -> &'static [glib::ParamSpec] {
@@ -121,6 +600,7 @@ mod imp {
                     ),
                     ParamSpecBoolean::new("running", "", "", false, ParamFlags::READABLE),
                     ParamSpecBoolean::new("stopped", "", "", true, ParamFlags::READABLE),
+                    ParamSpecBoolean::new("recording", "", "", false, ParamFlags::READABLE),
                     ParamSpecBoolean::new(
                         "allow-render-on-resize",
                         "",
@@ -144,12 +624,29 @@ mod imp {
                 .to_value(),
                 "running" => obj.is_running().to_value(),
                 "stopped" => (!obj.is_running()).to_value(),
+                "recording" => self.recording.borrow().is_some().to_value(),
                 "allow-render-on-resize" => self.settings.allow_render_during_resize().to_value(),
                 _ => unimplemented!(),
             }
         }
     }
-    impl WidgetImpl for GameOfLifeWindow {}
+    impl WidgetImpl for GameOfLifeWindow {
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+
+            // The layout is changed once this allocation is done, as changing
+            // it now would resize the window again while it is being allocated
+            let narrow = width < NARROW_LAYOUT_WIDTH;
+            if self.narrow.replace(narrow) != narrow {
+                let window = self.obj().downgrade();
+                glib::idle_add_local_once(move || {
+                    if let Some(window) = window.upgrade() {
+                        window.update_layout();
+                    }
+                });
+            }
+        }
+    }
     impl WindowImpl for GameOfLifeWindow {}
     impl ApplicationWindowImpl for GameOfLifeWindow {}
     impl adw::subclass::application_window::AdwApplicationWindowImpl for GameOfLifeWindow {}
@@ -169,10 +666,29 @@ impl GameOfLifeWindow {
 
         win.update_widgets();
 
+        glib::idle_add_local_once(clone!(@weak win => move || {
+            if is_safe_mode() {
+                win.add_toast(i18n("Safe mode: default settings are used and changes are not saved"));
+            }
+            win.offer_crash_report();
+        }));
+
         style_manager.connect_dark_notify(glib::clone!(@strong win as this => move |_sm| {
             this.update_widgets();
         }));
 
+        // Newer libadwaita releases follow the accent color of the desktop. The
+        // stylesheet is only reloaded after the notification, so the new color
+        // is looked up once idle.
+        style_manager.connect_notify_local(
+            Some("accent-color"),
+            glib::clone!(@weak win as this => move |_, _| {
+                glib::idle_add_local_once(clone!(@weak this => move || {
+                    this.update_widgets();
+                }));
+            }),
+        );
+
         win
     }
 
@@ -185,62 +701,797 @@ impl GameOfLifeWindow {
     }
 
     fn setup_widgets(&self) {
+        let imp = self.imp();
+        set_crash_reports_enabled(imp.settings.write_crash_reports());
+
+        let tools = gio::SimpleActionGroup::new();
+        tools.add_action(&imp.settings.create_action("design-tool"));
+        tools.add_action(&imp.settings.create_action("wireworld-cell"));
+        tools.add_action(&imp.settings.create_action("drawing-color"));
+        tools.add_action(&imp.settings.create_action("draw-ants"));
+        tools.add_action(&imp.settings.create_action("stamp-blend"));
+        self.insert_action_group("tool", Some(&tools));
+        imp.settings
+            .bind("brush-size", &imp.brush_size_adjustment.get(), "value");
+        imp.settings
+            .bind("ant-steps", &imp.ant_steps_adjustment.get(), "value");
+
+        imp.tab_view
+            .connect_selected_page_notify(clone!(@weak self as this => move |_| {
+                this.on_selected_tab_changed();
+            }));
+
+        imp.tab_view.connect_close_page(
+            clone!(@weak self as this => @default-return false, move |_, page| {
+                this.close_tab(page);
+                true
+            }),
+        );
+
+        imp.tab_view
+            .connect_page_detached(clone!(@weak self as this => move |_, page, _| {
+                if let Some(grid) = page_grid(page) {
+                    this.imp().tabs.borrow_mut().retain(|tab| tab.grid != grid);
+                    grid.halt();
+                }
+            }));
+
+        self.add_universe_tab();
+    }
+
+    /// Applies the preferences to a new grid. The rule and random events are
+    /// only copied, each tab keeping its own afterwards.
+    fn setup_grid(&self, grid: &GameOfLifeUniverseGrid) {
         let settings = &self.imp().settings;
-        let grid = self.imp().universe_grid.get();
+        grid.set_hexpand(true);
+        grid.set_vexpand(true);
+        grid.set_mode(self.instrument_mode());
         grid.set_allow_render_on_resize(settings.allow_render_during_resize());
         grid.set_evolution_speed(settings.evolution_speed());
+        grid.set_turbo(settings.turbo());
+        grid.set_power_saving(self.imp().saving_power.get());
+        grid.set_halts_when_settled(self.halts_when_settled());
+        grid.set_detects_oscillators(settings.detect_oscillators());
+        grid.set_stabilization_patience(self.reseed_patience());
+        grid.set_history_depth(settings.rewind_depth() as usize);
+        grid.set_heatmap_generations(settings.heatmap_generations() as usize);
         grid.set_draw_cells_outline(settings.draw_cells_outline());
+        grid.set_shows_grid_coordinates(settings.show_grid_coordinates());
+        grid.set_fixed_cell_size(settings.cell_size());
+        grid.set_stylus_brush_radius(settings.stylus_brush_radius() as usize);
+        grid.set_tool(settings.design_tool());
+        grid.set_brush_size(settings.brush_size() as usize);
+        grid.set_wireworld_cell(settings.wireworld_cell());
+        grid.set_drawing_color(settings.drawing_color());
+        grid.set_draws_ants(settings.draws_ants());
+        grid.set_ant_steps(settings.ant_steps());
+        grid.set_stamp_blend(settings.stamp_blend());
         grid.set_fades_dead_cells(settings.fade_out_cells());
+        grid.set_death_trail(settings.death_trail_length());
+        grid.set_rule(settings.rule());
+        grid.set_rain(settings.rain());
+        grid.set_chance(settings.chance());
+        grid.set_injection(settings.injection());
+        grid.set_palette(self.scene_palette());
     }
 
-    fn setup_provider(&self) {
+    /// The grid of the selected tab
+    pub fn universe_grid(&self) -> GameOfLifeUniverseGrid {
+        self.imp()
+            .selected_grid
+            .borrow()
+            .clone()
+            .expect("A universe tab is always selected")
+    }
+
+    fn is_selected_grid(&self, grid: &GameOfLifeUniverseGrid) -> bool {
+        self.imp().selected_grid.borrow().as_ref() == Some(grid)
+    }
+
+    /// The grids of every tab, selected or not
+    fn universe_grids(&self) -> Vec<GameOfLifeUniverseGrid> {
+        self.imp()
+            .tabs
+            .borrow()
+            .iter()
+            .map(|tab| tab.grid.clone())
+            .collect()
+    }
+
+    /// The tab page showing `grid`
+    fn tab_page(&self, grid: &GameOfLifeUniverseGrid) -> adw::TabPage {
+        let scroller = grid.parent().expect("Tab grids are in a scrolled window");
+        self.imp().tab_view.page(&scroller)
+    }
+
+    /// Runs `f` on the tab hosting `grid`
+    fn with_tab<R>(
+        &self,
+        grid: &GameOfLifeUniverseGrid,
+        f: impl FnOnce(&mut UniverseTab) -> R,
+    ) -> Option<R> {
+        self.imp()
+            .tabs
+            .borrow_mut()
+            .iter_mut()
+            .find(|tab| &tab.grid == grid)
+            .map(f)
+    }
+
+    fn with_selected_tab<R>(&self, f: impl FnOnce(&mut UniverseTab) -> R) -> R {
+        self.with_tab(&self.universe_grid(), f)
+            .expect("The selected grid belongs to a tab")
+    }
+
+    /// Opens a new tab with a random universe and selects it
+    pub fn add_universe_tab(&self) {
         let imp = self.imp();
-        imp.provider
-            .load_from_resource(format!("{}/{}", APPLICATION_G_PATH, "style.css").as_str());
-        if let Some(display) = gtk::gdk::Display::default() {
-            gtk::StyleContext::add_provider_for_display(&display, &imp.provider, 400);
+        let grid = GameOfLifeUniverseGrid::new();
+        self.setup_grid(&grid);
+        self.connect_grid_events(&grid);
+
+        imp.tabs.borrow_mut().push(UniverseTab {
+            grid: grid.clone(),
+            pattern_key: None,
+            project_file: None,
+            saved_project_state: 0,
+        });
+        let page = imp.tab_view.append(&grid_scroller(&grid));
+        self.update_tab_title(&grid);
+        imp.tab_view.set_selected_page(&page);
+    }
+
+    /// Opens a window showing the universe of the selected tab as it evolves,
+    /// sized and rendered independently of the tab
+    fn open_universe_view(&self) {
+        let source = self.universe_grid();
+        let grid = GameOfLifeUniverseGrid::new();
+        self.setup_grid(&grid);
+        grid.observe(&source);
+        grid.set_mode(UniverseGridMode::Locked);
+
+        let title = adw::WindowTitle::new(&i18n("Universe View"), &self.tab_page(&source).title());
+        let header_bar = adw::HeaderBar::new();
+        header_bar.set_title_widget(Some(&title));
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.append(&header_bar);
+        content.append(&grid_scroller(&grid));
+
+        let view = adw::Window::new();
+        view.set_transient_for(Some(self));
+        view.set_destroy_with_parent(true);
+        view.set_default_size(480, 480);
+        view.set_content(Some(&content));
+        view.show();
+    }
+
+    /// Closes the selected tab, or the window along with its last tab
+    fn close_selected_tab(&self) {
+        let tab_view = self.imp().tab_view.get();
+        if let Some(page) = tab_view.selected_page() {
+            tab_view.close_page(&page);
         }
     }
 
-    fn connect_events(&self) {
+    /// Closes the tab of `page` once its unsaved changes are saved or discarded
+    fn close_tab(&self, page: &adw::TabPage) {
+        let tab_view = self.imp().tab_view.get();
+        if tab_view.n_pages() <= 1 {
+            tab_view.close_page_finish(page, false);
+            self.close();
+            return;
+        }
+
+        let unsaved = page_grid(page)
+            .map(|grid| self.has_unsaved_changes(&grid))
+            .unwrap_or(false);
+        if !unsaved {
+            tab_view.close_page_finish(page, true);
+            return;
+        }
+
+        // The dialog saves the selected tab
+        tab_view.set_selected_page(page);
+        self.confirm_unsaved_changes(clone!(@strong page => move |win, proceed| {
+            win.imp().tab_view.close_page_finish(&page, proceed);
+        }));
+    }
+
+    /// Updates the window to show the universe of the newly selected tab
+    fn on_selected_tab_changed(&self) {
         let imp = self.imp();
-        let settings = &imp.settings;
+        let grid = match imp
+            .tab_view
+            .selected_page()
+            .and_then(|page| page_grid(&page))
+        {
+            Some(grid) => grid,
+            None => return,
+        };
+
+        // The demo playlist and recordings follow the universe they started in
+        if imp.selected_grid.borrow().is_some() {
+            self.stop_playlist();
+            self.stop_ambient();
+            self.stop_recording();
+        }
+
+        grid.set_mode(self.instrument_mode());
+        imp.selected_grid.replace(Some(grid.clone()));
+        imp.stats_panel.set_controller(&grid.controller());
+        if let Some(dialog) = imp.stall_dialog.take() {
+            dialog.close();
+        }
+
+        // The rule shown in the preferences is the one of the selected universe
+        if imp.settings.rule() != grid.rule() {
+            imp.settings.set_rule(&grid.rule());
+        }
+
+        self.notify("run-button-icon-name");
+        self.notify("running");
+        self.notify("stopped");
+        self.update_timeline();
+        self.update_fast_forward_progress();
+        self.update_rate();
+        self.on_engine_changed();
+        self.load_pattern_notes();
+        self.update_title();
+        self.update_placement_bar();
+        self.update_palettes();
+    }
 
+    /// Follows the state of `grid`, updating the window while its tab is selected
+    fn connect_grid_events(&self, grid: &GameOfLifeUniverseGrid) {
         // Updates buttons and other stuff when UniverseGrid running state changes
-        imp.universe_grid.connect_notify_local(
+        grid.connect_notify_local(
             Some("running"),
-            clone!(@strong self as this => move |_widget, _param| {
-                this.notify("run-button-icon-name");
-                this.notify("running");
-                this.notify("stopped");
+            clone!(@weak self as this => move |grid, _param| {
+                this.update_tab_indicator(grid);
+                if this.is_selected_grid(grid) {
+                    this.notify("run-button-icon-name");
+                    this.notify("running");
+                    this.notify("stopped");
+                    this.report_halt_reason();
+                    this.update_timeline();
+                    this.update_fast_forward_progress();
+                    this.update_rate();
+                }
             }),
         );
 
-        settings.connect_changed(
-            "draw-cells-outline",
-            clone!(@strong self as this, @strong settings as s => move |_,_| {
-                this.imp().universe_grid.set_draw_cells_outline(s.draw_cells_outline())
+        grid.connect_notify_local(
+            Some("placing"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.update_placement_bar();
+                }
             }),
         );
 
-        settings.connect_changed(
-            "fade-out-cells",
+        grid.connect_notify_local(
+            Some("generations-per-second"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.update_rate();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("fast-forward-progress"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.update_fast_forward_progress();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("generation"),
+            clone!(@weak self as this => move |grid, _param| {
+                this.update_tab_indicator(grid);
+                if this.is_selected_grid(grid) {
+                    this.on_generation_changed();
+                    this.update_timeline();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("stable"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.on_stable_changed();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("period"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.on_period_changed();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("unbounded"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.on_engine_changed();
+                }
+            }),
+        );
+
+        grid.connect_notify_local(
+            Some("stalled"),
+            clone!(@weak self as this => move |grid, _param| {
+                if this.is_selected_grid(grid) {
+                    this.on_stalled_changed();
+                }
+            }),
+        );
+    }
+
+    /// Titles the tab of `grid` after the pattern it holds
+    fn update_tab_title(&self, grid: &GameOfLifeUniverseGrid) {
+        let title = match self.with_tab(grid, |tab| tab.pattern_key.clone()).flatten() {
+            Some(key) => pattern_title(&key),
+            None => i18n("Untitled Universe"),
+        };
+        self.tab_page(grid).set_title(&title);
+        if self.is_selected_grid(grid) {
+            self.update_title();
+        }
+    }
+
+    /// Flags the tab of `grid` while its project has unsaved changes
+    fn update_tab_indicator(&self, grid: &GameOfLifeUniverseGrid) {
+        // Closed tabs may still report the simulation halting
+        if self.with_tab(grid, |_| ()).is_none() {
+            return;
+        }
+
+        let page = self.tab_page(grid);
+
+        // Once changed, a universe stays so until saved: the state is not
+        // compared again at every generation
+        let flagged = page.indicator_icon().is_some();
+        if flagged == self.has_unsaved_changes(grid) {
+            return;
+        }
+
+        match flagged {
+            true => page.set_indicator_icon(gio::Icon::NONE),
+            false => {
+                page.set_indicator_icon(Some(&gio::ThemedIcon::new("document-modified-symbolic")));
+                page.set_indicator_tooltip(&i18n("Unsaved changes"));
+            }
+        }
+    }
+
+    /// Names the window after the selected tab
+    fn update_title(&self) {
+        let title = self.tab_page(&self.universe_grid()).title();
+        self.set_title(Some(&format!("{} — Game of Life", title)));
+    }
+
+    /// The grid mode matching the instrument chosen in the controls
+    fn instrument_mode(&self) -> UniverseGridMode {
+        let controls = self.imp().controls.get();
+        if controls.brush() {
+            UniverseGridMode::Unlocked
+        } else if controls.selection() {
+            UniverseGridMode::Select
+        } else {
+            UniverseGridMode::Locked
+        }
+    }
+
+    fn setup_provider(&self) {
+        let imp = self.imp();
+        imp.provider
+            .load_from_resource(format!("{}/{}", APPLICATION_G_PATH, "style.css").as_str());
+        if let Some(display) = gtk::gdk::Display::default() {
+            gtk::StyleContext::add_provider_for_display(&display, &imp.provider, 400);
+        }
+    }
+
+    fn connect_events(&self) {
+        let imp = self.imp();
+        let settings = &imp.settings;
+
+        imp.notes_view
+            .buffer()
+            .connect_changed(clone!(@strong self as this => move |_| {
+                this.store_pattern_notes();
+            }));
+
+        settings.connect_changed(
+            "draw-cells-outline",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_draw_cells_outline(s.draw_cells_outline());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "show-grid-coordinates",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_shows_grid_coordinates(s.show_grid_coordinates());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "stylus-brush-radius",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_stylus_brush_radius(s.stylus_brush_radius() as usize);
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "design-tool",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_tool(s.design_tool());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "brush-size",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_brush_size(s.brush_size() as usize);
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "wireworld-cell",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_wireworld_cell(s.wireworld_cell());
+                }
+            }),
+        );
+
+        for key in ["draw-ants", "ant-steps"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this, @strong settings as s => move |_,_| {
+                    for grid in this.universe_grids() {
+                        grid.set_draws_ants(s.draws_ants());
+                        grid.set_ant_steps(s.ant_steps());
+                    }
+                }),
+            );
+        }
+
+        settings.connect_changed(
+            "drawing-color",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_drawing_color(s.drawing_color());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "stamp-blend",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_stamp_blend(s.stamp_blend());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "fade-out-cells",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_fades_dead_cells(s.fade_out_cells());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "cell-size",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_fixed_cell_size(s.cell_size());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "death-trail-length",
             clone!(@strong self as this, @strong settings as s => move |_,_| {
-                this.imp().universe_grid.set_fades_dead_cells(s.fade_out_cells())
+                for grid in this.universe_grids() {
+                    grid.set_death_trail(s.death_trail_length());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "dying-color",
+            clone!(@strong self as this => move |_, _| {
+                this.update_widgets();
             }),
         );
 
+        for key in ["cell-age-coloring", "cell-age-color", "cell-age-span"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this => move |_, _| {
+                    this.update_widgets();
+                }),
+            );
+        }
+
         settings.connect_changed(
             "evolution-speed",
             clone!(@strong self as this, @strong settings as s => move |_,_| {
-                this.imp().universe_grid.set_evolution_speed(s.evolution_speed())
+                for grid in this.universe_grids() {
+                    grid.set_evolution_speed(s.evolution_speed());
+                }
             }),
         );
 
-        settings.connect_changed("allow-render-during-resize",
+        settings.connect_changed(
+            "turbo",
             clone!(@strong self as this, @strong settings as s => move |_,_| {
-                this.imp().universe_grid.set_property("allow-render-on-resize", s.allow_render_during_resize())
-            })
+                for grid in this.universe_grids() {
+                    grid.set_turbo(s.turbo());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "throttle-when-saving-power",
+            clone!(@strong self as this => move |_,_| {
+                this.update_power_saving();
+            }),
+        );
+
+        imp.power_monitor
+            .connect_changed(clone!(@weak self as this => move |_| {
+                this.update_power_saving();
+            }));
+
+        settings.connect_changed(
+            "write-crash-reports",
+            clone!(@strong settings as s => move |_,_| {
+                set_crash_reports_enabled(s.write_crash_reports());
+            }),
+        );
+
+        settings.connect_changed(
+            "heatmap-generations",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_heatmap_generations(s.heatmap_generations() as usize);
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "rewind-depth",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_history_depth(s.rewind_depth() as usize);
+                }
+                this.update_timeline();
+            }),
+        );
+
+        // The rule number only changes the rule of universes already running
+        // an elementary one
+        imp.elementary_rule_adjustment.connect_value_changed(
+            clone!(@strong settings as s => move |adjustment| {
+                let number = adjustment.value().round() as u8;
+                let rule = s.rule();
+                if rule.is_elementary() && rule.elementary_number() != Some(number) {
+                    s.set_rule(&UniverseRule::elementary(number));
+                }
+            }),
+        );
+
+        imp.timeline_adjustment.connect_value_changed(
+            clone!(@weak self as this => move |adjustment| {
+                if !this.imp().updating_timeline.get() {
+                    this.universe_grid().rewind_to(adjustment.value().round() as u64);
+                }
+            }),
+        );
+
+        for key in ["halt-when-settled", "reseed-when-stable"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this => move |_,_| {
+                    // The demo playlist and the ambient mode handle settled
+                    // patterns by themselves
+                    let playing = this.imp().playlist.borrow().is_some()
+                        || this.imp().ambient.borrow().is_some();
+                    for grid in this.universe_grids() {
+                        if !playing || !this.is_selected_grid(&grid) {
+                            grid.set_halts_when_settled(this.halts_when_settled());
+                        }
+                    }
+                }),
+            );
+        }
+
+        for key in ["reseed-when-stable", "reseed-patience"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this => move |_,_| {
+                    // The ambient mode reseeds settled universes regardless
+                    let ambient = this.imp().ambient.borrow().is_some();
+                    for grid in this.universe_grids() {
+                        if !ambient || !this.is_selected_grid(&grid) {
+                            grid.set_stabilization_patience(this.reseed_patience());
+                        }
+                    }
+                }),
+            );
+        }
+
+        settings.connect_changed(
+            "detect-oscillators",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_detects_oscillators(s.detect_oscillators());
+                }
+            }),
+        );
+
+        settings.connect_changed(
+            "rule",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                this.universe_grid().set_rule(s.rule());
+                this.warn_unsupported_rule();
+                this.update_palettes();
+            }),
+        );
+
+        for key in [
+            "birth-probability",
+            "survival-probability",
+            "temperature",
+            "stochastic-seed",
+        ] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this, @strong settings as s => move |_,_| {
+                    this.universe_grid().set_chance(s.chance())
+                }),
+            );
+        }
+
+        for key in ["injection-pattern", "injection-edge", "injection-period"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this, @strong settings as s => move |_,_| {
+                    this.universe_grid().set_injection(s.injection())
+                }),
+            );
+        }
+
+        for key in ["rain-rate", "rain-at-edges"] {
+            settings.connect_changed(
+                key,
+                clone!(@strong self as this, @strong settings as s => move |_,_| {
+                    this.universe_grid().set_rain(s.rain())
+                }),
+            );
+        }
+
+        // Holding the compare key shows the reference state until the key is released
+        let compare_key_controller = gtk::EventControllerKey::new();
+        compare_key_controller.connect_key_pressed(clone!(@strong self as this => move |_, key, _, modifiers| {
+            if key == COMPARE_KEY && modifiers.is_empty() && this.universe_grid().has_reference() {
+                if !this.universe_grid().is_comparing() {
+                    this.universe_grid().set_comparing(true);
+                    this.show_reference_diff();
+                }
+                glib::signal::Inhibit(true)
+            } else if key == gtk::gdk::Key::Escape && this.universe_grid().is_placing() {
+                this.universe_grid().cancel_placement();
+                glib::signal::Inhibit(true)
+            } else if key == gtk::gdk::Key::Escape && this.is_fullscreened() {
+                this.unfullscreen();
+                glib::signal::Inhibit(true)
+            } else if this.universe_grid().is_placing() && (key.to_lower() == ROTATE_KEY || key.to_lower() == FLIP_KEY) {
+                // Shift reverses the rotation, or flips along the other axis
+                let shift = modifiers.contains(gtk::gdk::ModifierType::SHIFT_MASK);
+                let transform = match (key.to_lower() == ROTATE_KEY, shift) {
+                    (true, false) => UniverseGridTransform::RotateClockwise,
+                    (true, true) => UniverseGridTransform::RotateCounterClockwise,
+                    (false, false) => UniverseGridTransform::FlipHorizontally,
+                    (false, true) => UniverseGridTransform::FlipVertically,
+                };
+                this.universe_grid().transform_placement(transform);
+                glib::signal::Inhibit(true)
+            } else {
+                glib::signal::Inhibit(false)
+            }
+        }));
+        compare_key_controller.connect_key_released(
+            clone!(@strong self as this => move |_, key, _, _| {
+                if key == COMPARE_KEY {
+                    this.universe_grid().set_comparing(false);
+                }
+            }),
+        );
+        self.add_controller(&compare_key_controller);
+
+        // Moving the pointer brings back the controls hidden while fullscreen
+        let motion_controller = gtk::EventControllerMotion::new();
+        motion_controller.connect_motion(clone!(@weak self as this => move |_, _, _| {
+            if this.is_fullscreened() && !this.imp().controls_revealer.reveals_child() {
+                this.reveal_controls();
+            }
+        }));
+        self.add_controller(&motion_controller);
+
+        let controls_motion_controller = gtk::EventControllerMotion::new();
+        controls_motion_controller.connect_enter(clone!(@weak self as this => move |_, _, _| {
+            this.imp().pointer_over_controls.set(true);
+        }));
+        controls_motion_controller.connect_leave(clone!(@weak self as this => move |_| {
+            this.imp().pointer_over_controls.set(false);
+            if this.is_fullscreened() {
+                this.reveal_controls();
+            }
+        }));
+        self.imp()
+            .controls_revealer
+            .add_controller(&controls_motion_controller);
+
+        self.connect_fullscreened_notify(move |window| {
+            if !window.is_fullscreened() {
+                window.imp().presenting.set(false);
+            }
+            window.update_chrome();
+        });
+
+        self.connect_is_active_notify(move |window| {
+            window.on_presence_changed();
+        });
+
+        self.connect_map(move |window| {
+            window.on_presence_changed();
+        });
+
+        self.connect_unmap(move |window| {
+            window.on_presence_changed();
+        });
+
+        // Minimized windows stay mapped, their surface tells about it
+        self.connect_realize(move |window| {
+            if let Ok(toplevel) = window.surface().downcast::<gtk::gdk::Toplevel>() {
+                toplevel.connect_state_notify(clone!(@weak window => move |_| {
+                    window.on_presence_changed();
+                }));
+            }
+        });
+
+        settings.connect_changed(
+            "allow-render-during-resize",
+            clone!(@strong self as this, @strong settings as s => move |_,_| {
+                for grid in this.universe_grids() {
+                    grid.set_allow_render_on_resize(s.allow_render_during_resize());
+                }
+            }),
         );
 
         settings.connect_changed(
@@ -250,6 +1501,13 @@ impl GameOfLifeWindow {
             }),
         );
 
+        settings.connect_changed(
+            "cell-accent-color",
+            clone!(@strong self as this => move |_, _| {
+                this.update_widgets();
+            }),
+        );
+
         settings.connect_changed(
             "bg-color",
             clone!(@strong self as this, @strong settings as s => move |_, _| {
@@ -272,56 +1530,202 @@ impl GameOfLifeWindow {
         );
 
         self.connect_close_request(move |window| {
+            // Unsaved tabs are asked about one at a time, each answer closing
+            // the window again
+            let unsaved = window
+                .universe_grids()
+                .into_iter()
+                .find(|grid| window.has_unsaved_changes(grid));
+            if let Some(grid) = unsaved {
+                let tab_view = window.imp().tab_view.get();
+                tab_view.set_selected_page(&window.tab_page(&grid));
+                window.confirm_unsaved_changes(|win, proceed| {
+                    if proceed {
+                        win.set_project_file(None);
+                        win.close();
+                    }
+                });
+                return glib::signal::Inhibit(true);
+            }
+
             glib::g_debug!(G_LOG_DOMAIN, "Saving window state");
             let width = window.default_size().0;
             let height = window.default_size().1;
             let settings = GameOfLifeSettings::default();
             settings.set_window_width(width);
             settings.set_window_height(height);
+            settings.set_window_maximized(window.is_maximized());
+            window.save_session_universe(&settings);
+            window.save_pattern_notes();
+
+            // Stops the workers right away rather than along with the window
+            for grid in window.universe_grids() {
+                grid.halt();
+            }
             glib::signal::Inhibit(false)
         });
     }
 
     pub fn is_running(&self) -> bool {
-        let grid = &self.imp().universe_grid;
+        self.imp()
+            .selected_grid
+            .borrow()
+            .as_ref()
+            .is_some_and(|grid| grid.is_running())
+    }
 
-        if grid.is_bound() {
-            self.imp().universe_grid.get().is_running()
-        } else {
-            false
+    /// Exports the D-Bus control interface next to the application, so that
+    /// scripts can drive the simulation of this window
+    fn setup_control(&self) {
+        let app = match self.application() {
+            Some(app) => app,
+            None => return,
+        };
+        let (connection, object_path) = match (app.dbus_connection(), app.dbus_object_path()) {
+            (Some(connection), Some(object_path)) => (connection, object_path),
+            _ => return,
+        };
+
+        let handler = clone!(@weak self as win => @default-return ControlReply::Failed(String::from("The window is closed")), move |request| {
+            win.handle_control_request(request)
+        });
+        match ControlService::register(&connection, &object_path, handler) {
+            Ok(control) => {
+                self.imp().control.replace(Some(control));
+            }
+            Err(error) => {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Could not export the control interface: {}",
+                    error
+                );
+            }
+        }
+    }
+
+    fn handle_control_request(&self, request: ControlRequest) -> ControlReply {
+        match request {
+            ControlRequest::Run if !self.is_running() => self.toggle_run(),
+            ControlRequest::Halt if self.is_running() => self.toggle_run(),
+            ControlRequest::Run | ControlRequest::Halt => (),
+            ControlRequest::Step if self.is_running() => {
+                return ControlReply::Failed(String::from("The simulation is running"));
+            }
+            ControlRequest::Step => self.skip_forward_one(),
+            ControlRequest::LoadPattern(text) => match parse_pattern(&text) {
+                Ok(pattern) => {
+                    self.seed_from_snapshot(pattern.snapshot().clone());
+                    self.set_pattern_key(None);
+                }
+                Err(_) => return ControlReply::Failed(String::from("Invalid pattern")),
+            },
+            ControlRequest::GetPopulation => {
+                return ControlReply::Population(self.universe_grid().population());
+            }
         }
+        ControlReply::Done
     }
 
     pub fn toggle_run(&self) {
-        self.imp().universe_grid.toggle_run();
+        self.stop_playlist();
+        self.stop_ambient();
+        self.universe_grid().toggle_run();
         self.notify("running");
     }
 
-    pub fn toggle_edit_mode(&self) {
-        let controls = self.imp().controls.get();
-        let tools_revealed = controls.tools_revealed();
-        controls.set_tools_revealed(!tools_revealed);
+    /// Whether the window is minimized or otherwise not shown
+    fn is_hidden(&self) -> bool {
+        !self.is_mapped()
+            || self
+                .surface()
+                .downcast::<gtk::gdk::Toplevel>()
+                .is_ok_and(|toplevel| {
+                    toplevel
+                        .state()
+                        .contains(gtk::gdk::ToplevelState::MINIMIZED)
+                })
     }
 
-    pub fn toggle_instrument_brush(&self) {
-        let settings = &self.imp().settings;
-        let controls = self.imp().controls.get();
+    /// Pauses the simulation when the window loses focus or gets hidden, if
+    /// the user asked so, and resumes it once the window is back
+    fn on_presence_changed(&self) {
+        let imp = self.imp();
+        let settings = &imp.settings;
+        let away = (settings.pause_on_unfocus() && !self.is_active())
+            || (settings.pause_when_hidden() && self.is_hidden());
+        if !away {
+            if imp.paused_while_away.replace(false) && !self.is_running() {
+                self.universe_grid().run();
+            }
+        } else if self.is_running() {
+            imp.paused_while_away.set(true);
+            self.universe_grid().halt();
+        }
+    }
+
+    /// Slows running simulations down while the system asks to save power, if
+    /// the user did not opt out, telling why once it starts
+    fn update_power_saving(&self) {
+        let imp = self.imp();
+        let saving_power =
+            imp.settings.throttle_when_saving_power() && imp.power_monitor.is_saving_power();
+        if imp.saving_power.replace(saving_power) == saving_power {
+            return;
+        }
+
+        for grid in self.universe_grids() {
+            grid.set_power_saving(saving_power);
+        }
+        if saving_power {
+            self.add_toast(i18n("Simulations run slower to save power"));
+        }
+    }
+
+    pub fn toggle_edit_mode(&self) {
+        let controls = self.imp().controls.get();
+        let tools_revealed = controls.tools_revealed();
+        controls.set_tools_revealed(!tools_revealed);
+    }
+
+    pub fn toggle_instrument_brush(&self) {
+        let settings = &self.imp().settings;
+        let controls = self.imp().controls.get();
 
         controls.toggle_brush();
 
         if controls.brush() {
-            self.imp()
-                .universe_grid
-                .set_mode(UniverseGridMode::Unlocked);
+            controls.set_selection(false);
+            self.universe_grid().set_mode(UniverseGridMode::Unlocked);
+            if settings.show_design_hint() {
+                let msg = i18n("Drag to draw with the selected tool, right click to erase");
+                let toast = adw::Toast::new(&msg);
+                toast.set_action_name(Some("app.disable-design-hint"));
+                toast.set_button_label(Some(i18n("Do not show again").as_str()));
+                self.imp().toast_overlay.add_toast(&toast);
+            }
+        } else {
+            self.universe_grid().set_mode(UniverseGridMode::Locked);
+        }
+    }
+
+    pub fn toggle_instrument_selection(&self) {
+        let settings = &self.imp().settings;
+        let controls = self.imp().controls.get();
+
+        controls.toggle_selection();
+
+        if controls.selection() {
+            controls.set_brush(false);
+            self.universe_grid().set_mode(UniverseGridMode::Select);
             if settings.show_design_hint() {
-                let msg = i18n("Left click to make a cell alive, right click to make it dead");
+                let msg = i18n("Drag to select an area, right click for selection actions");
                 let toast = adw::Toast::new(&msg);
                 toast.set_action_name(Some("app.disable-design-hint"));
                 toast.set_button_label(Some(i18n("Do not show again").as_str()));
                 self.imp().toast_overlay.add_toast(&toast);
             }
         } else {
-            self.imp().universe_grid.set_mode(UniverseGridMode::Locked);
+            self.universe_grid().set_mode(UniverseGridMode::Locked);
         }
     }
 
@@ -345,12 +1749,30 @@ impl GameOfLifeWindow {
             .select_multiple(false)
             .action(gtk::FileChooserAction::Save)
             .build();
+        let filters = Self::snapshot_file_filters();
+        for (filter, _) in filters.iter() {
+            dialog.add_filter(filter);
+        }
 
         dialog.connect_response(
             clone!(@strong dialog, @weak self as win => move |_, response| {
                 if response == gtk::ResponseType::Accept {
                     if let Some(file) = dialog.file().as_ref() {
-                        let snapshot = win.imp().universe_grid.get_universe_snapshot();
+                        let format = dialog
+                            .filter()
+                            .and_then(|chosen| {
+                                filters
+                                    .iter()
+                                    .find(|(filter, _)| *filter == chosen)
+                                    .and_then(|(_, format)| *format)
+                            })
+                            .or_else(|| pattern_file_format(file));
+                        if let Some(format) = format {
+                            win.write_pattern_file(file, format);
+                            return;
+                        }
+
+                        let snapshot = win.universe_grid().get_universe_snapshot();
                         match snapshot.serialize() {
                             Ok(serialized) => {
                                 let file_io_stream = if file.query_exists(gtk::gio::Cancellable::NONE) {
@@ -363,6 +1785,10 @@ impl GameOfLifeWindow {
                                 match write_result {
                                     Ok((bytes_written, _)) => {
                                         glib::info!("Written {} bytes", bytes_written);
+                                        win.with_selected_tab(|tab| tab.pattern_key = Some(file.uri().to_string()));
+                                        win.update_tab_title(&win.universe_grid());
+                                        win.update_notes_subject();
+                                        win.store_pattern_notes();
                                     },
                                     Err(error) => {
                                         win.add_toast(i18n("Unable to write to file"));
@@ -409,6 +1835,10 @@ impl GameOfLifeWindow {
                 let file = dialog.file();
                 if response == gtk::ResponseType::Accept {
                     if let Some(file) = file.as_ref() {
+                        if win.read_pattern_file(file) {
+                            return;
+                        }
+
                         if file.query_exists(gio::Cancellable::NONE) {
                             let mut buffer: Vec<u8> = vec![];
 
@@ -424,6 +1854,7 @@ impl GameOfLifeWindow {
                                     match UniverseSnapshot::try_from(&buffer) {
                                         Ok(snapshot) => {
                                             win.seed_from_snapshot(snapshot);
+                                            win.set_pattern_key(Some(file_io_stream.uri().to_string()));
                                         },
                                         Err(error) => {
                                             glib::g_critical!(G_LOG_DOMAIN, "Unreadable file: {:?}", error);
@@ -449,106 +1880,2121 @@ impl GameOfLifeWindow {
         dialog.show();
     }
 
-    fn new_universe_dialog(&self) {
-        let app = gio::Application::default()
-            .expect("Failed to retrieve application singleton")
-            .downcast::<gtk::Application>()
-            .unwrap();
-        let win = app
-            .active_window()
-            .unwrap()
-            .downcast::<gtk::Window>()
-            .unwrap();
-        let dialog = GameOfLifeNewUniverseView::new();
-        dialog.set_modal(true);
-        dialog.set_transient_for(Some(&win));
+    /// The filters of the snapshot dialog, `None` standing for binary snapshots
+    fn snapshot_file_filters() -> Vec<(gtk::FileFilter, Option<PatternFormat>)> {
+        let snapshots = gtk::FileFilter::new();
+        snapshots.set_name(Some(&i18n("Universe snapshots")));
+        snapshots.add_pattern("*");
+
+        let mut filters = vec![(snapshots, None)];
+        for format in PatternFormat::ALL {
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(&match format {
+                PatternFormat::Rle => i18n("RLE patterns"),
+                PatternFormat::Plaintext => i18n("Plaintext patterns"),
+                PatternFormat::Life106 => i18n("Life 1.06 patterns"),
+                PatternFormat::Life105 => i18n("Life 1.05 patterns"),
+            }));
+            filter.add_pattern(&format!("*.{}", format.extension()));
+            filters.push((filter, Some(format)));
+        }
+        filters
+    }
+
+    /// Writes the universe as a pattern in a text format
+    fn write_pattern_file(&self, file: &gio::File, format: PatternFormat) {
+        let pattern = self.universe_grid().current_pattern();
+        match file.replace_contents(
+            format.write(&pattern).as_bytes(),
+            None,
+            false,
+            gio::FileCreateFlags::REPLACE_DESTINATION,
+            gio::Cancellable::NONE,
+        ) {
+            Ok(_) => self.set_pattern_key(Some(file.uri().to_string())),
+            Err(error) => {
+                self.add_toast(i18n("Unable to write to file"));
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+            }
+        }
+    }
+
+    /// Replaces the universe with the pattern in `file`, if it is in a text format,
+    /// recognized by its extension or its header. Returns whether the file was
+    /// handled, as opposed to being left to be read as a binary snapshot.
+    fn read_pattern_file(&self, file: &gio::File) -> bool {
+        let data = match file.load_contents(gio::Cancellable::NONE) {
+            Ok((data, _)) => data,
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "File not accessible: {}", error);
+                self.add_toast(i18n("File not existing or not accessible"));
+                return true;
+            }
+        };
+
+        let text = String::from_utf8_lossy(&data);
+        if pattern_file_format(file).is_none() && PatternFormat::detect(&text).is_none() {
+            return false;
+        }
+
+        match parse_pattern(&text) {
+            Ok(pattern) => {
+                self.seed_from_snapshot(pattern.snapshot().clone());
+                self.set_pattern_key(Some(file.uri().to_string()));
+            }
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "Unreadable file: {:?}", error);
+                self.add_toast(i18n("Unreadable file"));
+            }
+        }
+        true
+    }
+
+    fn bundle_file_filter() -> gtk::FileFilter {
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&i18n("Workspace bundles")));
+        filter.add_pattern("*.zip");
+        filter
+    }
+
+    /// Saves the universe, its notes, the current scene and the demo playlist
+    /// in a single bundle file
+    fn export_bundle(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export workspace bundle"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        dialog.add_filter(&Self::bundle_file_filter());
+        dialog.set_current_name(&format!("{}.zip", i18n("Universe")));
 
         dialog.connect_response(
             clone!(@strong dialog, @weak self as win => move |_, response| {
-                if response == gtk::ResponseType::Ok {
-                    let (target_w, target_h) = dialog.size();
-                    match dialog.option() {
-                        NewUniverseType::Empty => win.new_empty(target_w as usize, target_h as usize),
-                        NewUniverseType::Random => win.new_random(target_w as usize, target_h as usize),
-                        NewUniverseType::Template(template_name) => {
-                            glib::debug!("Seeding from {} template", template_name);
-                            match Template::read_template(template_name) {
-                                Ok(read) => {
-                                    match UniverseSnapshot::try_from(&read) {
-                                        Ok(snapshot) => {
-                                            win.seed_from_snapshot(snapshot);
-                                        },
-                                        Err(error) => {
-                                            glib::g_critical!(G_LOG_DOMAIN, "Unreadable template: {:?}", error);
-                                            win.add_toast(i18n("Bad template data"));
-                                        }
-                                    }
-                                },
-                                Err(error) => {
-                                    glib::g_critical!(G_LOG_DOMAIN, "Could not load template: {}", error);
-                                    win.add_toast(i18n("Template not found"));
-                                }
-                            }
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    let imp = win.imp();
+                    let buffer = imp.notes_view.buffer();
+                    let bundle = WorkspaceBundle::new(
+                        win.universe_grid().get_universe_snapshot(),
+                        buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string(),
+                        ScenePreset::from_settings(&imp.settings),
+                        imp.settings.playlist(),
+                    );
+
+                    let data = match bundle.to_bytes() {
+                        Ok(data) => data,
+                        Err(error) => {
+                            win.add_toast(i18n("Unable to serialize snapshot"));
+                            glib::g_critical!(G_LOG_DOMAIN, "Unable to serialize universe snapshot: {}", error);
+                            return;
+                        }
+                    };
+
+                    match file.replace_contents(&data, None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        Ok(_) => win.add_toast(i18n("Workspace bundle exported")),
+                        Err(error) => {
+                            win.add_toast(i18n("Unable to write to file"));
+                            glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
                         }
                     }
                 }
-                dialog.close();
             })
         );
+
         dialog.show();
     }
 
-    fn new_empty(&self, rows: usize, columns: usize) {
-        let universe_grid = self.imp().universe_grid.get();
-        universe_grid.set_universe(Universe::new_empty(rows, columns));
+    /// Saves the statistics of the generations the selected universe went
+    /// through, as JSON when the file name ends with `.json` and as comma
+    /// separated values otherwise
+    fn export_statistics(&self) {
+        let log = self.universe_grid().controller().stats_log();
+        if log.is_empty() {
+            self.add_toast(i18n("Run the universe to gather statistics first"));
+            return;
+        }
+
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export statistics"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        for (name, mime_type, extension) in [
+            (i18n("Comma separated values"), "text/csv", "csv"),
+            (i18n("JSON documents"), "application/json", "json"),
+        ] {
+            let filter = gtk::FileFilter::new();
+            filter.set_name(Some(&name));
+            filter.add_mime_type(mime_type);
+            filter.add_pattern(&format!("*.{}", extension));
+            dialog.add_filter(&filter);
+        }
+        dialog.set_current_name(&format!("{}.csv", i18n("Statistics")));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    let json = file
+                        .basename()
+                        .and_then(|name| name.extension().map(|extension| extension == "json"))
+                        .unwrap_or(false);
+                    let content = match json {
+                        true => log.to_json(),
+                        false => log.to_csv(),
+                    };
+
+                    match file.replace_contents(content.as_bytes(), None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        Ok(_) => win.add_toast(i18n("Statistics exported")),
+                        Err(error) => {
+                            win.add_toast(i18n("Unable to write to file"));
+                            glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                        }
+                    }
+                }
+            })
+        );
+
+        dialog.show();
     }
 
-    fn new_random(&self, rows: usize, columns: usize) {
-        let universe_grid = self.imp().universe_grid.get();
-        universe_grid.set_universe(Universe::new_random(rows, columns));
+    fn toggle_recording(&self) {
+        let imp = self.imp();
+        if imp.recording.borrow().is_some() {
+            self.stop_recording();
+            return;
+        }
+
+        imp.recording.replace(Some(Recording::new(
+            imp.settings.recording_format(),
+            imp.settings.recording_interval(),
+        )));
+        self.capture_recording_frame();
+        self.notify("recording");
+        self.add_toast(i18n("Recording started"));
     }
 
-    fn seed_universe(&self) {
-        let universe_grid = self.imp().universe_grid.get();
-        universe_grid.random_seed();
+    fn capture_recording_frame(&self) {
+        let imp = self.imp();
+        let full = match imp.recording.borrow_mut().as_mut() {
+            Some(recording) => {
+                let grid = self.universe_grid();
+                recording.capture(grid.generation(), || grid.get_universe_snapshot());
+                recording.is_full()
+            }
+            None => false,
+        };
+
+        if full {
+            self.add_toast(i18n_f(
+                "Recording stopped after {} frames",
+                &[&RECORDING_MAX_FRAMES.to_string()],
+            ));
+            self.stop_recording();
+        }
     }
 
-    fn skip_forward_one(&self) {
-        let universe_grid = self.imp().universe_grid.get();
-        universe_grid.skip_forward_one();
+    /// Stops recording and asks where to save the animation
+    fn stop_recording(&self) {
+        let recording = match self.imp().recording.take() {
+            Some(recording) => recording,
+            None => return,
+        };
+        self.notify("recording");
+
+        if recording.frames_count() == 0 {
+            self.add_toast(i18n("Nothing was recorded"));
+            return;
+        }
+
+        let format = recording.format();
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Save"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Save recording"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&match format {
+            RecordingFormat::Gif => i18n("GIF animations"),
+            RecordingFormat::Apng => i18n("APNG animations"),
+        }));
+        filter.add_mime_type(format.mime_type());
+        filter.add_pattern(&format!("*.{}", format.extension()));
+        dialog.add_filter(&filter);
+        dialog.set_current_name(&format!("{}.{}", i18n("Recording"), format.extension()));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    win.encode_recording(recording.clone(), file);
+                }
+            }),
+        );
+
+        dialog.show();
     }
 
-    fn seed_from_snapshot(&self, snapshot: UniverseSnapshot) {
-        let universe_grid = self.imp().universe_grid.get();
-        let universe = snapshot.into();
-        universe_grid.set_universe(universe);
+    /// Encodes `recording` off the main thread, showing the progress, and
+    /// writes the animation to `file`
+    fn encode_recording(&self, recording: Recording, file: gio::File) {
+        let (cell_color, background_color) = self.scene_colors();
+        let frame_delay_ms =
+            recording.interval() * 1000 / self.universe_grid().evolution_speed().max(1);
+        let frames_count = recording.frames_count();
+
+        let progress_bar = gtk::ProgressBar::new();
+        progress_bar.set_show_text(true);
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Saving Recording")),
+            Some(&i18n_f("Encoding {} frames", &[&frames_count.to_string()])),
+        );
+        dialog.set_extra_child(Some(&progress_bar));
+        dialog.add_responses(&[("cancel", &i18n("_Cancel"))]);
+        dialog.set_close_response("cancel");
+
+        let cancelled = Arc::new(AtomicBool::new(false));
+        dialog.connect_response(
+            None,
+            clone!(@strong cancelled => move |_, _| {
+                cancelled.store(true, Ordering::Relaxed);
+            }),
+        );
+
+        let (sender, receiver) = glib::MainContext::channel(glib::PRIORITY_DEFAULT);
+        let thread_cancelled = cancelled.clone();
+        std::thread::spawn(move || {
+            let data = recording.encode(&cell_color, &background_color, frame_delay_ms, |frame| {
+                sender.send(EncodingProgress::Frame(frame)).is_ok()
+                    && !thread_cancelled.load(Ordering::Relaxed)
+            });
+            sender.send(EncodingProgress::Finished(data)).ok();
+        });
+
+        receiver.attach(
+            None,
+            clone!(@weak self as win, @strong dialog => @default-return Continue(false), move |progress| {
+                let data = match progress {
+                    EncodingProgress::Frame(frame) => {
+                        progress_bar.set_fraction(frame as f64 / frames_count as f64);
+                        return Continue(true);
+                    }
+                    EncodingProgress::Finished(data) => data,
+                };
+
+                dialog.close();
+                if let Some(data) = data {
+                    match file.replace_contents(&data, None, false, gio::FileCreateFlags::REPLACE_DESTINATION, gio::Cancellable::NONE) {
+                        Ok(_) => win.add_toast(i18n("Recording saved")),
+                        Err(error) => {
+                            win.add_toast(i18n("Unable to write to file"));
+                            glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                        }
+                    }
+                }
+                Continue(false)
+            }),
+        );
+
+        dialog.present();
     }
 
-    fn update_widgets(&self) {
-        let style_manager = &self.imp().style_manager;
-        let settings = &self.imp().settings;
-        let grid = self.imp().universe_grid.get();
-        let (cell_color, background_color);
+    /// Asks for a scale and a file, then saves the universe as a PNG image
+    fn export_image(&self) {
+        let snapshot = self.universe_grid().get_universe_snapshot();
+        let (width, height) = (snapshot.rows().max(1), snapshot.columns().max(1));
+        let max_scale = (IMAGE_MAX_SIZE / width.max(height)).max(1);
+
+        let adjustment = gtk::Adjustment::new(
+            EXPORT_IMAGE_DEFAULT_SCALE.min(max_scale) as f64,
+            1.0,
+            max_scale as f64,
+            1.0,
+            8.0,
+            0.0,
+        );
+        let scale_button = gtk::SpinButton::new(Some(&adjustment), 1.0, 0);
+        let resolution_label = gtk::Label::new(None);
+        resolution_label.add_css_class("dim-label");
+        let update_resolution = clone!(@weak resolution_label => move |adjustment: &gtk::Adjustment| {
+            let scale = adjustment.value() as usize;
+            resolution_label.set_label(&i18n_f(
+                "{} × {} pixels",
+                &[&(width * scale).to_string(), &(height * scale).to_string()],
+            ));
+        });
+        update_resolution(&adjustment);
+        adjustment.connect_value_changed(update_resolution);
+
+        let scale_row = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        scale_row.set_halign(gtk::Align::Center);
+        scale_row.append(&gtk::Label::new(Some(&i18n("Pixels per cell"))));
+        scale_row.append(&scale_button);
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 6);
+        content.append(&scale_row);
+        content.append(&resolution_label);
+
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Export as Image")),
+            Some(&i18n(
+                "Choose how large each cell of the universe is in the image.",
+            )),
+        );
+        dialog.set_extra_child(Some(&content));
+        dialog.add_responses(&[("cancel", &i18n("_Cancel")), ("export", &i18n("_Export"))]);
+        dialog.set_response_appearance("export", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("export"));
+        dialog.set_close_response("cancel");
 
-        if style_manager.is_dark() {
-            cell_color = settings.fg_color_dark();
-            background_color = settings.bg_color_dark();
-        } else {
-            cell_color = settings.fg_color();
-            background_color = settings.bg_color();
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                if response == "export" {
+                    win.select_image_file(snapshot.clone(), adjustment.value() as usize);
+                }
+            }),
+        );
+
+        dialog.present();
+    }
+
+    fn select_image_file(&self, snapshot: UniverseSnapshot, scale: usize) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Export"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Export as Image"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&i18n("PNG images")));
+        filter.add_mime_type("image/png");
+        filter.add_pattern("*.png");
+        dialog.add_filter(&filter);
+        dialog.set_current_name(&format!("{}.png", i18n("Universe")));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    win.write_image(&file, &snapshot, scale);
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    fn write_image(&self, file: &gio::File, snapshot: &UniverseSnapshot, scale: usize) {
+        let (cell_color, background_color) = self.scene_colors();
+        let data = render_snapshot_surface(snapshot, scale, &cell_color, &background_color)
+            .map_err(|error| error.to_string())
+            .and_then(|surface| encode_surface_png(&surface).map_err(|error| error.to_string()));
+
+        let data = match data {
+            Ok(data) => data,
+            Err(error) => {
+                self.add_toast(i18n("Unable to render the universe"));
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to render universe image: {}", error);
+                return;
+            }
+        };
+
+        match file.replace_contents(
+            &data,
+            None,
+            false,
+            gio::FileCreateFlags::REPLACE_DESTINATION,
+            gio::Cancellable::NONE,
+        ) {
+            Ok(_) => self.add_toast(i18n("Image exported")),
+            Err(error) => {
+                self.add_toast(i18n("Unable to write to file"));
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+            }
         }
+    }
+
+    /// Restores the universe, notes, scene and demo playlist of a bundle file
+    fn import_bundle(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Import"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Import workspace bundle"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Open)
+            .build();
+        dialog.add_filter(&Self::bundle_file_filter());
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    let imp = win.imp();
+                    let data = match file.load_contents(gio::Cancellable::NONE) {
+                        Ok((data, _)) => data,
+                        Err(error) => {
+                            glib::g_critical!(G_LOG_DOMAIN, "File not accessible: {}", error);
+                            win.add_toast(i18n("File not existing or not accessible"));
+                            return;
+                        }
+                    };
+
+                    match WorkspaceBundle::from_bytes(&data, &imp.settings) {
+                        Ok(bundle) => {
+                            bundle.scene().apply(&imp.settings);
+                            imp.settings.set_playlist(bundle.playlist());
+                            win.seed_from_snapshot(bundle.snapshot().clone());
+
+                            let key = file.uri().to_string();
+                            imp.notes.set_notes(&key, bundle.notes());
+                            win.set_pattern_key(Some(key));
+                        }
+                        Err(error) => {
+                            glib::g_critical!(G_LOG_DOMAIN, "Unreadable bundle: {}", error);
+                            win.add_toast(i18n("Unreadable file"));
+                        }
+                    }
+                }
+            }),
+        );
 
-        grid.set_cell_color(Some(gtk::gdk::RGBA::from_str(&cell_color).unwrap()));
-        grid.set_background_color(Some(gtk::gdk::RGBA::from_str(&background_color).unwrap()));
+        dialog.show();
     }
 
-    fn restore_window_state(&self) {
-        let settings = &self.imp().settings;
-        self.set_default_size(settings.window_width(), settings.window_height());
+    fn project_file_filter() -> gtk::FileFilter {
+        let filter = gtk::FileFilter::new();
+        filter.set_name(Some(&i18n("Game of Life projects")));
+        filter.add_mime_type("application/x-game-of-life-project");
+        filter.add_pattern("*.gol");
+        filter
     }
 
-    fn add_toast(&self, msg: String) {
-        let toast = adw::Toast::new(&msg);
-        self.imp().toast_overlay.add_toast(&toast);
+    /// A hash telling whether the universe of `grid` changed since the project was saved
+    fn project_state(grid: &GameOfLifeUniverseGrid) -> u64 {
+        let universe = grid.get_universe();
+        universe.state_hash() ^ universe.generations()
+    }
+
+    /// Whether the universe of `grid` changed since its project was opened or saved.
+    /// Universes not coming from a project are never considered unsaved.
+    fn has_unsaved_changes(&self, grid: &GameOfLifeUniverseGrid) -> bool {
+        let saved_project_state = self
+            .with_tab(grid, |tab| {
+                tab.project_file.as_ref().map(|_| tab.saved_project_state)
+            })
+            .flatten();
+        saved_project_state.is_some_and(|state| state != Self::project_state(grid))
+    }
+
+    /// The project file the selected universe was last opened from or saved to
+    fn project_file(&self) -> Option<gio::File> {
+        self.with_selected_tab(|tab| tab.project_file.clone())
+    }
+
+    fn set_project_file(&self, file: Option<gio::File>) {
+        self.with_selected_tab(|tab| tab.project_file = file);
+        self.update_tab_indicator(&self.universe_grid());
+    }
+
+    /// Remembers the selected universe as saved in its project
+    fn mark_project_saved(&self) {
+        let grid = self.universe_grid();
+        let state = Self::project_state(&grid);
+        self.with_selected_tab(|tab| tab.saved_project_state = state);
+        self.update_tab_indicator(&grid);
     }
+
+    /// Asks whether to save the project of the selected tab before it is closed,
+    /// calling `done` with whether closing can proceed
+    fn confirm_unsaved_changes<F: Fn(&Self, bool) + 'static>(&self, done: F) {
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Save changes to the project?")),
+            Some(&i18n(
+                "The universe changed since the project was last saved. Unsaved changes will be lost.",
+            )),
+        );
+        dialog.add_responses(&[
+            ("cancel", &i18n("_Cancel")),
+            ("discard", &i18n("_Discard")),
+            ("save", &i18n("_Save")),
+        ]);
+        dialog.set_response_appearance("discard", adw::ResponseAppearance::Destructive);
+        dialog.set_response_appearance("save", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("save"));
+        dialog.set_close_response("cancel");
+
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                let proceed = match response {
+                    "save" => win.write_project(&win.project_file().unwrap()),
+                    "discard" => true,
+                    _ => false,
+                };
+                done(&win, proceed);
+            }),
+        );
+
+        dialog.present();
+    }
+
+    /// Saves the universe to the current project file, asking for one if
+    /// the universe does not come from a project yet
+    fn save_project(&self) {
+        let file = self.project_file();
+        match file {
+            Some(file) => {
+                if self.write_project(&file) {
+                    self.add_toast(i18n("Project saved"));
+                }
+            }
+            None => self.save_project_as(),
+        }
+    }
+
+    fn save_project_as(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Save"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Save project"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Save)
+            .build();
+        dialog.add_filter(&Self::project_file_filter());
+        dialog.set_current_name(&format!("{}.gol", i18n("Universe")));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    if win.write_project(&file) {
+                        win.set_project_file(Some(file));
+                        win.add_toast(i18n("Project saved"));
+                    }
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Writes the universe as a project to `file`, returning whether it succeeded
+    fn write_project(&self, file: &gio::File) -> bool {
+        let imp = self.imp();
+        let grid = self.universe_grid();
+        let project = GameOfLifeProject::new(
+            grid.get_universe(),
+            grid.unbounded(),
+            ProjectColors::from_settings(&imp.settings),
+        );
+
+        let data = match project.to_bytes() {
+            Ok(data) => data,
+            Err(error) => {
+                self.add_toast(i18n("Unable to serialize project"));
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to serialize project: {}", error);
+                return false;
+            }
+        };
+
+        match file.replace_contents(
+            &data,
+            None,
+            false,
+            gio::FileCreateFlags::REPLACE_DESTINATION,
+            gio::Cancellable::NONE,
+        ) {
+            Ok(_) => {
+                self.mark_project_saved();
+                true
+            }
+            Err(error) => {
+                self.add_toast(i18n("Unable to write to file"));
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to write to file: {}", error);
+                false
+            }
+        }
+    }
+
+    fn select_and_open_project(&self) {
+        let dialog = gtk::FileChooserNative::builder()
+            .accept_label(&i18n("_Open"))
+            .cancel_label(&i18n("_Cancel"))
+            .modal(true)
+            .title(&i18n("Open project"))
+            .transient_for(self)
+            .select_multiple(false)
+            .action(gtk::FileChooserAction::Open)
+            .build();
+        dialog.add_filter(&Self::project_file_filter());
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response != gtk::ResponseType::Accept {
+                    return;
+                }
+
+                if let Some(file) = dialog.file() {
+                    win.open_project(&file);
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Replaces the universe with the one of the project in `file`, along with
+    /// its rule and colors
+    pub fn open_project(&self, file: &gio::File) {
+        let imp = self.imp();
+        let data = match file.load_contents(gio::Cancellable::NONE) {
+            Ok((data, _)) => data,
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "File not accessible: {}", error);
+                self.add_toast(i18n("File not existing or not accessible"));
+                return;
+            }
+        };
+
+        let project = match GameOfLifeProject::from_bytes(&data) {
+            Ok(project) => project,
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "Unreadable project: {}", error);
+                self.add_toast(i18n("Unreadable file"));
+                return;
+            }
+        };
+
+        self.stop_playlist();
+        self.stop_ambient();
+        let grid = self.universe_grid();
+        if grid.is_running() {
+            grid.halt();
+        }
+
+        imp.settings.set_rule(project.universe().rule());
+        grid.set_rule(project.universe().rule().clone());
+        project.colors().apply(&imp.settings);
+        grid.set_unbounded(false);
+        grid.set_universe(project.universe().clone());
+        grid.set_unbounded(project.unbounded());
+
+        self.set_project_file(Some(file.clone()));
+        self.mark_project_saved();
+        self.set_pattern_key(Some(file.uri().to_string()));
+    }
+
+    fn resize_universe_dialog(&self) {
+        let grid = self.universe_grid();
+        let dialog = GameOfLifeResizeUniverseView::new(grid.rows(), grid.columns());
+        dialog.set_modal(true);
+        dialog.set_transient_for(Some(self));
+
+        dialog.connect_response(clone!(@weak self as win => move |dialog, response| {
+            if response == gtk::ResponseType::Ok {
+                let (rows, columns) = dialog.size();
+                win.universe_grid().resize_universe(rows, columns, dialog.anchor());
+            }
+            dialog.close();
+        }));
+
+        dialog.show();
+    }
+
+    fn new_universe_dialog(&self) {
+        let app = gio::Application::default()
+            .expect("Failed to retrieve application singleton")
+            .downcast::<gtk::Application>()
+            .unwrap();
+        let win = app
+            .active_window()
+            .unwrap()
+            .downcast::<gtk::Window>()
+            .unwrap();
+        let dialog = GameOfLifeNewUniverseView::new();
+        dialog.set_modal(true);
+        dialog.set_transient_for(Some(&win));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response == gtk::ResponseType::Ok {
+                    let (target_w, target_h) = dialog.size();
+                    win.universe_grid().set_unbounded(dialog.unbounded());
+                    match dialog.option() {
+                        NewUniverseType::Empty => {
+                            win.new_empty(target_w as usize, target_h as usize);
+                            win.set_pattern_key(None);
+                        },
+                        NewUniverseType::Random => {
+                            win.new_random(target_w as usize, target_h as usize);
+                            win.set_pattern_key(None);
+                        },
+                        NewUniverseType::Template(template_name) => {
+                            glib::debug!("Seeding from {} template", template_name);
+                            match Template::read_template(template_name) {
+                                Ok(read) => {
+                                    match UniverseSnapshot::try_from(&read) {
+                                        Ok(snapshot) => {
+                                            win.seed_from_snapshot(snapshot);
+                                            win.set_pattern_key(Some(format!("template:{}", template_name)));
+                                        },
+                                        Err(error) => {
+                                            glib::g_critical!(G_LOG_DOMAIN, "Unreadable template: {:?}", error);
+                                            win.add_toast(i18n("Bad template data"));
+                                        }
+                                    }
+                                },
+                                Err(error) => {
+                                    glib::g_critical!(G_LOG_DOMAIN, "Could not load template: {}", error);
+                                    win.add_toast(i18n("Template not found"));
+                                }
+                            }
+                        }
+                    }
+                }
+                dialog.close();
+            })
+        );
+        dialog.show();
+    }
+
+    /// Shows the pattern library, with the pattern identified by `selected_id`
+    /// selected if given
+    pub fn pattern_library_dialog(&self, selected_id: Option<&str>) {
+        let grid = self.universe_grid();
+        let dialog = GameOfLifePatternLibrary::new(grid.rows(), grid.columns());
+        dialog.set_modal(true);
+        dialog.set_transient_for(Some(self));
+        if let Some(id) = selected_id {
+            dialog.select_pattern(id);
+        }
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if let Some(library_pattern) = dialog.selected_pattern() {
+                    if response == gtk::ResponseType::Ok {
+                        let (row, column) = dialog.location();
+                        win.universe_grid()
+                            .stamp(library_pattern.pattern().snapshot(), row, column);
+                    } else if response == gtk::ResponseType::Apply {
+                        win.begin_placement(library_pattern.pattern().snapshot().clone());
+                    }
+                }
+                dialog.close();
+            }),
+        );
+        dialog.show();
+    }
+
+    /// Replaces the universe with an empty one holding `library_pattern` at its center
+    pub fn open_library_pattern(&self, library_pattern: &LibraryPattern) {
+        self.clear_universe();
+        let grid = self.universe_grid();
+        let snapshot = library_pattern.pattern().snapshot();
+        grid.stamp(
+            snapshot,
+            grid.rows().saturating_sub(snapshot.rows()) / 2,
+            grid.columns().saturating_sub(snapshot.columns()) / 2,
+        );
+        self.set_pattern_key(Some(format!("library:{}", library_pattern.id())));
+    }
+
+    /// Asks for a name and saves the selection, or the whole universe if nothing
+    /// is selected, to the library of the user
+    fn add_to_library(&self) {
+        let selected = self.universe_grid().selected_pattern();
+        let snapshot = match selected.snapshot().bounding_box() {
+            Some((top, left, rows, columns)) => selected.snapshot().crop(top, left, rows, columns),
+            None => {
+                self.add_toast(i18n("There are no living cells to add to the library"));
+                return;
+            }
+        };
+        let mut pattern = UniversePattern::new(snapshot);
+        pattern.set_rule(selected.rule().cloned());
+
+        let name_entry = gtk::Entry::new();
+        name_entry.set_placeholder_text(Some(&i18n("Pattern name")));
+        name_entry.set_activates_default(true);
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Add to Library")),
+            Some(&i18n(
+                "The selection, or the whole universe if nothing is selected, is saved to your pattern library.",
+            )),
+        );
+        dialog.set_extra_child(Some(&name_entry));
+        dialog.add_responses(&[("cancel", &i18n("_Cancel")), ("add", &i18n("_Add"))]);
+        dialog.set_response_appearance("add", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("add"));
+        dialog.set_close_response("cancel");
+
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                if response != "add" {
+                    return;
+                }
+
+                let name = name_entry.text().trim().to_string();
+                let mut pattern = pattern.clone();
+                pattern.set_name(Some(match name.is_empty() {
+                    true => i18n("Untitled pattern"),
+                    false => name,
+                }));
+                match PatternLibrary::find_duplicate(pattern.snapshot()) {
+                    Some(existing) => win.offer_library_duplicate(pattern, existing),
+                    None => win.save_to_library(&pattern),
+                }
+            }),
+        );
+
+        dialog.present();
+    }
+
+    /// Offers to show the library entry `pattern` duplicates rather than
+    /// storing another copy of it
+    fn offer_library_duplicate(&self, pattern: UniversePattern, existing: LibraryPattern) {
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Pattern Already in Library")),
+            Some(&i18n_f(
+                "This pattern is {}, possibly moved, rotated or mirrored. You can use the existing entry instead of saving another copy.",
+                &[&existing.name()],
+            )),
+        );
+        dialog.add_responses(&[
+            ("cancel", &i18n("_Cancel")),
+            ("save", &i18n("Save _Copy")),
+            ("show", &i18n("_Show Existing")),
+        ]);
+        dialog.set_response_appearance("show", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("show"));
+        dialog.set_close_response("cancel");
+
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                match response {
+                    "save" => win.save_to_library(&pattern),
+                    "show" => win.pattern_library_dialog(Some(existing.id())),
+                    _ => {}
+                }
+            }),
+        );
+
+        dialog.present();
+    }
+
+    fn save_to_library(&self, pattern: &UniversePattern) {
+        match PatternLibrary::save_user_pattern(pattern) {
+            Ok(library_pattern) => self.add_toast(i18n_f(
+                "{} added to the library",
+                &[&library_pattern.name()],
+            )),
+            Err(error) => {
+                glib::g_critical!(G_LOG_DOMAIN, "Unable to save library pattern: {}", error);
+                self.add_toast(i18n("Unable to add the pattern to the library"));
+            }
+        }
+    }
+
+    /// Copies the selection, or the whole universe if nothing is selected, to the
+    /// clipboard as RLE text, along with the rule and generation it was copied at.
+    /// While editing notes, the selected text is copied instead.
+    fn copy_pattern(&self) {
+        let imp = self.imp();
+        if imp.notes_view.has_focus() {
+            imp.notes_view.emit_copy_clipboard();
+            return;
+        }
+
+        let grid = self.universe_grid();
+        let pattern = grid.selected_pattern();
+        let metadata = PatternMetadata::new(grid.rule(), grid.generation(), VERSION);
+        let provider = gtk::gdk::ContentProvider::new_union(&[
+            gtk::gdk::ContentProvider::for_value(&write_rle(&pattern).to_value()),
+            gtk::gdk::ContentProvider::for_bytes(
+                PATTERN_METADATA_MIME_TYPE,
+                &glib::Bytes::from_owned(metadata.write().into_bytes()),
+            ),
+        ]);
+        if let Err(error) = self.clipboard().set_content(Some(&provider)) {
+            glib::g_warning!(G_LOG_DOMAIN, "Could not copy pattern: {}", error);
+            return;
+        }
+        self.add_toast(i18n("Pattern copied to clipboard"));
+    }
+
+    /// Copies the selection to the clipboard as RLE text, then clears it
+    fn cut_pattern(&self) {
+        let imp = self.imp();
+        if imp.notes_view.has_focus() {
+            imp.notes_view.emit_cut_clipboard();
+            return;
+        }
+
+        if self.universe_grid().selection().is_some() {
+            self.copy_pattern();
+            self.universe_grid().clear_selection();
+        }
+    }
+
+    /// Reads a pattern in any supported text format from the clipboard and lets the user place it on the grid.
+    /// While editing notes, the clipboard text is pasted in them instead.
+    fn paste_pattern(&self) {
+        let imp = self.imp();
+        if imp.notes_view.has_focus() {
+            imp.notes_view.emit_paste_clipboard();
+            return;
+        }
+
+        self.clipboard().read_text_async(
+            gio::Cancellable::NONE,
+            clone!(@weak self as win => move |result| {
+                let pattern = match result {
+                    Ok(Some(text)) => parse_pattern(&text).ok(),
+                    Ok(None) => None,
+                    Err(error) => {
+                        glib::g_warning!(G_LOG_DOMAIN, "Could not read clipboard: {}", error);
+                        None
+                    }
+                };
+
+                match pattern {
+                    Some(pattern) => {
+                        win.begin_placement(pattern.snapshot().clone());
+                        win.read_clipboard_metadata(clone!(@weak win => move |metadata| {
+                            win.offer_pasted_rule(&metadata);
+                        }));
+                    }
+                    None => win.add_toast(i18n("The clipboard does not contain a pattern")),
+                }
+            }),
+        );
+    }
+
+    /// Reads the metadata of a pattern copied by this application, if the
+    /// clipboard holds any, and hands it to `f`
+    fn read_clipboard_metadata<F: FnOnce(PatternMetadata) + 'static>(&self, f: F) {
+        let clipboard = self.clipboard();
+        if !clipboard
+            .formats()
+            .contain_mime_type(PATTERN_METADATA_MIME_TYPE)
+        {
+            return;
+        }
+
+        clipboard.read_async(
+            &[PATTERN_METADATA_MIME_TYPE],
+            glib::PRIORITY_DEFAULT,
+            gio::Cancellable::NONE,
+            move |result| {
+                let stream = match result {
+                    Ok((stream, _)) => stream,
+                    Err(error) => {
+                        glib::g_warning!(
+                            G_LOG_DOMAIN,
+                            "Could not read pattern metadata: {}",
+                            error
+                        );
+                        return;
+                    }
+                };
+
+                // Metadata takes a few lines, a single read is enough
+                stream.read_bytes_async(
+                    PATTERN_METADATA_MAX_SIZE,
+                    glib::PRIORITY_DEFAULT,
+                    gio::Cancellable::NONE,
+                    move |result| {
+                        let metadata = result.ok().and_then(|bytes| {
+                            let text = String::from_utf8_lossy(&bytes).to_string();
+                            PatternMetadata::parse(&text).ok()
+                        });
+                        if let Some(metadata) = metadata {
+                            f(metadata);
+                        }
+                    },
+                );
+            },
+        );
+    }
+
+    /// Offers to switch to the rule a pasted pattern was copied under, when it
+    /// differs from the current one
+    fn offer_pasted_rule(&self, metadata: &PatternMetadata) {
+        if *metadata.rule() == self.universe_grid().rule() {
+            return;
+        }
+
+        let toast = adw::Toast::new(&i18n_f(
+            "Pattern copied at generation {} under rule {}",
+            &[
+                &metadata.generation().to_string(),
+                &metadata.rule().to_string(),
+            ],
+        ));
+        toast.set_button_label(Some(i18n("Switch Rule").as_str()));
+        toast.set_action_name(Some("win.switch-rule"));
+        toast.set_action_target_value(Some(&metadata.rule().to_string().to_variant()));
+        self.imp().toast_overlay.add_toast(&toast);
+    }
+
+    /// Lets the user position the pattern on the universe with a preview
+    fn begin_placement(&self, pattern: UniverseSnapshot) {
+        self.universe_grid().begin_placement(pattern);
+        self.add_toast(i18n(
+            "Click to stamp the pattern, R to rotate, F to flip, right click or Esc when done",
+        ));
+    }
+
+    fn edit_playlist_dialog(&self) {
+        let dialog = GameOfLifePlaylistEditor::new(self.imp().settings.playlist());
+        dialog.set_modal(true);
+        dialog.set_transient_for(Some(self));
+
+        dialog.connect_response(
+            clone!(@strong dialog, @weak self as win => move |_, response| {
+                if response == gtk::ResponseType::Ok {
+                    win.imp().settings.set_playlist(&dialog.entries());
+                }
+                dialog.close();
+            }),
+        );
+        dialog.show();
+    }
+
+    fn experiments_dialog(&self) {
+        let imp = self.imp();
+        let (cell_color, background_color) = self.scene_colors();
+        let dialog = GameOfLifeExperimentsDialog::new(
+            &imp.settings.rule(),
+            (
+                imp.settings.universe_width() as usize,
+                imp.settings.universe_height() as usize,
+            ),
+            (cell_color, background_color),
+        );
+        dialog.set_transient_for(Some(self));
+        dialog.show();
+    }
+
+    fn soup_search_dialog(&self) {
+        let imp = self.imp();
+        let dialog = GameOfLifeSoupSearchDialog::new(
+            &imp.settings.rule(),
+            (
+                imp.settings.universe_width() as usize,
+                imp.settings.universe_height() as usize,
+            ),
+            imp.settings.soup(),
+        );
+        dialog.set_transient_for(Some(self));
+        dialog.show();
+    }
+
+    /// Starts playing the demo playlist from its first entry, or stops it if it is playing
+    pub fn toggle_playlist(&self) {
+        let imp = self.imp();
+        if imp.playlist.borrow().is_some() {
+            self.stop_playlist();
+            self.universe_grid().halt();
+            return;
+        }
+
+        match PlaylistPlayer::new(imp.settings.playlist()) {
+            Some(player) => {
+                self.stop_ambient();
+                imp.playlist.replace(Some(player));
+                self.universe_grid().set_halts_when_settled(false);
+                self.universe_grid()
+                    .set_stabilization_patience(self.reseed_patience());
+                self.play_playlist_entry(false);
+            }
+            None => self.add_toast(i18n("The demo playlist is empty")),
+        }
+    }
+
+    /// Stops the demo playlist, if playing, restoring the preferred evolution speed.
+    /// The simulation itself is left as it is.
+    fn stop_playlist(&self) {
+        let imp = self.imp();
+        if imp.playlist.take().is_some() {
+            self.universe_grid()
+                .set_evolution_speed(imp.settings.evolution_speed());
+            self.universe_grid()
+                .set_stabilization_patience(self.reseed_patience());
+            self.universe_grid()
+                .set_halts_when_settled(self.halts_when_settled());
+        }
+    }
+
+    /// Loads the current playlist entry into a new universe and runs it, fading
+    /// from the previous entry if `transition` is set
+    fn play_playlist_entry(&self, transition: bool) {
+        let imp = self.imp();
+        let entry = match imp.playlist.borrow().as_ref() {
+            Some(player) => player.current().clone(),
+            None => return,
+        };
+
+        let library_pattern = PatternLibrary::bundled()
+            .into_iter()
+            .find(|library_pattern| library_pattern.id() == entry.pattern_id());
+        let library_pattern = match library_pattern {
+            Some(library_pattern) => library_pattern,
+            None => {
+                glib::g_warning!(
+                    G_LOG_DOMAIN,
+                    "Playlist pattern {} not found",
+                    entry.pattern_id()
+                );
+                self.add_toast(i18n_f(
+                    "Pattern {} not found, playlist stopped",
+                    &[entry.pattern_id()],
+                ));
+                self.stop_playlist();
+                self.universe_grid().halt();
+                return;
+            }
+        };
+
+        let snapshot = library_pattern.pattern().snapshot();
+        let (rows, columns) = entry.universe_size(
+            (
+                imp.settings.universe_width() as usize,
+                imp.settings.universe_height() as usize,
+            ),
+            (snapshot.rows(), snapshot.columns()),
+        );
+        let mut universe = Universe::new_empty(rows, columns);
+        universe.stamp(
+            snapshot,
+            (rows - snapshot.rows()) / 2,
+            (columns - snapshot.columns()) / 2,
+        );
+
+        let grid = self.universe_grid();
+        grid.set_evolution_speed(entry.speed());
+        if transition && grid.is_running() {
+            grid.transition_to(universe);
+        } else {
+            grid.halt();
+            grid.set_universe(universe);
+            grid.run();
+        }
+    }
+
+    /// Starts reseeding the universe with random symmetric soups whenever it
+    /// settles down, or stops doing so
+    fn toggle_ambient(&self) {
+        let imp = self.imp();
+        if imp.ambient.borrow().is_some() {
+            self.stop_ambient();
+            self.universe_grid().halt();
+            return;
+        }
+
+        self.stop_playlist();
+        imp.ambient.replace(Some(AmbientPlayer::new(
+            imp.settings.soup().density(),
+            imp.settings.ambient_palette_period(),
+        )));
+        let grid = self.universe_grid();
+        grid.set_halts_when_settled(false);
+        grid.set_stabilization_patience(Some(imp.settings.reseed_patience() as usize));
+        self.reseed_ambient(false);
+    }
+
+    /// Leaves the ambient mode, if in it, restoring the colors of the current
+    /// style. The simulation itself is left as it is.
+    fn stop_ambient(&self) {
+        let imp = self.imp();
+        if imp.ambient.take().is_some() {
+            self.universe_grid()
+                .set_stabilization_patience(self.reseed_patience());
+            self.universe_grid()
+                .set_halts_when_settled(self.halts_when_settled());
+            self.update_widgets();
+        }
+    }
+
+    /// Fills the universe with the next soup of the ambient mode and runs it,
+    /// fading from the settled one if `transition` is set
+    fn reseed_ambient(&self, transition: bool) {
+        let soup = match self.imp().ambient.borrow_mut().as_mut() {
+            Some(player) => player.next_soup(),
+            None => return,
+        };
+
+        let grid = self.universe_grid();
+        let universe = Universe::new_soup(grid.rows(), grid.columns(), &soup);
+        self.update_widgets();
+        if transition && grid.is_running() {
+            grid.transition_to(universe);
+        } else {
+            grid.halt();
+            grid.set_universe(universe);
+            grid.run();
+        }
+    }
+
+    fn advance_playlist(&self) {
+        if let Some(player) = self.imp().playlist.borrow_mut().as_mut() {
+            player.advance();
+        }
+        self.play_playlist_entry(true);
+    }
+
+    /// Moves to the next playlist entry once the current one ran long enough
+    fn on_generation_changed(&self) {
+        let imp = self.imp();
+        self.capture_recording_frame();
+
+        if let Some(control) = imp.control.borrow().as_ref() {
+            control.emit_generation_changed(
+                self.universe_grid().generation(),
+                self.universe_grid().population(),
+            );
+        }
+
+        let finished = match imp.playlist.borrow().as_ref() {
+            Some(player) => {
+                self.universe_grid().is_running()
+                    && !self.universe_grid().in_transition()
+                    && self.universe_grid().generation()
+                        >= u64::from(player.current().generations())
+            }
+            None => false,
+        };
+
+        if finished {
+            self.advance_playlist();
+        }
+    }
+
+    /// How many generations a running universe has to be settled for before
+    /// it is reseeded, or `None` if settled universes are left as they are
+    fn reseed_patience(&self) -> Option<usize> {
+        let settings = &self.imp().settings;
+        settings
+            .reseed_when_stable()
+            .then(|| settings.reseed_patience() as usize)
+    }
+
+    /// Whether a running simulation stops by itself once settled, which it
+    /// does not when settled universes are reseeded instead
+    fn halts_when_settled(&self) -> bool {
+        let settings = &self.imp().settings;
+        settings.halt_when_settled() && !settings.reseed_when_stable()
+    }
+
+    /// Fades a settled universe into a random soup of the preferred density
+    fn reseed_soup(&self) {
+        let grid = self.universe_grid();
+        let universe = Universe::new_soup(grid.rows(), grid.columns(), &self.imp().settings.soup());
+        grid.transition_to(universe);
+    }
+
+    /// Reseeds settled universes, moving on to the next pattern instead while
+    /// the demo playlist plays and to the next symmetric soup in ambient mode
+    fn on_stable_changed(&self) {
+        let imp = self.imp();
+        let grid = self.universe_grid();
+        if !grid.is_stable() || !grid.is_running() || grid.in_transition() {
+            return;
+        }
+
+        if imp.playlist.borrow().is_some() {
+            self.advance_playlist();
+        } else if imp.ambient.borrow().is_some() {
+            self.reseed_ambient(true);
+        } else if imp.settings.reseed_when_stable() {
+            self.reseed_soup();
+        }
+    }
+
+    /// Tells about the oscillator a running universe settled into, unless the
+    /// simulation halts for it and reports it by itself
+    fn on_period_changed(&self) {
+        let grid = self.universe_grid();
+        if !grid.is_running()
+            || grid.halts_when_settled()
+            || grid.population() == 0
+            || self.imp().playlist.borrow().is_some()
+        {
+            return;
+        }
+
+        match grid.period() {
+            Some(1) => self.add_toast(i18n("Still life detected")),
+            Some(period) => self.add_toast(i18n_f(
+                "Oscillator detected: period {}",
+                &[&period.to_string()],
+            )),
+            None => {}
+        }
+    }
+
+    /// Shows the recorded generations on the timeline while the simulation is
+    /// stopped, with the current one selected
+    fn update_timeline(&self) {
+        let imp = self.imp();
+        let grid = self.universe_grid();
+        if grid.is_running() {
+            imp.timeline_revealer.set_reveal_child(false);
+            return;
+        }
+
+        match grid.history_range() {
+            Some((first, last)) if first < last => {
+                imp.updating_timeline.set(true);
+                imp.timeline_adjustment.configure(
+                    grid.generation() as f64,
+                    first as f64,
+                    last as f64,
+                    1.0,
+                    10.0,
+                    0.0,
+                );
+                imp.updating_timeline.set(false);
+                imp.timeline_revealer.set_reveal_child(true);
+            }
+            _ => imp.timeline_revealer.set_reveal_child(false),
+        }
+    }
+
+    /// Offers the WireWorld cells, the colors of colored rules or ants to draw
+    /// with while the selected universe runs a rule that has them, and the
+    /// number of elementary rules
+    fn update_palettes(&self) {
+        let imp = self.imp();
+        let rule = self.universe_grid().rule();
+        imp.wireworld_palette.set_visible(rule.is_wireworld());
+        imp.ant_palette.set_visible(rule.is_ant());
+        imp.elementary_palette.set_visible(rule.is_elementary());
+        if let Some(number) = rule.elementary_number() {
+            imp.elementary_rule_adjustment.set_value(number as f64);
+        }
+        imp.colors_palette.set_visible(rule.is_colored());
+        imp.quadlife_colors.set_visible(rule.colors() > 2);
+    }
+
+    /// Shows how placed patterns are stamped while the selected universe places one
+    fn update_placement_bar(&self) {
+        let placing = self.universe_grid().is_placing();
+        self.imp().placement_revealer.set_reveal_child(placing);
+    }
+
+    /// Shows the rate of the selected universe while it runs in turbo mode
+    fn update_rate(&self) {
+        let grid = self.universe_grid();
+        let rate = grid.generations_per_second().filter(|_| grid.is_turbo());
+        self.imp().controls.set_generations_per_second(rate);
+    }
+
+    /// Shows how far the selected universe got while fast forwarding
+    fn update_fast_forward_progress(&self) {
+        let imp = self.imp();
+        let controller = self.universe_grid().controller();
+        let (Some((_, target)), Some(progress)) = (
+            controller.fast_forward(),
+            controller.fast_forward_progress(),
+        ) else {
+            imp.fast_forward_revealer.set_reveal_child(false);
+            return;
+        };
+
+        imp.fast_forward_progress_bar.set_fraction(progress);
+        imp.fast_forward_progress_bar.set_text(Some(&i18n_f(
+            "Fast forwarding to generation {}",
+            &[&target.to_string()],
+        )));
+        imp.fast_forward_revealer.set_reveal_child(true);
+    }
+
+    /// Tells about a crash report written since the last time, offering to
+    /// open the folder it is in
+    fn offer_crash_report(&self) {
+        let settings = &self.imp().settings;
+        let report_name = match latest_crash_report().and_then(|path| {
+            path.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        }) {
+            Some(report_name) if report_name > settings.last_crash_report() => report_name,
+            _ => return,
+        };
+        settings.set_last_crash_report(&report_name);
+
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Game of Life Crashed")),
+            Some(&i18n_f(
+                "A crash report was saved as {}. You can attach it to a bug report.",
+                &[&report_name],
+            )),
+        );
+        dialog.add_responses(&[("close", &i18n("_Close")), ("open", &i18n("_Open Folder"))]);
+        dialog.set_response_appearance("open", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("open"));
+        dialog.set_close_response("close");
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                if response == "open" {
+                    let uri = gio::File::for_path(crash_reports_dir()).uri();
+                    gtk::show_uri(Some(&win), &uri, gtk::gdk::CURRENT_TIME);
+                }
+            }),
+        );
+        dialog.present();
+    }
+
+    /// Tells why the simulation halted, if it did by itself
+    fn report_halt_reason(&self) {
+        let reason = self.universe_grid().take_halt_reason();
+        if matches!(reason, Some(SimulationHaltReason::Stabilized(_)))
+            && self.imp().settings.screenshot_when_stable()
+        {
+            self.save_stable_screenshot();
+        }
+
+        match reason {
+            Some(SimulationHaltReason::Extinct(generations)) => self.add_toast(i18n_f(
+                "Universe died after {} generations",
+                &[&generations.to_string()],
+            )),
+            Some(SimulationHaltReason::Stabilized(1)) => {
+                self.add_toast(i18n("Stabilized into a still life"))
+            }
+            Some(SimulationHaltReason::Stabilized(period)) => {
+                self.add_toast(i18n_f("Stabilized with period {}", &[&period.to_string()]))
+            }
+            None => {}
+        }
+    }
+
+    /// Saves an image and an RLE pattern of the universe, named after the time
+    /// and the generation, to the screenshots folder
+    fn save_stable_screenshot(&self) {
+        let imp = self.imp();
+        let grid = self.universe_grid();
+        let folder = imp.settings.screenshot_folder();
+        let (cell_color, background_color) = self.scene_colors();
+
+        let snapshot = grid.get_universe_snapshot();
+        let cell_size = SCREENSHOT_MAX_SIZE / snapshot.rows().max(snapshot.columns()).max(1);
+        let image = render_snapshot_surface(&snapshot, cell_size, &cell_color, &background_color)
+            .map_err(|error| error.to_string())
+            .and_then(|surface| encode_surface_png(&surface).map_err(|error| error.to_string()));
+        let image = match image {
+            Ok(image) => image,
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Could not render screenshot: {}", error);
+                self.add_toast(i18n("Unable to save the screenshot of the stable universe"));
+                return;
+            }
+        };
+        let pattern = write_rle(&grid.current_pattern());
+
+        let timestamp = glib::DateTime::now_local()
+            .and_then(|now| now.format("%Y-%m-%d %H-%M-%S"))
+            .map(|value| value.to_string())
+            .unwrap_or_default();
+        let name = format!("{} gen {}", timestamp, grid.generation());
+        let result = std::fs::create_dir_all(&folder)
+            .and_then(|_| std::fs::write(folder.join(format!("{}.png", name)), image))
+            .and_then(|_| std::fs::write(folder.join(format!("{}.rle", name)), pattern));
+
+        if let Err(error) = result {
+            glib::g_warning!(
+                G_LOG_DOMAIN,
+                "Could not save screenshot to {}: {}",
+                folder.display(),
+                error
+            );
+            self.add_toast(i18n("Unable to save the screenshot of the stable universe"));
+        }
+    }
+
+    /// Turns off the actions the engine of the current universe does not support
+    fn on_engine_changed(&self) {
+        let capabilities = self.universe_grid().engine().capabilities();
+        self.action_set_enabled("win.freeze-selection", capabilities.freeze_zones());
+        self.action_set_enabled("win.unfreeze-selection", capabilities.freeze_zones());
+        self.warn_unsupported_rule();
+    }
+
+    fn warn_unsupported_rule(&self) {
+        let grid = self.universe_grid();
+        let rule = grid.rule();
+        if !grid.engine().capabilities().supports_rule(&rule) {
+            self.add_toast(if rule.is_birth(0) {
+                i18n("Unbounded universes cannot simulate births on zero neighbours")
+            } else if rule.has_dying_states() {
+                i18n("Unbounded universes cannot simulate dying states")
+            } else if rule.is_colored() {
+                i18n("Unbounded universes cannot tell cells apart by color")
+            } else if rule.is_ant() {
+                i18n("Unbounded universes cannot be walked by ants")
+            } else if rule.is_elementary() {
+                i18n("Unbounded universes cannot scroll the line of elementary rules")
+            } else {
+                i18n("Unbounded universes only count the eight cells around each cell")
+            });
+        }
+    }
+
+    /// Offers to restart a simulation that stopped delivering generations, and
+    /// takes the offer back if it recovers on its own
+    fn on_stalled_changed(&self) {
+        let imp = self.imp();
+        if !self.universe_grid().is_stalled() {
+            if let Some(dialog) = imp.stall_dialog.take() {
+                dialog.close();
+            }
+            return;
+        }
+
+        if imp.stall_dialog.borrow().is_some() {
+            return;
+        }
+
+        let dialog = adw::MessageDialog::new(
+            Some(self),
+            Some(&i18n("Simulation Not Responding")),
+            Some(&i18n(
+                "No new generation has been computed for a while. You can keep waiting, or restart the simulation from the last generation shown.",
+            )),
+        );
+        dialog.add_responses(&[
+            ("wait", &i18n("_Keep Waiting")),
+            ("restart", &i18n("_Restart Simulation")),
+        ]);
+        dialog.set_response_appearance("restart", adw::ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("restart"));
+        dialog.set_close_response("wait");
+        dialog.connect_response(
+            None,
+            clone!(@weak self as win => move |_, response| {
+                win.imp().stall_dialog.take();
+                if response == "restart" {
+                    win.universe_grid().restart_engine();
+                }
+            }),
+        );
+
+        imp.stall_dialog.replace(Some(dialog.clone()));
+        dialog.present();
+    }
+
+    fn new_empty(&self, rows: usize, columns: usize) {
+        self.stop_playlist();
+        self.stop_ambient();
+        let universe_grid = self.universe_grid();
+        universe_grid.set_universe(Universe::new_empty(rows, columns));
+    }
+
+    fn new_random(&self, rows: usize, columns: usize) {
+        self.stop_playlist();
+        self.stop_ambient();
+        let universe_grid = self.universe_grid();
+        universe_grid.set_universe(Universe::new_soup(
+            rows,
+            columns,
+            &self.imp().settings.soup(),
+        ));
+    }
+
+    /// Fills the universe, or only the selection if asked to, with a random soup
+    fn seed_universe(&self) {
+        let settings = &self.imp().settings;
+        let universe_grid = self.universe_grid();
+        let soup = settings.soup();
+        match universe_grid.selection().is_some() && settings.soup_selection_only() {
+            true => universe_grid.randomize_selection(&soup),
+            false => universe_grid.random_seed(&soup),
+        }
+    }
+
+    fn clear_universe(&self) {
+        self.stop_playlist();
+        self.stop_ambient();
+        self.universe_grid().clear();
+    }
+
+    fn skip_forward_one(&self) {
+        let universe_grid = self.universe_grid();
+        universe_grid.skip_forward_one();
+    }
+
+    /// Runs the universe as fast as possible for, or until, the number of
+    /// generations chosen in the controls
+    fn fast_forward(&self) {
+        self.stop_playlist();
+        self.stop_ambient();
+        self.imp().controls.close_fast_forward();
+        let universe_grid = self.universe_grid();
+        let target = self
+            .imp()
+            .settings
+            .fast_forward()
+            .target(universe_grid.generation());
+        if target <= universe_grid.generation() {
+            self.add_toast(i18n("The universe is already past that generation"));
+            return;
+        }
+
+        universe_grid.run_until(target);
+        self.notify("running");
+    }
+
+    fn seed_from_snapshot(&self, snapshot: UniverseSnapshot) {
+        self.stop_playlist();
+        self.stop_ambient();
+        let universe_grid = self.universe_grid();
+        let universe = snapshot.into();
+        universe_grid.set_universe(universe);
+    }
+
+    /// How living cells are colored by age, if they are
+    fn age_gradient(&self) -> Option<UniverseGridAgeGradient> {
+        let settings = &self.imp().settings;
+        if !settings.cell_age_coloring() {
+            return None;
+        }
+
+        let color = gtk::gdk::RGBA::from_str(&settings.cell_age_color()).ok()?;
+        Some(UniverseGridAgeGradient::new(
+            color,
+            settings.cell_age_span(),
+        ))
+    }
+
+    /// The color dying cells start from, if not the cell color
+    fn dying_color(&self) -> Option<gtk::gdk::RGBA> {
+        gtk::gdk::RGBA::from_str(&self.imp().settings.dying_color()).ok()
+    }
+
+    /// The cell and background colors for the current style, the cells taking
+    /// the accent color if asked to, or those of the ambient mode once it
+    /// switched colors
+    fn scene_colors(&self) -> (String, String) {
+        let settings = &self.imp().settings;
+        let palette = self
+            .imp()
+            .ambient
+            .borrow()
+            .as_ref()
+            .and_then(|player| player.palette());
+        if let Some((cell_color, background_color)) = palette {
+            return (cell_color.to_string(), background_color.to_string());
+        }
+
+        let (cell_color, background_color) = match self.imp().style_manager.is_dark() {
+            true => (settings.fg_color_dark(), settings.bg_color_dark()),
+            false => (settings.fg_color(), settings.bg_color()),
+        };
+        let accent_color = settings
+            .cell_accent_color()
+            .then(|| self.style_context().lookup_color("accent_color"))
+            .flatten();
+        match accent_color {
+            Some(accent_color) => (accent_color.to_string(), background_color),
+            None => (cell_color, background_color),
+        }
+    }
+
+    /// The colors grids paint with, from the settings and the current style
+    fn scene_palette(&self) -> UniverseGridPalette {
+        let (cell_color, background_color) = self.scene_colors();
+        UniverseGridPalette::new(
+            gtk::gdk::RGBA::from_str(&cell_color).unwrap(),
+            gtk::gdk::RGBA::from_str(&background_color).unwrap(),
+        )
+        .with_age_gradient(self.age_gradient())
+        .with_dying_color(self.dying_color())
+    }
+
+    fn update_widgets(&self) {
+        let palette = self.scene_palette();
+        for grid in self.universe_grids() {
+            grid.set_palette(palette);
+        }
+    }
+
+    fn set_reference(&self) {
+        self.universe_grid().set_reference_from_current();
+        self.add_toast(i18n("Reference set, hold C to compare with it"));
+    }
+
+    /// Sums up what changed since the reference was set
+    fn show_reference_diff(&self) {
+        let diff = match self.universe_grid().reference_diff() {
+            Some(diff) => diff,
+            None => return,
+        };
+
+        let message = match (diff.is_empty(), diff.bounding_box_offset()) {
+            (true, _) => i18n("Nothing changed since the reference"),
+            (false, Some((rows, columns))) if rows != 0 || columns != 0 => i18n_f(
+                "{} cells born and {} died since the reference, moved by {}×{}",
+                &[
+                    &diff.births().len().to_string(),
+                    &diff.deaths().len().to_string(),
+                    &rows.to_string(),
+                    &columns.to_string(),
+                ],
+            ),
+            (false, _) => i18n_f(
+                "{} cells born and {} died since the reference",
+                &[
+                    &diff.births().len().to_string(),
+                    &diff.deaths().len().to_string(),
+                ],
+            ),
+        };
+        self.add_toast(message);
+    }
+
+    pub fn toggle_notes(&self) {
+        let imp = self.imp();
+        let revealer = imp.notes_revealer.get();
+        revealer.set_reveal_child(!revealer.reveals_child());
+        if imp.narrow.get() && revealer.reveals_child() {
+            imp.stats_revealer.set_reveal_child(false);
+        }
+    }
+
+    /// Shows or hides the charts of the population, births and deaths
+    pub fn toggle_stats(&self) {
+        let imp = self.imp();
+        let revealer = imp.stats_revealer.get();
+        revealer.set_reveal_child(!revealer.reveals_child());
+        if imp.narrow.get() && revealer.reveals_child() {
+            imp.notes_revealer.set_reveal_child(false);
+        }
+    }
+
+    fn toggle_fullscreen(&self) {
+        match self.is_fullscreened() {
+            true => self.unfullscreen(),
+            false => self.fullscreen(),
+        }
+    }
+
+    /// Shows nothing but the running universe, fullscreen, or leaves the
+    /// presentation
+    fn toggle_presentation(&self) {
+        let presenting = !self.imp().presenting.get();
+        self.imp().presenting.set(presenting);
+        match presenting {
+            true => {
+                self.fullscreen();
+                if !self.is_running() {
+                    self.toggle_run();
+                }
+            }
+            false => self.unfullscreen(),
+        }
+        self.update_chrome();
+    }
+
+    /// Shows everything around the universe when windowed, controls hiding
+    /// away when fullscreen, and nothing while presenting
+    fn update_chrome(&self) {
+        let imp = self.imp();
+        let presenting = imp.presenting.get();
+        let chrome: [&gtk::Widget; 7] = [
+            imp.tab_bar.upcast_ref(),
+            imp.notes_revealer.upcast_ref(),
+            imp.stats_revealer.upcast_ref(),
+            imp.timeline_revealer.upcast_ref(),
+            imp.fast_forward_revealer.upcast_ref(),
+            imp.placement_revealer.upcast_ref(),
+            imp.tools_revealer.upcast_ref(),
+        ];
+        for widget in chrome {
+            widget.set_visible(!presenting);
+        }
+        imp.main_box
+            .set_margin_bottom(match self.is_fullscreened() {
+                true => 0,
+                false => 10,
+            });
+        self.reveal_controls();
+    }
+
+    /// Shows the controls, hiding them again once the pointer rested for
+    /// `CONTROLS_HIDE_DELAY` while fullscreen
+    fn reveal_controls(&self) {
+        let imp = self.imp();
+        if let Some(source) = imp.controls_hide_source.take() {
+            source.remove();
+        }
+        imp.controls_revealer
+            .set_reveal_child(!imp.presenting.get());
+        if !self.is_fullscreened() || imp.presenting.get() {
+            return;
+        }
+
+        let source = glib::timeout_add_local_once(
+            CONTROLS_HIDE_DELAY,
+            clone!(@weak self as this => move || {
+                let imp = this.imp();
+                imp.controls_hide_source.take();
+                if !imp.pointer_over_controls.get() {
+                    imp.controls_revealer.set_reveal_child(false);
+                }
+            }),
+        );
+        imp.controls_hide_source.replace(Some(source));
+    }
+
+    /// Lays the window out for its width. Narrow windows show the notes and
+    /// statistics as sheets below the universe, one at a time, and fold the
+    /// less used controls into a menu.
+    fn update_layout(&self) {
+        let imp = self.imp();
+        let narrow = imp.narrow.get();
+        let (orientation, transition) = match narrow {
+            true => (
+                gtk::Orientation::Vertical,
+                gtk::RevealerTransitionType::SlideUp,
+            ),
+            false => (
+                gtk::Orientation::Horizontal,
+                gtk::RevealerTransitionType::SlideLeft,
+            ),
+        };
+        imp.content_box.set_orientation(orientation);
+        imp.notes_revealer.set_transition_type(transition);
+        imp.stats_revealer.set_transition_type(transition);
+        if narrow && imp.notes_revealer.reveals_child() {
+            imp.stats_revealer.set_reveal_child(false);
+        }
+        imp.controls.set_compact(narrow);
+    }
+
+    /// Sets the pattern notes are attached to and loads its notes. `None` means
+    /// the current universe has not been saved anywhere yet.
+    fn set_pattern_key(&self, key: Option<String>) {
+        self.with_selected_tab(|tab| tab.pattern_key = key);
+        self.update_tab_title(&self.universe_grid());
+        self.load_pattern_notes();
+    }
+
+    fn pattern_key(&self) -> Option<String> {
+        self.with_selected_tab(|tab| tab.pattern_key.clone())
+    }
+
+    fn load_pattern_notes(&self) {
+        let imp = self.imp();
+        self.save_pattern_notes();
+        let notes = match self.pattern_key().as_ref() {
+            Some(key) => imp.notes.notes(key),
+            None => String::new(),
+        };
+
+        imp.loading_notes.set(true);
+        imp.notes_view.buffer().set_text(&notes);
+        imp.loading_notes.set(false);
+        self.update_notes_subject();
+    }
+
+    fn store_pattern_notes(&self) {
+        let imp = self.imp();
+        if imp.loading_notes.get() {
+            return;
+        }
+
+        if let Some(key) = self.pattern_key().as_ref() {
+            let buffer = imp.notes_view.buffer();
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            imp.notes.set_notes(key, &text);
+
+            if let Some(source) = imp.notes_save_source.take() {
+                source.remove();
+            }
+            let source = glib::timeout_add_local_once(
+                NOTES_SAVE_DELAY,
+                clone!(@weak self as this => move || {
+                    this.imp().notes_save_source.take();
+                    this.imp().notes.save();
+                }),
+            );
+            imp.notes_save_source.replace(Some(source));
+        }
+    }
+
+    /// Writes the edited notes right away rather than once typing paused
+    fn save_pattern_notes(&self) {
+        let imp = self.imp();
+        if let Some(source) = imp.notes_save_source.take() {
+            source.remove();
+        }
+        imp.notes.save();
+    }
+
+    fn update_notes_subject(&self) {
+        let imp = self.imp();
+        let subject = match self.pattern_key().as_ref() {
+            Some(key) => pattern_title(key),
+            None => i18n("Unsaved universe, notes are kept once saved"),
+        };
+        imp.notes_subject_label.set_label(&subject);
+    }
+
+    fn restore_window_state(&self) {
+        let settings = &self.imp().settings;
+        self.set_default_size(settings.window_width(), settings.window_height());
+        if settings.window_maximized() {
+            self.maximize();
+        }
+
+        if !settings.restore_session() {
+            return;
+        }
+
+        let data = match std::fs::read(session_project_path()) {
+            Ok(data) => data,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return,
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Unable to read last session: {}", error);
+                return;
+            }
+        };
+
+        match GameOfLifeProject::from_bytes(&data) {
+            Ok(project) => {
+                let grid = self.universe_grid();
+                grid.set_rule(project.universe().rule().clone());
+                grid.set_unbounded(false);
+                grid.set_universe(project.universe().clone());
+                grid.set_unbounded(project.unbounded());
+            }
+            Err(error) => {
+                glib::g_warning!(G_LOG_DOMAIN, "Unable to restore last session: {}", error)
+            }
+        }
+    }
+
+    /// Stores the selected universe as a project for the next session to start
+    /// from, or forgets the stored one if sessions are not restored
+    fn save_session_universe(&self, settings: &GameOfLifeSettings) {
+        let path = session_project_path();
+        if !settings.restore_session() {
+            if let Err(error) = std::fs::remove_file(&path) {
+                if error.kind() != std::io::ErrorKind::NotFound {
+                    glib::g_warning!(G_LOG_DOMAIN, "Unable to forget last session: {}", error);
+                }
+            }
+            return;
+        }
+
+        let grid = self.universe_grid();
+        let project = GameOfLifeProject::new(
+            grid.get_universe(),
+            grid.unbounded(),
+            ProjectColors::from_settings(settings),
+        );
+        let written = project
+            .to_bytes()
+            .map_err(|error| error.to_string())
+            .and_then(|data| {
+                std::fs::create_dir_all(user_data_dir())
+                    .and_then(|_| std::fs::write(&path, data))
+                    .map_err(|error| error.to_string())
+            });
+        if let Err(error) = written {
+            glib::g_warning!(G_LOG_DOMAIN, "Unable to save session universe: {}", error)
+        }
+    }
+
+    fn add_toast(&self, msg: String) {
+        let toast = adw::Toast::new(&msg);
+        self.imp().toast_overlay.add_toast(&toast);
+    }
+}
+
+/// The text format of `file` according to its extension, if any
+fn pattern_file_format(file: &gio::File) -> Option<PatternFormat> {
+    file.path()?
+        .extension()
+        .and_then(|extension| PatternFormat::from_extension(&extension.to_string_lossy()))
+}
+
+/// A readable name for the pattern identified by `key`
+fn pattern_title(key: &str) -> String {
+    match key
+        .strip_prefix("template:")
+        .or_else(|| key.strip_prefix("library:"))
+    {
+        Some(name) => name.replace('_', " "),
+        None => gio::File::for_uri(key)
+            .basename()
+            .map(|name| name.display().to_string())
+            .unwrap_or_else(|| key.to_owned()),
+    }
+}
+
+/// A scrolled window showing `grid`, with scrollbars once its cells no longer
+/// fit the window
+fn grid_scroller(grid: &GameOfLifeUniverseGrid) -> gtk::ScrolledWindow {
+    gtk::ScrolledWindow::builder()
+        .child(grid)
+        .hexpand(true)
+        .vexpand(true)
+        .build()
+}
+
+/// The grid shown by a tab page
+fn page_grid(page: &adw::TabPage) -> Option<GameOfLifeUniverseGrid> {
+    page.child()
+        .downcast::<gtk::ScrolledWindow>()
+        .ok()?
+        .child()?
+        .downcast()
+        .ok()
 }